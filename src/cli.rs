@@ -0,0 +1,497 @@
+use std::{io::Write, path::Path, str::FromStr};
+
+use anyhow::{bail, Result};
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+
+use crate::{
+    db::{error::DbError, JiraDatabase},
+    models::{Epic, Status, Story},
+    ui::views::EpicView,
+};
+
+/// `Cli` holds the command line arguments. Without a subcommand, the interactive UI is
+/// launched.
+#[derive(Parser)]
+#[command(name = "clira", about = "A Jira clone for the terminal")]
+pub struct Cli {
+    /// Keep the board in memory; changes are not saved
+    #[arg(long)]
+    pub memory: bool,
+    /// Move through epics and stories with j/k or the arrow keys, and open them with enter
+    #[arg(long)]
+    pub keys: bool,
+    /// Start on the home page instead of reopening the pages open when clira last exited
+    #[arg(long)]
+    pub fresh: bool,
+    /// Redraw the current page every SECONDS, e.g. to keep the board open as a dashboard
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        conflicts_with = "keys"
+    )]
+    pub watch: Option<u64>,
+    /// The board file, instead of $CLIRA_DB or ~/.local/share/clira/db.json
+    #[arg(long, global = true)]
+    pub db: Option<String>,
+    /// Print the results of list and show commands as JSON
+    #[arg(long, global = true)]
+    pub json: bool,
+    /// Print without colors, as when NO_COLOR is set
+    #[arg(long, global = true)]
+    pub no_color: bool,
+    /// Print the changes that would be made instead of saving them
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Create an empty board
+    Init {
+        /// Where to create the board
+        path: Option<String>,
+        /// Overwrite an existing board
+        #[arg(long)]
+        force: bool,
+    },
+    /// Add the issues of a Jira Cloud JSON export to the board
+    ImportJira {
+        /// The exported JSON file
+        export: String,
+    },
+    #[command(flatten)]
+    Board(BoardCommand),
+}
+
+/// `BoardCommand` is a non-interactive command run against the board by `run_cli`.
+#[derive(Subcommand)]
+pub enum BoardCommand {
+    /// Print the whole board as JSON
+    Dump,
+    /// Print one `id<TAB>status<TAB>name` line per epic
+    ListEpics,
+    /// Manage epics
+    #[command(subcommand)]
+    Epic(EpicCommand),
+    /// Manage stories
+    #[command(subcommand)]
+    Story(StoryCommand),
+}
+
+#[derive(Subcommand)]
+pub enum EpicCommand {
+    /// Print one `id<TAB>status<TAB>name` line per epic
+    List,
+    /// Print an epic and its stories
+    Show { id: u32 },
+    /// Create an epic
+    Create {
+        #[arg(long)]
+        name: String,
+        #[arg(long, default_value = "")]
+        description: String,
+    },
+    /// Delete an epic along with its stories and tasks
+    Delete {
+        id: u32,
+        /// Confirm the deletion
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum StoryCommand {
+    /// Create a story in an epic
+    Create {
+        #[arg(long)]
+        epic: u32,
+        #[arg(long)]
+        name: String,
+        #[arg(long, default_value = "")]
+        description: String,
+    },
+    /// Set the status of a story, e.g. "in progress"
+    Status {
+        id: u32,
+        #[arg(value_parser = Status::from_str)]
+        status: Status,
+    },
+}
+
+impl BoardCommand {
+    /// `is_read_only` returns whether the command leaves the board untouched.
+    pub fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            Self::Dump
+                | Self::ListEpics
+                | Self::Epic(EpicCommand::List)
+                | Self::Epic(EpicCommand::Show { .. })
+        )
+    }
+}
+
+/// `DB_ENV_VAR` is the environment variable holding the board file when `--db` is not passed.
+pub const DB_ENV_VAR: &str = "CLIRA_DB";
+
+/// `FALLBACK_DB_PATH` is the board file used when neither `--db`, `CLIRA_DB`, the config
+/// file nor `HOME` set one.
+const FALLBACK_DB_PATH: &str = "data/db.json";
+
+/// `resolve_db_path` picks the board file from the `--db` flag, then the `CLIRA_DB`
+/// environment variable `env`, then the `db_path` of the config file `config`, then
+/// `~/.local/share/clira/db.json` under `home`. Empty values are ignored.
+pub fn resolve_db_path(
+    flag: Option<&str>,
+    env: Option<&str>,
+    config: Option<&str>,
+    home: Option<&str>,
+) -> String {
+    if let Some(path) = [flag, env, config]
+        .into_iter()
+        .flatten()
+        .find(|path| !path.is_empty())
+    {
+        return path.to_owned();
+    }
+    match home.filter(|home| !home.is_empty()) {
+        Some(home) => Path::new(home)
+            .join(".local/share/clira/db.json")
+            .to_string_lossy()
+            .into_owned(),
+        None => FALLBACK_DB_PATH.to_owned(),
+    }
+}
+
+/// `run_cli` runs `command` against `db` and prints its results to `out`. With `json`, list
+/// and show commands print JSON instead of text. Returns `Err` if the command failed, in
+/// which case nothing was changed.
+pub fn run_cli(
+    command: BoardCommand,
+    json: bool,
+    db: &JiraDatabase,
+    out: &mut impl Write,
+) -> Result<()> {
+    match command {
+        BoardCommand::Dump => write_json(&db.read()?, out),
+        BoardCommand::ListEpics | BoardCommand::Epic(EpicCommand::List) => {
+            let epics = EpicView::list(&db.read()?);
+            if json {
+                return write_json(&epics, out);
+            }
+            for view in epics {
+                writeln!(out, "{}\t{}\t{}", view.id, view.epic.status, view.epic.name)?;
+            }
+            Ok(())
+        }
+        BoardCommand::Epic(EpicCommand::Show { id }) => {
+            let view = EpicView::with_stories(&db.read()?, id).ok_or(DbError::EpicNotFound(id))?;
+            if json {
+                return write_json(&view, out);
+            }
+            writeln!(
+                out,
+                "#{} {} ({})",
+                view.id, view.epic.name, view.epic.status
+            )?;
+            if !view.epic.description.is_empty() {
+                writeln!(out, "{}", view.epic.description)?;
+            }
+            for story in view.stories.into_iter().flatten() {
+                writeln!(
+                    out,
+                    "{}\t{}\t{}",
+                    story.id, story.story.status, story.story.name
+                )?;
+            }
+            Ok(())
+        }
+        BoardCommand::Epic(EpicCommand::Create { name, description }) => {
+            let epic_id = db.create_epic(&Epic::new(&name, &description))?;
+            db.record_audit("Created epic", Some(epic_id))?;
+            writeln!(out, "Created epic #{}", epic_id)?;
+            Ok(())
+        }
+        BoardCommand::Epic(EpicCommand::Delete { id, yes }) => {
+            if !yes {
+                bail!("pass --yes to delete epic #{} and its stories", id);
+            }
+            let story_count = db
+                .read()?
+                .epics
+                .get(&id)
+                .map_or(0, |epic| epic.story_ids.len());
+            db.delete_epic(id)?;
+            db.record_audit("Deleted epic", Some(id))?;
+            writeln!(
+                out,
+                "Deleted epic #{} and {} {}",
+                id,
+                story_count,
+                if story_count == 1 { "story" } else { "stories" }
+            )?;
+            Ok(())
+        }
+        BoardCommand::Story(StoryCommand::Create {
+            epic,
+            name,
+            description,
+        }) => {
+            let story_id = db.create_story(&Story::new(&name, &description), epic)?;
+            db.record_audit("Created story", Some(story_id))?;
+            writeln!(out, "Created story #{}", story_id)?;
+            Ok(())
+        }
+        BoardCommand::Story(StoryCommand::Status { id, status }) => {
+            let action = format!("Updated story status to {}", status);
+            let message = format!("Story #{} status → {}", id, status);
+            db.update_story_status(id, status)?;
+            db.record_audit(&action, Some(id))?;
+            writeln!(out, "{}", message)?;
+            Ok(())
+        }
+    }
+}
+
+/// `write_json` writes `value` to `out` as pretty-printed JSON.
+fn write_json(value: &impl Serialize, out: &mut impl Write) -> Result<()> {
+    serde_json::to_writer_pretty(&mut *out, value)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use crate::models::DatabaseState;
+
+    use super::*;
+
+    /// `run` parses `args` as a board command and runs it against `db`, returning what was
+    /// printed.
+    fn run(args: &[&str], db: &JiraDatabase) -> Result<String> {
+        let cli = Cli::try_parse_from(["clira"].iter().chain(args))?;
+        let Some(Command::Board(command)) = cli.command else {
+            panic!("{:?} is not a board command", args);
+        };
+        let mut out = Vec::new();
+        run_cli(command, cli.json, db, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    /// `fixture_board` writes a board with two epics to a temporary file and opens it.
+    fn fixture_board() -> (tempfile::NamedTempFile, JiraDatabase) {
+        let db = JiraDatabase::in_memory();
+        let epic_id = db
+            .create_epic(&Epic::new("Onboarding", "First run"))
+            .unwrap();
+        let story_id = db
+            .create_story(&Story::new("Welcome screen", "Greet the user"), epic_id)
+            .unwrap();
+        db.update_story_status(story_id, Status::InProgress)
+            .unwrap();
+        let other_epic_id = db.create_epic(&Epic::new("Reporting", "")).unwrap();
+        db.update_epic_status(other_epic_id, Status::Closed)
+            .unwrap();
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            "{}",
+            serde_json::to_string(&db.read().unwrap()).unwrap()
+        )
+        .unwrap();
+        let db = JiraDatabase::open_existing(file.path().to_str().unwrap()).unwrap();
+        (file, db)
+    }
+
+    #[test]
+    fn dump_should_round_trip() {
+        let (file, db) = fixture_board();
+        let before = std::fs::read_to_string(file.path()).unwrap();
+
+        let out = run(&["dump"], &db).unwrap();
+        let state: DatabaseState = serde_json::from_str(&out).unwrap();
+        assert_eq!(state, db.read().unwrap());
+        assert_eq!(std::fs::read_to_string(file.path()).unwrap(), before);
+    }
+
+    #[test]
+    fn list_epics_should_print_one_line_per_epic() {
+        let (_file, db) = fixture_board();
+        assert_eq!(
+            run(&["list-epics"], &db).unwrap(),
+            "0\tIn Progress\tOnboarding\n2\tClosed\tReporting\n"
+        );
+    }
+
+    #[test]
+    fn epic_list_should_print_json() {
+        let (_file, db) = fixture_board();
+        let out = run(&["epic", "list", "--json"], &db).unwrap();
+        let epics: Vec<EpicView> = serde_json::from_str(&out).unwrap();
+
+        let state = db.read().unwrap();
+        assert_eq!(epics.len(), state.epics.len());
+        for view in epics {
+            assert_eq!(view.epic, state.epics[&view.id]);
+            assert!(view.stories.is_none());
+        }
+    }
+
+    #[test]
+    fn epic_show_should_print_json_with_stories() {
+        let (_file, db) = fixture_board();
+        let out = run(&["--json", "epic", "show", "0"], &db).unwrap();
+        let view: EpicView = serde_json::from_str(&out).unwrap();
+
+        let state = db.read().unwrap();
+        assert_eq!(view.id, 0);
+        assert_eq!(view.epic, state.epics[&0]);
+        let stories = view.stories.unwrap();
+        assert_eq!(stories.len(), 1);
+        assert_eq!(stories[0].story, state.stories[&stories[0].id]);
+        assert!(run(&["epic", "show", "9", "--json"], &db).is_err());
+    }
+
+    #[test]
+    fn epic_show_should_print_stories() {
+        let (_file, db) = fixture_board();
+        assert_eq!(
+            run(&["epic", "show", "0"], &db).unwrap(),
+            "#0 Onboarding (In Progress)\nFirst run\n1\tIn Progress\tWelcome screen\n"
+        );
+    }
+
+    #[test]
+    fn run_cli_should_create_epics_and_stories() {
+        let db = JiraDatabase::in_memory();
+        assert_eq!(
+            run(
+                &[
+                    "epic",
+                    "create",
+                    "--name",
+                    "Epic 1",
+                    "--description",
+                    "First"
+                ],
+                &db
+            )
+            .unwrap(),
+            "Created epic #0\n"
+        );
+        assert_eq!(
+            run(
+                &["story", "create", "--epic", "0", "--name", "Story 1"],
+                &db
+            )
+            .unwrap(),
+            "Created story #1\n"
+        );
+        assert_eq!(run(&["epic", "list"], &db).unwrap(), "0\tOpen\tEpic 1\n");
+
+        let state = db.read().unwrap();
+        assert_eq!(state.epics[&0].description, "First");
+        assert_eq!(state.epics[&0].story_ids, vec![1]);
+        assert_eq!(state.stories[&1].name, "Story 1");
+        assert_eq!(state.audit.len(), 2);
+
+        assert!(run(&["story", "create", "--epic", "9", "--name", "Story"], &db).is_err());
+        assert!(run(&["epic", "create", "--name", " "], &db).is_err());
+    }
+
+    #[test]
+    fn run_cli_should_update_story_status() {
+        let db = JiraDatabase::in_memory();
+        run(&["epic", "create", "--name", "Epic 1"], &db).unwrap();
+        run(
+            &["story", "create", "--epic", "0", "--name", "Story 1"],
+            &db,
+        )
+        .unwrap();
+
+        assert_eq!(
+            run(&["story", "status", "1", "In Progress"], &db).unwrap(),
+            "Story #1 status → In Progress\n"
+        );
+        assert_eq!(db.read().unwrap().stories[&1].status, Status::InProgress);
+        assert_eq!(db.read().unwrap().epics[&0].status, Status::InProgress);
+        run(&["story", "status", "1", "closed"], &db).unwrap();
+        assert_eq!(db.read().unwrap().epics[&0].status, Status::Closed);
+        run(
+            &["story", "create", "--epic", "0", "--name", "Story 2"],
+            &db,
+        )
+        .unwrap();
+        assert_eq!(db.read().unwrap().epics[&0].status, Status::InProgress);
+        assert!(run(&["story", "status", "1", "done"], &db).is_err());
+        assert!(run(&["story", "status", "9", "closed"], &db).is_err());
+    }
+
+    #[test]
+    fn run_cli_should_require_yes_to_delete_epic() {
+        let db = JiraDatabase::in_memory();
+        run(&["epic", "create", "--name", "Epic 1"], &db).unwrap();
+        run(
+            &["story", "create", "--epic", "0", "--name", "Story 1"],
+            &db,
+        )
+        .unwrap();
+
+        assert!(run(&["epic", "delete", "0"], &db).is_err());
+        assert!(db.read().unwrap().epics.contains_key(&0));
+        assert_eq!(
+            run(&["epic", "delete", "0", "--yes"], &db).unwrap(),
+            "Deleted epic #0 and 1 story\n"
+        );
+        assert!(db.read().unwrap().epics.is_empty());
+    }
+
+    #[test]
+    fn resolve_db_path_should_prefer_flag_then_env_then_config_then_default() {
+        let home = Some("/home/me");
+        assert_eq!(
+            resolve_db_path(
+                Some("flag.json"),
+                Some("env.json"),
+                Some("config.json"),
+                home
+            ),
+            "flag.json"
+        );
+        assert_eq!(
+            resolve_db_path(None, Some("env.json"), Some("config.json"), home),
+            "env.json"
+        );
+        assert_eq!(
+            resolve_db_path(None, Some(""), Some("config.json"), home),
+            "config.json"
+        );
+        assert_eq!(
+            resolve_db_path(Some(""), Some(""), None, home),
+            "/home/me/.local/share/clira/db.json"
+        );
+        assert_eq!(resolve_db_path(None, None, None, None), FALLBACK_DB_PATH);
+    }
+
+    #[test]
+    fn watch_should_take_positive_seconds_without_keys() {
+        let cli = Cli::try_parse_from(["clira", "--watch", "5"]).unwrap();
+        assert_eq!(cli.watch, Some(5));
+        assert!(Cli::try_parse_from(["clira", "--watch", "0"]).is_err());
+        assert!(Cli::try_parse_from(["clira", "--watch", "5", "--keys"]).is_err());
+    }
+
+    #[test]
+    fn db_flag_should_be_accepted_after_subcommand() {
+        let cli = Cli::try_parse_from(["clira", "epic", "list", "--db", "board.json"]).unwrap();
+        assert_eq!(cli.db.as_deref(), Some("board.json"));
+    }
+}
@@ -0,0 +1,383 @@
+use std::{fmt::Write, fs, io::ErrorKind, path::Path};
+
+use anyhow::Result;
+
+use chrono::{
+    format::{Item, StrftimeItems},
+    NaiveDate,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{models::Template, theme::Theme, utils::DATE_FORMAT};
+
+/// `CONFIG_ENV_VAR` names the environment variable that points at another config file.
+pub const CONFIG_ENV_VAR: &str = "CLIRA_CONFIG";
+
+/// `Config` holds the user's settings, read from `~/.config/clira/config.toml` such as:
+/// ```toml
+/// rows_per_page = 20
+/// db_path = "/home/me/boards/work.json"
+/// confirm_exit = false
+/// sort_by_status = true
+/// date_format = "%d/%m/%Y"
+///
+/// [theme]
+/// resolved = "cyan"
+///
+/// [[templates]]
+/// name = "Release {date}"
+/// stories = [{ name = "Freeze the branch" }, { name = "Tag", description = "v{date}" }]
+/// ```
+/// Every setting is optional and falls back to its default.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// `rows_per_page` is the number of stories listed per page, instead of fitting them
+    /// to the terminal. `CLIRA_ROWS_PER_PAGE` takes precedence.
+    pub rows_per_page: Option<usize>,
+    /// `db_path` is the board file used when neither `--db` nor `CLIRA_DB` are set.
+    pub db_path: Option<String>,
+    /// `confirm_exit` is whether quitting asks for confirmation first.
+    pub confirm_exit: bool,
+    /// `sort_by_status` is whether the stories of an epic are listed by status, from Open to
+    /// Closed, instead of in the epic's order.
+    pub sort_by_status: bool,
+    /// `date_format` is the format of the dates entered by the user, in `strftime` syntax.
+    pub date_format: String,
+    pub theme: Theme,
+    /// `templates` are the epics that can be created with their stories in one go.
+    pub templates: Vec<Template>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            rows_per_page: None,
+            db_path: None,
+            confirm_exit: true,
+            sort_by_status: false,
+            date_format: DATE_FORMAT.to_owned(),
+            theme: Theme::default(),
+            templates: vec![],
+        }
+    }
+}
+
+impl Config {
+    /// `parse` reads the settings from the TOML `config`, along with warnings about the
+    /// entries that were ignored. Invalid settings keep their default, and so does every
+    /// setting if `config` is not valid TOML, so that a broken config never stops clira
+    /// from starting.
+    pub fn parse(config: &str) -> (Self, Vec<String>) {
+        let mut table: toml::Table = match toml::from_str(config) {
+            Ok(table) => table,
+            Err(error) => {
+                return (
+                    Self::default(),
+                    vec![format!("config: {}, using the defaults", error.message())],
+                )
+            }
+        };
+        let mut warnings = vec![];
+        let defaults = Self::default();
+        let (theme, theme_warnings) = Theme::parse(config).unwrap_or_default();
+        table.remove("theme");
+        warnings.extend(theme_warnings);
+
+        let rows_per_page =
+            setting(&mut table, "rows_per_page", &mut warnings).and_then(|rows: usize| {
+                if rows == 0 {
+                    warnings.push("config: rows_per_page must be at least 1, ignored".to_owned());
+                    return None;
+                }
+                Some(rows)
+            });
+        let db_path =
+            setting(&mut table, "db_path", &mut warnings).filter(|path: &String| !path.is_empty());
+        let confirm_exit =
+            setting(&mut table, "confirm_exit", &mut warnings).unwrap_or(defaults.confirm_exit);
+        let sort_by_status =
+            setting(&mut table, "sort_by_status", &mut warnings).unwrap_or(defaults.sort_by_status);
+        let date_format = setting(&mut table, "date_format", &mut warnings)
+            .and_then(|format: String| {
+                if is_date_format(&format) {
+                    return Some(format);
+                }
+                warnings.push(format!(
+                    "config: date_format {:?} cannot read dates, using {:?}",
+                    format, defaults.date_format
+                ));
+                None
+            })
+            .unwrap_or(defaults.date_format);
+        let templates = setting(&mut table, "templates", &mut warnings).unwrap_or_default();
+        for key in table.keys() {
+            warnings.push(format!("config: unknown setting {:?}, ignored", key));
+        }
+
+        let config = Self {
+            rows_per_page,
+            db_path,
+            confirm_exit,
+            sort_by_status,
+            date_format,
+            theme,
+            templates,
+        };
+        (config, warnings)
+    }
+
+    /// `save` writes the settings to the config file at `path`, creating its directory if
+    /// needed. Returns `Err` if the file could not be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut table = toml::Table::new();
+        if let Some(rows) = self.rows_per_page {
+            table.insert("rows_per_page".to_owned(), (rows as i64).into());
+        }
+        if let Some(db_path) = &self.db_path {
+            table.insert("db_path".to_owned(), db_path.clone().into());
+        }
+        table.insert("confirm_exit".to_owned(), self.confirm_exit.into());
+        table.insert("sort_by_status".to_owned(), self.sort_by_status.into());
+        table.insert("date_format".to_owned(), self.date_format.clone().into());
+        let theme: toml::Table = self
+            .theme
+            .entries()
+            .into_iter()
+            .map(|(key, color)| (key, color.into()))
+            .collect();
+        table.insert("theme".to_owned(), theme.into());
+        if !self.templates.is_empty() {
+            table.insert(
+                "templates".to_owned(),
+                toml::Value::try_from(&self.templates)?,
+            );
+        }
+
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string(&table)?)?;
+        Ok(())
+    }
+
+    /// `load` reads the settings from the config file at `path`, see `parse`. A missing file
+    /// means the default settings, and so does a file that could not be read, with a warning.
+    pub fn load(path: &Path) -> (Self, Vec<String>) {
+        match fs::read_to_string(path) {
+            Ok(config) => Self::parse(&config),
+            Err(error) if error.kind() == ErrorKind::NotFound => (Self::default(), vec![]),
+            Err(error) => (
+                Self::default(),
+                vec![format!("config: {}, using the defaults", error)],
+            ),
+        }
+    }
+
+    /// `path` returns where the config file is: the `CLIRA_CONFIG` environment variable
+    /// `env` if set, or else under the user's `home`. `None` is returned if neither is set.
+    /// Empty values are ignored.
+    pub fn path(env: Option<&str>, home: Option<&str>) -> Option<String> {
+        if let Some(path) = env.filter(|path| !path.is_empty()) {
+            return Some(path.to_owned());
+        }
+        home.filter(|home| !home.is_empty()).map(|home| {
+            Path::new(home)
+                .join(".config/clira/config.toml")
+                .to_string_lossy()
+                .into_owned()
+        })
+    }
+}
+
+/// `setting` takes the setting `key` out of `table`. `None` is returned if it is missing,
+/// or if it is not a `T`, with a warning.
+fn setting<T: DeserializeOwned>(
+    table: &mut toml::Table,
+    key: &str,
+    warnings: &mut Vec<String>,
+) -> Option<T> {
+    let value = table.remove(key)?;
+    match value.try_into() {
+        Ok(value) => Some(value),
+        Err(error) => {
+            warnings.push(format!("config: {} {}, ignored", key, error.message()));
+            None
+        }
+    }
+}
+
+/// `is_date_format` returns whether `format` writes dates that it can also read back.
+fn is_date_format(format: &str) -> bool {
+    if StrftimeItems::new(format).any(|item| item == Item::Error) {
+        return false;
+    }
+    let date = NaiveDate::from_ymd_opt(2024, 5, 31).expect("valid date");
+    // Formats with times cannot write a date, which fails instead of panicking here.
+    let mut text = String::new();
+    if write!(text, "{}", date.format(format)).is_err() {
+        return false;
+    }
+    NaiveDate::parse_from_str(&text, format) == Ok(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{models::StoryStub, theme::ThemeColor};
+
+    use super::*;
+
+    #[test]
+    fn parse_should_read_every_setting() {
+        let (config, warnings) = Config::parse(
+            r#"
+            rows_per_page = 20
+            db_path = "/boards/work.json"
+            confirm_exit = false
+            date_format = "%d/%m/%Y"
+
+            [theme]
+            resolved = "cyan"
+
+            [[templates]]
+            name = "Release {date}"
+            stories = [{ name = "Freeze the branch" }, { name = "Tag", description = "v{date}" }]
+            "#,
+        );
+        assert_eq!(
+            config,
+            Config {
+                rows_per_page: Some(20),
+                db_path: Some("/boards/work.json".to_owned()),
+                confirm_exit: false,
+                sort_by_status: false,
+                date_format: "%d/%m/%Y".to_owned(),
+                theme: Theme {
+                    resolved: ThemeColor::Cyan,
+                    ..Theme::default()
+                },
+                templates: vec![Template {
+                    name: "Release {date}".to_owned(),
+                    description: "".to_owned(),
+                    stories: vec![
+                        StoryStub {
+                            name: "Freeze the branch".to_owned(),
+                            description: "".to_owned(),
+                        },
+                        StoryStub {
+                            name: "Tag".to_owned(),
+                            description: "v{date}".to_owned(),
+                        },
+                    ],
+                }],
+            }
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn parse_should_default_missing_settings() {
+        let (config, warnings) = Config::parse("confirm_exit = false\n");
+        assert_eq!(
+            config,
+            Config {
+                confirm_exit: false,
+                ..Config::default()
+            }
+        );
+        assert!(warnings.is_empty());
+        assert_eq!(Config::parse(""), (Config::default(), vec![]));
+    }
+
+    #[test]
+    fn parse_should_warn_and_default_invalid_settings() {
+        let (config, warnings) = Config::parse(
+            r#"
+            rows_per_page = 0
+            db_path = 3
+            confirm_exit = "no"
+            sort_by_status = 1
+            date_format = "%H:%M"
+            colour = "always"
+
+            [theme]
+            closed = "teal"
+            "#,
+        );
+        assert_eq!(config, Config::default());
+        assert_eq!(warnings.len(), 7);
+        assert!(warnings[0].starts_with("theme: unknown color \"teal\""));
+        assert_eq!(
+            warnings[1],
+            "config: rows_per_page must be at least 1, ignored"
+        );
+        assert!(warnings[2].starts_with("config: db_path "));
+        assert!(warnings[3].starts_with("config: confirm_exit "));
+        assert!(warnings[4].starts_with("config: sort_by_status "));
+        assert_eq!(
+            warnings[5],
+            "config: date_format \"%H:%M\" cannot read dates, using \"%Y-%m-%d\""
+        );
+        assert_eq!(warnings[6], "config: unknown setting \"colour\", ignored");
+
+        let (config, warnings) = Config::parse("rows_per_page = ");
+        assert_eq!(config, Config::default());
+        assert_eq!(warnings.len(), 1);
+
+        let (config, warnings) = Config::parse("[[templates]]\ndescription = \"No name\"\n");
+        assert!(config.templates.is_empty());
+        assert!(warnings[0].starts_with("config: templates "));
+    }
+
+    #[test]
+    fn load_should_default_without_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        assert_eq!(Config::load(&path), (Config::default(), vec![]));
+
+        fs::write(&path, "[theme]\nresolved = \"cyan\"\n").unwrap();
+        assert_eq!(Config::load(&path).0.theme.resolved, ThemeColor::Cyan);
+    }
+
+    #[test]
+    fn save_should_round_trip_through_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clira/config.toml");
+        let mut theme = Theme::default();
+        theme.set_color(crate::models::Status::InProgress, ThemeColor::BrightMagenta);
+        let config = Config {
+            rows_per_page: Some(12),
+            db_path: Some("/boards/work.json".to_owned()),
+            confirm_exit: false,
+            sort_by_status: true,
+            date_format: "%d.%m.%Y".to_owned(),
+            theme,
+            templates: vec![Template {
+                name: "Release {date}".to_owned(),
+                description: "Every sprint".to_owned(),
+                stories: vec![StoryStub {
+                    name: "Tag".to_owned(),
+                    description: "".to_owned(),
+                }],
+            }],
+        };
+
+        config.save(&path).unwrap();
+        assert_eq!(Config::load(&path), (config, vec![]));
+
+        Config::default().save(&path).unwrap();
+        assert_eq!(Config::load(&path), (Config::default(), vec![]));
+    }
+
+    #[test]
+    fn path_should_prefer_env_then_home() {
+        assert_eq!(
+            Config::path(Some("other.toml"), Some("/home/me")),
+            Some("other.toml".to_owned())
+        );
+        assert_eq!(
+            Config::path(Some(""), Some("/home/me")),
+            Some("/home/me/.config/clira/config.toml".to_owned())
+        );
+        assert_eq!(Config::path(None, None), None);
+    }
+}
@@ -0,0 +1,247 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashSet},
+};
+
+use crate::models::DatabaseState;
+
+use super::{error::DbError, Database};
+
+/// `DryRunDatabase` wraps another `Database` and never writes to it. Writes are held as a
+/// pending state, which is read back until `discard_changes` describes and drops it, so that
+/// an action sees its own changes while it runs.
+pub struct DryRunDatabase {
+    db: Box<dyn Database>,
+    pending: RefCell<Option<DatabaseState>>,
+}
+
+impl DryRunDatabase {
+    /// `new` returns a `DryRunDatabase` on top of `db`.
+    pub fn new(db: Box<dyn Database>) -> Self {
+        Self {
+            db,
+            pending: RefCell::new(None),
+        }
+    }
+}
+
+impl Database for DryRunDatabase {
+    fn read(&self) -> Result<DatabaseState, DbError> {
+        match self.pending.borrow().as_ref() {
+            Some(state) => Ok(state.clone()),
+            None => self.db.read(),
+        }
+    }
+
+    fn write(&self, state: &DatabaseState) -> anyhow::Result<()> {
+        *self.pending.borrow_mut() = Some(state.clone());
+        Ok(())
+    }
+
+    fn refresh_if_changed(&self) -> Result<bool, DbError> {
+        self.db.refresh_if_changed()
+    }
+
+    fn discard_changes(&self) -> Result<Vec<String>, DbError> {
+        match self.pending.take() {
+            Some(state) => Ok(describe_changes(&self.db.read()?, &state)),
+            None => Ok(vec![]),
+        }
+    }
+}
+
+/// `describe_changes` lists what writing `after` over `before` would do, e.g. "would delete
+/// epic #3 and its 4 stories". Children deleted with their parent, and parents whose only
+/// change is a deleted or created child, are not listed separately. The audit log is
+/// ignored.
+fn describe_changes(before: &DatabaseState, after: &DatabaseState) -> Vec<String> {
+    let deleted_epics = deleted(&before.epics, &after.epics);
+    let deleted_stories = deleted(&before.stories, &after.stories);
+    let deleted_tasks = deleted(&before.tasks, &after.tasks);
+    let created_stories = created(&before.stories, &after.stories);
+    let created_tasks = created(&before.tasks, &after.tasks);
+    let touched_stories: HashSet<u32> = deleted_stories
+        .iter()
+        .chain(&created_stories)
+        .copied()
+        .collect();
+    let touched_tasks: HashSet<u32> = deleted_tasks
+        .iter()
+        .chain(&created_tasks)
+        .copied()
+        .collect();
+    let untouched = |ids: &[u32], touched: &HashSet<u32>| -> Vec<u32> {
+        ids.iter()
+            .filter(|id| !touched.contains(id))
+            .copied()
+            .collect()
+    };
+
+    let mut changes = vec![];
+    for id in &deleted_epics {
+        let stories = before.epics[id].story_ids.len();
+        changes.push(format!(
+            "would delete epic #{} and its {} {}",
+            id,
+            stories,
+            if stories == 1 { "story" } else { "stories" }
+        ));
+    }
+    for id in &deleted_stories {
+        if deleted_epics
+            .iter()
+            .any(|epic_id| before.epics[epic_id].story_ids.contains(id))
+        {
+            continue;
+        }
+        let tasks = before.stories[id].task_ids.len();
+        changes.push(format!(
+            "would delete story #{} and its {} {}",
+            id,
+            tasks,
+            if tasks == 1 { "task" } else { "tasks" }
+        ));
+    }
+    for id in &deleted_tasks {
+        if !deleted_stories
+            .iter()
+            .any(|story_id| before.stories[story_id].task_ids.contains(id))
+        {
+            changes.push(format!("would delete task #{}", id));
+        }
+    }
+    for id in deleted(&before.sprints, &after.sprints) {
+        changes.push(format!("would delete sprint #{}", id));
+    }
+
+    for id in created(&before.epics, &after.epics) {
+        changes.push(format!("would create epic #{}", id));
+    }
+    for id in &created_stories {
+        changes.push(format!("would create story #{}", id));
+    }
+    for id in &created_tasks {
+        changes.push(format!("would create task #{}", id));
+    }
+    for id in created(&before.sprints, &after.sprints) {
+        changes.push(format!("would create sprint #{}", id));
+    }
+
+    for id in updated(&before.epics, &after.epics, |epic| {
+        let mut epic = epic.clone();
+        epic.story_ids = untouched(&epic.story_ids, &touched_stories);
+        epic
+    }) {
+        changes.push(format!("would update epic #{}", id));
+    }
+    for id in updated(&before.stories, &after.stories, |story| {
+        let mut story = story.clone();
+        story.task_ids = untouched(&story.task_ids, &touched_tasks);
+        story.blocked_by = untouched(&story.blocked_by, &touched_stories);
+        story
+    }) {
+        changes.push(format!("would update story #{}", id));
+    }
+    for id in updated(&before.tasks, &after.tasks, Clone::clone) {
+        changes.push(format!("would update task #{}", id));
+    }
+    for id in updated(&before.sprints, &after.sprints, |sprint| {
+        let mut sprint = sprint.clone();
+        sprint.story_ids = untouched(&sprint.story_ids, &touched_stories);
+        sprint
+    }) {
+        changes.push(format!("would update sprint #{}", id));
+    }
+    changes
+}
+
+/// `deleted` returns the ids in `before` but not in `after`, in ascending order.
+fn deleted<T>(before: &BTreeMap<u32, T>, after: &BTreeMap<u32, T>) -> Vec<u32> {
+    before
+        .keys()
+        .filter(|id| !after.contains_key(id))
+        .copied()
+        .collect()
+}
+
+/// `created` returns the ids in `after` but not in `before`, in ascending order.
+fn created<T>(before: &BTreeMap<u32, T>, after: &BTreeMap<u32, T>) -> Vec<u32> {
+    deleted(after, before)
+}
+
+/// `updated` returns the ids in both `before` and `after` whose items differ once compared
+/// through `key`, in ascending order.
+fn updated<T, K: PartialEq>(
+    before: &BTreeMap<u32, T>,
+    after: &BTreeMap<u32, T>,
+    key: impl Fn(&T) -> K,
+) -> Vec<u32> {
+    before
+        .iter()
+        .filter(|(id, item)| after.get(id).is_some_and(|other| key(item) != key(other)))
+        .map(|(id, _)| *id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::{test_utils::MockDatabase, JiraDatabase},
+        models::{Epic, Status, Story},
+    };
+
+    use super::*;
+
+    fn board() -> (JiraDatabase, u32, u32) {
+        let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("Story 1", ""), epic_id)
+            .unwrap();
+        db.create_story(&Story::new("Story 2", ""), epic_id)
+            .unwrap();
+        (db, epic_id, story_id)
+    }
+
+    #[test]
+    fn describe_changes_should_fold_children_into_deleted_parent() {
+        let (db, epic_id, _) = board();
+        let before = db.read().unwrap();
+        db.delete_epic(epic_id).unwrap();
+        assert_eq!(
+            describe_changes(&before, &db.read().unwrap()),
+            vec![format!("would delete epic #{} and its 2 stories", epic_id)]
+        );
+    }
+
+    #[test]
+    fn describe_changes_should_not_list_parent_of_deleted_child() {
+        let (db, epic_id, story_id) = board();
+        let before = db.read().unwrap();
+        db.delete_story(story_id, epic_id).unwrap();
+        db.update_epic_status(epic_id, Status::InProgress).unwrap();
+        assert_eq!(
+            describe_changes(&before, &db.read().unwrap()),
+            vec![
+                format!("would delete story #{} and its 0 tasks", story_id),
+                format!("would update epic #{}", epic_id),
+            ]
+        );
+    }
+
+    #[test]
+    fn dry_run_should_read_back_pending_changes_until_discarded() {
+        let (db, epic_id, _) = board();
+        let before = db.read().unwrap();
+        let db = db.dry_run();
+        db.delete_epic(epic_id).unwrap();
+        assert!(db.read().unwrap().epics.is_empty());
+
+        assert_eq!(
+            db.discard_changes().unwrap(),
+            vec![format!("would delete epic #{} and its 2 stories", epic_id)]
+        );
+        assert_eq!(db.read().unwrap(), before);
+        assert!(db.discard_changes().unwrap().is_empty());
+    }
+}
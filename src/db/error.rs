@@ -0,0 +1,111 @@
+use std::io;
+
+use thiserror::Error;
+
+use super::validate::ValidationError;
+use crate::models::IdsExhausted;
+
+/// A `DbError` explains why the board could not be read or saved, why an item on it could
+/// not be found, or why a change was refused. `JiraDatabase` methods return it, so callers
+/// can match on it, and the UI turns it into an `anyhow::Error` with `?`.
+#[derive(Debug, Error)]
+pub enum DbError {
+    /// `EpicNotFound` means that there is no epic with the id.
+    #[error("no epic found for id {0}")]
+    EpicNotFound(u32),
+    /// `StoryNotFound` means that there is no story with the id.
+    #[error("no story found for id {0}")]
+    StoryNotFound(u32),
+    /// `TaskNotFound` means that there is no task with the id.
+    #[error("no task found for id {0}")]
+    TaskNotFound(u32),
+    /// `StoryOrTaskNotFound` means that there is neither a story nor a task with the id.
+    #[error("no story or task found for id {0}")]
+    StoryOrTaskNotFound(u32),
+    /// `SprintNotFound` means that there is no sprint with the id.
+    #[error("no sprint found for id {0}")]
+    SprintNotFound(u32),
+    /// `TrashedItemNotFound` means that there is no epic or story with the id in the trash.
+    #[error("no item in the trash for id {0}")]
+    TrashedItemNotFound(u32),
+    /// `Validation` means that a name, description or estimate is not allowed.
+    #[error(transparent)]
+    Validation(#[from] ValidationError),
+    /// `IdsExhausted` means that no more items can be created.
+    #[error(transparent)]
+    IdsExhausted(#[from] IdsExhausted),
+    /// `Invalid` means that the change breaks a rule of the board, e.g. a story that would
+    /// block itself. It holds the rule that was broken.
+    #[error("{0}")]
+    Invalid(String),
+    /// `Csv` means that a CSV file could not be read or written, e.g. because it is
+    /// malformed.
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+    /// `Export` means that an export could not be written.
+    #[error("the export could not be written: {0}")]
+    Export(#[from] io::Error),
+    /// `AlreadyExists` means that there is already a board at `path`, which is not
+    /// overwritten.
+    #[error("{path} already exists, use --force to overwrite it")]
+    AlreadyExists { path: String },
+    /// `NotFound` means that there is no file at `path`, e.g. because it was deleted while
+    /// clira was running.
+    #[error("there is no board at {path}")]
+    NotFound { path: String },
+    /// `Parse` means that the file at `path` does not hold a valid board.
+    #[error("{path} does not hold a valid board: {source}")]
+    Parse {
+        path: String,
+        source: serde_json::Error,
+    },
+    /// `Io` is any other error reading the file at `path`.
+    #[error("{path} could not be read: {source}")]
+    Io { path: String, source: io::Error },
+    /// `Write` means that the board was read, but a change to it could not be saved, e.g.
+    /// because another program changed the file in the meantime.
+    #[error("the board could not be saved: {source}")]
+    Write { source: anyhow::Error },
+}
+
+impl DbError {
+    /// `from_io` returns the `DbError` for `error`, raised while reading the file at `path`.
+    pub fn from_io(path: &str, error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::NotFound => Self::NotFound {
+                path: path.to_owned(),
+            },
+            _ => Self::Io {
+                path: path.to_owned(),
+                source: error,
+            },
+        }
+    }
+
+    /// `from_parse` returns the `DbError` for `error`, raised while parsing the file at
+    /// `path`.
+    pub fn from_parse(path: &str, error: serde_json::Error) -> Self {
+        Self::Parse {
+            path: path.to_owned(),
+            source: error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+
+    #[test]
+    fn from_io_should_tell_missing_files_apart() {
+        let error = DbError::from_io("db.json", io::Error::from(io::ErrorKind::NotFound));
+        assert!(matches!(error, DbError::NotFound { ref path } if path == "db.json"));
+        assert_eq!(error.to_string(), "there is no board at db.json");
+
+        let error = DbError::from_io("db.json", io::Error::from(io::ErrorKind::PermissionDenied));
+        assert!(matches!(error, DbError::Io { .. }));
+        assert!(error.source().is_some());
+    }
+}
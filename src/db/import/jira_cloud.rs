@@ -0,0 +1,398 @@
+use std::{collections::HashMap, fs};
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    db::{
+        validate::{self, ValidationError},
+        ImportSummary, JiraDatabase,
+    },
+    models::{DatabaseState, Epic, Status, Story, Task},
+};
+
+/// `UNPARENTED_EPIC_NAME` is the name of the epic that holds the imported issues that have no
+/// parent epic.
+pub const UNPARENTED_EPIC_NAME: &str = "Imported (unparented)";
+
+/// `JiraExport` is the subset of a Jira Cloud issue search export that is imported.
+#[derive(Deserialize)]
+struct JiraExport {
+    issues: Vec<JiraIssue>,
+}
+
+#[derive(Deserialize)]
+struct JiraIssue {
+    key: String,
+    fields: JiraFields,
+}
+
+#[derive(Deserialize)]
+struct JiraFields {
+    summary: Option<String>,
+    /// `description` is plain text in older exports and an Atlassian Document Format tree
+    /// in newer ones.
+    description: Option<Value>,
+    issuetype: Option<JiraIssueType>,
+    status: Option<JiraStatus>,
+    parent: Option<JiraParent>,
+    /// `epic_link` is the parent epic of an issue in company-managed projects.
+    #[serde(rename = "customfield_10014")]
+    epic_link: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JiraIssueType {
+    name: String,
+    #[serde(default)]
+    subtask: bool,
+}
+
+#[derive(Deserialize)]
+struct JiraStatus {
+    name: String,
+    #[serde(rename = "statusCategory")]
+    category: Option<JiraStatusCategory>,
+}
+
+#[derive(Deserialize)]
+struct JiraStatusCategory {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct JiraParent {
+    key: String,
+}
+
+/// `import_from_jira` reads the Jira Cloud JSON export at `path` and adds its issues to `db`
+/// in a single write. Issues of type Epic become epics, sub-tasks become tasks under their
+/// parent story, and every other issue becomes a story under its parent or epic link. Issues
+/// without a parent go into the "Imported (unparented)" epic. Statuses are mapped from their
+/// status category. Issues that cannot be placed, or whose summary or description is not
+/// valid, are reported in the summary's `skipped`.
+/// Returns `Err` if the file could not be read or parsed, or if there was an error
+/// reading/writing to the database.
+pub fn import_from_jira(path: &str, db: &JiraDatabase) -> Result<ImportSummary> {
+    let export: JiraExport = serde_json::from_str(&fs::read_to_string(path)?)?;
+    let mut state = db.read()?;
+    let mut summary = ImportSummary::default();
+
+    let (epics, others): (Vec<_>, Vec<_>) = export
+        .issues
+        .into_iter()
+        .filter_map(|issue| match issue.fields.summary.as_deref() {
+            Some(summary) if !summary.trim().is_empty() => Some(issue),
+            _ => {
+                summary
+                    .skipped
+                    .push(format!("{}: the issue has no summary", issue.key));
+                None
+            }
+        })
+        .partition(|issue| {
+            issue
+                .fields
+                .issuetype
+                .as_ref()
+                .is_some_and(|issuetype| issuetype.name == "Epic")
+        });
+    let (subtasks, stories): (Vec<_>, Vec<_>) = others.into_iter().partition(|issue| {
+        issue
+            .fields
+            .issuetype
+            .as_ref()
+            .is_some_and(|issuetype| issuetype.subtask)
+    });
+
+    let mut epic_ids = HashMap::new();
+    for issue in epics {
+        let Some((name, description)) = name_and_description(&issue, &mut summary) else {
+            continue;
+        };
+        let mut epic = Epic::new(&name, &description);
+        epic.status = status(&issue, &mut summary);
+        let id = state.next_id()?;
+        state.epics.insert(id, epic);
+        epic_ids.insert(issue.key, id);
+        summary.epics_created += 1;
+    }
+
+    let mut story_ids = HashMap::new();
+    for issue in stories {
+        let Some((name, description)) = name_and_description(&issue, &mut summary) else {
+            continue;
+        };
+        let parent = issue
+            .fields
+            .parent
+            .as_ref()
+            .map(|parent| parent.key.clone())
+            .or(issue.fields.epic_link.clone());
+        let epic_id = match parent {
+            Some(parent) => match epic_ids.get(&parent) {
+                Some(epic_id) => *epic_id,
+                None => {
+                    summary.skipped.push(format!(
+                        "{}: the parent epic {} is not in the export",
+                        issue.key, parent
+                    ));
+                    continue;
+                }
+            },
+            None => unparented_epic_id(&mut state, &mut summary)?,
+        };
+        let mut story = Story::new(&name, &description);
+        story.status = status(&issue, &mut summary);
+        let id = state.next_id()?;
+        state.stories.insert(id, story);
+        if let Some(epic) = state.epics.get_mut(&epic_id) {
+            epic.story_ids.push(id);
+        }
+        story_ids.insert(issue.key, id);
+        summary.stories_created += 1;
+    }
+
+    for issue in subtasks {
+        let parent = issue.fields.parent.as_ref().map(|parent| &parent.key);
+        let Some(story_id) = parent.and_then(|parent| story_ids.get(parent)).copied() else {
+            summary.skipped.push(format!(
+                "{}: the parent story of the sub-task is not in the export",
+                issue.key
+            ));
+            continue;
+        };
+        let Some((name, description)) = name_and_description(&issue, &mut summary) else {
+            continue;
+        };
+        let mut task = Task::new(&name, &description);
+        task.status = status(&issue, &mut summary);
+        let id = state.next_id()?;
+        state.tasks.insert(id, task);
+        if let Some(story) = state.stories.get_mut(&story_id) {
+            story.task_ids.push(id);
+        }
+        summary.tasks_created += 1;
+    }
+
+    db.write(&state)?;
+    Ok(summary)
+}
+
+/// `unparented_epic_id` returns the id of the epic for issues without a parent, creating it
+/// if it does not exist yet. Returns `Err` if no id is left for it.
+fn unparented_epic_id(state: &mut DatabaseState, summary: &mut ImportSummary) -> Result<u32> {
+    if let Some(id) = state
+        .epics
+        .iter()
+        .filter(|(_, epic)| epic.name == UNPARENTED_EPIC_NAME)
+        .map(|(id, _)| *id)
+        .min()
+    {
+        return Ok(id);
+    }
+    let id = state.next_id()?;
+    state.epics.insert(
+        id,
+        Epic::new(
+            UNPARENTED_EPIC_NAME,
+            "Issues imported from Jira without a parent",
+        ),
+    );
+    summary.epics_created += 1;
+    Ok(id)
+}
+
+/// `name_and_description` returns the validated name and description of the issue. Invalid
+/// issues are reported in the summary's `skipped`.
+fn name_and_description(
+    issue: &JiraIssue,
+    summary: &mut ImportSummary,
+) -> Option<(String, String)> {
+    let valid = || -> Result<_, ValidationError> {
+        let name = validate::name(issue.fields.summary.as_deref().unwrap_or_default())?;
+        Ok((name, validate::description(&description(issue))?))
+    };
+    match valid() {
+        Ok(valid) => Some(valid),
+        Err(error) => {
+            summary.skipped.push(format!("{}: {}", issue.key, error));
+            None
+        }
+    }
+}
+
+/// `description` returns the plain text of the issue's description.
+fn description(issue: &JiraIssue) -> String {
+    match &issue.fields.description {
+        Some(Value::String(text)) => text.trim().to_owned(),
+        Some(document) => {
+            let mut paragraphs = vec![];
+            collect_paragraphs(document, &mut paragraphs);
+            paragraphs.join("\n")
+        }
+        None => String::new(),
+    }
+}
+
+/// `collect_paragraphs` pushes the text of each paragraph in the Atlassian Document Format
+/// `node` to `paragraphs`.
+fn collect_paragraphs(node: &Value, paragraphs: &mut Vec<String>) {
+    let children = node["content"].as_array();
+    if node["type"] == "paragraph" {
+        let text: String = children
+            .into_iter()
+            .flatten()
+            .filter_map(|child| child["text"].as_str())
+            .collect();
+        paragraphs.push(text);
+        return;
+    }
+    for child in children.into_iter().flatten() {
+        collect_paragraphs(child, paragraphs);
+    }
+}
+
+/// `status` maps the status category of the issue to a `Status`. Unknown categories fall back
+/// to Open and are reported in the summary's warnings.
+fn status(issue: &JiraIssue, summary: &mut ImportSummary) -> Status {
+    let Some(status) = &issue.fields.status else {
+        return Status::Open;
+    };
+    match status
+        .category
+        .as_ref()
+        .map(|category| category.name.as_str())
+    {
+        Some("To Do") => Status::Open,
+        Some("In Progress") => Status::InProgress,
+        Some("Done") => Status::Closed,
+        _ => {
+            summary.warnings.push(format!(
+                "{}: unknown status {:?}, using Open",
+                issue.key, status.name
+            ));
+            Status::Open
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use crate::db::test_utils::MockDatabase;
+
+    use super::*;
+
+    const FIXTURE: &str = include_str!("fixtures/jira_cloud.json");
+
+    fn import_fixture(db: &JiraDatabase) -> ImportSummary {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "{}", FIXTURE).unwrap();
+        import_from_jira(file.path().to_str().unwrap(), db).unwrap()
+    }
+
+    fn find_epic<'a>(state: &'a DatabaseState, name: &str) -> &'a Epic {
+        state.epics.values().find(|epic| epic.name == name).unwrap()
+    }
+
+    #[test]
+    fn import_from_jira_should_map_issues() {
+        let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+        let summary = import_fixture(&db);
+        assert_eq!(summary.epics_created, 3);
+        assert_eq!(summary.stories_created, 3);
+        assert_eq!(summary.tasks_created, 1);
+
+        let state = db.read().unwrap();
+        let onboarding = find_epic(&state, "Onboarding");
+        assert_eq!(onboarding.status, Status::InProgress);
+        assert_eq!(onboarding.description, "Everything a new user sees first");
+        assert_eq!(find_epic(&state, "Reporting").status, Status::Closed);
+
+        let stories: Vec<_> = onboarding
+            .story_ids
+            .iter()
+            .map(|id| &state.stories[id])
+            .collect();
+        assert_eq!(stories[0].name, "Write the welcome screen");
+        assert_eq!(
+            stories[0].description,
+            "Greet the user\nand explain the menu"
+        );
+        assert_eq!(stories[0].status, Status::Closed);
+        assert_eq!(stories[1].name, "Add a help command");
+        assert_eq!(stories[1].description, "");
+        assert_eq!(stories[1].status, Status::InProgress);
+        assert_eq!(stories[1].task_ids.len(), 1);
+        let task = &state.tasks[&stories[1].task_ids[0]];
+        assert_eq!(task.name, "Spell-check the help text");
+        assert_eq!(task.status, Status::Open);
+
+        let unparented = find_epic(&state, UNPARENTED_EPIC_NAME);
+        assert_eq!(unparented.story_ids.len(), 1);
+        let story = &state.stories[&unparented.story_ids[0]];
+        assert_eq!(story.name, "Fix the crash on startup");
+        assert_eq!(story.status, Status::Open);
+    }
+
+    #[test]
+    fn import_from_jira_should_report_skipped_issues() {
+        let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+        let summary = import_fixture(&db);
+        assert_eq!(
+            summary.skipped,
+            vec![
+                "CLI-7: the issue has no summary",
+                "CLI-6: the parent epic OTHER-1 is not in the export",
+                "CLI-9: the parent story of the sub-task is not in the export",
+            ]
+        );
+        assert_eq!(
+            summary.warnings,
+            vec!["CLI-5: unknown status \"Triage\", using Open"]
+        );
+    }
+
+    #[test]
+    fn import_from_jira_should_reuse_unparented_epic() {
+        let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+        import_fixture(&db);
+        let summary = import_fixture(&db);
+        assert_eq!(summary.epics_created, 2);
+
+        let state = db.read().unwrap();
+        assert_eq!(find_epic(&state, UNPARENTED_EPIC_NAME).story_ids.len(), 2);
+    }
+
+    #[test]
+    fn import_from_jira_should_skip_invalid_names() {
+        let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            "{{ \"issues\": [{{ \"key\": \"CLI-1\", \"fields\": {{ \"summary\": \"{}\" }} }}] }}",
+            "a".repeat(40)
+        )
+        .unwrap();
+
+        let summary = import_from_jira(file.path().to_str().unwrap(), &db).unwrap();
+        assert_eq!(summary.stories_created, 0);
+        assert_eq!(
+            summary.skipped,
+            vec!["CLI-1: a name can be at most 30 characters long, this one is 40"]
+        );
+        assert!(db.read().unwrap().epics.is_empty());
+    }
+
+    #[test]
+    fn import_from_jira_should_fail_on_invalid_json() {
+        let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "{{ \"issues\": ").unwrap();
+        assert!(import_from_jira(file.path().to_str().unwrap(), &db).is_err());
+        assert!(db.read().unwrap().epics.is_empty());
+    }
+}
@@ -0,0 +1 @@
+pub mod jira_cloud;
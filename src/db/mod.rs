@@ -0,0 +1,4060 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, VecDeque},
+    fs,
+    io::{self, Read, Write},
+    time::SystemTime,
+};
+
+use anyhow::bail;
+use chrono::Utc;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use self::error::DbError;
+use crate::models::{
+    derive_status, AuditEntry, ChecklistItem, Comment, DatabaseState, Direction, Epic, EpicBundle,
+    RecentItem, Sprint, Status, Story, Task, Template, TrashedEpic, TrashedStory, WorkEntry,
+};
+
+/// `UNDO_HISTORY_LIMIT` is the number of snapshots kept by `JiraDatabase` for `undo`.
+pub const UNDO_HISTORY_LIMIT: usize = 20;
+
+/// `AUDIT_LOG_LIMIT` is the number of audit entries kept in the database. Older entries are
+/// dropped first.
+pub const AUDIT_LOG_LIMIT: usize = 500;
+
+/// `RECENT_LIMIT` is the number of recently viewed epics and stories kept in the database.
+pub const RECENT_LIMIT: usize = 10;
+
+/// `RESTORED_EPIC_NAME` is the name of the epic that stories are restored to when the epic
+/// they were deleted from is gone.
+pub const RESTORED_EPIC_NAME: &str = "Restored items";
+
+pub mod dry_run;
+pub mod error;
+pub mod import;
+pub mod validate;
+
+/// `StoryRecord` is a story along with its id and the id of its epic, written as one line
+/// by `JiraDatabase::export_stories_jsonl`.
+#[derive(Serialize)]
+struct StoryRecord<'a> {
+    id: u32,
+    #[serde(rename = "epicId")]
+    epic_id: Option<u32>,
+    #[serde(flatten)]
+    story: &'a Story,
+}
+
+/// `ImportSummary` describes what an import, such as `JiraDatabase::import_csv`, created.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub epics_created: usize,
+    pub stories_created: usize,
+    pub tasks_created: usize,
+    /// `stories_skipped` counts the stories that already existed when skipping existing
+    /// stories.
+    pub stories_skipped: usize,
+    /// `skipped` describes the records that could not be imported.
+    pub skipped: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// `CsvRow` is a row read by `JiraDatabase::import_csv`. Columns are matched by header, so
+/// files written by `export_csv` can be imported too.
+#[derive(Deserialize)]
+struct CsvRow {
+    #[serde(rename = "Epic Name")]
+    epic_name: String,
+    #[serde(rename = "Story Name", default)]
+    story_name: String,
+    #[serde(rename = "Description", default)]
+    description: String,
+    #[serde(rename = "Status", default)]
+    status: String,
+}
+
+/// `JiraDatabase` is the main database for the application to interact with. There should be at
+/// most one instance of this type. Instances need not be mutable.
+pub struct JiraDatabase {
+    pub db: Box<dyn Database>,
+    /// `history` holds the states that preceded the most recent writes, oldest first.
+    history: RefCell<VecDeque<DatabaseState>>,
+    /// `undo_group` is `Some` while writes are being grouped into a single undo step. The
+    /// inner value records whether the group's snapshot has been taken.
+    undo_group: Cell<Option<bool>>,
+    /// `saves` counts the changes saved so far, see `save_count`.
+    saves: Cell<u64>,
+    /// `recent` holds the recently viewed items while they differ from the saved ones, see
+    /// `record_recent`.
+    recent: RefCell<Option<Vec<RecentItem>>>,
+}
+
+/// `Database` outlines the main functionalities of a database. Use `read` to fetch the current
+/// state of the database.
+pub trait Database {
+    fn read(&self) -> Result<DatabaseState, DbError>;
+    /// `write` saves `state`. Why it failed is up to the implementation, `JiraDatabase`
+    /// reports it as a `DbError::Write`.
+    fn write(&self, state: &DatabaseState) -> anyhow::Result<()>;
+    /// `refresh_if_changed` returns whether the database was changed by someone else since it
+    /// was last written or refreshed, and accepts the change.
+    fn refresh_if_changed(&self) -> Result<bool, DbError> {
+        Ok(false)
+    }
+    /// `discard_changes` drops the changes that were held back instead of written, and
+    /// describes them. Only a dry run holds changes back.
+    fn discard_changes(&self) -> Result<Vec<String>, DbError> {
+        Ok(vec![])
+    }
+}
+
+/// `JSONFileDatabase` stores the database in a JSON file. The modification time of the file
+/// is tracked to notice when another program changes it.
+struct JSONFileDatabase {
+    pub file_path: String,
+    /// `modified` is the modification time of the file when it was last written or
+    /// refreshed.
+    modified: Cell<Option<SystemTime>>,
+}
+
+impl JSONFileDatabase {
+    fn new(file_path: &str) -> Self {
+        Self {
+            file_path: file_path.to_owned(),
+            modified: Cell::new(None),
+        }
+    }
+
+    /// `current_modified` returns the modification time of the file, or `None` if it does
+    /// not exist.
+    fn current_modified(&self) -> Option<SystemTime> {
+        fs::metadata(&self.file_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+}
+
+impl JiraDatabase {
+    /// `new` creates a new instance of the `JiraDatabase`. There should be a single instance of
+    /// this type to avoid any issues when reading and writing to disk. Returns `JiraDatabase`
+    /// wrapped in `Result`.
+    ///
+    /// Missing parent directories of `file_path` are created, and a missing or empty file
+    /// starts as an empty board. `Err` means there was a problem initializing the database,
+    /// e.g. a `DbError` if the file holds something other than a board, which is left as is.
+    pub fn new(file_path: &str) -> Result<Self, DbError> {
+        create_parent_dirs(file_path)?;
+        let db = JSONFileDatabase::new(file_path);
+        let state = match db.read() {
+            Ok(state) => state,
+            Err(error) if is_missing_or_empty(&error, file_path) => DatabaseState::default(),
+            Err(error) => return Err(error),
+        };
+        db.write(&state)
+            .map_err(|source| DbError::Write { source })?;
+        Ok(Self::from_database(Box::new(db)))
+    }
+
+    /// `open_existing` opens the database at `file_path` without writing to it. Returns `Err`
+    /// if the file does not exist or does not hold a valid board.
+    pub fn open_existing(file_path: &str) -> Result<Self, DbError> {
+        let db = JSONFileDatabase::new(file_path);
+        db.read()?;
+        Ok(Self::from_database(Box::new(db)))
+    }
+
+    /// `init_empty` creates the file at `file_path`, and any missing parent directories,
+    /// holding an empty board. Returns `Err` if the file already has content and `force` is
+    /// not set, or if the file could not be written.
+    pub fn init_empty(file_path: &str, force: bool) -> Result<(), DbError> {
+        let path = std::path::Path::new(file_path);
+        if !force && fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) {
+            return Err(DbError::AlreadyExists {
+                path: file_path.to_owned(),
+            });
+        }
+        create_parent_dirs(file_path)?;
+        let db = JSONFileDatabase::new(file_path);
+        db.write(&DatabaseState::default())
+            .map_err(|source| DbError::Write { source })
+    }
+
+    /// `reset_to_empty` replaces the board with an empty one, e.g. to recreate a board file
+    /// that was deleted. Returns `Err` if the database could not be written.
+    pub fn reset_to_empty(&self) -> Result<(), DbError> {
+        self.save(&DatabaseState::default())
+    }
+
+    /// `in_memory` creates a new instance of the `JiraDatabase` that is never read from or
+    /// written to disk. Changes are discarded when the program exits.
+    pub fn in_memory() -> Self {
+        Self::from_database(Box::new(MemoryDatabase::new()))
+    }
+
+    /// `from_database` creates a new instance of the `JiraDatabase` on top of an existing
+    /// `Database` implementation, with an empty undo history.
+    pub fn from_database(db: Box<dyn Database>) -> Self {
+        Self {
+            db,
+            history: RefCell::new(VecDeque::new()),
+            undo_group: Cell::new(None),
+            saves: Cell::new(0),
+            recent: RefCell::new(None),
+        }
+    }
+
+    /// `dry_run` turns the database into a dry run: changes are held back instead of
+    /// written, until `discard_changes` describes and drops them.
+    pub fn dry_run(self) -> Self {
+        Self::from_database(Box::new(dry_run::DryRunDatabase::new(self.db)))
+    }
+
+    /// `discard_changes` drops the changes made since it was last called if this is a dry
+    /// run, and describes them, e.g. "would delete epic #3 and its 4 stories". Returns `Err`
+    /// if there was an error reading the database.
+    pub fn discard_changes(&self) -> Result<Vec<String>, DbError> {
+        self.db.discard_changes()
+    }
+
+    /// `save_count` returns how many changes to the board were saved, so that callers can
+    /// tell whether an action changed it. Writing the audit log or the recently viewed items
+    /// does not count.
+    pub fn save_count(&self) -> u64 {
+        self.saves.get()
+    }
+
+    /// `save` writes `state` to the underlying database. Returns a `DbError::Write` if it
+    /// could not be written, to tell it apart from errors reading the board. The recently
+    /// viewed items of `state` are saved along with it.
+    fn save(&self, state: &DatabaseState) -> Result<(), DbError> {
+        self.db
+            .write(state)
+            .map_err(|source| DbError::Write { source })?;
+        self.recent.replace(None);
+        Ok(())
+    }
+
+    /// `write` records the current state of the database in the undo history and then
+    /// saves `state`. Only the first write of an undo group is recorded.
+    fn write(&self, state: &DatabaseState) -> Result<(), DbError> {
+        let previous = self.db.read()?;
+        match self.undo_group.get() {
+            Some(true) => {}
+            group => {
+                let mut history = self.history.borrow_mut();
+                if history.len() == UNDO_HISTORY_LIMIT {
+                    history.pop_front();
+                }
+                history.push_back(previous);
+                if group.is_some() {
+                    self.undo_group.set(Some(true));
+                }
+            }
+        }
+        self.save(state)?;
+        self.saves.set(self.saves.get() + 1);
+        Ok(())
+    }
+
+    /// `refresh_if_changed` checks whether the underlying database was changed by another
+    /// program and accepts the change so that it can be written to again. The undo history
+    /// is cleared, since undoing would overwrite the outside change. Returns whether the
+    /// database was changed, or `Err` if the changed database could not be read.
+    pub fn refresh_if_changed(&self) -> Result<bool, DbError> {
+        let changed = self.db.refresh_if_changed()?;
+        if changed {
+            self.history.borrow_mut().clear();
+        }
+        Ok(changed)
+    }
+
+    /// `begin_undo_group` starts grouping writes so that they are undone together. Every
+    /// call must be paired with `end_undo_group`.
+    pub fn begin_undo_group(&self) {
+        self.undo_group.set(Some(false));
+    }
+
+    /// `end_undo_group` stops grouping writes started with `begin_undo_group`.
+    pub fn end_undo_group(&self) {
+        self.undo_group.set(None);
+    }
+
+    /// `undo` restores the database to the state before the most recent write (or undo
+    /// group). The audit log and the recently viewed items are kept as is, except for items
+    /// that no longer exist. Returns `false` wrapped in a `Result` if there is nothing to
+    /// undo.
+    ///
+    /// `Err` means there was a problem reading/writing to the underlying database.
+    pub fn undo(&self) -> Result<bool, DbError> {
+        let previous = self.history.borrow_mut().pop_back();
+        match previous {
+            Some(mut state) => {
+                let current = self.read()?;
+                state.audit = current.audit;
+                state.recent = current.recent;
+                state.prune_recent();
+                self.save(&state)?;
+                self.saves.set(self.saves.get() + 1);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// `record_audit` appends an entry describing `action` on the item `id` to the audit log,
+    /// dropping the oldest entries beyond `AUDIT_LOG_LIMIT`. The log is not part of the undo
+    /// history. Returns `Err` if there was an error reading/writing to the database.
+    pub fn record_audit(&self, action: &str, id: Option<u32>) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        state.audit.push(AuditEntry {
+            at: Utc::now(),
+            action: action.to_string(),
+            id,
+        });
+        if state.audit.len() > AUDIT_LOG_LIMIT {
+            let overflow = state.audit.len() - AUDIT_LOG_LIMIT;
+            state.audit.drain(..overflow);
+        }
+        self.save(&state)
+    }
+
+    /// `record_recent` moves `item` to the front of the recently viewed items, dropping the
+    /// oldest items beyond `RECENT_LIMIT`. Like the audit log, they are not part of the undo
+    /// history. They are kept in memory until the next write or `save_recent`, so that
+    /// viewing an item does not write the board. Returns `Err` if there was an error reading
+    /// the database.
+    pub fn record_recent(&self, item: RecentItem) -> Result<(), DbError> {
+        let mut recent = self.read()?.recent;
+        recent.retain(|recent| *recent != item);
+        recent.insert(0, item);
+        recent.truncate(RECENT_LIMIT);
+        self.recent.replace(Some(recent));
+        Ok(())
+    }
+
+    /// `save_recent` writes the recently viewed items kept in memory by `record_recent`, if
+    /// any. Returns `Err` if there was an error reading/writing to the database.
+    pub fn save_recent(&self) -> Result<(), DbError> {
+        if self.recent.borrow().is_none() {
+            return Ok(());
+        }
+        let state = self.read()?;
+        self.save(&state)
+    }
+
+    /// `epic_id_for_story` returns the id of the epic that owns the story `story_id`, or
+    /// `None` if no epic does. Returns `Err` if there was an error reading the database.
+    pub fn epic_id_for_story(&self, story_id: u32) -> Result<Option<u32>, DbError> {
+        Ok(self.read()?.epic_id_for_story(story_id))
+    }
+
+    /// `story_id_for_task` returns the id of the story that owns the task `task_id`, or
+    /// `None` if no story does. Returns `Err` if there was an error reading the database.
+    pub fn story_id_for_task(&self, task_id: u32) -> Result<Option<u32>, DbError> {
+        Ok(self.read()?.story_id_for_task(task_id))
+    }
+
+    /// `read` reads the data from the database and returns a `DatabaseState` wrapped in a
+    /// `Result`.
+    ///
+    /// `Err` means there was a problem reading from the underlying database.
+    pub fn read(&self) -> Result<DatabaseState, DbError> {
+        let mut state = self.db.read()?;
+        if let Some(recent) = self.recent.borrow().as_ref() {
+            state.recent = recent.clone();
+        }
+        Ok(state)
+    }
+
+    /// `create_epic` writes a new epic to the database. Returns the epic's corresponding id
+    /// wrapped in a `Result`.
+    ///
+    /// `Err` will explain the cause, but may be for one of the following reasons:
+    ///   - The epic's name or description is not valid
+    ///   - There was a problem reading from the database
+    ///   - There was a problem writing to the database
+    pub fn create_epic(&self, epic: &Epic) -> Result<u32, DbError> {
+        let epic = Epic {
+            name: validate::name(&epic.name)?,
+            description: validate::description(&epic.description)?,
+            ..epic.clone()
+        };
+        let mut state = self.read()?;
+        let id = state.next_id()?;
+        state.epics.insert(id, epic);
+        self.write(&state)?;
+        Ok(id)
+    }
+
+    /// `create_story` adds a new story to the epic `epic_id` and writes to the database, along
+    /// with the epic's status derived again, see `derive_epic_status`. Returns the story's
+    /// corresponding id wrapped in a `Result`.
+    ///
+    /// `Err` will explain the cause, but may be for one of the following reasons:
+    ///   - The story's name or description is not valid
+    ///   - There was a problem reading from the database
+    ///   - An epic does not exist for the input parameter `epic_id`
+    ///   - There was a problem writing to the database
+    pub fn create_story(&self, story: &Story, epic_id: u32) -> Result<u32, DbError> {
+        let story = Story {
+            name: validate::name(&story.name)?,
+            description: validate::description(&story.description)?,
+            ..story.clone()
+        };
+        let mut state = self.read()?;
+        let id = state.next_id()?;
+
+        let mut epic = state
+            .epics
+            .get(&epic_id)
+            .ok_or(DbError::EpicNotFound(epic_id))
+            .cloned()?;
+        epic.story_ids.push(id);
+        state.epics.insert(epic_id, epic);
+
+        state.stories.insert(id, story);
+        derive_epic_status(&mut state, epic_id);
+        self.write(&state)?;
+        Ok(id)
+    }
+
+    /// `create_stories_bulk` creates a story for each of `names`, with an empty description,
+    /// at the end of the epic `epic_id`. The stories are written at once. Returns their ids
+    /// in the order of `names`, or `Err` if one of the names is not valid, if the epic was
+    /// not found or if there was an error reading/writing to the database. Nothing is
+    /// created on `Err`.
+    pub fn create_stories_bulk(&self, epic_id: u32, names: &[String]) -> Result<Vec<u32>, DbError> {
+        let names = names
+            .iter()
+            .map(|name| validate::name(name))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut state = self.read()?;
+        let mut epic = state
+            .epics
+            .get(&epic_id)
+            .ok_or(DbError::EpicNotFound(epic_id))
+            .cloned()?;
+        let mut story_ids = vec![];
+        for name in names {
+            let id = state.next_id()?;
+            epic.story_ids.push(id);
+            state.stories.insert(id, Story::new(&name, ""));
+            story_ids.push(id);
+        }
+        state.epics.insert(epic_id, epic);
+        self.write(&state)?;
+        Ok(story_ids)
+    }
+
+    /// `apply_template` creates an epic from `template`, with a story for each of its stubs in
+    /// order, all under new ids. Placeholders are written as is, see
+    /// `Template::fill_placeholders`. Returns the id of the new epic, or `Err` if a name or
+    /// description is not valid or if there was an error reading/writing to the database.
+    /// Nothing is created on `Err`.
+    pub fn apply_template(&self, template: &Template) -> Result<u32, DbError> {
+        let mut epic = Epic::new(
+            &validate::name(&template.name)?,
+            &validate::description(&template.description)?,
+        );
+        let stories = template
+            .stories
+            .iter()
+            .map(|stub| {
+                Ok(Story::new(
+                    &validate::name(&stub.name)?,
+                    &validate::description(&stub.description)?,
+                ))
+            })
+            .collect::<Result<Vec<_>, DbError>>()?;
+        let mut state = self.read()?;
+        let epic_id = state.next_id()?;
+        state.epics.insert(epic_id, epic.clone());
+        for story in stories {
+            let id = state.next_id()?;
+            epic.story_ids.push(id);
+            state.stories.insert(id, story);
+        }
+        state.epics.insert(epic_id, epic);
+        self.write(&state)?;
+        Ok(epic_id)
+    }
+
+    /// `duplicate_epic` deep-copies the epic `epic_id`, its stories and their tasks under new
+    /// ids. The copied epic and stories have their names suffixed with " (copy)", see
+    /// `copy_name`, and every copy starts out Open, unarchived and with its checklist
+    /// unchecked. Returns the id of the new epic, or `Err` if the epic was not found or if
+    /// there was an error reading/writing to the database.
+    pub fn duplicate_epic(&self, epic_id: u32) -> Result<u32, DbError> {
+        let mut state = self.read()?;
+        let epic = state
+            .epics
+            .get(&epic_id)
+            .ok_or(DbError::EpicNotFound(epic_id))
+            .cloned()?;
+        let new_epic_id = state.next_id()?;
+        let mut new_epic = Epic {
+            name: copy_name(&epic.name)?,
+            status: Status::Open,
+            status_override: false,
+            archived: false,
+            story_ids: vec![],
+            ..epic.clone()
+        };
+        for story_id in &epic.story_ids {
+            let Some(story) = state.stories.get(story_id).cloned() else {
+                continue;
+            };
+            let new_story_id = state.next_id()?;
+            let mut new_story = Story {
+                name: copy_name(&story.name)?,
+                status: Status::Open,
+                checklist: unchecked(&story.checklist),
+                blocked_by: vec![],
+                work_log: vec![],
+                comments: vec![],
+                task_ids: vec![],
+                ..story.clone()
+            };
+            for task_id in &story.task_ids {
+                let Some(task) = state.tasks.get(task_id).cloned() else {
+                    continue;
+                };
+                let new_task_id = state.next_id()?;
+                state.tasks.insert(
+                    new_task_id,
+                    Task {
+                        status: Status::Open,
+                        checklist: unchecked(&task.checklist),
+                        ..task
+                    },
+                );
+                new_story.task_ids.push(new_task_id);
+            }
+            state.stories.insert(new_story_id, new_story);
+            new_epic.story_ids.push(new_story_id);
+        }
+        state.epics.insert(new_epic_id, new_epic);
+        self.write(&state)?;
+        Ok(new_epic_id)
+    }
+
+    /// `create_task` creates a new Task with Story `story_id` as the parent. Returns the Tasks'
+    /// id id `Ok`. If `Err` is returned, the task's name or description is not valid, there
+    /// was an issue reading/writing to db or the `story_id` is invalid.
+    pub fn create_task(&self, task: &Task, story_id: u32) -> Result<u32, DbError> {
+        let task = Task {
+            name: validate::name(&task.name)?,
+            description: validate::description(&task.description)?,
+            ..task.clone()
+        };
+        let mut state = self.read()?;
+        let id = state.next_id()?;
+        let mut story = state
+            .stories
+            .get(&story_id)
+            .ok_or(DbError::StoryNotFound(story_id))
+            .cloned()?;
+        story.task_ids.push(id);
+        state.stories.insert(story_id, story);
+        state.tasks.insert(id, task);
+        self.write(&state)?;
+        Ok(id)
+    }
+
+    /// `update_epic_name` updates the name of the epic `id`. Returns `Err` if the name is not
+    /// valid, if epic was not found or if there was an error reading/writinig to the database.
+    pub fn update_epic_name(&self, id: u32, name: &str) -> Result<(), DbError> {
+        let name = validate::name(name)?;
+        let mut state = self.read()?;
+        if let Some(epic) = state.epics.get(&id) {
+            let mut epic = epic.clone();
+            epic.name = name;
+            state.epics.insert(id, epic);
+            self.write(&state)?;
+        } else {
+            return Err(DbError::EpicNotFound(id));
+        }
+        Ok(())
+    }
+
+    /// `update_epic_description` updates the description of the epic `id`. Returns
+    /// `Err` if the description is not valid, if epic was not found or if there was an
+    /// error reading/writinig to the database.
+    pub fn update_epic_description(&self, id: u32, description: &str) -> Result<(), DbError> {
+        let description = validate::description(description)?;
+        let mut state = self.read()?;
+        if let Some(epic) = state.epics.get(&id) {
+            let mut epic = epic.clone();
+            epic.description = description;
+            state.epics.insert(id, epic);
+            self.write(&state)?;
+        } else {
+            return Err(DbError::EpicNotFound(id));
+        }
+        Ok(())
+    }
+
+    /// `update_epic_status` updates the status of the epic `id` to the new status `status`. Returns
+    /// an empty tuple wrapped in a `Result`.
+    ///
+    /// `Err` will explain the cause, but may be for one of the following reasons:
+    ///   - There was a problem reading from the database
+    ///   - An epic does not exist for the input parameter `id`
+    ///   - There was a problem writing to the database
+    pub fn update_epic_status(&self, id: u32, status: Status) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        let mut epic = state
+            .epics
+            .get(&id)
+            .ok_or(DbError::EpicNotFound(id))
+            .cloned()?;
+        epic.status = status;
+
+        state.epics.insert(id, epic);
+        self.write(&state)?;
+        Ok(())
+    }
+
+    /// `set_epic_status_override` marks whether the status of the epic `id` was chosen
+    /// manually. Returns `Err` if the epic was not found or if there was an error
+    /// reading/writing to the database.
+    pub fn set_epic_status_override(&self, id: u32, status_override: bool) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        let mut epic = state
+            .epics
+            .get(&id)
+            .ok_or(DbError::EpicNotFound(id))
+            .cloned()?;
+        epic.status_override = status_override;
+        state.epics.insert(id, epic);
+        self.write(&state)?;
+        Ok(())
+    }
+
+    /// `set_epic_archived` archives the epic `id`, or brings it back when `archived` is
+    /// false. Returns `Err` if the epic was not found or if there was an error
+    /// reading/writing to the database.
+    pub fn set_epic_archived(&self, id: u32, archived: bool) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        let mut epic = state
+            .epics
+            .get(&id)
+            .ok_or(DbError::EpicNotFound(id))
+            .cloned()?;
+        epic.archived = archived;
+        state.epics.insert(id, epic);
+        self.write(&state)?;
+        Ok(())
+    }
+
+    /// `update_epic_assignee` sets the assignee of the epic `id` to `assignee`. Passing
+    /// `None` unassigns the epic. Returns `Err` if the epic was not found or if there was
+    /// an error reading/writing to the database.
+    pub fn update_epic_assignee(&self, id: u32, assignee: Option<&str>) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        let mut epic = state
+            .epics
+            .get(&id)
+            .ok_or(DbError::EpicNotFound(id))
+            .cloned()?;
+        epic.assignee = assignee.map(|assignee| assignee.to_string());
+        state.epics.insert(id, epic);
+        self.write(&state)?;
+        Ok(())
+    }
+
+    /// `update_story_name` updates the name of the story `id`. Returns `Err` if the name is
+    /// not valid, if the story was not found or if there was an error reading/writing to
+    /// the database.
+    pub fn update_story_name(&self, id: u32, name: &str) -> Result<(), DbError> {
+        let name = validate::name(name)?;
+        let mut state = self.read()?;
+        if let Some(story) = state.stories.get(&id) {
+            let mut story = story.clone();
+            story.name = name;
+            state.stories.insert(id, story);
+            self.write(&state)?;
+            Ok(())
+        } else {
+            Err(DbError::StoryNotFound(id))
+        }
+    }
+
+    /// `update_story_description` updates the description of the story `id`. Returns
+    /// `Err` if the description is not valid, if the story was not found or if there was an
+    /// error reading/writing to the database.
+    pub fn update_story_description(&self, id: u32, description: &str) -> Result<(), DbError> {
+        let description = validate::description(description)?;
+        let mut state = self.read()?;
+        if let Some(story) = state.stories.get(&id) {
+            let mut story = story.clone();
+            story.description = description;
+            state.stories.insert(id, story);
+            self.write(&state)?;
+            Ok(())
+        } else {
+            Err(DbError::StoryNotFound(id))
+        }
+    }
+
+    /// `update_derived_epic_status` derives the status of the epic `epic_id` from its stories
+    /// again, see `derive_epic_status`, e.g. after stories were moved in or out of it. Returns
+    /// `Err` if the epic was not found or if there was an error reading/writing to the
+    /// database.
+    pub fn update_derived_epic_status(&self, epic_id: u32) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        let before = state
+            .epics
+            .get(&epic_id)
+            .ok_or(DbError::EpicNotFound(epic_id))?
+            .status
+            .clone();
+        derive_epic_status(&mut state, epic_id);
+        if state.epics[&epic_id].status != before {
+            self.write(&state)?;
+        }
+        Ok(())
+    }
+
+    /// `update_story_status` updates the status of the `id` to the new status `status`, and
+    /// derives the status of its epic again, see `derive_epic_status`. Returns an empty tuple
+    /// wrapped in a `Result`.
+    ///
+    /// `Err` will explain the cause, but may be for one of the following reasons:
+    ///   - There was a problem reading from the database
+    ///   - An story does not exist for the input parameter `id`
+    ///   - The story is being closed while one of its blockers is not closed yet
+    ///   - There was a problem writing to the database
+    pub fn update_story_status(&self, id: u32, status: Status) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        let mut story = state
+            .stories
+            .get(&id)
+            .ok_or(DbError::StoryNotFound(id))
+            .cloned()?;
+        if status == Status::Closed {
+            check_blockers(&state, id)?;
+        }
+        story.status = status;
+
+        state.stories.insert(id, story);
+        if let Some(epic_id) = state.epic_id_for_story(id) {
+            derive_epic_status(&mut state, epic_id);
+        }
+        self.write(&state)?;
+        Ok(())
+    }
+
+    /// `bulk_update_story_status` sets the status of every story in the epic `epic_id` to
+    /// `status` in a single write. Returns the number of stories updated, or `Err` if the
+    /// epic was not found, if a story would be closed while blocked by an open story outside
+    /// the epic, or if there was an error reading/writing to the database.
+    pub fn bulk_update_story_status(&self, epic_id: u32, status: Status) -> Result<usize, DbError> {
+        let mut state = self.read()?;
+        let epic = state
+            .epics
+            .get(&epic_id)
+            .ok_or(DbError::EpicNotFound(epic_id))?;
+        let mut count = 0;
+        for id in &epic.story_ids {
+            if let Some(story) = state.stories.get_mut(id) {
+                story.status = status.clone();
+                count += 1;
+            }
+        }
+        if status == Status::Closed {
+            for id in &epic.story_ids {
+                check_blockers(&state, *id)?;
+            }
+        }
+        self.write(&state)?;
+        Ok(count)
+    }
+
+    /// `update_story_assignee` sets the assignee of the story `id` to `assignee`. Passing
+    /// `None` unassigns the story. Returns `Err` if the story was not found or if there was
+    /// an error reading/writing to the database.
+    pub fn update_story_assignee(&self, id: u32, assignee: Option<&str>) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        let mut story = state
+            .stories
+            .get(&id)
+            .ok_or(DbError::StoryNotFound(id))
+            .cloned()?;
+        story.assignee = assignee.map(|assignee| assignee.to_string());
+        state.stories.insert(id, story);
+        self.write(&state)?;
+        Ok(())
+    }
+
+    /// `update_story_points` sets the story points of the story `id` to `points`. Passing
+    /// `None` clears the estimate. Returns `Err` if the story was not found, if `points` is
+    /// more than `validate::MAX_STORY_POINTS`, or if there was an error reading/writing to
+    /// the database.
+    pub fn update_story_points(&self, id: u32, points: Option<u32>) -> Result<(), DbError> {
+        let points = validate::points(points)?;
+        let mut state = self.read()?;
+        let mut story = state
+            .stories
+            .get(&id)
+            .ok_or(DbError::StoryNotFound(id))
+            .cloned()?;
+        story.points = points;
+        state.stories.insert(id, story);
+        self.write(&state)?;
+        Ok(())
+    }
+
+    /// `add_story_tag` adds `tag` to the story `id`. Tags are normalized to lowercase and
+    /// duplicates are ignored. Returns `Err` if the story was not found or if there was an
+    /// error reading/writing to the database.
+    pub fn add_story_tag(&self, id: u32, tag: &str) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        let story = state
+            .stories
+            .get_mut(&id)
+            .ok_or(DbError::StoryNotFound(id))?;
+        if story.add_tag(tag) {
+            self.write(&state)?;
+        }
+        Ok(())
+    }
+
+    /// `remove_story_tag` removes `tag` from the story `id`. Returns `Err` if the story was
+    /// not found or if there was an error reading/writing to the database.
+    pub fn remove_story_tag(&self, id: u32, tag: &str) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        let story = state
+            .stories
+            .get_mut(&id)
+            .ok_or(DbError::StoryNotFound(id))?;
+        if story.remove_tag(tag) {
+            self.write(&state)?;
+        }
+        Ok(())
+    }
+
+    /// `add_story_dependency` marks the story `id` as blocked by the story `blocker_id`, so that
+    /// it cannot be closed before `blocker_id` is. Returns
+    /// `Err` if either story was not found, if a story would block itself, if the link would
+    /// create a cycle, or if there was an error reading/writing to the database.
+    pub fn add_story_dependency(&self, id: u32, blocker_id: u32) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        if !state.stories.contains_key(&blocker_id) {
+            return Err(DbError::StoryNotFound(blocker_id));
+        }
+        if id == blocker_id {
+            return Err(DbError::Invalid("a story cannot block itself".to_owned()));
+        }
+        if state.is_blocked_by(blocker_id, id) {
+            return Err(DbError::Invalid(format!(
+                "story #{} is already blocked by story #{}, so it cannot block it",
+                blocker_id, id
+            )));
+        }
+        let story = state
+            .stories
+            .get_mut(&id)
+            .ok_or(DbError::StoryNotFound(id))?;
+        if !story.blocked_by.contains(&blocker_id) {
+            story.blocked_by.push(blocker_id);
+            self.write(&state)?;
+        }
+        Ok(())
+    }
+
+    /// `remove_story_dependency` removes the story `blocker_id` from the blockers of the story
+    /// `id`. Returns `Err` if either story was not found or if there was an error
+    /// reading/writing to the database.
+    pub fn remove_story_dependency(&self, id: u32, blocker_id: u32) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        if !state.stories.contains_key(&blocker_id) {
+            return Err(DbError::StoryNotFound(blocker_id));
+        }
+        let story = state
+            .stories
+            .get_mut(&id)
+            .ok_or(DbError::StoryNotFound(id))?;
+        let len = story.blocked_by.len();
+        story.blocked_by.retain(|blocker| *blocker != blocker_id);
+        if story.blocked_by.len() != len {
+            self.write(&state)?;
+        }
+        Ok(())
+    }
+
+    /// `log_work` adds `entry` to the work log of the story `story_id`. Returns `Err` if the
+    /// story was not found, if no time was logged, if the total time logged on the story
+    /// would no longer fit a `u32` of minutes, or if there was an error reading/writing to the
+    /// database.
+    pub fn log_work(&self, story_id: u32, entry: &WorkEntry) -> Result<(), DbError> {
+        if entry.minutes == 0 {
+            return Err(DbError::Invalid("no time was logged".to_owned()));
+        }
+        let mut state = self.read()?;
+        let story = state
+            .stories
+            .get_mut(&story_id)
+            .ok_or(DbError::StoryNotFound(story_id))?;
+        let logged = story
+            .work_log
+            .iter()
+            .try_fold(entry.minutes, |total, entry| {
+                total.checked_add(entry.minutes)
+            });
+        if logged.is_none() {
+            return Err(DbError::Invalid(format!(
+                "story #{} cannot have that much time logged",
+                story_id
+            )));
+        }
+        story.work_log.push(entry.clone());
+        self.write(&state)?;
+        Ok(())
+    }
+
+    /// `add_comment` appends `comment` to the comments of the story `story_id`, with its body
+    /// validated like a description. Returns `Err` if the story was not found, if the comment
+    /// is empty or too long, or if there was an error reading/writing to the database.
+    pub fn add_comment(&self, story_id: u32, comment: &Comment) -> Result<(), DbError> {
+        let body = validate::description(comment.body.trim())?;
+        if body.is_empty() {
+            return Err(DbError::Invalid("a comment cannot be empty".to_owned()));
+        }
+        let mut state = self.read()?;
+        state
+            .stories
+            .get_mut(&story_id)
+            .ok_or(DbError::StoryNotFound(story_id))?
+            .comments
+            .push(Comment {
+                body,
+                ..comment.clone()
+            });
+        self.write(&state)?;
+        Ok(())
+    }
+
+    /// `add_checklist_item` appends an unchecked item reading `text` to the checklist of the
+    /// story or task `id`, with `text` validated like a name. Returns the number of the item,
+    /// counted from 1, or `Err` if there is no story or task `id`, if `text` is empty or too
+    /// long, or if there was an error reading/writing to the database.
+    pub fn add_checklist_item(&self, id: u32, text: &str) -> Result<usize, DbError> {
+        if text.trim().is_empty() {
+            return Err(DbError::Invalid(
+                "a checklist item cannot be empty".to_owned(),
+            ));
+        }
+        let text = validate::name(text)?;
+        let mut state = self.read()?;
+        let checklist = checklist_mut(&mut state, id)?.1;
+        checklist.push(ChecklistItem { text, done: false });
+        let number = checklist.len();
+        self.write(&state)?;
+        Ok(number)
+    }
+
+    /// `toggle_checklist_item` checks the item at `index` of the checklist of the story or
+    /// task `id`, or unchecks it if it was checked. Returns whether the item is now checked,
+    /// or `Err` if the story, task or item was not found, or if there was an error
+    /// reading/writing to the database.
+    pub fn toggle_checklist_item(&self, id: u32, index: usize) -> Result<bool, DbError> {
+        let mut state = self.read()?;
+        let item = &mut checklist_at(&mut state, id, index)?[index];
+        item.done = !item.done;
+        let done = item.done;
+        self.write(&state)?;
+        Ok(done)
+    }
+
+    /// `remove_checklist_item` removes the item at `index` of the checklist of the story or
+    /// task `id`, moving the items after it up. Returns the removed item, or `Err` if the
+    /// story, task or item was not found, or if there was an error reading/writing to the
+    /// database.
+    pub fn remove_checklist_item(&self, id: u32, index: usize) -> Result<ChecklistItem, DbError> {
+        let mut state = self.read()?;
+        let item = checklist_at(&mut state, id, index)?.remove(index);
+        self.write(&state)?;
+        Ok(item)
+    }
+
+    /// `reorder_story` moves the story `story_id` one position `direction` within the order of
+    /// the stories of the epic `epic_id`. Moving the first story up or the last story down
+    /// leaves the order unchanged. Returns `Err` if the epic was not found, if the story is
+    /// not in the epic, or if there was an error reading/writing to the database.
+    pub fn reorder_story(
+        &self,
+        epic_id: u32,
+        story_id: u32,
+        direction: Direction,
+    ) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        let epic = state
+            .epics
+            .get_mut(&epic_id)
+            .ok_or(DbError::EpicNotFound(epic_id))?;
+        let position = epic
+            .story_ids
+            .iter()
+            .position(|id| *id == story_id)
+            .ok_or_else(|| {
+                DbError::Invalid(format!("story #{} is not in epic #{}", story_id, epic_id))
+            })?;
+        let target = match direction {
+            Direction::Up => position.checked_sub(1),
+            Direction::Down => Some(position + 1).filter(|target| *target < epic.story_ids.len()),
+        };
+        if let Some(target) = target {
+            epic.story_ids.swap(position, target);
+            self.write(&state)?;
+        }
+        Ok(())
+    }
+
+    /// `replace_in_descriptions` replaces every occurrence of `find` with `replace` in the
+    /// descriptions of all epics, stories and tasks. The match is literal and case-sensitive.
+    /// Returns the number of items whose description changed, or `Err` if `find` is empty, if
+    /// a changed description is not valid or if there was an error reading/writing to the
+    /// database.
+    pub fn replace_in_descriptions(&self, find: &str, replace: &str) -> Result<usize, DbError> {
+        if find.is_empty() {
+            return Err(DbError::Invalid(
+                "the text to find cannot be empty".to_owned(),
+            ));
+        }
+        let mut state = self.read()?;
+        let descriptions = state
+            .epics
+            .values_mut()
+            .map(|epic| &mut epic.description)
+            .chain(
+                state
+                    .stories
+                    .values_mut()
+                    .map(|story| &mut story.description),
+            )
+            .chain(state.tasks.values_mut().map(|task| &mut task.description));
+        let mut changed = 0;
+        for description in descriptions {
+            if description.contains(find) {
+                *description = validate::description(&description.replace(find, replace))?;
+                changed += 1;
+            }
+        }
+        if changed > 0 {
+            self.write(&state)?;
+        }
+        Ok(changed)
+    }
+
+    /// `create_sprint` adds a new sprint and writes to the database. Returns the sprint's id,
+    /// or `Err` if the name is not valid, if the sprint ends before it starts or if there was
+    /// an error reading/writing to the database.
+    pub fn create_sprint(&self, sprint: &Sprint) -> Result<u32, DbError> {
+        if sprint.end < sprint.start {
+            return Err(DbError::Invalid(
+                "a sprint cannot end before it starts".to_owned(),
+            ));
+        }
+        let sprint = Sprint {
+            name: validate::name(&sprint.name)?,
+            ..sprint.clone()
+        };
+        let mut state = self.read()?;
+        let id = state.next_id()?;
+        state.sprints.insert(id, sprint);
+        self.write(&state)?;
+        Ok(id)
+    }
+
+    /// `add_story_to_sprint` plans the story `story_id` in the sprint `sprint_id`. Returns
+    /// `Err` if the sprint or the story was not found, or if there was an error
+    /// reading/writing to the database.
+    pub fn add_story_to_sprint(&self, sprint_id: u32, story_id: u32) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        if !state.stories.contains_key(&story_id) {
+            return Err(DbError::StoryNotFound(story_id));
+        }
+        let sprint = state
+            .sprints
+            .get_mut(&sprint_id)
+            .ok_or(DbError::SprintNotFound(sprint_id))?;
+        if !sprint.story_ids.contains(&story_id) {
+            sprint.story_ids.push(story_id);
+            self.write(&state)?;
+        }
+        Ok(())
+    }
+
+    /// `remove_story_from_sprint` takes the story `story_id` out of the sprint `sprint_id`.
+    /// Returns `Err` if the sprint was not found or if there was an error reading/writing to
+    /// the database.
+    pub fn remove_story_from_sprint(&self, sprint_id: u32, story_id: u32) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        let sprint = state
+            .sprints
+            .get_mut(&sprint_id)
+            .ok_or(DbError::SprintNotFound(sprint_id))?;
+        let len = sprint.story_ids.len();
+        sprint.story_ids.retain(|id| *id != story_id);
+        if sprint.story_ids.len() != len {
+            self.write(&state)?;
+        }
+        Ok(())
+    }
+
+    /// `update_task_name` updates the name of Task `id` to `name`. Returns `Err` if `name`
+    /// is not valid, if there was an error reading/writing to db or if `id` was invalid.
+    pub fn update_task_name(&self, id: u32, name: &str) -> Result<(), DbError> {
+        let name = validate::name(name)?;
+        let mut state = self.read()?;
+        let mut task = state
+            .tasks
+            .get(&id)
+            .ok_or(DbError::TaskNotFound(id))
+            .cloned()?;
+        task.name = name;
+        state.tasks.insert(id, task);
+        self.write(&state)?;
+        Ok(())
+    }
+
+    /// `update_task_description` updates the description of Task `id` to `description`.
+    /// Returns `Err` if `description` is not valid, if there was an error reading/writing to
+    /// db or if `id` was invalid.
+    pub fn update_task_description(&self, id: u32, description: &str) -> Result<(), DbError> {
+        let description = validate::description(description)?;
+        let mut state = self.read()?;
+        let mut task = state
+            .tasks
+            .get(&id)
+            .ok_or(DbError::TaskNotFound(id))
+            .cloned()?;
+        task.description = description;
+        state.tasks.insert(id, task);
+        self.write(&state)?;
+        Ok(())
+    }
+
+    /// `update_task_status` updates the status of Task `id` to `status`. Returns `Err` if
+    /// there was an error reading/writing to db or if `id` was invalid.
+    pub fn update_task_status(&self, id: u32, status: Status) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        let mut task = state
+            .tasks
+            .get(&id)
+            .ok_or(DbError::TaskNotFound(id))
+            .cloned()?;
+        task.status = status.clone();
+        state.tasks.insert(id, task);
+        self.write(&state)?;
+        Ok(())
+    }
+
+    /// `delete_epic` moves the epic corresponding to `id` to the trash, along with its
+    /// stories and their tasks. Returns an empty tuple wrapped in a `Result`.
+    ///
+    /// `Err` will explain the cause, but may be for one of the following reasons:
+    ///   - There was a problem reading from the database
+    ///   - An epic does not exist for the input parameter `id`
+    ///   - There was a problem writing to the database
+    pub fn delete_epic(&self, id: u32) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        let epic = state.epics.remove(&id).ok_or(DbError::EpicNotFound(id))?;
+
+        let mut stories = BTreeMap::new();
+        let mut tasks = BTreeMap::new();
+        for story_id in &epic.story_ids {
+            if let Some(story) = state.stories.remove(story_id) {
+                for task_id in &story.task_ids {
+                    if let Some(task) = state.tasks.remove(task_id) {
+                        tasks.insert(*task_id, task);
+                    }
+                }
+                stories.insert(*story_id, story);
+            }
+        }
+        for story in state.stories.values_mut() {
+            story.blocked_by.retain(|id| !epic.story_ids.contains(id));
+        }
+        for sprint in state.sprints.values_mut() {
+            sprint.story_ids.retain(|id| !epic.story_ids.contains(id));
+        }
+        state.trash_epics.insert(
+            id,
+            TrashedEpic {
+                epic,
+                stories,
+                tasks,
+                deleted_at: Utc::now(),
+            },
+        );
+        state.prune_recent();
+        self.write(&state)?;
+        Ok(())
+    }
+
+    /// `delete_story` moves the story corresponding to `id` to the trash, along with its tasks.
+    /// Returns an empty tuple wrapped in a `Result`.
+    ///
+    /// `Err` will explain the cause, but may be for one of the following reasons:
+    ///   - There was a problem reading from the database
+    ///   - An epic does not exist for the input parameter `id`
+    ///   - There was a problem writing to the database
+    pub fn delete_story(&self, story_id: u32, epic_id: u32) -> Result<(), DbError> {
+        let mut state = self.read()?;
+        let mut epic = state
+            .epics
+            .get(&epic_id)
+            .ok_or(DbError::EpicNotFound(epic_id))?
+            .clone();
+        if state.epic_id_for_story(story_id) != Some(epic_id) {
+            return Err(DbError::StoryNotFound(story_id));
+        }
+        epic.story_ids.retain(|id| *id != story_id);
+
+        state.epics.insert(epic_id, epic);
+        trash_story(&mut state, story_id, epic_id);
+        state.prune_recent();
+        self.write(&state)?;
+        Ok(())
+    }
+
+    /// `delete_stories` moves the stories `story_ids` of the epic `epic_id` to the trash, along
+    /// with their tasks, in a single write. Ids that are not stories of the epic are skipped, so
+    /// that they do not stop the others from being deleted. Returns the ids that were
+    /// deleted, in the order given.
+    ///
+    /// `Err` will explain the cause, but may be for one of the following reasons:
+    ///   - There was a problem reading from the database
+    ///   - An epic does not exist for the input parameter `epic_id`
+    ///   - There was a problem writing to the database
+    pub fn delete_stories(&self, story_ids: &[u32], epic_id: u32) -> Result<Vec<u32>, DbError> {
+        let mut state = self.read()?;
+        let mut epic = state
+            .epics
+            .get(&epic_id)
+            .ok_or(DbError::EpicNotFound(epic_id))?
+            .clone();
+        let mut deleted = vec![];
+        for story_id in story_ids {
+            if epic.story_ids.contains(story_id) && !deleted.contains(story_id) {
+                deleted.push(*story_id);
+            }
+        }
+        if deleted.is_empty() {
+            return Ok(deleted);
+        }
+        epic.story_ids.retain(|id| !deleted.contains(id));
+
+        state.epics.insert(epic_id, epic);
+        for story_id in &deleted {
+            trash_story(&mut state, *story_id, epic_id);
+        }
+        state.prune_recent();
+        self.write(&state)?;
+        Ok(deleted)
+    }
+
+    /// `delete_task` deletes Task `task_id` from parent Story `story_id`. Returns
+    /// `Err` if there was an error reading/writing to db or if `task_id` or
+    /// `story_id` was invalid.
+    pub fn delete_task(&self, task_id: u32, story_id: u32) -> Result<(), DbError> {
+        let mut state = self.db.read()?;
+        let mut story = state
+            .stories
+            .get(&story_id)
+            .ok_or(DbError::StoryNotFound(story_id))?
+            .clone();
+        let (index, _) = story
+            .task_ids
+            .iter()
+            .enumerate()
+            .find(|(_, id)| *id == &task_id)
+            .ok_or(DbError::TaskNotFound(task_id))?;
+        story.task_ids.remove(index);
+        state.stories.insert(story_id, story);
+        state.tasks.remove(&task_id);
+        self.write(&state)?;
+        Ok(())
+    }
+
+    /// `story_to_task` turns the story `story_id` into a task at the end of the story
+    /// `target_story_id`, under a new id. The task keeps the story's name, description,
+    /// status and checklist; the rest of the story, e.g. its comments, is dropped, and so are the links
+    /// to it from its epic, blockers and sprints. Returns the id of the task.
+    ///
+    /// `Err` will explain the cause, but may be for one of the following reasons:
+    ///   - Either story was not found, or they are the same story
+    ///   - The story has tasks, which would be left without a parent
+    ///   - There was a problem reading/writing to the database
+    pub fn story_to_task(&self, story_id: u32, target_story_id: u32) -> Result<u32, DbError> {
+        let mut state = self.read()?;
+        let story = state
+            .stories
+            .get(&story_id)
+            .ok_or(DbError::StoryNotFound(story_id))?
+            .clone();
+        if !state.stories.contains_key(&target_story_id) {
+            return Err(DbError::StoryNotFound(target_story_id));
+        }
+        if story_id == target_story_id {
+            return Err(DbError::Invalid(
+                "a story cannot become a task of itself".to_owned(),
+            ));
+        }
+        if !story.task_ids.is_empty() {
+            return Err(DbError::Invalid(format!(
+                "story #{} has {} {}, which would be left without a story",
+                story_id,
+                story.task_ids.len(),
+                if story.task_ids.len() == 1 {
+                    "task"
+                } else {
+                    "tasks"
+                }
+            )));
+        }
+        state.stories.remove(&story_id);
+        for epic in state.epics.values_mut() {
+            epic.story_ids.retain(|id| *id != story_id);
+        }
+        for other in state.stories.values_mut() {
+            other.blocked_by.retain(|id| *id != story_id);
+        }
+        for sprint in state.sprints.values_mut() {
+            sprint.story_ids.retain(|id| *id != story_id);
+        }
+        let task_id = state.next_id()?;
+        state.tasks.insert(
+            task_id,
+            Task {
+                name: story.name,
+                description: story.description,
+                status: story.status,
+                checklist: story.checklist,
+            },
+        );
+        if let Some(target) = state.stories.get_mut(&target_story_id) {
+            target.task_ids.push(task_id);
+        }
+        self.write(&state)?;
+        Ok(task_id)
+    }
+
+    /// `task_to_story` turns the task `task_id` into a story at the end of the epic
+    /// `target_epic_id`, under a new id. The story keeps the task's name, description,
+    /// status and checklist. Returns the id of the story, or `Err` if the task or the epic
+    /// was not found or if there was an error reading/writing to the database.
+    pub fn task_to_story(&self, task_id: u32, target_epic_id: u32) -> Result<u32, DbError> {
+        let mut state = self.read()?;
+        let task = state
+            .tasks
+            .remove(&task_id)
+            .ok_or(DbError::TaskNotFound(task_id))?;
+        if !state.epics.contains_key(&target_epic_id) {
+            return Err(DbError::EpicNotFound(target_epic_id));
+        }
+        for story in state.stories.values_mut() {
+            story.task_ids.retain(|id| *id != task_id);
+        }
+        let story_id = state.next_id()?;
+        state.stories.insert(
+            story_id,
+            Story {
+                status: task.status,
+                checklist: task.checklist,
+                ..Story::new(&task.name, &task.description)
+            },
+        );
+        if let Some(epic) = state.epics.get_mut(&target_epic_id) {
+            epic.story_ids.push(story_id);
+        }
+        self.write(&state)?;
+        Ok(story_id)
+    }
+
+    /// `restore_from_trash` takes the epic or story `id` out of the trash, along with what was
+    /// deleted with it. A story goes back to the end of the epic it was deleted from, or to
+    /// the `RESTORED_EPIC_NAME` epic, created if needed, when that epic is gone. Links to
+    /// blockers and sprints are not restored. Returns the id of the epic the item is in: the
+    /// epic itself when restoring an epic.
+    ///
+    /// `Err` will explain the cause, but may be for one of the following reasons:
+    ///   - There was a problem reading from the database
+    ///   - There is no epic or story in the trash for the input parameter `id`
+    ///   - There was a problem writing to the database
+    pub fn restore_from_trash(&self, id: u32) -> Result<u32, DbError> {
+        let mut state = self.read()?;
+        if let Some(trashed) = state.trash_epics.remove(&id) {
+            state.epics.insert(id, trashed.epic);
+            state.stories.extend(trashed.stories);
+            state.tasks.extend(trashed.tasks);
+            self.write(&state)?;
+            return Ok(id);
+        }
+        let trashed = state
+            .trash_stories
+            .remove(&id)
+            .ok_or(DbError::TrashedItemNotFound(id))?;
+        let epic_id = if state.epics.contains_key(&trashed.epic_id) {
+            trashed.epic_id
+        } else {
+            let existing = state
+                .epics
+                .iter()
+                .find(|(_, epic)| epic.name == RESTORED_EPIC_NAME)
+                .map(|(id, _)| *id);
+            match existing {
+                Some(epic_id) => epic_id,
+                None => {
+                    let epic_id = state.next_id()?;
+                    state.epics.insert(
+                        epic_id,
+                        Epic::new(
+                            RESTORED_EPIC_NAME,
+                            "Stories restored from the trash after their epic was deleted.",
+                        ),
+                    );
+                    epic_id
+                }
+            }
+        };
+        if let Some(epic) = state.epics.get_mut(&epic_id) {
+            epic.story_ids.push(id);
+        }
+        state.stories.insert(id, trashed.story);
+        state.tasks.extend(trashed.tasks);
+        self.write(&state)?;
+        Ok(epic_id)
+    }
+
+    /// `purge_trash` permanently deletes every epic and story in the trash. Returns the number
+    /// of epics and stories deleted, or `Err` if there was an error reading/writing to db.
+    pub fn purge_trash(&self) -> Result<usize, DbError> {
+        let mut state = self.read()?;
+        let count = state.trash_epics.len() + state.trash_stories.len();
+        if count == 0 {
+            return Ok(0);
+        }
+        state.trash_epics.clear();
+        state.trash_stories.clear();
+        self.write(&state)?;
+        Ok(count)
+    }
+
+    /// `export_epic_markdown` renders a report of the epic `epic_id` as Markdown: its name as a
+    /// heading, its description, a checklist of its stories and a summary of their statuses.
+    /// Returns `Err` if the epic was not found or if there was an error reading the database.
+    pub fn export_epic_markdown(&self, epic_id: u32) -> Result<String, DbError> {
+        let state = self.read()?;
+        let epic = state
+            .epics
+            .get(&epic_id)
+            .ok_or(DbError::EpicNotFound(epic_id))?;
+        let mut markdown = format!("# {}\n\n", epic.name);
+        if !epic.description.is_empty() {
+            markdown += &format!("{}\n\n", epic.description);
+        }
+        markdown += &format!("Status: {}\n\n## Stories\n\n", epic.status);
+
+        let stories: Vec<_> = epic
+            .story_ids
+            .iter()
+            .filter_map(|id| state.stories.get(id).map(|story| (id, story)))
+            .collect();
+        if stories.is_empty() {
+            markdown += "No stories.\n\nSummary: 0 stories\n";
+            return Ok(markdown);
+        }
+        for (id, story) in &stories {
+            let check = if story.status == Status::Closed {
+                "x"
+            } else {
+                " "
+            };
+            markdown += &format!("- [{}] {} (#{}, {})\n", check, story.name, id, story.status);
+        }
+        let count = |status: Status| {
+            stories
+                .iter()
+                .filter(|(_, story)| story.status == status)
+                .count()
+        };
+        markdown += &format!(
+            "\nSummary: {} {}, {} Open, {} Blocked, {} In Progress, {} Resolved, {} Closed\n",
+            stories.len(),
+            if stories.len() == 1 {
+                "story"
+            } else {
+                "stories"
+            },
+            count(Status::Open),
+            count(Status::Blocked),
+            count(Status::InProgress),
+            count(Status::Resolved),
+            count(Status::Closed)
+        );
+        Ok(markdown)
+    }
+
+    /// `export_epic` bundles the epic `epic_id` with its stories and their tasks, see
+    /// `import_epic`. Returns `Err` if the epic was not found or if there was an error reading
+    /// the database.
+    pub fn export_epic(&self, epic_id: u32) -> Result<EpicBundle, DbError> {
+        let state = self.read()?;
+        let epic = state
+            .epics
+            .get(&epic_id)
+            .ok_or(DbError::EpicNotFound(epic_id))?;
+        let stories: BTreeMap<u32, Story> = epic
+            .story_ids
+            .iter()
+            .filter_map(|id| Some((*id, state.stories.get(id)?.clone())))
+            .collect();
+        let tasks = stories
+            .values()
+            .flat_map(|story| &story.task_ids)
+            .filter_map(|id| Some((*id, state.tasks.get(id)?.clone())))
+            .collect();
+        Ok(EpicBundle {
+            epic_id,
+            epic: epic.clone(),
+            stories,
+            tasks,
+        })
+    }
+
+    /// `import_epic` adds the epic in `bundle`, with its stories and their tasks, under new
+    /// ids in a single write. Links between the stories are kept, while blockers outside of
+    /// the bundle are dropped. Returns the id of the imported epic.
+    ///
+    /// `Err` will explain the cause, but may be for one of the following reasons:
+    ///   - The bundle is missing a story or task that it links to
+    ///   - A name or description is not valid
+    ///   - There was a problem reading/writing to the database
+    pub fn import_epic(&self, bundle: &EpicBundle) -> Result<u32, DbError> {
+        for story_id in &bundle.epic.story_ids {
+            let story = bundle.stories.get(story_id).ok_or_else(|| {
+                DbError::Invalid(format!("the bundle is missing story #{}", story_id))
+            })?;
+            validate::name(&story.name)?;
+            validate::description(&story.description)?;
+            for task_id in &story.task_ids {
+                let task = bundle.tasks.get(task_id).ok_or_else(|| {
+                    DbError::Invalid(format!("the bundle is missing task #{}", task_id))
+                })?;
+                validate::name(&task.name)?;
+                validate::description(&task.description)?;
+            }
+        }
+        let mut epic = Epic {
+            name: validate::name(&bundle.epic.name)?,
+            description: validate::description(&bundle.epic.description)?,
+            story_ids: vec![],
+            ..bundle.epic.clone()
+        };
+        let mut state = self.read()?;
+        let epic_id = state.next_id()?;
+        let mut story_ids = BTreeMap::new();
+        for old_id in &bundle.epic.story_ids {
+            story_ids.insert(*old_id, state.next_id()?);
+        }
+        for old_id in &bundle.epic.story_ids {
+            let new_id = story_ids[old_id];
+            let mut story = bundle.stories[old_id].clone();
+            story.blocked_by = story
+                .blocked_by
+                .iter()
+                .filter_map(|id| story_ids.get(id).copied())
+                .collect();
+            story.task_ids = vec![];
+            for task_id in &bundle.stories[old_id].task_ids {
+                let id = state.next_id()?;
+                state.tasks.insert(id, bundle.tasks[task_id].clone());
+                story.task_ids.push(id);
+            }
+            state.stories.insert(new_id, story);
+            epic.story_ids.push(new_id);
+        }
+        state.epics.insert(epic_id, epic);
+        self.write(&state)?;
+        Ok(epic_id)
+    }
+
+    /// `import_csv` reads rows of epic name, story name, description and status from `reader`
+    /// and adds them to the database in a single write. Epics are matched by exact name and
+    /// created when missing. Rows without a story name only create the epic. Unknown statuses
+    /// fall back to Open and are reported in the summary's warnings, and rows with an invalid
+    /// name or description are skipped with a warning. With `skip_existing`,
+    /// stories whose epic already has a story of the same name are skipped. Returns `Err` if
+    /// the CSV is malformed or if there was an error reading/writing to the database.
+    pub fn import_csv(
+        &self,
+        reader: impl Read,
+        skip_existing: bool,
+    ) -> Result<ImportSummary, DbError> {
+        let mut state = self.read()?;
+        let mut summary = ImportSummary::default();
+        let mut csv = csv::Reader::from_reader(reader);
+        for (line, row) in csv.deserialize::<CsvRow>().enumerate() {
+            let row = row?;
+            if row.epic_name.trim().is_empty() {
+                summary
+                    .warnings
+                    .push(format!("row {}: missing epic name, skipped", line + 1));
+                continue;
+            }
+            let story_name = row.story_name.trim();
+            let valid = validate::name(&row.epic_name).and_then(|epic_name| {
+                let story_name = match story_name {
+                    "" => None,
+                    story_name => Some(validate::name(story_name)?),
+                };
+                Ok((
+                    epic_name,
+                    story_name,
+                    validate::description(&row.description)?,
+                ))
+            });
+            let (epic_name, story_name, description) = match valid {
+                Ok(valid) => valid,
+                Err(error) => {
+                    summary
+                        .warnings
+                        .push(format!("row {}: {}, skipped", line + 1, error));
+                    continue;
+                }
+            };
+            let epic_id = match state
+                .epics
+                .iter()
+                .filter(|(_, epic)| epic.name == epic_name)
+                .map(|(id, _)| *id)
+                .min()
+            {
+                Some(epic_id) => epic_id,
+                None => {
+                    let epic_id = state.next_id()?;
+                    let description = if story_name.is_none() {
+                        description.as_str()
+                    } else {
+                        ""
+                    };
+                    state
+                        .epics
+                        .insert(epic_id, Epic::new(&epic_name, description));
+                    summary.epics_created += 1;
+                    epic_id
+                }
+            };
+
+            let Some(story_name) = story_name else {
+                continue;
+            };
+            if skip_existing
+                && state.epics[&epic_id]
+                    .story_ids
+                    .iter()
+                    .any(|id| state.stories.get(id).is_some_and(|s| s.name == story_name))
+            {
+                summary.stories_skipped += 1;
+                continue;
+            }
+            let mut story = Story::new(&story_name, &description);
+            if !row.status.trim().is_empty() {
+                match row.status.parse() {
+                    Ok(status) => story.status = status,
+                    Err(_) => summary.warnings.push(format!(
+                        "row {}: unknown status {:?}, using Open",
+                        line + 1,
+                        row.status
+                    )),
+                }
+            }
+            let story_id = state.next_id()?;
+            state.stories.insert(story_id, story);
+            if let Some(epic) = state.epics.get_mut(&epic_id) {
+                epic.story_ids.push(story_id);
+            }
+            summary.stories_created += 1;
+        }
+        self.write(&state)?;
+        Ok(summary)
+    }
+
+    /// `export_csv` writes the whole project to `writer` as CSV, with one row per story and
+    /// one row for each epic without stories. Returns the number of rows written, not
+    /// counting the header, or `Err` if there was an error reading the database or writing
+    /// to `writer`.
+    pub fn export_csv(&self, writer: &mut impl Write) -> Result<usize, DbError> {
+        let state = self.read()?;
+        let mut csv = csv::Writer::from_writer(writer);
+        csv.write_record([
+            "Epic ID",
+            "Epic Name",
+            "Story ID",
+            "Story Name",
+            "Description",
+            "Status",
+        ])?;
+        let mut rows = 0;
+        for (epic_id, epic) in &state.epics {
+            let stories: Vec<_> = epic
+                .story_ids
+                .iter()
+                .filter_map(|id| state.stories.get(id).map(|story| (id, story)))
+                .collect();
+            if stories.is_empty() {
+                csv.write_record([
+                    epic_id.to_string(),
+                    epic.name.clone(),
+                    String::new(),
+                    String::new(),
+                    epic.description.clone(),
+                    epic.status.to_string(),
+                ])?;
+                rows += 1;
+            }
+            for (story_id, story) in stories {
+                csv.write_record([
+                    epic_id.to_string(),
+                    epic.name.clone(),
+                    story_id.to_string(),
+                    story.name.clone(),
+                    story.description.clone(),
+                    story.status.to_string(),
+                ])?;
+                rows += 1;
+            }
+        }
+        csv.flush()?;
+        Ok(rows)
+    }
+
+    /// `export_stories_jsonl` writes every story to `writer` as JSON Lines: one JSON object
+    /// per line with the story's id and the id of its epic, sorted by id. Returns the number
+    /// of stories written, or `Err` if there was an error reading the database or writing to
+    /// `writer`.
+    pub fn export_stories_jsonl(&self, writer: &mut impl Write) -> Result<usize, DbError> {
+        let state = self.read()?;
+        for (id, story) in &state.stories {
+            let record = StoryRecord {
+                id: *id,
+                epic_id: state.epic_id_for_story(*id),
+                story,
+            };
+            writeln!(
+                writer,
+                "{}",
+                serde_json::to_string(&record).map_err(io::Error::from)?
+            )?;
+        }
+        writer.flush()?;
+        Ok(state.stories.len())
+    }
+
+    /// `export_html` renders the whole board into a single self-contained HTML page written to
+    /// `writer`: a summary of the story statuses, then one section per epic with a table of
+    /// its stories. Styles are inlined so the page can be shared as a single file. Returns
+    /// `Err` if there was an error reading the database or writing to `writer`.
+    pub fn export_html(&self, writer: &mut impl Write) -> Result<(), DbError> {
+        let state = self.read()?;
+        let count = |status: Status| {
+            state
+                .stories
+                .values()
+                .filter(|story| story.status == status)
+                .count()
+        };
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, "<html lang=\"en\">")?;
+        writeln!(writer, "<head>")?;
+        writeln!(writer, "<meta charset=\"utf-8\">")?;
+        writeln!(writer, "<title>Clira report</title>")?;
+        writeln!(writer, "<style>{}</style>", HTML_REPORT_STYLE)?;
+        writeln!(writer, "</head>")?;
+        writeln!(writer, "<body>")?;
+        writeln!(writer, "<h1>Clira report</h1>")?;
+        writeln!(
+            writer,
+            "<p class=\"summary\">Epics: {} | Stories: {} ({} Open, {} Blocked, {} In Progress, \
+             {} Resolved, {} Closed)</p>",
+            state.epics.len(),
+            state.stories.len(),
+            count(Status::Open),
+            count(Status::Blocked),
+            count(Status::InProgress),
+            count(Status::Resolved),
+            count(Status::Closed)
+        )?;
+
+        for (epic_id, epic) in &state.epics {
+            writeln!(writer, "<section>")?;
+            writeln!(
+                writer,
+                "<h2>#{} {} {}</h2>",
+                epic_id,
+                escape_html(&epic.name),
+                status_badge(&epic.status)
+            )?;
+            if !epic.description.is_empty() {
+                writeln!(writer, "<p>{}</p>", escape_html(&epic.description))?;
+            }
+            let stories: Vec<_> = epic
+                .story_ids
+                .iter()
+                .filter_map(|id| state.stories.get(id).map(|story| (id, story)))
+                .collect();
+            if stories.is_empty() {
+                writeln!(writer, "<p>No stories.</p>")?;
+            } else {
+                writeln!(writer, "<table>")?;
+                writeln!(
+                    writer,
+                    "<tr><th>ID</th><th>Name</th><th>Description</th><th>Status</th></tr>"
+                )?;
+                for (story_id, story) in stories {
+                    writeln!(
+                        writer,
+                        "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                        story_id,
+                        escape_html(&story.name),
+                        escape_html(&story.description),
+                        status_badge(&story.status)
+                    )?;
+                }
+                writeln!(writer, "</table>")?;
+            }
+            writeln!(writer, "</section>")?;
+        }
+        writeln!(writer, "</body>")?;
+        writeln!(writer, "</html>")?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// `create_parent_dirs` creates the missing parent directories of `file_path`.
+fn create_parent_dirs(file_path: &str) -> Result<(), DbError> {
+    if let Some(parent) = std::path::Path::new(file_path)
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(|error| DbError::Write {
+            source: error.into(),
+        })?;
+    }
+    Ok(())
+}
+
+/// `HTML_REPORT_STYLE` is the stylesheet inlined into the page written by `export_html`. The
+/// status colors match the terminal UI.
+const HTML_REPORT_STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; color: #222; } \
+table { border-collapse: collapse; width: 100%; } \
+th, td { border: 1px solid #ccc; padding: 0.4em; text-align: left; } \
+.status { border-radius: 0.3em; padding: 0.1em 0.5em; font-size: 0.8em; } \
+.status-open { background: #eee; } \
+.status-blocked { background: #d9534f; color: #fff; } \
+.status-in-progress { background: #f5d442; } \
+.status-resolved { background: #4a7fe0; color: #fff; } \
+.status-closed { background: #3a9d4a; color: #fff; }";
+
+/// `escape_html` escapes the characters of `text` that have a meaning in HTML.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// `status_badge` renders `status` as a colored HTML badge.
+fn status_badge(status: &Status) -> String {
+    let class = match status {
+        Status::Open => "open",
+        Status::Blocked => "blocked",
+        Status::InProgress => "in-progress",
+        Status::Resolved => "resolved",
+        Status::Closed => "closed",
+    };
+    format!("<span class=\"status status-{}\">{}</span>", class, status)
+}
+
+/// `derive_epic_status` sets the status of the epic `epic_id` in `state` from the statuses
+/// of its stories, see `derive_status`. Epics whose status was set manually, and missing
+/// epics, are left untouched.
+fn derive_epic_status(state: &mut DatabaseState, epic_id: u32) {
+    let Some(epic) = state.epics.get(&epic_id) else {
+        return;
+    };
+    if epic.status_override {
+        return;
+    }
+    let statuses: Vec<_> = epic
+        .story_ids
+        .iter()
+        .filter_map(|id| state.stories.get(id))
+        .map(|story| story.status.clone())
+        .collect();
+    let status = derive_status(&statuses);
+    if let Some(epic) = state.epics.get_mut(&epic_id) {
+        epic.status = status;
+    }
+}
+
+/// `check_blockers` returns `Err` naming the blockers of the story `story_id` that are not
+/// closed yet, if it has any.
+fn check_blockers(state: &DatabaseState, story_id: u32) -> Result<(), DbError> {
+    let blockers = state.unclosed_blockers(story_id);
+    if blockers.is_empty() {
+        return Ok(());
+    }
+    Err(DbError::Invalid(format!(
+        "story #{} cannot be closed before {} {} {} closed",
+        story_id,
+        if blockers.len() == 1 {
+            "story"
+        } else {
+            "stories"
+        },
+        blockers.iter().map(|id| format!("#{}", id)).join(", "),
+        if blockers.len() == 1 { "is" } else { "are" }
+    )))
+}
+
+/// `trash_story` moves the story `story_id` and its tasks from `state` to the trash, noting
+/// `epic_id` as where it was deleted from, and removes any links to it from other stories
+/// and sprints. The story must already be removed from its epic.
+fn trash_story(state: &mut DatabaseState, story_id: u32, epic_id: u32) {
+    if let Some(story) = state.stories.remove(&story_id) {
+        let tasks = story
+            .task_ids
+            .iter()
+            .filter_map(|task_id| Some((*task_id, state.tasks.remove(task_id)?)))
+            .collect();
+        state.trash_stories.insert(
+            story_id,
+            TrashedStory {
+                story,
+                epic_id,
+                tasks,
+                deleted_at: Utc::now(),
+            },
+        );
+    }
+    for story in state.stories.values_mut() {
+        story.blocked_by.retain(|id| *id != story_id);
+    }
+    for sprint in state.sprints.values_mut() {
+        sprint.story_ids.retain(|id| *id != story_id);
+    }
+}
+
+/// `copy_name` returns `name` suffixed with " (copy)", shortening `name` so that the
+/// result still fits `validate::MAX_NAME_LENGTH`.
+fn copy_name(name: &str) -> Result<String, DbError> {
+    const SUFFIX: &str = " (copy)";
+    let kept = validate::MAX_NAME_LENGTH - SUFFIX.chars().count();
+    let name: String = name.chars().take(kept).collect();
+    Ok(validate::name(&format!("{}{}", name.trim_end(), SUFFIX))?)
+}
+
+/// `unchecked` returns a copy of `checklist` with every item not done yet.
+fn unchecked(checklist: &[ChecklistItem]) -> Vec<ChecklistItem> {
+    checklist
+        .iter()
+        .map(|item| ChecklistItem {
+            done: false,
+            ..item.clone()
+        })
+        .collect()
+}
+
+/// `is_missing_or_empty` returns whether `error`, raised while reading the board at
+/// `file_path`, is because the file is missing or empty.
+fn is_missing_or_empty(error: &DbError, file_path: &str) -> bool {
+    match error {
+        DbError::NotFound { .. } => true,
+        DbError::Parse { .. } => {
+            fs::read_to_string(file_path).is_ok_and(|data| data.trim().is_empty())
+        }
+        _ => false,
+    }
+}
+
+/// `checklist_mut` returns the checklist of the story or task `id` in `state`, along with
+/// what it belongs to, e.g. "task #3". Returns `Err` if there is no story or task `id`.
+fn checklist_mut(
+    state: &mut DatabaseState,
+    id: u32,
+) -> Result<(String, &mut Vec<ChecklistItem>), DbError> {
+    if let Some(story) = state.stories.get_mut(&id) {
+        return Ok((format!("story #{}", id), &mut story.checklist));
+    }
+    match state.tasks.get_mut(&id) {
+        Some(task) => Ok((format!("task #{}", id), &mut task.checklist)),
+        None => Err(DbError::StoryOrTaskNotFound(id)),
+    }
+}
+
+/// `checklist_at` returns the checklist of the story or task `id` in `state`, once it made
+/// sure that it has an item at `index`. Returns `Err` if there is no story or task `id`, or
+/// if its checklist has no item at `index`.
+fn checklist_at(
+    state: &mut DatabaseState,
+    id: u32,
+    index: usize,
+) -> Result<&mut Vec<ChecklistItem>, DbError> {
+    let (owner, checklist) = checklist_mut(state, id)?;
+    match checklist.len() {
+        0 => Err(DbError::Invalid(format!("{} has no checklist", owner))),
+        len if index >= len => Err(DbError::Invalid(format!(
+            "{} has no checklist item {}, it has items 1 to {}",
+            owner,
+            index + 1,
+            len
+        ))),
+        _ => Ok(checklist),
+    }
+}
+
+impl Database for JSONFileDatabase {
+    /// `read` returns a `DbError` if the file is missing, could not be read or does not hold
+    /// a valid board.
+    fn read(&self) -> Result<DatabaseState, DbError> {
+        let data = fs::read_to_string(&self.file_path)
+            .map_err(|error| DbError::from_io(&self.file_path, error))?;
+        let data: DatabaseState = serde_json::from_str(&data)
+            .map_err(|error| DbError::from_parse(&self.file_path, error))?;
+        if self.modified.get().is_none() {
+            self.modified.set(self.current_modified());
+        }
+        Ok(data)
+    }
+
+    /// `write` refuses to overwrite the file if another program changed it since it was last
+    /// written or refreshed.
+    fn write(&self, state: &DatabaseState) -> anyhow::Result<()> {
+        if let (Some(expected), Some(actual)) = (self.modified.get(), self.current_modified()) {
+            if expected != actual {
+                bail!(
+                    "{} was changed by another program, so it was not overwritten",
+                    self.file_path
+                );
+            }
+        }
+        let data = serde_json::to_string(state)?;
+        fs::write(&self.file_path, data)?;
+        self.modified.set(self.current_modified());
+        Ok(())
+    }
+
+    fn refresh_if_changed(&self) -> Result<bool, DbError> {
+        let modified = self.current_modified();
+        if modified == self.modified.get() {
+            return Ok(false);
+        }
+        self.read()?;
+        self.modified.set(modified);
+        Ok(true)
+    }
+}
+
+/// `MemoryDatabase` keeps the state of a database in memory. Nothing is read from or written
+/// to disk, so changes only last as long as the instance does. It uses the interior
+/// mutability design pattern to keep `JiraDatabase`'s immutability state.
+pub struct MemoryDatabase {
+    pub last_written_state: RefCell<DatabaseState>,
+}
+
+impl MemoryDatabase {
+    /// `new` returns an instance of `MemoryDatabase` holding an empty board.
+    pub fn new() -> Self {
+        Self {
+            last_written_state: RefCell::new(DatabaseState::default()),
+        }
+    }
+}
+
+impl Database for MemoryDatabase {
+    fn read(&self) -> Result<DatabaseState, DbError> {
+        let state = self.last_written_state.borrow().clone();
+        Ok(state)
+    }
+
+    fn write(&self, state: &DatabaseState) -> anyhow::Result<()> {
+        *self.last_written_state.borrow_mut() = state.clone();
+        Ok(())
+    }
+}
+
+/// `test_utils` contains utilities used for testing.
+pub mod test_utils {
+    /// `MockDatabase` is the in-memory database used by tests.
+    pub use super::MemoryDatabase as MockDatabase;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod jira {
+        use chrono::NaiveDate;
+
+        use self::test_utils::MockDatabase;
+        use crate::{db::validate::ValidationError, models::StoryStub};
+
+        use super::*;
+
+        #[test]
+        fn create_epic_should_succeed() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic = Epic::new("Epic 1", "Epic description 1");
+            assert!(db.create_epic(&epic).is_ok());
+        }
+
+        #[test]
+        fn create_story_should_error_on_invalid_epic_id() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let story = Story::new("Story 1", "Story description 1");
+
+            let invalid_epic_id = 999u32;
+            let res = db.create_story(&story, invalid_epic_id);
+            assert!(res.is_err());
+        }
+
+        #[test]
+        fn create_story_should_succeed() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+
+            let epic = Epic::new("Epic 1", "Epic 1 description");
+            let epic_id = db.create_epic(&epic).unwrap();
+            db.create_epic(&epic).unwrap();
+
+            let story = Story::new("Story 1", "Story 1 description");
+            let res = db.create_story(&story, epic_id);
+            assert!(res.is_ok());
+        }
+
+        #[test]
+        fn create_stories_bulk_should_link_stories_to_epic() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let names = ["Story 1", " Story 2 ", "Story 3"].map(str::to_owned);
+
+            let story_ids = db.create_stories_bulk(epic_id, &names).unwrap();
+            assert_eq!(story_ids.len(), 3);
+            let state = db.read().unwrap();
+            assert_eq!(state.epics[&epic_id].story_ids, story_ids);
+            assert_eq!(state.stories[&story_ids[1]].name, "Story 2");
+            assert_eq!(state.stories[&story_ids[2]].description, "");
+
+            let res = db.create_stories_bulk(epic_id, &["Story 4".to_owned(), "a".repeat(99)]);
+            assert!(res.is_err());
+            assert_eq!(db.read().unwrap().stories.len(), 3);
+            assert!(db.create_stories_bulk(999, &names).is_err());
+        }
+
+        #[test]
+        fn apply_template_should_create_epic_with_stories() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let other_epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let stub = |name: &str| StoryStub {
+                name: name.to_owned(),
+                description: format!("Done on {{date}}: {}", name),
+            };
+            let template = Template {
+                name: "Release {date}".to_owned(),
+                description: "Checklist".to_owned(),
+                stories: vec![stub("Freeze branch"), stub("Tag {date}"), stub("Announce")],
+            }
+            .fill_placeholders(NaiveDate::from_ymd_opt(2024, 5, 31).unwrap());
+
+            let epic_id = db.apply_template(&template).unwrap();
+            assert_ne!(epic_id, other_epic_id);
+            let state = db.read().unwrap();
+            let epic = &state.epics[&epic_id];
+            assert_eq!(epic.name, "Release 2024-05-31");
+            assert_eq!(epic.description, "Checklist");
+            let stories: Vec<_> = epic.story_ids.iter().map(|id| &state.stories[id]).collect();
+            assert_eq!(
+                stories.iter().map(|story| &story.name).collect::<Vec<_>>(),
+                ["Freeze branch", "Tag 2024-05-31", "Announce"]
+            );
+            assert_eq!(stories[1].description, "Done on 2024-05-31: Tag 2024-05-31");
+            assert!(!epic.story_ids.contains(&epic_id));
+            assert_eq!(state.stories.len(), 3);
+
+            let invalid = Template {
+                stories: vec![stub(&"a".repeat(99))],
+                ..template
+            };
+            assert!(db.apply_template(&invalid).is_err());
+            assert_eq!(db.read().unwrap().epics.len(), 2);
+        }
+
+        #[test]
+        fn story_to_task_should_move_story_under_target_story() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", "Description 1"), epic_id)
+                .unwrap();
+            let target_id = db
+                .create_story(&Story::new("Story 2", ""), epic_id)
+                .unwrap();
+            let blocked_id = db
+                .create_story(&Story::new("Story 3", ""), epic_id)
+                .unwrap();
+            db.add_story_dependency(blocked_id, story_id).unwrap();
+            db.update_story_status(story_id, Status::Resolved).unwrap();
+            let sprint_id = db
+                .create_sprint(&Sprint::new(
+                    "Sprint 1",
+                    NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+                    NaiveDate::from_ymd_opt(2024, 5, 14).unwrap(),
+                ))
+                .unwrap();
+            db.add_story_to_sprint(sprint_id, story_id).unwrap();
+
+            let task_id = db.story_to_task(story_id, target_id).unwrap();
+            assert!(task_id > sprint_id);
+            let state = db.read().unwrap();
+            assert_eq!(
+                state.tasks[&task_id],
+                Task {
+                    name: "Story 1".to_owned(),
+                    description: "Description 1".to_owned(),
+                    status: Status::Resolved,
+                    checklist: vec![],
+                }
+            );
+            assert_eq!(state.stories[&target_id].task_ids, vec![task_id]);
+            assert!(!state.stories.contains_key(&story_id));
+            assert_eq!(state.epics[&epic_id].story_ids, vec![target_id, blocked_id]);
+            assert!(state.stories[&blocked_id].blocked_by.is_empty());
+            assert!(state.sprints[&sprint_id].story_ids.is_empty());
+        }
+
+        #[test]
+        fn story_to_task_should_reject_story_with_tasks() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let target_id = db
+                .create_story(&Story::new("Story 2", ""), epic_id)
+                .unwrap();
+            db.create_task(&Task::new("Task 1", ""), story_id).unwrap();
+            let before = db.read().unwrap();
+
+            let error = db.story_to_task(story_id, target_id).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                format!(
+                    "story #{} has 1 task, which would be left without a story",
+                    story_id
+                )
+            );
+            assert!(db.story_to_task(target_id, target_id).is_err());
+            assert!(db.story_to_task(target_id, 999).is_err());
+            assert_eq!(db.read().unwrap(), before);
+        }
+
+        #[test]
+        fn task_to_story_should_move_task_under_target_epic() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let target_id = db.create_epic(&Epic::new("Epic 2", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let task_id = db
+                .create_task(&Task::new("Task 1", "Description 1"), story_id)
+                .unwrap();
+            let other_task_id = db.create_task(&Task::new("Task 2", ""), story_id).unwrap();
+            db.update_task_status(task_id, Status::InProgress).unwrap();
+            assert!(db.task_to_story(task_id, 999).is_err());
+
+            let new_story_id = db.task_to_story(task_id, target_id).unwrap();
+            let state = db.read().unwrap();
+            let story = &state.stories[&new_story_id];
+            assert_eq!(story.name, "Task 1");
+            assert_eq!(story.description, "Description 1");
+            assert_eq!(story.status, Status::InProgress);
+            assert_eq!(state.epics[&target_id].story_ids, vec![new_story_id]);
+            assert_eq!(state.stories[&story_id].task_ids, vec![other_task_id]);
+            assert!(!state.tasks.contains_key(&task_id));
+            assert!(matches!(
+                db.task_to_story(task_id, target_id).unwrap_err(),
+                DbError::TaskNotFound(_)
+            ));
+        }
+
+        #[test]
+        fn set_epic_archived_should_keep_epic_and_stories() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+
+            db.set_epic_archived(epic_id, true).unwrap();
+            let state = db.read().unwrap();
+            assert!(state.epics[&epic_id].archived);
+            assert!(state.stories.contains_key(&story_id));
+
+            db.set_epic_archived(epic_id, false).unwrap();
+            assert!(!db.read().unwrap().epics[&epic_id].archived);
+            assert!(db.set_epic_archived(999, true).is_err());
+        }
+
+        #[test]
+        fn create_should_not_reuse_ids_below_last_item_id() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let mut state = db.read().unwrap();
+            state.last_item_id = Some(0);
+            db.write(&state).unwrap();
+
+            let new_epic_id = db.create_epic(&Epic::new("Epic 2", "")).unwrap();
+            assert_eq!(new_epic_id, story_id + 1);
+            let new_story_id = db
+                .create_story(&Story::new("Story 2", ""), epic_id)
+                .unwrap();
+            assert_eq!(new_story_id, new_epic_id + 1);
+
+            let state = db.read().unwrap();
+            assert_eq!(state.epics[&epic_id].name, "Epic 1");
+            assert_eq!(state.stories[&story_id].name, "Story 1");
+            assert_eq!(
+                state.epics[&epic_id].story_ids,
+                vec![story_id, new_story_id]
+            );
+            assert_eq!(state.last_item_id, Some(new_story_id));
+        }
+
+        #[test]
+        fn create_should_error_without_changes_once_ids_are_exhausted() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let mut state = db.read().unwrap();
+            state.last_item_id = Some(u32::MAX);
+            db.write(&state).unwrap();
+
+            let error = db.create_epic(&Epic::new("Epic 2", "")).unwrap_err();
+            assert!(matches!(error, DbError::IdsExhausted(_)));
+            assert!(db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .is_err());
+            assert!(db.duplicate_epic(epic_id).is_err());
+            assert_eq!(db.read().unwrap(), state);
+        }
+
+        #[test]
+        fn create_and_update_should_validate_names_and_descriptions() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let long_description = "a".repeat(validate::DEFAULT_MAX_DESCRIPTION_LENGTH + 1);
+            assert!(db.create_epic(&Epic::new(" ", "")).is_err());
+            assert!(db
+                .create_epic(&Epic::new("Epic 1", &long_description))
+                .is_err());
+            assert!(db.read().unwrap().epics.is_empty());
+
+            let epic_id = db
+                .create_epic(&Epic::new(" Epic\u{1b} 1 ", "Line 1\nLine 2\u{0}"))
+                .unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let task_id = db.create_task(&Task::new("Task 1", ""), story_id).unwrap();
+            let epic = &db.read().unwrap().epics[&epic_id];
+            assert_eq!(epic.name, "Epic 1");
+            assert_eq!(epic.description, "Line 1\nLine 2");
+
+            let long_name = "a".repeat(validate::MAX_NAME_LENGTH + 1);
+            assert!(db.update_epic_name(epic_id, &long_name).is_err());
+            assert!(db.update_story_name(story_id, "").is_err());
+            assert!(db.update_task_name(task_id, &long_name).is_err());
+            assert!(db
+                .update_story_description(story_id, &long_description)
+                .is_err());
+            assert!(db
+                .replace_in_descriptions("Line", &long_description)
+                .is_err());
+            assert_eq!(db.read().unwrap().epics[&epic_id].name, "Epic 1");
+
+            db.update_task_name(task_id, "Task\t2").unwrap();
+            assert_eq!(db.read().unwrap().tasks[&task_id].name, "Task2");
+        }
+
+        #[test]
+        fn in_memory_should_create_and_read() {
+            let db = JiraDatabase::in_memory();
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
+                .unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", "Story 1 description"), epic_id)
+                .unwrap();
+            let task_id = db
+                .create_task(&Task::new("Task 1", "Task 1 description"), story_id)
+                .unwrap();
+
+            let state = db.read().unwrap();
+            assert_eq!(state.epics[&epic_id].story_ids, vec![story_id]);
+            assert_eq!(state.stories[&story_id].task_ids, vec![task_id]);
+            assert_eq!(state.tasks[&task_id].name, "Task 1");
+        }
+
+        #[test]
+        fn duplicate_epic_should_copy_with_independent_ids() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
+                .unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", "Story 1 description"), epic_id)
+                .unwrap();
+            db.update_story_status(story_id, Status::Closed).unwrap();
+            let task_id = db
+                .create_task(&Task::new("Task 1", "Task 1 description"), story_id)
+                .unwrap();
+
+            let copy_id = db.duplicate_epic(epic_id).unwrap();
+            let state = db.read().unwrap();
+            let copy = &state.epics[&copy_id];
+            assert_eq!(copy.name, "Epic 1 (copy)");
+            assert_eq!(copy.story_ids.len(), 1);
+            let copy_story_id = copy.story_ids[0];
+            assert_ne!(copy_story_id, story_id);
+            let copy_story = &state.stories[&copy_story_id];
+            assert_eq!(copy_story.name, "Story 1 (copy)");
+            assert_eq!(copy_story.status, Status::Open);
+            assert_eq!(copy_story.task_ids.len(), 1);
+            assert_ne!(copy_story.task_ids[0], task_id);
+
+            db.update_story_name(copy_story_id, "Renamed").unwrap();
+            db.delete_epic(copy_id).unwrap();
+            let state = db.read().unwrap();
+            assert_eq!(state.stories[&story_id].name, "Story 1");
+            assert_eq!(state.stories[&story_id].status, Status::Closed);
+            assert!(state.tasks.contains_key(&task_id));
+
+            let next_id = db.create_epic(&Epic::new("Epic 2", "")).unwrap();
+            assert!(next_id > copy_story_id);
+            assert!(db.duplicate_epic(999).is_err());
+        }
+
+        #[test]
+        fn duplicate_epic_should_shorten_long_names() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let name = "x".repeat(validate::MAX_NAME_LENGTH);
+            let epic_id = db.create_epic(&Epic::new(&name, "")).unwrap();
+            db.create_story(&Story::new("A story named 27 characters", ""), epic_id)
+                .unwrap();
+
+            let copy_id = db.duplicate_epic(epic_id).unwrap();
+            let state = db.read().unwrap();
+            let copy = &state.epics[&copy_id];
+            assert_eq!(copy.name, format!("{} (copy)", "x".repeat(23)));
+            assert_eq!(
+                state.stories[&copy.story_ids[0]].name,
+                "A story named 27 charac (copy)"
+            );
+        }
+
+        #[test]
+        fn duplicate_epic_should_reset_archived_and_checklists() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let task_id = db.create_task(&Task::new("Task 1", ""), story_id).unwrap();
+            for id in [story_id, task_id] {
+                db.add_checklist_item(id, "Step").unwrap();
+                db.toggle_checklist_item(id, 0).unwrap();
+            }
+            db.set_epic_archived(epic_id, true).unwrap();
+
+            let copy_id = db.duplicate_epic(epic_id).unwrap();
+            let state = db.read().unwrap();
+            let copy = &state.epics[&copy_id];
+            assert!(!copy.archived);
+            let copy_story = &state.stories[&copy.story_ids[0]];
+            assert_eq!(
+                copy_story.checklist,
+                vec![ChecklistItem {
+                    text: "Step".to_owned(),
+                    done: false
+                }]
+            );
+            assert!(!state.tasks[&copy_story.task_ids[0]].checklist[0].done);
+            assert!(state.epics[&epic_id].archived);
+            assert!(state.stories[&story_id].checklist[0].done);
+        }
+
+        #[test]
+        fn export_csv_should_escape_fields() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
+                .unwrap();
+            let story_id = db
+                .create_story(
+                    &Story::new("Story, with comma", "First line\nsecond, \"quoted\" line"),
+                    epic_id,
+                )
+                .unwrap();
+            let empty_epic_id = db.create_epic(&Epic::new("Epic 2", "No stories")).unwrap();
+
+            let mut out = Vec::new();
+            assert_eq!(db.export_csv(&mut out).unwrap(), 2);
+
+            let mut reader = csv::Reader::from_reader(out.as_slice());
+            let rows: Vec<Vec<String>> = reader
+                .records()
+                .map(|record| record.unwrap().iter().map(str::to_owned).collect())
+                .collect();
+            assert_eq!(
+                rows,
+                vec![
+                    vec![
+                        epic_id.to_string(),
+                        "Epic 1".to_owned(),
+                        story_id.to_string(),
+                        "Story, with comma".to_owned(),
+                        "First line\nsecond, \"quoted\" line".to_owned(),
+                        "Open".to_owned(),
+                    ],
+                    vec![
+                        empty_epic_id.to_string(),
+                        "Epic 2".to_owned(),
+                        String::new(),
+                        String::new(),
+                        "No stories".to_owned(),
+                        "Open".to_owned(),
+                    ],
+                ]
+            );
+        }
+
+        #[test]
+        fn epic_id_for_story_should_find_parent_epic() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
+                .unwrap();
+            let other_epic_id = db
+                .create_epic(&Epic::new("Epic 2", "Epic 2 description"))
+                .unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", "Story 1 description"), other_epic_id)
+                .unwrap();
+
+            assert_eq!(db.epic_id_for_story(story_id).unwrap(), Some(other_epic_id));
+            assert_eq!(db.epic_id_for_story(epic_id).unwrap(), None);
+            assert_eq!(db.epic_id_for_story(999).unwrap(), None);
+        }
+
+        const IMPORT_FIXTURE: &str = "Epic Name,Story Name,Description,Status
+Epic 1,Story 1,\"First, story\",Open
+Epic 1,Story 2,Second story,In Progress
+Epic 2,Story 3,Third story,resolved
+Epic 1,Story 4,\"Multi
+line\",Closed
+Epic 2,Story 5,Fifth story,Done
+";
+
+        #[test]
+        fn import_csv_should_create_epics_and_stories() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let existing_id = db.create_epic(&Epic::new("Epic 2", "")).unwrap();
+
+            let summary = db.import_csv(IMPORT_FIXTURE.as_bytes(), false).unwrap();
+            assert_eq!(summary.epics_created, 1);
+            assert_eq!(summary.stories_created, 5);
+            assert_eq!(summary.warnings.len(), 1);
+            assert!(summary.warnings[0].contains("Done"));
+
+            let state = db.read().unwrap();
+            assert_eq!(state.epics.len(), 2);
+            assert_eq!(state.last_item_id, Some(existing_id + 6));
+            let epic_id = state
+                .epics
+                .iter()
+                .find(|(_, e)| e.name == "Epic 1")
+                .unwrap()
+                .0;
+            let names = |id: &u32| -> Vec<_> {
+                state.epics[id]
+                    .story_ids
+                    .iter()
+                    .map(|id| state.stories[id].name.clone())
+                    .collect()
+            };
+            assert_eq!(names(epic_id), vec!["Story 1", "Story 2", "Story 4"]);
+            assert_eq!(names(&existing_id), vec!["Story 3", "Story 5"]);
+
+            let story = |name: &str| state.stories.values().find(|s| s.name == name).unwrap();
+            assert_eq!(story("Story 1").description, "First, story");
+            assert_eq!(story("Story 2").status, Status::InProgress);
+            assert_eq!(story("Story 3").status, Status::Resolved);
+            assert_eq!(story("Story 4").description, "Multi\nline");
+            assert_eq!(story("Story 5").status, Status::Open);
+        }
+
+        #[test]
+        fn import_csv_should_skip_invalid_rows() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let csv = format!(
+                "Epic Name,Story Name,Description,Status
+Epic 1,{},,Open
+Epic 1,Story{} 1,,Open
+",
+                "a".repeat(40),
+                '\u{7}'
+            );
+
+            let summary = db.import_csv(csv.as_bytes(), false).unwrap();
+            assert_eq!(summary.epics_created, 1);
+            assert_eq!(summary.stories_created, 1);
+            assert_eq!(
+                summary.warnings,
+                vec!["row 1: a name can be at most 30 characters long, this one is 40, skipped"]
+            );
+            let state = db.read().unwrap();
+            assert_eq!(state.stories.values().next().unwrap().name, "Story 1");
+        }
+
+        #[test]
+        fn import_csv_should_skip_existing_stories() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            db.import_csv(IMPORT_FIXTURE.as_bytes(), false).unwrap();
+            let before = db.read().unwrap();
+
+            let summary = db.import_csv(IMPORT_FIXTURE.as_bytes(), true).unwrap();
+            assert_eq!(summary.epics_created, 0);
+            assert_eq!(summary.stories_created, 0);
+            assert_eq!(summary.stories_skipped, 5);
+            let after = db.read().unwrap();
+            assert_eq!(after.epics, before.epics);
+            assert_eq!(after.stories, before.stories);
+
+            let summary = db.import_csv(IMPORT_FIXTURE.as_bytes(), false).unwrap();
+            assert_eq!(summary.stories_created, 5);
+        }
+
+        #[test]
+        fn import_csv_should_read_exported_csv() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            db.create_story(&Story::new("Story 1", "Story 1 description"), epic_id)
+                .unwrap();
+            db.create_epic(&Epic::new("Epic 2", "Epic 2 description"))
+                .unwrap();
+            let mut out = Vec::new();
+            db.export_csv(&mut out).unwrap();
+
+            let copy = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let summary = copy.import_csv(out.as_slice(), false).unwrap();
+            assert_eq!(summary.epics_created, 2);
+            assert_eq!(summary.stories_created, 1);
+            assert!(summary.warnings.is_empty());
+        }
+
+        #[test]
+        fn new_should_create_missing_directories() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("clira").join("boards").join("db.json");
+            let db = JiraDatabase::new(path.to_str().unwrap()).unwrap();
+            assert!(path.exists());
+            assert_eq!(db.read().unwrap(), DatabaseState::default());
+        }
+
+        #[test]
+        fn new_should_keep_invalid_board_files() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("db.json");
+            let file_path = path.to_str().unwrap();
+            fs::write(&path, "").unwrap();
+            assert!(JiraDatabase::new(file_path).is_ok());
+
+            fs::write(&path, "not a board").unwrap();
+            assert!(JiraDatabase::new(file_path).is_err());
+            assert_eq!(fs::read_to_string(&path).unwrap(), "not a board");
+        }
+
+        #[test]
+        fn read_should_explain_why_the_board_could_not_be_read() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("db.json");
+            let db = JiraDatabase::new(path.to_str().unwrap()).unwrap();
+
+            fs::remove_file(&path).unwrap();
+            let error = db.read().unwrap_err();
+            assert!(matches!(error, DbError::NotFound { .. }));
+
+            fs::write(&path, "{").unwrap();
+            let error = db.read().unwrap_err();
+            assert!(matches!(error, DbError::Parse { .. }));
+
+            fs::remove_file(&path).unwrap();
+            fs::create_dir(&path).unwrap();
+            let error = db.read().unwrap_err();
+            assert!(matches!(error, DbError::Io { .. }));
+        }
+
+        #[test]
+        fn reset_to_empty_should_recreate_deleted_board() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("db.json");
+            let db = JiraDatabase::new(path.to_str().unwrap()).unwrap();
+            db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+
+            fs::remove_file(&path).unwrap();
+            db.reset_to_empty().unwrap();
+            assert_eq!(db.read().unwrap(), DatabaseState::default());
+        }
+
+        #[test]
+        fn open_existing_should_not_create_missing_file() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("db.json");
+            assert!(JiraDatabase::open_existing(path.to_str().unwrap()).is_err());
+            assert!(!path.exists());
+        }
+
+        #[test]
+        fn export_stories_jsonl_should_write_one_object_per_line() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let first_id = db
+                .create_story(&Story::new("Story 1", "First line\nsecond line"), epic_id)
+                .unwrap();
+            let second_id = db
+                .create_story(&Story::new("Story 2", ""), epic_id)
+                .unwrap();
+
+            let mut out = Vec::new();
+            assert_eq!(db.export_stories_jsonl(&mut out).unwrap(), 2);
+            let out = String::from_utf8(out).unwrap();
+            let lines: Vec<serde_json::Value> = out
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect();
+            assert_eq!(lines.len(), 2);
+            assert_eq!(lines[0]["id"], first_id);
+            assert_eq!(lines[0]["epicId"], epic_id);
+            assert_eq!(lines[0]["description"], "First line\nsecond line");
+            assert_eq!(lines[1]["id"], second_id);
+            assert_eq!(lines[1]["name"], "Story 2");
+
+            let mut out = Vec::new();
+            let empty = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            assert_eq!(empty.export_stories_jsonl(&mut out).unwrap(), 0);
+            assert!(out.is_empty());
+        }
+
+        #[test]
+        fn export_html_should_escape_text() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db
+                .create_epic(&Epic::new("<script>alert(1)</script>", "Fish & chips"))
+                .unwrap();
+            db.create_story(&Story::new("Tom & Jerry", "a < b > c \"d\""), epic_id)
+                .unwrap();
+
+            let mut out = Vec::new();
+            db.export_html(&mut out).unwrap();
+            let html = String::from_utf8(out).unwrap();
+            assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+            assert!(!html.contains("<script>"));
+            assert!(html.contains("Fish &amp; chips"));
+            assert!(html.contains("Tom &amp; Jerry"));
+            assert!(html.contains("a &lt; b &gt; c &quot;d&quot;"));
+        }
+
+        #[test]
+        fn export_html_should_render_one_section_per_epic() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            db.create_epic(&Epic::new("Epic 2", "")).unwrap();
+            db.create_epic(&Epic::new("Epic 3", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            db.update_story_status(story_id, Status::InProgress)
+                .unwrap();
+
+            let mut out = Vec::new();
+            db.export_html(&mut out).unwrap();
+            let html = String::from_utf8(out).unwrap();
+            assert_eq!(html.matches("<section>").count(), 3);
+            assert_eq!(html.matches("No stories.").count(), 2);
+            assert!(html.contains("Epics: 3 | Stories: 1 (0 Open, 0 Blocked, 1 In Progress"));
+            assert!(html.contains("<span class=\"status status-in-progress\">In Progress</span>"));
+            assert!(!html.contains("<link"));
+        }
+
+        #[test]
+        fn export_epic_should_round_trip_through_import_under_new_ids() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let outside_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let outside_story_id = db
+                .create_story(&Story::new("Outside", ""), outside_id)
+                .unwrap();
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 2", "Epic 2 description"))
+                .unwrap();
+            db.update_epic_assignee(epic_id, Some("ana")).unwrap();
+            let first_id = db
+                .create_story(&Story::new("Story 1", "Story 1 description"), epic_id)
+                .unwrap();
+            db.add_story_tag(first_id, "backend").unwrap();
+            let second_id = db
+                .create_story(&Story::new("Story 2", ""), epic_id)
+                .unwrap();
+            db.add_story_dependency(second_id, first_id).unwrap();
+            db.add_story_dependency(second_id, outside_story_id)
+                .unwrap();
+            for name in ["Task 1", "Task 2"] {
+                db.create_task(&Task::new(name, ""), second_id).unwrap();
+            }
+            let bundle = db.export_epic(epic_id).unwrap();
+            let json = serde_json::to_string(&bundle).unwrap();
+            let bundle: EpicBundle = serde_json::from_str(&json).unwrap();
+            assert_eq!(bundle.stories.len(), 2);
+            assert_eq!(bundle.tasks.len(), 2);
+            assert!(db.export_epic(999).is_err());
+
+            let other = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let existing_epic_id = other.create_epic(&Epic::new("Existing", "")).unwrap();
+            let existing_story_id = other
+                .create_story(&Story::new("Existing", ""), existing_epic_id)
+                .unwrap();
+            other
+                .create_task(&Task::new("Existing", ""), existing_story_id)
+                .unwrap();
+            let before = other.read().unwrap();
+
+            let imported_id = other.import_epic(&bundle).unwrap();
+            let imported = other.export_epic(imported_id).unwrap();
+            let state = other.read().unwrap();
+            assert_eq!(
+                state.epics[&existing_epic_id],
+                before.epics[&existing_epic_id]
+            );
+            let new_ids: Vec<u32> = std::iter::once(imported_id)
+                .chain(imported.stories.keys().copied())
+                .chain(imported.tasks.keys().copied())
+                .collect();
+            assert_eq!(new_ids.len(), 5);
+            assert!(new_ids.iter().all(|id| !before.epics.contains_key(id)
+                && !before.stories.contains_key(id)
+                && !before.tasks.contains_key(id)));
+
+            let without_ids = |epic: &Epic| Epic {
+                story_ids: vec![],
+                ..epic.clone()
+            };
+            assert_eq!(without_ids(&imported.epic), without_ids(&bundle.epic));
+            for (old_id, new_id) in bundle.epic.story_ids.iter().zip(&imported.epic.story_ids) {
+                let (old, new) = (&bundle.stories[old_id], &imported.stories[new_id]);
+                let without_ids = |story: &Story| Story {
+                    task_ids: vec![],
+                    blocked_by: vec![],
+                    ..story.clone()
+                };
+                assert_eq!(without_ids(new), without_ids(old));
+                assert_eq!(new.task_ids.len(), old.task_ids.len());
+                for (old_task, new_task) in old.task_ids.iter().zip(&new.task_ids) {
+                    assert_eq!(imported.tasks[new_task], bundle.tasks[old_task]);
+                }
+            }
+            let (new_first_id, new_second_id) =
+                (imported.epic.story_ids[0], imported.epic.story_ids[1]);
+            assert_eq!(
+                imported.stories[&new_second_id].blocked_by,
+                vec![new_first_id]
+            );
+        }
+
+        #[test]
+        fn import_epic_should_reject_incomplete_bundle() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            db.create_task(&Task::new("Task 1", ""), story_id).unwrap();
+            let mut bundle = db.export_epic(epic_id).unwrap();
+            bundle.tasks.clear();
+            let before = db.read().unwrap();
+
+            let error = db.import_epic(&bundle).unwrap_err();
+            assert!(error
+                .to_string()
+                .starts_with("the bundle is missing task #"));
+            bundle.stories.clear();
+            let error = db.import_epic(&bundle).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                format!("the bundle is missing story #{}", story_id)
+            );
+            assert_eq!(db.read().unwrap(), before);
+        }
+
+        #[test]
+        fn export_epic_markdown_should_render_empty_epic() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
+                .unwrap();
+            assert_eq!(
+                db.export_epic_markdown(epic_id).unwrap(),
+                "# Epic 1\n\nEpic 1 description\n\nStatus: Open\n\n## Stories\n\n\
+                 No stories.\n\nSummary: 0 stories\n"
+            );
+            assert!(db.export_epic_markdown(999).is_err());
+        }
+
+        #[test]
+        fn export_epic_markdown_should_render_single_story() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", "Story 1 description"), epic_id)
+                .unwrap();
+            db.update_story_status(story_id, Status::Closed).unwrap();
+            assert_eq!(
+                db.export_epic_markdown(epic_id).unwrap(),
+                format!(
+                    "# Epic 1\n\nStatus: Closed\n\n## Stories\n\n\
+                     - [x] Story 1 (#{}, Closed)\n\n\
+                     Summary: 1 story, 0 Open, 0 Blocked, 0 In Progress, 0 Resolved, 1 Closed\n",
+                    story_id
+                )
+            );
+        }
+
+        #[test]
+        fn export_epic_markdown_should_render_mixed_stories() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
+                .unwrap();
+            db.update_epic_status(epic_id, Status::InProgress).unwrap();
+            let mut ids = vec![];
+            for (name, status) in [
+                ("Story 1", Status::Open),
+                ("Story 2", Status::InProgress),
+                ("Story 3", Status::Resolved),
+                ("Story 4", Status::Closed),
+            ] {
+                let id = db.create_story(&Story::new(name, ""), epic_id).unwrap();
+                db.update_story_status(id, status).unwrap();
+                ids.push(id);
+            }
+            assert_eq!(
+                db.export_epic_markdown(epic_id).unwrap(),
+                format!(
+                    "# Epic 1\n\nEpic 1 description\n\nStatus: In Progress\n\n## Stories\n\n\
+                     - [ ] Story 1 (#{}, Open)\n\
+                     - [ ] Story 2 (#{}, In Progress)\n\
+                     - [ ] Story 3 (#{}, Resolved)\n\
+                     - [x] Story 4 (#{}, Closed)\n\n\
+                     Summary: 4 stories, 1 Open, 0 Blocked, 1 In Progress, 1 Resolved, 1 Closed\n",
+                    ids[0], ids[1], ids[2], ids[3]
+                )
+            );
+        }
+
+        #[test]
+        fn delete_epic_should_error_on_invalid_epic_id() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let invalid_epic_id = 999u32;
+            let res = db.delete_epic(invalid_epic_id);
+            assert!(res.is_err());
+        }
+
+        #[test]
+        fn delete_epic_should_succeed() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic = Epic::new("Epic 1 ", "Epic 1 description");
+            let id = db.create_epic(&epic).unwrap();
+            assert!(db.delete_epic(id).is_ok());
+        }
+
+        #[test]
+        fn delete_epic_should_delete_stories_and_tasks() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
+                .unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", "Story 1 description"), epic_id)
+                .unwrap();
+            db.create_task(&Task::new("Task 1", "Task 1 description"), story_id)
+                .unwrap();
+            let other_epic_id = db
+                .create_epic(&Epic::new("Epic 2", "Epic 2 description"))
+                .unwrap();
+            let other_story_id = db
+                .create_story(&Story::new("Story 2", "Story 2 description"), other_epic_id)
+                .unwrap();
+
+            assert!(db.delete_epic(epic_id).is_ok());
+            let state = db.read().unwrap();
+            assert!(state.tasks.is_empty());
+            assert_eq!(
+                state.stories.keys().collect::<Vec<_>>(),
+                vec![&other_story_id]
+            );
+        }
+
+        #[test]
+        fn delete_story_should_error_on_story_not_found_in_epic() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+
+            let epic = Epic::new("Epic 1", "Epic 1 description");
+            let epic_id = db.create_epic(&epic).unwrap();
+
+            let invalid_story_id = 999u32;
+            assert!(db.delete_story(invalid_story_id, epic_id).is_err());
+        }
+
+        #[test]
+        fn delete_story_should_error_on_invalid_story_id() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+
+            let epic = Epic::new("Epic 1", "Epic 1 description");
+            let epic_id = db.create_epic(&epic).unwrap();
+
+            let story = Story::new("Story 1", "Story 1 description");
+            let story_id = db.create_story(&story, epic_id).unwrap();
+            let invalid_epic_id = 999u32;
+            assert!(db.delete_story(story_id, invalid_epic_id).is_err());
+        }
+
+        #[test]
+        fn not_found_errors_should_name_the_missing_item() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+
+            let error = db.delete_story(story_id, 999).unwrap_err();
+            assert!(matches!(error, DbError::EpicNotFound(999)));
+            let error = db.delete_story(999, epic_id).unwrap_err();
+            assert!(matches!(error, DbError::StoryNotFound(999)));
+            let error = db.update_task_status(999, Status::Closed).unwrap_err();
+            assert!(matches!(error, DbError::TaskNotFound(999)));
+        }
+
+        #[test]
+        fn delete_story_should_succeed() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+
+            let epic = Epic::new("Epic 1", "Epic 1 description");
+            let epic_id = db.create_epic(&epic).unwrap();
+
+            let story = Story::new("Story 1", "Story 1 description");
+            let story_id = db.create_story(&story, epic_id).unwrap();
+
+            assert!(db.delete_story(story_id, epic_id).is_ok());
+        }
+
+        #[test]
+        fn delete_stories_should_delete_only_the_listed_stories() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let other_epic_id = db.create_epic(&Epic::new("Epic 2", "")).unwrap();
+            let first_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let second_id = db
+                .create_story(&Story::new("Story 2", ""), epic_id)
+                .unwrap();
+            let third_id = db
+                .create_story(&Story::new("Story 3", ""), epic_id)
+                .unwrap();
+            let other_id = db
+                .create_story(&Story::new("Story 4", ""), other_epic_id)
+                .unwrap();
+            db.create_task(&Task::new("Task 1", ""), first_id).unwrap();
+
+            let deleted = db
+                .delete_stories(&[first_id, 999, third_id, other_id, first_id], epic_id)
+                .unwrap();
+            assert_eq!(deleted, vec![first_id, third_id]);
+
+            let state = db.read().unwrap();
+            assert_eq!(state.epics[&epic_id].story_ids, vec![second_id]);
+            assert!(state.stories.contains_key(&second_id));
+            assert!(state.stories.contains_key(&other_id));
+            assert!(!state.stories.contains_key(&first_id));
+            assert!(!state.stories.contains_key(&third_id));
+            assert!(state.tasks.is_empty());
+
+            assert!(db.delete_stories(&[second_id], 999).is_err());
+        }
+
+        #[test]
+        fn restore_from_trash_should_round_trip_deleted_epics_and_stories() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let first_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let second_id = db
+                .create_story(&Story::new("Story 2", ""), epic_id)
+                .unwrap();
+            db.create_task(&Task::new("Task 1", ""), first_id).unwrap();
+            let before = db.read().unwrap();
+
+            db.delete_story(first_id, epic_id).unwrap();
+            let state = db.read().unwrap();
+            assert!(!state.stories.contains_key(&first_id));
+            assert!(state.tasks.is_empty());
+            assert_eq!(state.trash_stories[&first_id].epic_id, epic_id);
+            assert_eq!(db.restore_from_trash(first_id).unwrap(), epic_id);
+            let state = db.read().unwrap();
+            assert_eq!(state.epics[&epic_id].story_ids, vec![second_id, first_id]);
+            assert_eq!(state.tasks.len(), 1);
+            assert!(state.trash_stories.is_empty());
+
+            db.delete_epic(epic_id).unwrap();
+            let state = db.read().unwrap();
+            assert!(state.epics.is_empty() && state.stories.is_empty());
+            assert_eq!(state.trash_epics[&epic_id].stories.len(), 2);
+            assert_eq!(db.restore_from_trash(epic_id).unwrap(), epic_id);
+            let state = db.read().unwrap();
+            assert_eq!(state.stories, before.stories);
+            assert_eq!(state.tasks, before.tasks);
+            assert!(state.trash_epics.is_empty());
+
+            let error = db.restore_from_trash(epic_id).unwrap_err();
+            assert!(matches!(
+                error,
+                DbError::TrashedItemNotFound(id) if id == epic_id
+            ));
+        }
+
+        #[test]
+        fn restore_from_trash_should_move_orphaned_stories_to_restored_epic() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let first_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let second_id = db
+                .create_story(&Story::new("Story 2", ""), epic_id)
+                .unwrap();
+            db.delete_stories(&[first_id, second_id], epic_id).unwrap();
+            db.delete_epic(epic_id).unwrap();
+
+            let restored_id = db.restore_from_trash(first_id).unwrap();
+            assert_ne!(restored_id, epic_id);
+            assert_eq!(db.restore_from_trash(second_id).unwrap(), restored_id);
+            let state = db.read().unwrap();
+            let restored = &state.epics[&restored_id];
+            assert_eq!(restored.name, RESTORED_EPIC_NAME);
+            assert_eq!(restored.story_ids, vec![first_id, second_id]);
+            assert!(state.trash_epics.contains_key(&epic_id));
+
+            let mut state = db.read().unwrap();
+            assert!(state.next_id().unwrap() > restored_id.max(epic_id));
+        }
+
+        #[test]
+        fn purge_trash_should_delete_trashed_items_permanently() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let other_id = db.create_epic(&Epic::new("Epic 2", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), other_id)
+                .unwrap();
+            db.delete_story(story_id, other_id).unwrap();
+            db.delete_epic(epic_id).unwrap();
+
+            assert_eq!(db.purge_trash().unwrap(), 2);
+            let state = db.read().unwrap();
+            assert!(state.trash_epics.is_empty() && state.trash_stories.is_empty());
+            assert!(db.restore_from_trash(story_id).is_err());
+            assert_eq!(state.epics.keys().collect::<Vec<_>>(), vec![&other_id]);
+            assert_eq!(db.purge_trash().unwrap(), 0);
+        }
+
+        #[test]
+        fn undo_should_restore_previous_states_in_order() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let first_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let second_id = db.create_epic(&Epic::new("Epic 2", "")).unwrap();
+
+            assert!(db.undo().unwrap());
+            let state = db.read().unwrap();
+            assert!(state.epics.contains_key(&first_id));
+            assert!(!state.epics.contains_key(&second_id));
+
+            assert!(db.undo().unwrap());
+            assert!(db.read().unwrap().epics.is_empty());
+            assert!(!db.undo().unwrap());
+        }
+
+        #[test]
+        fn undo_should_restore_grouped_writes_at_once() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+
+            db.begin_undo_group();
+            db.update_epic_name(epic_id, "new name").unwrap();
+            db.update_epic_status(epic_id, Status::Closed).unwrap();
+            db.end_undo_group();
+
+            assert!(db.undo().unwrap());
+            let state = db.read().unwrap();
+            assert_eq!(state.epics[&epic_id].name, "Epic 1");
+            assert_eq!(state.epics[&epic_id].status, Status::Open);
+        }
+
+        #[test]
+        fn undo_history_should_be_bounded() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            for _ in 0..UNDO_HISTORY_LIMIT + 5 {
+                db.create_epic(&Epic::new("Epic", "")).unwrap();
+            }
+            let mut undos = 0;
+            while db.undo().unwrap() {
+                undos += 1;
+            }
+            assert_eq!(undos, UNDO_HISTORY_LIMIT);
+            assert_eq!(db.read().unwrap().epics.len(), 5);
+        }
+
+        #[test]
+        fn undo_should_keep_audit_log() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            db.record_audit("Created epic", Some(epic_id)).unwrap();
+
+            assert!(db.undo().unwrap());
+            assert_eq!(db.read().unwrap().audit.len(), 1);
+        }
+
+        #[test]
+        fn record_audit_should_drop_oldest_entries() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            for id in 0..AUDIT_LOG_LIMIT as u32 + 5 {
+                db.record_audit("Created epic", Some(id)).unwrap();
+            }
+            let audit = db.read().unwrap().audit;
+            assert_eq!(audit.len(), AUDIT_LOG_LIMIT);
+            assert_eq!(audit.first().unwrap().id, Some(5));
+            assert_eq!(audit.last().unwrap().id, Some(AUDIT_LOG_LIMIT as u32 + 4));
+        }
+
+        #[test]
+        fn record_recent_should_keep_most_recent_first() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            for id in 0..RECENT_LIMIT as u32 + 2 {
+                db.record_recent(RecentItem::Epic(id)).unwrap();
+            }
+            db.record_recent(RecentItem::Epic(5)).unwrap();
+
+            let recent = db.read().unwrap().recent;
+            assert_eq!(recent.len(), RECENT_LIMIT);
+            assert_eq!(
+                recent[..3],
+                [
+                    RecentItem::Epic(5),
+                    RecentItem::Epic(RECENT_LIMIT as u32 + 1),
+                    RecentItem::Epic(RECENT_LIMIT as u32)
+                ]
+            );
+            assert!(!recent.contains(&RecentItem::Epic(1)));
+            assert!(!db.undo().unwrap());
+        }
+
+        #[test]
+        fn record_recent_should_not_write_until_saved() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            db.record_recent(RecentItem::Epic(epic_id)).unwrap();
+
+            assert!(db.db.read().unwrap().recent.is_empty());
+            assert_eq!(db.read().unwrap().recent, vec![RecentItem::Epic(epic_id)]);
+
+            db.save_recent().unwrap();
+            assert_eq!(
+                db.db.read().unwrap().recent,
+                vec![RecentItem::Epic(epic_id)]
+            );
+        }
+
+        #[test]
+        fn delete_should_prune_recent_items() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let other_id = db
+                .create_story(&Story::new("Story 2", ""), epic_id)
+                .unwrap();
+            for story_id in [story_id, other_id] {
+                db.record_recent(RecentItem::Story { story_id, epic_id })
+                    .unwrap();
+            }
+            db.record_recent(RecentItem::Epic(epic_id)).unwrap();
+
+            db.delete_story(story_id, epic_id).unwrap();
+            assert_eq!(
+                db.read().unwrap().recent,
+                vec![
+                    RecentItem::Epic(epic_id),
+                    RecentItem::Story {
+                        story_id: other_id,
+                        epic_id
+                    }
+                ]
+            );
+            db.delete_epic(epic_id).unwrap();
+            assert!(db.read().unwrap().recent.is_empty());
+        }
+
+        #[test]
+        fn update_epic_status_should_error_on_invalid_epic_id() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let invalid_epic_id = 999u32;
+            assert!(db
+                .update_epic_status(invalid_epic_id, Status::InProgress)
+                .is_err());
+        }
+
+        #[test]
+        fn update_epic_status_should_succeed() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic = Epic::new("Epic 1", "Epic 1 description");
+            let epic_id = db.create_epic(&epic).unwrap();
+            assert!(db.update_epic_status(epic_id, Status::InProgress).is_ok());
+        }
+
+        #[test]
+        fn story_tags_should_succeed() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
+                .unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", "Story 1 description"), epic_id)
+                .unwrap();
+
+            assert!(db.add_story_tag(story_id, "Bug").is_ok());
+            assert!(db.add_story_tag(story_id, "bug").is_ok());
+            assert_eq!(db.read().unwrap().stories[&story_id].tags, vec!["bug"]);
+
+            assert!(db.remove_story_tag(story_id, "BUG").is_ok());
+            assert!(db.read().unwrap().stories[&story_id].tags.is_empty());
+            assert!(db.add_story_tag(999, "bug").is_err());
+        }
+
+        #[test]
+        fn add_story_dependency_should_reject_self_links_and_cycles() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
+                .unwrap();
+            let ids: Vec<u32> = (0..3)
+                .map(|_| {
+                    db.create_story(&Story::new("Story", "Story description"), epic_id)
+                        .unwrap()
+                })
+                .collect();
+
+            assert!(db.add_story_dependency(ids[0], ids[1]).is_ok());
+            assert!(db.add_story_dependency(ids[1], ids[2]).is_ok());
+            assert!(db.add_story_dependency(ids[0], ids[0]).is_err());
+            assert!(db.add_story_dependency(ids[1], ids[0]).is_err());
+            assert!(db.add_story_dependency(ids[2], ids[0]).is_err());
+            assert!(db.add_story_dependency(ids[0], 999).is_err());
+            assert!(db.add_story_dependency(999, ids[0]).is_err());
+            assert_eq!(db.read().unwrap().stories[&ids[0]].blocked_by, vec![ids[1]]);
+
+            assert!(db.remove_story_dependency(ids[1], 999).is_err());
+            assert!(db.remove_story_dependency(ids[1], ids[2]).is_ok());
+            assert!(db.add_story_dependency(ids[2], ids[0]).is_ok());
+        }
+
+        #[test]
+        fn update_story_status_should_not_close_blocked_story() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
+                .unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", "Story 1 description"), epic_id)
+                .unwrap();
+            let blocker_id = db
+                .create_story(&Story::new("Story 2", "Story 2 description"), epic_id)
+                .unwrap();
+            db.add_story_dependency(story_id, blocker_id).unwrap();
+
+            let error = db
+                .update_story_status(story_id, Status::Closed)
+                .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                format!(
+                    "story #{} cannot be closed before story #{} is closed",
+                    story_id, blocker_id
+                )
+            );
+            assert!(db.update_story_status(story_id, Status::Resolved).is_ok());
+
+            db.update_story_status(blocker_id, Status::Resolved)
+                .unwrap();
+            assert!(db.update_story_status(story_id, Status::Closed).is_err());
+            db.update_story_status(blocker_id, Status::Closed).unwrap();
+            assert!(db.update_story_status(story_id, Status::Closed).is_ok());
+        }
+
+        #[test]
+        fn add_story_to_sprint_should_group_stories_across_epics() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let first_epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let second_epic_id = db.create_epic(&Epic::new("Epic 2", "")).unwrap();
+            let first_id = db
+                .create_story(&Story::new("Story 1", ""), first_epic_id)
+                .unwrap();
+            let second_id = db
+                .create_story(&Story::new("Story 2", ""), second_epic_id)
+                .unwrap();
+            let start = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+            let end = NaiveDate::from_ymd_opt(2024, 6, 14).unwrap();
+            let sprint_id = db
+                .create_sprint(&Sprint::new("Sprint 1", start, end))
+                .unwrap();
+
+            db.add_story_to_sprint(sprint_id, first_id).unwrap();
+            db.add_story_to_sprint(sprint_id, second_id).unwrap();
+            db.add_story_to_sprint(sprint_id, first_id).unwrap();
+            assert!(db.add_story_to_sprint(sprint_id, 999).is_err());
+            assert!(db.add_story_to_sprint(999, first_id).is_err());
+            assert_eq!(
+                db.read().unwrap().sprints[&sprint_id].story_ids,
+                vec![first_id, second_id]
+            );
+
+            db.remove_story_from_sprint(sprint_id, first_id).unwrap();
+            assert_eq!(
+                db.read().unwrap().sprints[&sprint_id].story_ids,
+                vec![second_id]
+            );
+        }
+
+        #[test]
+        fn create_sprint_should_reject_end_before_start() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let start = NaiveDate::from_ymd_opt(2024, 6, 14).unwrap();
+            let end = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+            assert!(db
+                .create_sprint(&Sprint::new("Sprint 1", start, end))
+                .is_err());
+            assert!(db.read().unwrap().sprints.is_empty());
+        }
+
+        #[test]
+        fn delete_story_should_remove_story_from_sprints() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let date = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+            let sprint_id = db
+                .create_sprint(&Sprint::new("Sprint 1", date, date))
+                .unwrap();
+            db.add_story_to_sprint(sprint_id, story_id).unwrap();
+
+            db.delete_story(story_id, epic_id).unwrap();
+            assert!(db.read().unwrap().sprints[&sprint_id].story_ids.is_empty());
+
+            let story_id = db
+                .create_story(&Story::new("Story 2", ""), epic_id)
+                .unwrap();
+            db.add_story_to_sprint(sprint_id, story_id).unwrap();
+            db.delete_epic(epic_id).unwrap();
+            assert!(db.read().unwrap().sprints[&sprint_id].story_ids.is_empty());
+        }
+
+        #[test]
+        fn log_work_should_add_entries() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let entry = |minutes| WorkEntry {
+                minutes,
+                note: "Pairing".to_owned(),
+                date: "2024-06-03".to_owned(),
+            };
+
+            db.log_work(story_id, &entry(90)).unwrap();
+            db.log_work(story_id, &entry(30)).unwrap();
+            assert!(db.log_work(story_id, &entry(0)).is_err());
+            assert!(db.log_work(999, &entry(30)).is_err());
+            assert!(db.log_work(story_id, &entry(u32::MAX - 100)).is_err());
+
+            let story = &db.read().unwrap().stories[&story_id];
+            assert_eq!(story.work_log, vec![entry(90), entry(30)]);
+            assert_eq!(story.logged_minutes(), 120);
+        }
+
+        #[test]
+        fn add_checklist_item_should_append_unchecked_items() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+
+            assert_eq!(db.add_checklist_item(story_id, "Docs").unwrap(), 1);
+            assert_eq!(db.add_checklist_item(story_id, " Tests ").unwrap(), 2);
+            assert!(matches!(
+                db.add_checklist_item(story_id, " ").unwrap_err(),
+                DbError::Invalid(_)
+            ));
+            let res = db.add_checklist_item(999, "Lost");
+            assert!(matches!(res, Err(DbError::StoryOrTaskNotFound(999))));
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                "no story or task found for id 999"
+            );
+
+            let checklist = &db.read().unwrap().stories[&story_id].checklist;
+            assert_eq!(
+                checklist,
+                &vec![
+                    ChecklistItem {
+                        text: "Docs".to_owned(),
+                        done: false
+                    },
+                    ChecklistItem {
+                        text: "Tests".to_owned(),
+                        done: false
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn toggle_checklist_item_should_flip_done() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let res = db.toggle_checklist_item(story_id, 0);
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                format!("story #{} has no checklist", story_id)
+            );
+            db.add_checklist_item(story_id, "Docs").unwrap();
+            db.add_checklist_item(story_id, "Tests").unwrap();
+
+            assert!(db.toggle_checklist_item(story_id, 1).unwrap());
+            let checklist = db.read().unwrap().stories[&story_id].checklist.clone();
+            assert!(!checklist[0].done);
+            assert!(checklist[1].done);
+            assert!(!db.toggle_checklist_item(story_id, 1).unwrap());
+            assert!(!db.read().unwrap().stories[&story_id].checklist[1].done);
+
+            let res = db.toggle_checklist_item(story_id, 2);
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                format!(
+                    "story #{} has no checklist item 3, it has items 1 to 2",
+                    story_id
+                )
+            );
+        }
+
+        #[test]
+        fn checklist_should_work_on_tasks() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let task_id = db.create_task(&Task::new("Task 1", ""), story_id).unwrap();
+            let res = db.toggle_checklist_item(task_id, 0);
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                format!("task #{} has no checklist", task_id)
+            );
+
+            assert_eq!(db.add_checklist_item(task_id, "Write").unwrap(), 1);
+            assert_eq!(db.add_checklist_item(task_id, "Review").unwrap(), 2);
+            assert!(db.toggle_checklist_item(task_id, 0).unwrap());
+            let res = db.toggle_checklist_item(task_id, 5);
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                format!(
+                    "task #{} has no checklist item 6, it has items 1 to 2",
+                    task_id
+                )
+            );
+
+            let state = db.read().unwrap();
+            let done: Vec<bool> = state.tasks[&task_id]
+                .checklist
+                .iter()
+                .map(|item| item.done)
+                .collect();
+            assert_eq!(done, vec![true, false]);
+            assert!(state.stories[&story_id].checklist.is_empty());
+        }
+
+        #[test]
+        fn remove_checklist_item_should_move_later_items_up() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            for text in ["Docs", "Tests", "Release"] {
+                db.add_checklist_item(story_id, text).unwrap();
+            }
+
+            assert_eq!(db.remove_checklist_item(story_id, 1).unwrap().text, "Tests");
+            assert!(db.remove_checklist_item(story_id, 2).is_err());
+            assert_eq!(db.remove_checklist_item(story_id, 0).unwrap().text, "Docs");
+            let texts: Vec<String> = db.read().unwrap().stories[&story_id]
+                .checklist
+                .iter()
+                .map(|item| item.text.clone())
+                .collect();
+            assert_eq!(texts, vec!["Release"]);
+        }
+
+        #[test]
+        fn add_comment_should_append_comments() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let comment = |body: &str| Comment {
+                author: "alice".to_owned(),
+                body: body.to_owned(),
+                at: Utc::now(),
+            };
+            let first = comment("First");
+            let second = comment(" Second ");
+
+            db.add_comment(story_id, &first).unwrap();
+            db.add_comment(story_id, &second).unwrap();
+            assert!(db.add_comment(story_id, &comment(" ")).is_err());
+            let res = db.add_comment(999, &comment("Lost"));
+            assert_eq!(res.unwrap_err().to_string(), "no story found for id 999");
+
+            let comments = &db.read().unwrap().stories[&story_id].comments;
+            assert_eq!(
+                comments,
+                &vec![
+                    first,
+                    Comment {
+                        body: "Second".to_owned(),
+                        ..second
+                    }
+                ]
+            );
+        }
+
+        #[test]
+        fn reorder_story_should_swap_neighbors() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
+                .unwrap();
+            let ids: Vec<u32> = (0..3)
+                .map(|_| {
+                    db.create_story(&Story::new("Story", "Story description"), epic_id)
+                        .unwrap()
+                })
+                .collect();
+
+            db.reorder_story(epic_id, ids[2], Direction::Up).unwrap();
+            assert_eq!(
+                db.read().unwrap().epics[&epic_id].story_ids,
+                vec![ids[0], ids[2], ids[1]]
+            );
+
+            db.reorder_story(epic_id, ids[0], Direction::Up).unwrap();
+            db.reorder_story(epic_id, ids[1], Direction::Down).unwrap();
+            assert_eq!(
+                db.read().unwrap().epics[&epic_id].story_ids,
+                vec![ids[0], ids[2], ids[1]]
+            );
+
+            assert!(db.reorder_story(epic_id, 999, Direction::Up).is_err());
+            assert!(db.reorder_story(999, ids[0], Direction::Up).is_err());
+        }
+
+        #[test]
+        fn exports_should_follow_backlog_order() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            db.create_story(&Story::new("First", ""), epic_id).unwrap();
+            let second_id = db.create_story(&Story::new("Second", ""), epic_id).unwrap();
+            db.reorder_story(epic_id, second_id, Direction::Up).unwrap();
+
+            let in_order = |text: &str| text.find("Second").unwrap() < text.find("First").unwrap();
+            assert!(in_order(&db.export_epic_markdown(epic_id).unwrap()));
+            let mut csv = Vec::new();
+            db.export_csv(&mut csv).unwrap();
+            assert!(in_order(&String::from_utf8(csv).unwrap()));
+            let mut html = Vec::new();
+            db.export_html(&mut html).unwrap();
+            assert!(in_order(&String::from_utf8(html).unwrap()));
+        }
+
+        #[test]
+        fn replace_in_descriptions_should_count_changed_items() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db
+                .create_epic(&Epic::new(
+                    "Epic 1",
+                    "Ship the widget, then the widget docs",
+                ))
+                .unwrap();
+            let story_id = db
+                .create_story(&Story::new("widget", "Build the Widget"), epic_id)
+                .unwrap();
+            let task_id = db
+                .create_task(&Task::new("Task 1", "Test the widget"), story_id)
+                .unwrap();
+            let other_story_id = db
+                .create_story(&Story::new("Story 2", "Unrelated"), epic_id)
+                .unwrap();
+            let before = db.read().unwrap();
+
+            assert_eq!(db.replace_in_descriptions("widget", "gadget").unwrap(), 2);
+
+            let state = db.read().unwrap();
+            assert_eq!(
+                state.epics[&epic_id].description,
+                "Ship the gadget, then the gadget docs"
+            );
+            assert_eq!(state.tasks[&task_id].description, "Test the gadget");
+            assert_eq!(state.stories[&story_id], before.stories[&story_id]);
+            assert_eq!(
+                state.stories[&other_story_id],
+                before.stories[&other_story_id]
+            );
+
+            assert_eq!(db.replace_in_descriptions("widget", "gadget").unwrap(), 0);
+            assert!(db.replace_in_descriptions("", "gadget").is_err());
+        }
+
+        #[test]
+        fn delete_story_should_remove_blocker_links() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
+                .unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", "Story 1 description"), epic_id)
+                .unwrap();
+            let blocker_id = db
+                .create_story(&Story::new("Story 2", "Story 2 description"), epic_id)
+                .unwrap();
+            db.add_story_dependency(story_id, blocker_id).unwrap();
+            db.delete_story(blocker_id, epic_id).unwrap();
+
+            assert!(db.read().unwrap().stories[&story_id].blocked_by.is_empty());
+            assert!(db.update_story_status(story_id, Status::Closed).is_ok());
+        }
+
+        #[test]
+        fn bulk_update_story_status_should_update_all_stories() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
+                .unwrap();
+            let other_epic_id = db
+                .create_epic(&Epic::new("Epic 2", "Epic 2 description"))
+                .unwrap();
+            for _ in 0..3 {
+                db.create_story(&Story::new("Story", "Story description"), epic_id)
+                    .unwrap();
+            }
+            let other_story_id = db
+                .create_story(&Story::new("Story", "Story description"), other_epic_id)
+                .unwrap();
+
+            assert_eq!(
+                db.bulk_update_story_status(epic_id, Status::InProgress)
+                    .unwrap(),
+                3
+            );
+            let state = db.read().unwrap();
+            assert!(state.epics[&epic_id]
+                .story_ids
+                .iter()
+                .all(|id| state.stories[id].status == Status::InProgress));
+            assert_eq!(state.stories[&other_story_id].status, Status::Open);
+            assert!(db.bulk_update_story_status(999, Status::Closed).is_err());
+        }
+
+        #[test]
+        fn update_story_assignee_should_succeed() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
+                .unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", "Story 1 description"), epic_id)
+                .unwrap();
+
+            assert!(db.update_story_assignee(story_id, Some("alice")).is_ok());
+            let state = db.read().unwrap();
+            assert_eq!(state.stories[&story_id].assignee, Some("alice".to_owned()));
+
+            assert!(db.update_story_assignee(story_id, None).is_ok());
+            let state = db.read().unwrap();
+            assert_eq!(state.stories[&story_id].assignee, None);
+        }
+
+        #[test]
+        fn update_story_points_should_reject_too_many_points() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+
+            db.update_story_points(story_id, Some(5)).unwrap();
+            assert!(matches!(
+                db.update_story_points(story_id, Some(3000000000)),
+                Err(DbError::Validation(ValidationError::TooManyPoints { .. }))
+            ));
+            assert!(matches!(
+                db.update_story_points(999, None),
+                Err(DbError::StoryNotFound(999))
+            ));
+            assert_eq!(db.read().unwrap().stories[&story_id].points, Some(5));
+        }
+
+        #[test]
+        fn update_story_status_should_error_on_invalid_story_id() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let invalid_story_id = 999u32;
+            assert!(db
+                .update_story_status(invalid_story_id, Status::InProgress)
+                .is_err());
+        }
+
+        #[test]
+        fn update_story_status_should_succeed() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+
+            let epic = Epic::new("Epic 1", "Epic 1 description");
+            let epic_id = db.create_epic(&epic).unwrap();
+
+            let story = Story::new("Story 1", "Story 1 description");
+            let story_id = db.create_story(&story, epic_id).unwrap();
+            assert!(db.update_story_status(story_id, Status::InProgress).is_ok());
+        }
+    }
+
+    mod database {
+        use std::{collections::BTreeMap, io::Write};
+
+        use crate::models::{Epic, Story};
+
+        use super::*;
+
+        #[test]
+        fn read_should_fail_with_invalid_path() {
+            let db = JSONFileDatabase::new("invalid");
+            assert!(db.read().is_err());
+        }
+
+        #[test]
+        fn read_should_fail_with_invalid_json() {
+            let mut file = tempfile::NamedTempFile::new().unwrap();
+            let malformed_data = r#"{ "lastItemId": null, "epics": {} "stories": {} "tasks": {} }"#;
+            write!(file, "{}", malformed_data).unwrap();
+
+            let file_path = file.path().to_str().unwrap();
+            let db = JSONFileDatabase::new(file_path);
+            assert!(db.read().is_err());
+        }
+
+        #[test]
+        fn read_should_parse_json_file() {
+            let mut file = tempfile::NamedTempFile::new().unwrap();
+            let data = r#"{ "lastItemId": null, "epics": {}, "stories": {}, "tasks": {} }"#;
+            write!(file, "{}", data).unwrap();
+
+            let file_path = file.path().to_str().unwrap();
+            let db = JSONFileDatabase::new(file_path);
+            assert!(db.read().is_ok());
+        }
+
+        #[test]
+        fn read_should_default_missing_fields() {
+            let mut file = tempfile::NamedTempFile::new().unwrap();
+            let data = r#"{
+                "lastItemId": 1,
+                "epics": { "0": { "name": "", "description": "", "status": "open", "storyIds": [1] } },
+                "stories": { "1": { "name": "", "description": "", "status": "open", "taskIds": [] } },
+                "tasks": {}
+            }"#;
+            write!(file, "{}", data).unwrap();
+
+            let file_path = file.path().to_str().unwrap();
+            let db = JSONFileDatabase::new(file_path);
+            let state = db.read().unwrap();
+            assert_eq!(state.epics[&0].assignee, None);
+            assert!(!state.epics[&0].status_override);
+            assert_eq!(state.stories[&1].assignee, None);
+            assert!(state.stories[&1].checklist.is_empty());
+            assert!(state.sprints.is_empty());
+        }
+
+        #[test]
+        fn init_empty_should_create_parseable_board() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("data").join("db.json");
+            let file_path = file_path.to_str().unwrap();
+
+            assert!(JiraDatabase::init_empty(file_path, false).is_ok());
+            let db = JSONFileDatabase::new(file_path);
+            assert_eq!(db.read().unwrap(), DatabaseState::default());
+        }
+
+        #[test]
+        fn init_empty_should_not_overwrite_without_force() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json");
+            let file_path = file_path.to_str().unwrap();
+            JiraDatabase::init_empty(file_path, false).unwrap();
+            let db = JiraDatabase::new(file_path).unwrap();
+            db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+
+            assert!(matches!(
+                JiraDatabase::init_empty(file_path, false),
+                Err(DbError::AlreadyExists { .. })
+            ));
+            assert_eq!(db.read().unwrap().epics.len(), 1);
+
+            assert!(JiraDatabase::init_empty(file_path, true).is_ok());
+            assert!(db.read().unwrap().epics.is_empty());
+        }
+
+        #[test]
+        fn write_should_write_to_file() {
+            let mut file = tempfile::NamedTempFile::new().unwrap();
+            let data = r#"{ "lastItemId": null, "epics": {}, "stories": {} }"#;
+            write!(file, "{}", data).unwrap();
+
+            let file_path = file.path().to_str().unwrap();
+            let db = JSONFileDatabase::new(file_path);
+
+            let story = Story::new("Story 1", "Story 1 description");
+            let mut stories = BTreeMap::<u32, Story>::new();
+            stories.insert(0, story);
+            let epic = Epic::new("Epic 1", "Epic 1 description");
+            let mut epics = BTreeMap::<u32, Epic>::new();
+            epics.insert(1, epic);
+            let task = Task::new("Task 1", "Task 1 description");
+            let mut tasks = BTreeMap::<u32, Task>::new();
+            tasks.insert(2, task);
+
+            let state = DatabaseState {
+                last_item_id: Some(1u32),
+                epics,
+                stories,
+                tasks,
+                ..Default::default()
+            };
+            assert!(db.write(&state).is_ok());
+            assert_eq!(db.read().unwrap(), state);
+        }
+
+        /// `modify_externally` replaces the contents of the file as another program would,
+        /// with a modification time that differs from the current one.
+        fn modify_externally(file_path: &str, state: &DatabaseState) {
+            let modified = fs::metadata(file_path).unwrap().modified().unwrap();
+            fs::write(file_path, serde_json::to_string(state).unwrap()).unwrap();
+            fs::File::options()
+                .write(true)
+                .open(file_path)
+                .unwrap()
+                .set_modified(modified + std::time::Duration::from_secs(1))
+                .unwrap();
+        }
+
+        #[test]
+        fn refresh_if_changed_should_detect_external_changes() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json");
+            let file_path = file_path.to_str().unwrap();
+            let db = JiraDatabase::new(file_path).unwrap();
+            db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            assert!(!db.refresh_if_changed().unwrap());
+
+            let mut state = db.read().unwrap();
+            state.epics.clear();
+            modify_externally(file_path, &state);
+            assert!(db.refresh_if_changed().unwrap());
+            assert!(!db.refresh_if_changed().unwrap());
+            assert!(db.read().unwrap().epics.is_empty());
+            assert!(!db.undo().unwrap());
+
+            assert!(db.create_epic(&Epic::new("Epic 2", "")).is_ok());
+        }
+
+        #[test]
+        fn write_should_not_overwrite_external_changes() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json");
+            let file_path = file_path.to_str().unwrap();
+            let db = JiraDatabase::new(file_path).unwrap();
+            db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+
+            let mut state = db.read().unwrap();
+            state
+                .epics
+                .values_mut()
+                .for_each(|epic| epic.name = "Renamed".to_owned());
+            modify_externally(file_path, &state);
+            let error = db.create_epic(&Epic::new("Epic 2", "")).unwrap_err();
+            assert!(matches!(error, DbError::Write { .. }));
+
+            let db = JSONFileDatabase::new(file_path);
+            assert_eq!(db.read().unwrap(), state);
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use std::{env, error::Error, fmt::Display};
+
+/// `MAX_NAME_LENGTH` is the number of characters allowed in the name of an epic, story,
+/// task or sprint.
+pub const MAX_NAME_LENGTH: usize = 30;
+
+/// `MAX_STORY_POINTS` is the largest estimate a story can have, so that the points of a
+/// whole board still add up without overflowing.
+pub const MAX_STORY_POINTS: u32 = 1000;
+
+/// `DEFAULT_MAX_DESCRIPTION_LENGTH` is the number of characters allowed in a description
+/// unless `CLIRA_MAX_DESCRIPTION_LENGTH` says otherwise.
+pub const DEFAULT_MAX_DESCRIPTION_LENGTH: usize = 1000;
+
+/// A `ValidationError` explains why a name, description or estimate cannot be written to the
+/// database.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    EmptyName,
+    NameTooLong { length: usize, max: usize },
+    DescriptionTooLong { length: usize, max: usize },
+    TooManyPoints { points: u32, max: u32 },
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyName => write!(f, "a name cannot be empty"),
+            Self::NameTooLong { length, max } => write!(
+                f,
+                "a name can be at most {} characters long, this one is {}",
+                max, length
+            ),
+            Self::DescriptionTooLong { length, max } => write!(
+                f,
+                "a description can be at most {} characters long, this one is {}",
+                max, length
+            ),
+            Self::TooManyPoints { points, max } => {
+                write!(
+                    f,
+                    "a story can be estimated at most {} points, not {}",
+                    max, points
+                )
+            }
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// `max_description_length` returns the number of characters allowed in a description,
+/// read from `CLIRA_MAX_DESCRIPTION_LENGTH`. Falls back to `DEFAULT_MAX_DESCRIPTION_LENGTH`.
+pub fn max_description_length() -> usize {
+    max_description_length_from(env::var("CLIRA_MAX_DESCRIPTION_LENGTH").ok().as_deref())
+}
+
+/// `max_description_length_from` parses the configured description length `value`. Missing,
+/// invalid or zero values keep the default.
+fn max_description_length_from(value: Option<&str>) -> usize {
+    value
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|max| *max > 0)
+        .unwrap_or(DEFAULT_MAX_DESCRIPTION_LENGTH)
+}
+
+/// `name` returns `name` with control characters stripped and surrounding whitespace
+/// trimmed, or `Err` if it is empty or longer than `MAX_NAME_LENGTH`.
+pub fn name(name: &str) -> Result<String, ValidationError> {
+    let name: String = name.chars().filter(|c| !c.is_control()).collect();
+    let name = name.trim();
+    let length = name.chars().count();
+    if length == 0 {
+        return Err(ValidationError::EmptyName);
+    }
+    if length > MAX_NAME_LENGTH {
+        return Err(ValidationError::NameTooLong {
+            length,
+            max: MAX_NAME_LENGTH,
+        });
+    }
+    Ok(name.to_owned())
+}
+
+/// `description` returns `description` with control characters other than line breaks
+/// stripped, or `Err` if it is longer than `max_description_length`.
+pub fn description(description: &str) -> Result<String, ValidationError> {
+    description_within(description, max_description_length())
+}
+
+/// `points` returns the story points `points`, or `Err` if they are more than
+/// `MAX_STORY_POINTS`. A missing estimate is always valid.
+pub fn points(points: Option<u32>) -> Result<Option<u32>, ValidationError> {
+    match points {
+        Some(points) if points > MAX_STORY_POINTS => Err(ValidationError::TooManyPoints {
+            points,
+            max: MAX_STORY_POINTS,
+        }),
+        points => Ok(points),
+    }
+}
+
+/// `description_within` is `description` with an explicit maximum length `max`.
+fn description_within(description: &str, max: usize) -> Result<String, ValidationError> {
+    let description: String = description
+        .chars()
+        .filter(|c| *c == '\n' || !c.is_control())
+        .collect();
+    let length = description.chars().count();
+    if length > max {
+        return Err(ValidationError::DescriptionTooLong { length, max });
+    }
+    Ok(description)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_should_reject_empty_names() {
+        assert_eq!(name(""), Err(ValidationError::EmptyName));
+        assert_eq!(name(" \t\u{7}"), Err(ValidationError::EmptyName));
+    }
+
+    #[test]
+    fn name_should_reject_long_names() {
+        let long = "é".repeat(MAX_NAME_LENGTH + 1);
+        assert_eq!(
+            name(&long),
+            Err(ValidationError::NameTooLong {
+                length: MAX_NAME_LENGTH + 1,
+                max: MAX_NAME_LENGTH
+            })
+        );
+        let longest = "é".repeat(MAX_NAME_LENGTH);
+        assert_eq!(name(&longest), Ok(longest.clone()));
+    }
+
+    #[test]
+    fn points_should_reject_more_than_max() {
+        assert_eq!(points(None), Ok(None));
+        assert_eq!(points(Some(MAX_STORY_POINTS)), Ok(Some(MAX_STORY_POINTS)));
+        assert_eq!(
+            points(Some(3000000000)),
+            Err(ValidationError::TooManyPoints {
+                points: 3000000000,
+                max: MAX_STORY_POINTS
+            })
+        );
+    }
+
+    #[test]
+    fn name_should_strip_control_characters() {
+        assert_eq!(
+            name(" Fix\u{1b}[31m the\n bug "),
+            Ok("Fix[31m the bug".to_owned())
+        );
+    }
+
+    #[test]
+    fn description_should_keep_line_breaks() {
+        assert_eq!(
+            description_within("first\r\nsecond\u{0}", 20),
+            Ok("first\nsecond".to_owned())
+        );
+    }
+
+    #[test]
+    fn description_should_reject_long_descriptions() {
+        assert_eq!(description_within("abcd", 4), Ok("abcd".to_owned()));
+        assert_eq!(
+            description_within("abcde", 4),
+            Err(ValidationError::DescriptionTooLong { length: 5, max: 4 })
+        );
+    }
+
+    #[test]
+    fn max_description_length_from_should_fall_back_to_default() {
+        assert_eq!(max_description_length_from(Some(" 200 ")), 200);
+        assert_eq!(
+            max_description_length_from(Some("0")),
+            DEFAULT_MAX_DESCRIPTION_LENGTH
+        );
+        assert_eq!(
+            max_description_length_from(Some("lots")),
+            DEFAULT_MAX_DESCRIPTION_LENGTH
+        );
+        assert_eq!(
+            max_description_length_from(None),
+            DEFAULT_MAX_DESCRIPTION_LENGTH
+        );
+    }
+
+    #[test]
+    fn validation_error_should_explain_limit() {
+        assert_eq!(
+            ValidationError::NameTooLong {
+                length: 40,
+                max: 30
+            }
+            .to_string(),
+            "a name can be at most 30 characters long, this one is 40"
+        );
+    }
+}
@@ -1,48 +1,254 @@
-use std::rc::Rc;
+use std::{
+    env,
+    io::{self, IsTerminal},
+    path::Path,
+    process,
+    rc::Rc,
+    time::Duration,
+};
 
-use db::JiraDatabase;
-use ui::navigator::{NavigationManager, Navigator};
-use utils::read_line;
+use clap::Parser;
+use cli::{BoardCommand, Cli, Command};
+use config::{Config, CONFIG_ENV_VAR};
+use db::{error::DbError, import::jira_cloud, JiraDatabase};
+use session::Session;
+use theme::Theme;
+use ui::{
+    input::{InputSource, KeyInput, LineInput, WatchInput},
+    io::StdIo,
+    navigator::Navigator,
+};
+use utils::{clira_user, ColorMode};
+use workspaces::Workspaces;
 
+mod cli;
+mod config;
 mod db;
 mod models;
+mod session;
+mod theme;
 mod ui;
 mod utils;
+mod workspaces;
 
 fn main() {
-    let file_path = "data/db.json";
-    let db =
-        Rc::new(JiraDatabase::new(file_path).expect("failed to load database file into program"));
-    let mut nav = Navigator::new(db.clone());
+    let cli = Cli::parse();
+    ColorMode::init(cli.no_color);
+    let (config, config_warnings) = load_config();
+    Theme::set(config.theme);
+    let db_path = cli::resolve_db_path(
+        cli.db.as_deref(),
+        env::var(cli::DB_ENV_VAR).ok().as_deref(),
+        config.db_path.as_deref(),
+        env::var("HOME").ok().as_deref(),
+    );
+    if cli.command.is_some() {
+        for warning in &config_warnings {
+            eprintln!("{}", warning);
+        }
+    }
+    match cli.command {
+        Some(Command::Init { path, force }) => {
+            init(path.as_deref().unwrap_or(&db_path), force, cli.dry_run)
+        }
+        Some(Command::ImportJira { export }) => import_jira(&db_path, &export, cli.dry_run),
+        Some(Command::Board(command)) => {
+            run_board_command(&db_path, command, cli.json, cli.dry_run)
+        }
+        None => run_ui(
+            &db_path,
+            config,
+            config_warnings,
+            cli.memory,
+            input_source(cli.keys, cli.watch),
+            cli.fresh,
+            cli.dry_run,
+        ),
+    }
+}
 
-    loop {
-        clearscreen::clear().expect("failed to clear the screen");
-        if nav.current_page().is_none() {
-            break;
-        }
-        let page = nav.current_page().unwrap();
-        if let Err(error) = page.draw() {
-            println!("Error rendering page: {}", error);
-            println!("Press (enter) to continue..");
-            let _ = read_line();
-        }
-        if let Some(line) = read_line() {
-            match page.action_from(&line) {
-                Ok(action) => {
-                    if let Some(action) = action {
-                        if let Err(error) = nav.dispatch_action(action) {
-                            println!("Error processing request: {}", error);
-                            println!("Press (enter) to continue..");
-                            let _ = read_line();
-                        }
-                    }
-                }
-                Err(error) => {
-                    println!("Error reading input: {}", error);
-                    println!("Press (enter) to continue..");
-                    let _ = read_line();
+/// `input_source` returns where the UI reads its input from. With `keys`, lists can be
+/// navigated with the navigation keys when stdin is a terminal. With `watch`, lines are
+/// read in the background and the page is redrawn every `watch` seconds.
+fn input_source(keys: bool, watch: Option<u64>) -> Box<dyn InputSource> {
+    match watch {
+        Some(seconds) => Box::new(WatchInput::spawn(Duration::from_secs(seconds), || {
+            LineInput::new(Rc::new(StdIo))
+        })),
+        None if keys && io::stdin().is_terminal() => Box::new(KeyInput),
+        None => Box::new(LineInput::new(Rc::new(StdIo))),
+    }
+}
+
+/// `config_path` returns the file named by `CLIRA_CONFIG`, or else
+/// `~/.config/clira/config.toml`.
+fn config_path() -> Option<String> {
+    Config::path(
+        env::var(CONFIG_ENV_VAR).ok().as_deref(),
+        env::var("HOME").ok().as_deref(),
+    )
+}
+
+/// `load_config` reads the user's settings from the `config_path`, along with warnings about
+/// the settings that were ignored. The default settings are used if there is no config file.
+fn load_config() -> (Config, Vec<String>) {
+    let Some(path) = config_path() else {
+        return (Config::default(), vec![]);
+    };
+    let (config, warnings) = Config::load(Path::new(&path));
+    let warnings = warnings
+        .into_iter()
+        .map(|warning| format!("Warning: {} ({})", warning, path))
+        .collect();
+    (config, warnings)
+}
+
+/// `run_ui` launches the interactive UI on the board at `db_path`, following the user's
+/// settings in `config`. The `config_warnings` are shown above the first page. With
+/// `in_memory`, the board is not loaded from or saved to disk. Input is read from `input`,
+/// see `input_source`. The epics and stories open on exit are reopened on the next run,
+/// unless `fresh` is set. With `dry_run`, changes are described instead of saved.
+fn run_ui(
+    db_path: &str,
+    config: Config,
+    config_warnings: Vec<String>,
+    in_memory: bool,
+    mut input: Box<dyn InputSource>,
+    fresh: bool,
+    dry_run: bool,
+) {
+    let db = if in_memory {
+        JiraDatabase::in_memory()
+    } else {
+        match open_board(db_path, false, dry_run) {
+            Ok(db) => db,
+            Err(error) => {
+                eprintln!("Error opening the board: {}", error);
+                if let Some(DbError::NotFound { .. }) = error.downcast_ref::<DbError>() {
+                    eprintln!("Create an empty board with `init`.");
                 }
+                process::exit(1);
+            }
+        }
+    };
+    let db = Rc::new(db);
+    let mut nav = Navigator::new(db.clone());
+    nav.set_dry_run(dry_run);
+    nav.set_user(clira_user());
+    nav.set_config(config);
+    nav.set_config_path(config_path());
+    if !in_memory {
+        nav.set_board_path(Some(db_path.to_owned()));
+    }
+    if let (false, Ok(home)) = (in_memory, env::var("HOME")) {
+        let config_path = Workspaces::config_path(&home);
+        match Workspaces::load(Path::new(&config_path)) {
+            Ok(workspaces) => nav.set_workspaces(workspaces),
+            Err(error) => eprintln!("Error loading workspaces from {}: {}", config_path, error),
+        }
+    }
+    if !in_memory && !fresh {
+        let restored = Session::load(Path::new(&Session::path(db_path)))
+            .and_then(|session| nav.restore_pages(&session.pages));
+        if let Err(error) = restored {
+            eprintln!("Error restoring the last session: {}", error);
+        }
+    }
+    let mut startup_messages = vec![];
+    if !in_memory {
+        startup_messages.push(format!("Using the board at {}", db_path));
+    }
+    startup_messages.extend(config_warnings);
+    let banner = if in_memory {
+        Some("In-memory board: changes won't be saved.")
+    } else if dry_run {
+        Some("Dry run: changes won't be saved.")
+    } else {
+        None
+    };
+    let (board_path, open_pages) = nav.run(input.as_mut(), banner, startup_messages);
+
+    // The session is saved for the board shown on exit, which is another one than `db_path`
+    // after switching workspaces.
+    if let Some(board_path) = board_path {
+        let session = Session { pages: open_pages };
+        if let Err(error) = session.save(Path::new(&Session::path(&board_path))) {
+            eprintln!("Error saving the session: {}", error);
+        }
+    }
+}
+
+/// `init` handles `init [path] [--force]`, which creates an empty board at `file_path`.
+/// With `dry_run`, the board is not created.
+fn init(file_path: &str, force: bool, dry_run: bool) {
+    if dry_run {
+        println!("Would create an empty board at {}", file_path);
+        return;
+    }
+    match JiraDatabase::init_empty(file_path, force) {
+        Ok(()) => println!("Created an empty board at {}", file_path),
+        Err(error) => {
+            eprintln!("Error creating board: {}", error);
+            process::exit(1);
+        }
+    }
+}
+
+/// `import_jira` handles `import-jira <export.json>`, which adds the issues of a Jira Cloud
+/// JSON export to the board at `db_path`. With `dry_run`, the issues are not saved.
+fn import_jira(db_path: &str, export_path: &str, dry_run: bool) {
+    let summary = open_board(db_path, false, dry_run)
+        .and_then(|db| jira_cloud::import_from_jira(export_path, &db));
+    match summary {
+        Ok(summary) => {
+            println!(
+                "Imported {} epics, {} stories and {} tasks from {}",
+                summary.epics_created, summary.stories_created, summary.tasks_created, export_path
+            );
+            for skipped in summary.skipped {
+                println!("  skipped {}", skipped);
+            }
+            for warning in summary.warnings {
+                println!("  warning: {}", warning);
+            }
+            if dry_run {
+                println!("Dry run, nothing was saved");
             }
         }
+        Err(error) => {
+            eprintln!("Error importing from Jira: {}", error);
+            process::exit(1);
+        }
+    }
+}
+
+/// `run_board_command` runs a non-interactive `command` against the board at `db_path` and
+/// prints its results to stdout, as JSON if `json` is set. Read-only commands open the board
+/// without writing to it. With `dry_run`, the changes the command would make are printed
+/// to stderr instead of saved. Exits with a nonzero status if the command failed.
+fn run_board_command(db_path: &str, command: BoardCommand, json: bool, dry_run: bool) {
+    let db = open_board(db_path, command.is_read_only(), dry_run);
+    let res = db.and_then(|db| {
+        cli::run_cli(command, json, &db, &mut io::stdout())?;
+        for change in db.discard_changes()? {
+            eprintln!("{}", change);
+        }
+        Ok(())
+    });
+    if let Err(error) = res {
+        eprintln!("Error: {}", error);
+        process::exit(1);
+    }
+}
+
+/// `open_board` opens the board at `db_path` for a command. A `read_only` command or a
+/// `dry_run` opens it without writing to it, and a `dry_run` holds back the changes made.
+fn open_board(db_path: &str, read_only: bool, dry_run: bool) -> anyhow::Result<JiraDatabase> {
+    if dry_run {
+        Ok(JiraDatabase::open_existing(db_path)?.dry_run())
+    } else if read_only {
+        Ok(JiraDatabase::open_existing(db_path)?)
+    } else {
+        Ok(JiraDatabase::new(db_path)?)
     }
 }
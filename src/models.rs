@@ -1,50 +1,401 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    error::Error,
+    fmt::Display,
+    str::FromStr,
+};
 
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
+/// A `Setting` is a setting of the config file that can be changed on the settings page.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Setting {
+    RowsPerPage,
+    ConfirmExit,
+    SortByStatus,
+    Theme,
+}
+
+/// `EDITABLE_SETTINGS` lists the settings that can be changed on the settings page, in the
+/// order they are numbered there.
+pub const EDITABLE_SETTINGS: [Setting; 4] = [
+    Setting::RowsPerPage,
+    Setting::ConfirmExit,
+    Setting::SortByStatus,
+    Setting::Theme,
+];
+
 /// An `Action` represents the different types of actions that are accepted from
 /// user input.
 #[derive(Debug, PartialEq, Eq)]
 pub enum Action {
-    NavigateToEpicDetail { epic_id: u32 },
-    NavigateToStoryDetail { story_id: u32, epic_id: u32 },
-    NavigateToTaskDetail { task_id: u32, story_id: u32 },
+    NavigateToEpicDetail {
+        epic_id: u32,
+    },
+    NavigateToStoryDetail {
+        story_id: u32,
+        epic_id: u32,
+    },
+    NavigateToTaskDetail {
+        task_id: u32,
+        story_id: u32,
+    },
+    /// `NavigateToDescription` shows the whole description of the epic or story `id`.
+    NavigateToDescription {
+        id: u32,
+    },
+    NavigateToHistory,
+    NavigateToRecent,
+    /// `NavigateToModified` lists the epics and stories changed most recently.
+    NavigateToModified,
+    /// `JumpToItem` asks for the id of an epic or story and opens it, from any page.
+    JumpToItem,
+    /// `OpenPalette` lists the command palette entries matching `query`, see
+    /// `ui::palette`, and runs the chosen one, from any page.
+    OpenPalette {
+        query: String,
+    },
+    NavigateToWorkspaces,
+    NavigateToSettings,
+    /// `UpdateSetting` changes `setting` and saves the settings to the config file.
+    UpdateSetting {
+        setting: Setting,
+    },
+    NavigateToTrash,
+    /// `RestoreFromTrash` restores the epic or story `id` from the trash.
+    RestoreFromTrash {
+        id: u32,
+    },
+    /// `PurgeTrash` permanently deletes everything in the trash.
+    PurgeTrash,
+    NavigateToSprints,
+    NavigateToSprintDetail {
+        sprint_id: u32,
+    },
     NavigateToPreviousPage,
+    NavigateHome,
     CreateEpic,
-    CreateStory { epic_id: u32 },
-    CreateTask { story_id: u32 },
-    UpdateEpicName { epic_id: u32 },
-    UpdateEpicDescription { epic_id: u32 },
-    UpdateEpicStatus { epic_id: u32 },
-    UpdateStoryName { story_id: u32 },
-    UpdateStoryDescription { story_id: u32 },
-    UpdateStoryStatus { story_id: u32 },
-    UpdateTaskName { task_id: u32 },
-    UpdateTaskDescription { task_id: u32 },
-    UpdateTaskStatus { task_id: u32 },
-    DeleteEpic { epic_id: u32 },
-    DeleteStory { story_id: u32, epic_id: u32 },
-    DeleteTask { task_id: u32, story_id: u32 },
+    /// `CreateEpicFromTemplate` creates an epic and its stories from one of the templates
+    /// in the config.
+    CreateEpicFromTemplate,
+    CreateStory {
+        epic_id: u32,
+    },
+    /// `CreateStoryNamed` creates a story called `name` with an empty description, without
+    /// prompting for it.
+    CreateStoryNamed {
+        epic_id: u32,
+        name: String,
+    },
+    /// `CreateStories` creates several stories at once from a list of names.
+    CreateStories {
+        epic_id: u32,
+    },
+    CreateTask {
+        story_id: u32,
+    },
+    CreateSprint,
+    UpdateSprintStories {
+        sprint_id: u32,
+    },
+    DuplicateEpic {
+        epic_id: u32,
+    },
+    UpdateEpicName {
+        epic_id: u32,
+    },
+    UpdateEpicDescription {
+        epic_id: u32,
+    },
+    UpdateEpicStatus {
+        epic_id: u32,
+    },
+    SetEpicStatus {
+        epic_id: u32,
+        status: Status,
+    },
+    UpdateEpicAssignee {
+        epic_id: u32,
+    },
+    /// `ToggleEpicArchived` archives the epic, or brings it back if it was archived.
+    ToggleEpicArchived {
+        epic_id: u32,
+    },
+    UpdateEpicStoriesStatus {
+        epic_id: u32,
+    },
+    /// `CloseEpicStories` closes every story of the epic at once, e.g. at the end of a
+    /// sprint.
+    CloseEpicStories {
+        epic_id: u32,
+    },
+    UpdateStoryName {
+        story_id: u32,
+    },
+    UpdateStoryDescription {
+        story_id: u32,
+    },
+    UpdateStoryStatus {
+        story_id: u32,
+    },
+    SetStoryStatus {
+        story_id: u32,
+        status: Status,
+    },
+    UpdateStoryAssignee {
+        story_id: u32,
+    },
+    /// `AssignStoryToMe` assigns the story to the default user, or asks for the assignee if
+    /// there is none.
+    AssignStoryToMe {
+        story_id: u32,
+    },
+    UpdateStoryTags {
+        story_id: u32,
+    },
+    /// `UpdateStoryPoints` asks for the story points estimated for the story.
+    UpdateStoryPoints {
+        story_id: u32,
+    },
+    UpdateStoryBlockers {
+        story_id: u32,
+    },
+    LogWork {
+        story_id: u32,
+    },
+    AddComment {
+        story_id: u32,
+    },
+    /// `AddChecklistItem` adds an unchecked item reading `text` to the checklist of the
+    /// story or task `id`.
+    AddChecklistItem {
+        id: u32,
+        text: String,
+    },
+    /// `ToggleChecklistItem` checks or unchecks the checklist item at `index` of the story
+    /// or task `id`.
+    ToggleChecklistItem {
+        id: u32,
+        index: usize,
+    },
+    /// `RemoveChecklistItem` removes the checklist item at `index` of the story or task
+    /// `id`.
+    RemoveChecklistItem {
+        id: u32,
+        index: usize,
+    },
+    MoveStory {
+        epic_id: u32,
+        story_id: u32,
+        direction: Direction,
+    },
+    UpdateTaskName {
+        task_id: u32,
+    },
+    UpdateTaskDescription {
+        task_id: u32,
+    },
+    UpdateTaskStatus {
+        task_id: u32,
+    },
+    DeleteEpic {
+        epic_id: u32,
+    },
+    DeleteStory {
+        story_id: u32,
+        epic_id: u32,
+    },
+    DeleteStories {
+        epic_id: u32,
+    },
+    /// `DeleteClosedStories` deletes every Closed story of the epic.
+    DeleteClosedStories {
+        epic_id: u32,
+    },
+    DeleteTask {
+        task_id: u32,
+        story_id: u32,
+    },
+    /// `ConvertStoryToTask` turns the story into a task of another story of the user's
+    /// choosing.
+    ConvertStoryToTask {
+        story_id: u32,
+        epic_id: u32,
+    },
+    /// `ConvertTaskToStory` turns the task into a story of an epic of the user's choosing.
+    ConvertTaskToStory {
+        task_id: u32,
+        story_id: u32,
+    },
+    ExportCsv,
+    /// `ExportEpic` writes the epic as a Markdown report and as a bundle that another board
+    /// can import.
+    ExportEpic {
+        epic_id: u32,
+    },
+    ExportHtml,
+    ExportStoriesJsonl,
+    ImportCsv,
+    /// `ImportEpicBundle` imports the epic bundle at `path`, see `JiraDatabase::import_epic`.
+    ImportEpicBundle {
+        path: String,
+    },
+    ReplaceInDescriptions,
+    SwitchWorkspace {
+        name: String,
+    },
+    Undo,
     Exit,
 }
 
+/// `Direction` is the way a story is moved within the order of its epic's stories.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Up => "up",
+            Self::Down => "down",
+        })
+    }
+}
+
 /// `DatabaseState` represents the state of the database. It is the base type that is
-/// serialized into the JSON file for persistence.
-#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+/// serialized into the JSON file for persistence. Items are keyed by id in ascending order,
+/// so that the same state is always serialized the same way.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
 pub struct DatabaseState {
     #[serde(rename = "lastItemId")]
     pub last_item_id: Option<u32>,
-    pub epics: HashMap<u32, Epic>,
-    pub stories: HashMap<u32, Story>,
-    pub tasks: HashMap<u32, Task>,
+    pub epics: BTreeMap<u32, Epic>,
+    pub stories: BTreeMap<u32, Story>,
+    pub tasks: BTreeMap<u32, Task>,
+    #[serde(default)]
+    pub audit: Vec<AuditEntry>,
+    #[serde(default)]
+    pub sprints: BTreeMap<u32, Sprint>,
+    /// `recent` holds the epics and stories viewed most recently, most recent first.
+    #[serde(default)]
+    pub recent: Vec<RecentItem>,
+    /// `trash_epics` holds the deleted epics, with their stories and tasks, until they are
+    /// restored or the trash is emptied.
+    #[serde(default, rename = "trashEpics")]
+    pub trash_epics: BTreeMap<u32, TrashedEpic>,
+    /// `trash_stories` holds the stories deleted on their own, with their tasks.
+    #[serde(default, rename = "trashStories")]
+    pub trash_stories: BTreeMap<u32, TrashedStory>,
 }
 
-/// `Status` models the different states that an `Epic` or `Story` can be in. `Open` is
-/// the default state.
+/// A `TrashedEpic` is a deleted epic in the trash, along with the stories and tasks that
+/// were deleted with it.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TrashedEpic {
+    pub epic: Epic,
+    pub stories: BTreeMap<u32, Story>,
+    pub tasks: BTreeMap<u32, Task>,
+    #[serde(rename = "deletedAt")]
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// A `TrashedStory` is a deleted story in the trash, along with its tasks. `epic_id` is the
+/// epic it was deleted from, which it is restored to if that epic still exists.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TrashedStory {
+    pub story: Story,
+    #[serde(rename = "epicId")]
+    pub epic_id: u32,
+    pub tasks: BTreeMap<u32, Task>,
+    #[serde(rename = "deletedAt")]
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// A `ModifiedItem` is an epic or story along with when it was last changed, see
+/// `DatabaseState::recently_modified`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ModifiedItem<'a> {
+    Epic {
+        id: u32,
+        epic: &'a Epic,
+        at: DateTime<Utc>,
+    },
+    Story {
+        id: u32,
+        epic_id: u32,
+        story: &'a Story,
+        at: DateTime<Utc>,
+    },
+}
+
+impl ModifiedItem<'_> {
+    /// `id` returns the id of the epic or story.
+    pub fn id(&self) -> u32 {
+        match self {
+            Self::Epic { id, .. } | Self::Story { id, .. } => *id,
+        }
+    }
+
+    /// `at` returns when the epic or story was last changed.
+    pub fn at(&self) -> DateTime<Utc> {
+        match self {
+            Self::Epic { at, .. } | Self::Story { at, .. } => *at,
+        }
+    }
+
+    /// `recent_item` returns the item to view the epic or story with.
+    pub fn recent_item(&self) -> RecentItem {
+        match *self {
+            Self::Epic { id, .. } => RecentItem::Epic(id),
+            Self::Story { id, epic_id, .. } => RecentItem::Story {
+                story_id: id,
+                epic_id,
+            },
+        }
+    }
+}
+
+/// An `EpicBundle` is an epic with its stories and their tasks, keyed by their ids on the
+/// board they were exported from, so that the epic can be imported on another board.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct EpicBundle {
+    #[serde(rename = "epicId")]
+    pub epic_id: u32,
+    pub epic: Epic,
+    pub stories: BTreeMap<u32, Story>,
+    #[serde(default)]
+    pub tasks: BTreeMap<u32, Task>,
+}
+
+/// A `RecentItem` is an epic or story that was navigated to.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecentItem {
+    Epic(u32),
+    Story { story_id: u32, epic_id: u32 },
+}
+
+/// An `AuditEntry` records a single change made to the database, such as creating an epic
+/// or updating the status of a story. Entries are kept oldest first.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AuditEntry {
+    pub at: DateTime<Utc>,
+    pub action: String,
+    pub id: Option<u32>,
+}
+
+/// `Status` models the different states that an `Epic` or `Story` can be in. `Open` is
+/// the default state. Statuses are ordered by progress, from `Open` to `Closed`.
+#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
 pub enum Status {
     #[serde(rename = "open")]
     Open,
+    /// `Blocked` is for work that cannot move forward until something outside of it is done.
+    #[serde(rename = "blocked")]
+    Blocked,
     #[serde(rename = "inProgress")]
     InProgress,
     #[serde(rename = "resolved")]
@@ -63,6 +414,15 @@ pub struct Epic {
     pub name: String,
     pub description: String,
     pub status: Status,
+    /// `status_override` is set when the status was chosen manually, which stops it from
+    /// being derived from the epic's stories.
+    #[serde(default, rename = "statusOverride")]
+    pub status_override: bool,
+    /// `archived` hides the epic from the home page, without deleting it.
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub assignee: Option<String>,
     #[serde(rename = "storyIds")]
     pub story_ids: Vec<u32>,
 }
@@ -74,10 +434,56 @@ pub struct Story {
     pub name: String,
     pub description: String,
     pub status: Status,
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// `tags` are lowercase labels used to group stories, without duplicates.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// `points` is the estimated size of the story, if it was estimated.
+    #[serde(default)]
+    pub points: Option<u32>,
+    /// `blocked_by` holds the ids of the stories that must be done before this story can be
+    /// closed.
+    #[serde(default, rename = "blockedBy")]
+    pub blocked_by: Vec<u32>,
+    /// `work_log` holds the time spent on the story, oldest first.
+    #[serde(default, rename = "workLog")]
+    pub work_log: Vec<WorkEntry>,
+    /// `comments` holds the discussion on the story, oldest first. Comments can only be
+    /// added.
+    #[serde(default)]
+    pub comments: Vec<Comment>,
+    /// `checklist` holds lightweight items to tick off, e.g. acceptance criteria, in order.
+    #[serde(default)]
+    pub checklist: Vec<ChecklistItem>,
     #[serde(rename = "taskIds")]
     pub task_ids: Vec<u32>,
 }
 
+/// A `ChecklistItem` is a line on a checklist, ticked off once `done`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ChecklistItem {
+    pub text: String,
+    pub done: bool,
+}
+
+/// A `Comment` is a remark left on a story by `author`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Comment {
+    pub author: String,
+    pub body: String,
+    pub at: DateTime<Utc>,
+}
+
+/// A `WorkEntry` records time spent on a story. `date` is the day the work was logged, in
+/// the `utils::DATE_FORMAT`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct WorkEntry {
+    pub minutes: u32,
+    pub note: String,
+    pub date: String,
+}
+
 /// A `Task` is an atomic child of a `Story`. Tasks represent bits of a Story that can be broken
 /// down into smaller, achievable units. Each task must have a parent `Story`.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -85,6 +491,188 @@ pub struct Task {
     pub name: String,
     pub description: String,
     pub status: Status,
+    /// `checklist` holds the steps of the task to tick off, in order.
+    #[serde(default)]
+    pub checklist: Vec<ChecklistItem>,
+}
+
+/// A `Sprint` is a time box grouping stories from any epic that are planned to be done
+/// between `start` and `end`, inclusive.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Sprint {
+    pub name: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    #[serde(rename = "storyIds")]
+    pub story_ids: Vec<u32>,
+}
+
+impl DatabaseState {
+    /// `next_id` allocates the id of a new item. Ids are shared by epics, stories, tasks and
+    /// sprints. The id follows both `last_item_id` and the largest id in use, so that a
+    /// hand-edited `last_item_id` that is too low cannot make a new item replace an existing
+    /// one. Items in the trash keep their ids, so that they can be restored. Returns
+    /// `IdsExhausted` instead of wrapping around once `u32::MAX` is in use.
+    pub fn next_id(&mut self) -> Result<u32, IdsExhausted> {
+        let largest = self
+            .epics
+            .keys()
+            .chain(self.stories.keys())
+            .chain(self.tasks.keys())
+            .chain(self.sprints.keys())
+            .copied()
+            .chain(self.trashed_ids())
+            .chain(self.last_item_id)
+            .max();
+        let id = match largest {
+            Some(largest) => largest.checked_add(1).ok_or(IdsExhausted)?,
+            None => 0,
+        };
+        self.last_item_id = Some(id);
+        Ok(id)
+    }
+
+    /// `trashed_ids` returns the ids of every epic, story and task in the trash.
+    pub fn trashed_ids(&self) -> Vec<u32> {
+        let epics = self.trash_epics.iter().flat_map(|(id, trashed)| {
+            std::iter::once(id)
+                .chain(trashed.stories.keys())
+                .chain(trashed.tasks.keys())
+        });
+        let stories = self
+            .trash_stories
+            .iter()
+            .flat_map(|(id, trashed)| std::iter::once(id).chain(trashed.tasks.keys()));
+        epics.chain(stories).copied().collect()
+    }
+
+    /// `epic_id_for_story` returns the id of the epic that owns the story `story_id`, or
+    /// `None` if no epic does.
+    pub fn epic_id_for_story(&self, story_id: u32) -> Option<u32> {
+        self.epics
+            .iter()
+            .find(|(_, epic)| epic.story_ids.contains(&story_id))
+            .map(|(id, _)| *id)
+    }
+
+    /// `story_id_for_task` returns the id of the story that owns the task `task_id`, or
+    /// `None` if no story does.
+    pub fn story_id_for_task(&self, task_id: u32) -> Option<u32> {
+        self.stories
+            .iter()
+            .find(|(_, story)| story.task_ids.contains(&task_id))
+            .map(|(id, _)| *id)
+    }
+
+    /// `jump_target` returns the action opening the epic or story `id`, with the story's
+    /// epic resolved, or `None` if there is no such epic or story. Stories not in an epic
+    /// cannot be opened.
+    pub fn jump_target(&self, id: u32) -> Option<Action> {
+        if self.epics.contains_key(&id) {
+            return Some(Action::NavigateToEpicDetail { epic_id: id });
+        }
+        self.epic_id_for_story(id)
+            .map(|epic_id| Action::NavigateToStoryDetail {
+                story_id: id,
+                epic_id,
+            })
+    }
+
+    /// `is_viewable` returns whether the epic or story of `item` still exists, with the story
+    /// still in its epic.
+    pub fn is_viewable(&self, item: &RecentItem) -> bool {
+        match *item {
+            RecentItem::Epic(epic_id) => self.epics.contains_key(&epic_id),
+            RecentItem::Story { story_id, epic_id } => self
+                .epics
+                .get(&epic_id)
+                .is_some_and(|epic| epic.story_ids.contains(&story_id)),
+        }
+    }
+
+    /// `prune_recent` drops the recently viewed items that are no longer viewable.
+    pub fn prune_recent(&mut self) {
+        let recent = std::mem::take(&mut self.recent);
+        self.recent = recent
+            .into_iter()
+            .filter(|item| self.is_viewable(item))
+            .collect();
+    }
+
+    /// `recently_modified` returns up to `limit` epics and stories, most recently changed
+    /// first, going by the audit log. Changes to a task count as changes to its story. Items
+    /// that were deleted, or stories not in an epic, are left out.
+    pub fn recently_modified(&self, limit: usize) -> Vec<ModifiedItem<'_>> {
+        let task_stories: HashMap<u32, u32> = self
+            .stories
+            .iter()
+            .flat_map(|(story_id, story)| story.task_ids.iter().map(|id| (*id, *story_id)))
+            .collect();
+        let mut latest: HashMap<u32, DateTime<Utc>> = HashMap::new();
+        for entry in &self.audit {
+            let Some(id) = entry.id else {
+                continue;
+            };
+            let id = task_stories.get(&id).copied().unwrap_or(id);
+            let at = latest.entry(id).or_insert(entry.at);
+            *at = (*at).max(entry.at);
+        }
+        let mut items: Vec<ModifiedItem> = latest
+            .into_iter()
+            .filter_map(|(id, at)| {
+                if let Some(epic) = self.epics.get(&id) {
+                    return Some(ModifiedItem::Epic { id, epic, at });
+                }
+                Some(ModifiedItem::Story {
+                    id,
+                    epic_id: self.epic_id_for_story(id)?,
+                    story: self.stories.get(&id)?,
+                    at,
+                })
+            })
+            .collect();
+        items.sort_by(|a, b| b.at().cmp(&a.at()).then(a.id().cmp(&b.id())));
+        items.truncate(limit);
+        items
+    }
+
+    /// `is_blocked_by` returns whether the story `story_id` is blocked by `blocker_id`, either
+    /// directly or through a chain of blockers.
+    pub fn is_blocked_by(&self, story_id: u32, blocker_id: u32) -> bool {
+        let mut seen = HashSet::new();
+        let mut pending = vec![story_id];
+        while let Some(id) = pending.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            let Some(story) = self.stories.get(&id) else {
+                continue;
+            };
+            if story.blocked_by.contains(&blocker_id) {
+                return true;
+            }
+            pending.extend(&story.blocked_by);
+        }
+        false
+    }
+
+    /// `unclosed_blockers` returns the ids of the stories blocking `story_id` that are not
+    /// closed yet.
+    pub fn unclosed_blockers(&self, story_id: u32) -> Vec<u32> {
+        let Some(story) = self.stories.get(&story_id) else {
+            return vec![];
+        };
+        story
+            .blocked_by
+            .iter()
+            .filter(|id| {
+                self.stories
+                    .get(id)
+                    .is_some_and(|blocker| blocker.status != Status::Closed)
+            })
+            .copied()
+            .collect()
+    }
 }
 
 impl Epic {
@@ -93,6 +681,20 @@ impl Epic {
             name: name.to_owned(),
             description: description.to_owned(),
             status: Status::Open,
+            status_override: false,
+            archived: false,
+            assignee: None,
+            story_ids: vec![],
+        }
+    }
+}
+
+impl Sprint {
+    pub fn new(name: &str, start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            name: name.to_owned(),
+            start,
+            end,
             story_ids: vec![],
         }
     }
@@ -104,9 +706,54 @@ impl Story {
             name: name.to_owned(),
             description: description.to_owned(),
             status: Status::Open,
+            assignee: None,
+            tags: vec![],
+            points: None,
+            blocked_by: vec![],
+            work_log: vec![],
+            comments: vec![],
+            checklist: vec![],
             task_ids: vec![],
         }
     }
+
+    /// `logged_minutes` returns the total time logged on the story, capped at `u32::MAX`
+    /// minutes.
+    pub fn logged_minutes(&self) -> u32 {
+        self.work_log
+            .iter()
+            .fold(0, |total: u32, entry| total.saturating_add(entry.minutes))
+    }
+
+    /// `add_tag` normalizes `tag` and adds it to the story, unless the story already has
+    /// it. Returns whether the tag was added.
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        let tag = normalize_tag(tag);
+        if tag.is_empty() || self.tags.contains(&tag) {
+            return false;
+        }
+        self.tags.push(tag);
+        true
+    }
+
+    /// `remove_tag` removes `tag` from the story. Returns whether the story had the tag.
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let tag = normalize_tag(tag);
+        let len = self.tags.len();
+        self.tags.retain(|t| *t != tag);
+        self.tags.len() != len
+    }
+
+    /// `has_tag` returns whether the story has `tag`, ignoring case.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(&normalize_tag(tag))
+    }
+}
+
+/// `normalize_tag` trims and lowercases `tag` so that tags compare equal regardless of how
+/// they were typed.
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
 }
 
 impl Task {
@@ -115,18 +762,50 @@ impl Task {
             name: name.to_owned(),
             description: description.to_owned(),
             status: Status::Open,
+            checklist: vec![],
         }
     }
 }
 
-impl From<String> for Status {
-    fn from(value: String) -> Self {
-        match value.as_str() {
-            "Open" => Self::Open,
-            "In Progress" => Self::InProgress,
-            "Resolved" => Self::Resolved,
-            "Closed" => Self::Closed,
-            _ => Self::Open,
+impl Status {
+    /// `next` returns the status that follows this one, wrapping around from `Closed` back to
+    /// `Open`.
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Open => Self::Blocked,
+            Self::Blocked => Self::InProgress,
+            Self::InProgress => Self::Resolved,
+            Self::Resolved => Self::Closed,
+            Self::Closed => Self::Open,
+        }
+    }
+
+    /// `prev` returns the status that precedes this one, wrapping around from `Open` back to
+    /// `Closed`. It is the inverse of `next`.
+    pub fn prev(&self) -> Self {
+        match self {
+            Self::Open => Self::Closed,
+            Self::Blocked => Self::Open,
+            Self::InProgress => Self::Blocked,
+            Self::Resolved => Self::InProgress,
+            Self::Closed => Self::Resolved,
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = ParseStatusError;
+
+    /// `from_str` parses a status as displayed or as stored in the database, ignoring case,
+    /// e.g. "in progress" or "inProgress".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "open" => Ok(Self::Open),
+            "blocked" => Ok(Self::Blocked),
+            "in progress" | "inprogress" => Ok(Self::InProgress),
+            "resolved" => Ok(Self::Resolved),
+            "closed" => Ok(Self::Closed),
+            _ => Err(ParseStatusError(s.to_owned())),
         }
     }
 }
@@ -135,9 +814,412 @@ impl Display for Status {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
             Self::Open => "Open",
+            Self::Blocked => "Blocked",
             Self::InProgress => "In Progress",
             Self::Resolved => "Resolved",
             Self::Closed => "Closed",
         })
     }
 }
+
+/// `derive_status` returns the status a parent should have given the statuses of its
+/// `children`, where the higher conditions have higher precedence:
+/// - All children Closed => Closed
+/// - Any child Blocked => Blocked
+/// - All children Resolved or Closed => Resolved
+/// - All children Open => Open
+/// - Otherwise => In Progress
+///
+/// A parent without children is Open.
+pub fn derive_status(children: &[Status]) -> Status {
+    if children.is_empty() {
+        Status::Open
+    } else if children.iter().all(|status| *status == Status::Closed) {
+        Status::Closed
+    } else if children.contains(&Status::Blocked) {
+        Status::Blocked
+    } else if children
+        .iter()
+        .all(|status| matches!(status, Status::Resolved | Status::Closed))
+    {
+        Status::Resolved
+    } else if children.iter().all(|status| *status == Status::Open) {
+        Status::Open
+    } else {
+        Status::InProgress
+    }
+}
+
+/// A `ParseStatusError` is returned when parsing text that is not a `Status`. It holds the
+/// text that was parsed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseStatusError(pub String);
+
+impl Display for ParseStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown status {:?}", self.0)
+    }
+}
+
+impl Error for ParseStatusError {}
+
+/// `IdsExhausted` is returned when allocating an id after the largest one, `u32::MAX`, is
+/// in use.
+#[derive(Debug, PartialEq, Eq)]
+pub struct IdsExhausted;
+
+impl Display for IdsExhausted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "every id up to {} is in use, no more items can be created",
+            u32::MAX
+        )
+    }
+}
+
+impl Error for IdsExhausted {}
+
+/// `TEMPLATE_DATE_PLACEHOLDER` is replaced by the day a `Template` is applied, see
+/// `Template::fill_placeholders`.
+pub const TEMPLATE_DATE_PLACEHOLDER: &str = "{date}";
+
+/// A `Template` is an epic that is created again and again with the same stories, e.g. a
+/// release checklist. Templates are read from the `[[templates]]` of the config.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Template {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub stories: Vec<StoryStub>,
+}
+
+/// A `StoryStub` is a story created by a `Template`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StoryStub {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl Template {
+    /// `fill_placeholders` returns the template with `{date}` replaced by `today`, as
+    /// YYYY-MM-DD, in every name and description.
+    pub fn fill_placeholders(&self, today: NaiveDate) -> Self {
+        let date = today.format("%Y-%m-%d").to_string();
+        let fill = |text: &str| text.replace(TEMPLATE_DATE_PLACEHOLDER, &date);
+        Self {
+            name: fill(&self.name),
+            description: fill(&self.description),
+            stories: self
+                .stories
+                .iter()
+                .map(|story| StoryStub {
+                    name: fill(&story.name),
+                    description: fill(&story.description),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logged_minutes_should_not_overflow() {
+        let mut story = Story::new("Story 1", "");
+        for _ in 0..2 {
+            story.work_log.push(WorkEntry {
+                minutes: 71582788 * 60,
+                note: String::new(),
+                date: "2024-06-03".to_owned(),
+            });
+        }
+        assert_eq!(story.logged_minutes(), u32::MAX);
+    }
+
+    #[test]
+    fn fill_placeholders_should_replace_date_everywhere() {
+        let template = Template {
+            name: "Release {date}".to_owned(),
+            description: "Cut on {date}, shipped after {date}".to_owned(),
+            stories: vec![StoryStub {
+                name: "Tag v{date}".to_owned(),
+                description: "{day}".to_owned(),
+            }],
+        };
+        let filled = template.fill_placeholders(NaiveDate::from_ymd_opt(2024, 5, 31).unwrap());
+        assert_eq!(filled.name, "Release 2024-05-31");
+        assert_eq!(
+            filled.description,
+            "Cut on 2024-05-31, shipped after 2024-05-31"
+        );
+        assert_eq!(filled.stories[0].name, "Tag v2024-05-31");
+        assert_eq!(filled.stories[0].description, "{day}");
+    }
+
+    #[test]
+    fn recently_modified_should_list_latest_change_first() {
+        let at = |minute: u32| {
+            NaiveDate::from_ymd_opt(2024, 5, 31)
+                .unwrap()
+                .and_hms_opt(9, minute, 0)
+                .unwrap()
+                .and_utc()
+        };
+        let mut state = DatabaseState::default();
+        state.epics.insert(
+            1,
+            Epic {
+                story_ids: vec![2, 3],
+                ..Epic::new("Epic 1", "")
+            },
+        );
+        state.epics.insert(4, Epic::new("Epic 2", ""));
+        state.stories.insert(
+            2,
+            Story {
+                task_ids: vec![5],
+                ..Story::new("Story 1", "")
+            },
+        );
+        state.stories.insert(3, Story::new("Story 2", ""));
+        state.stories.insert(6, Story::new("Unparented", ""));
+        state.tasks.insert(5, Task::new("Task 1", ""));
+        for (minute, id) in [
+            (0, Some(1)),
+            (1, Some(2)),
+            (2, Some(3)),
+            (3, Some(4)),
+            (4, Some(1)),
+            (5, None),
+            (6, Some(5)),
+            (7, Some(6)),
+            (8, Some(99)),
+        ] {
+            state.audit.push(AuditEntry {
+                at: at(minute),
+                action: "Changed".to_owned(),
+                id,
+            });
+        }
+
+        let modified = state.recently_modified(10);
+        assert_eq!(
+            modified.iter().map(ModifiedItem::id).collect::<Vec<_>>(),
+            vec![2, 1, 4, 3]
+        );
+        assert_eq!(
+            modified[0],
+            ModifiedItem::Story {
+                id: 2,
+                epic_id: 1,
+                story: &state.stories[&2],
+                at: at(6),
+            }
+        );
+        assert_eq!(modified[1].at(), at(4));
+        assert_eq!(
+            modified[3].recent_item(),
+            RecentItem::Story {
+                story_id: 3,
+                epic_id: 1
+            }
+        );
+        assert_eq!(state.recently_modified(2).len(), 2);
+    }
+
+    #[test]
+    fn next_id_should_skip_past_ids_in_use() {
+        let mut state = DatabaseState::default();
+        assert_eq!(state.next_id(), Ok(0));
+        assert_eq!(state.next_id(), Ok(1));
+
+        state.epics.insert(7, Epic::new("Epic 1", ""));
+        state.trash_stories.insert(
+            9,
+            TrashedStory {
+                story: Story::new("Story 1", ""),
+                epic_id: 7,
+                tasks: BTreeMap::new(),
+                deleted_at: Utc::now(),
+            },
+        );
+        state.last_item_id = Some(0);
+        assert_eq!(state.next_id(), Ok(10));
+        assert_eq!(state.last_item_id, Some(10));
+    }
+
+    #[test]
+    fn next_id_should_error_once_ids_are_exhausted() {
+        let mut state = DatabaseState {
+            last_item_id: Some(u32::MAX - 1),
+            ..Default::default()
+        };
+        assert_eq!(state.next_id(), Ok(u32::MAX));
+        assert_eq!(state.next_id(), Err(IdsExhausted));
+        assert_eq!(state.last_item_id, Some(u32::MAX));
+
+        let mut state = DatabaseState::default();
+        state.stories.insert(u32::MAX, Story::new("Story 1", ""));
+        assert_eq!(state.next_id(), Err(IdsExhausted));
+    }
+
+    #[test]
+    fn add_tag_should_normalize_and_dedupe() {
+        let mut story = Story::new("name", "description");
+        assert!(story.add_tag("  Bug "));
+        assert!(!story.add_tag("BUG"));
+        assert!(!story.add_tag("   "));
+        assert!(story.add_tag("backend"));
+        assert_eq!(story.tags, vec!["bug".to_owned(), "backend".to_owned()]);
+    }
+
+    #[test]
+    fn jump_target_should_resolve_story_epic() {
+        let mut state = DatabaseState::default();
+        let mut epic = Epic::new("Epic 1", "");
+        epic.story_ids = vec![2];
+        state.epics.insert(1, epic);
+        state.stories.insert(2, Story::new("Story 1", ""));
+        state.stories.insert(3, Story::new("Story 2", ""));
+
+        assert_eq!(
+            state.jump_target(2),
+            Some(Action::NavigateToStoryDetail {
+                story_id: 2,
+                epic_id: 1
+            })
+        );
+        assert_eq!(
+            state.jump_target(1),
+            Some(Action::NavigateToEpicDetail { epic_id: 1 })
+        );
+        assert_eq!(state.jump_target(3), None);
+        assert_eq!(state.jump_target(9), None);
+    }
+
+    #[test]
+    fn derive_status_should_follow_precedence() {
+        use Status::*;
+        assert_eq!(derive_status(&[]), Open);
+        assert_eq!(derive_status(&[Closed, Closed]), Closed);
+        assert_eq!(derive_status(&[Blocked, Resolved, Closed]), Blocked);
+        assert_eq!(derive_status(&[Resolved, Closed]), Resolved);
+        assert_eq!(derive_status(&[Open, Open]), Open);
+        assert_eq!(derive_status(&[Open, Closed]), InProgress);
+        assert_eq!(derive_status(&[InProgress]), InProgress);
+    }
+
+    #[test]
+    fn status_from_str_should_ignore_case() {
+        assert_eq!("In Progress".parse::<Status>().unwrap(), Status::InProgress);
+        assert_eq!(" closed ".parse::<Status>().unwrap(), Status::Closed);
+        assert!("done".parse::<Status>().is_err());
+    }
+
+    #[test]
+    fn status_from_str_should_accept_display_and_serde_names() {
+        for status in [
+            Status::Open,
+            Status::Blocked,
+            Status::InProgress,
+            Status::Resolved,
+            Status::Closed,
+        ] {
+            let serde_name = serde_json::to_value(&status).unwrap();
+            assert_eq!(status.to_string().parse::<Status>().unwrap(), status);
+            assert_eq!(
+                serde_name.as_str().unwrap().parse::<Status>().unwrap(),
+                status
+            );
+        }
+        assert_eq!("inProgress".parse::<Status>().unwrap(), Status::InProgress);
+    }
+
+    #[test]
+    fn status_from_str_should_fail_on_unknown_status() {
+        assert_eq!(
+            "garbage".parse::<Status>(),
+            Err(ParseStatusError("garbage".to_owned()))
+        );
+        assert_eq!(
+            "In  Progress".parse::<Status>().unwrap_err().to_string(),
+            "unknown status \"In  Progress\""
+        );
+        assert!("".parse::<Status>().is_err());
+    }
+
+    #[test]
+    fn remove_tag_should_ignore_case() {
+        let mut story = Story::new("name", "description");
+        story.add_tag("bug");
+        assert!(story.has_tag("Bug"));
+        assert!(story.remove_tag("BUG"));
+        assert!(!story.remove_tag("bug"));
+        assert!(story.tags.is_empty());
+    }
+
+    #[test]
+    fn is_blocked_by_should_follow_chains() {
+        let mut state = DatabaseState::default();
+        for id in 0..3 {
+            state.stories.insert(id, Story::new("name", "description"));
+        }
+        state.stories.get_mut(&0).unwrap().blocked_by.push(1);
+        state.stories.get_mut(&1).unwrap().blocked_by.push(2);
+        state.stories.get_mut(&2).unwrap().blocked_by.push(1);
+        assert!(state.is_blocked_by(0, 1));
+        assert!(state.is_blocked_by(0, 2));
+        assert!(!state.is_blocked_by(2, 0));
+    }
+
+    #[test]
+    fn serialization_should_be_deterministic() {
+        let mut state: DatabaseState = serde_json::from_str(
+            r#"{ "lastItemId": 12, "epics": {}, "stories": {}, "tasks": {} }"#,
+        )
+        .unwrap();
+        assert_eq!(state.last_item_id, Some(12));
+        for id in [10, 2, 7, 1] {
+            state.epics.insert(id, Epic::new("Epic", ""));
+            state.stories.insert(id + 1, Story::new("Story", ""));
+        }
+        let first = serde_json::to_string(&state).unwrap();
+        let second = serde_json::to_string(&state.clone()).unwrap();
+        assert_eq!(first, second);
+        assert!(first.starts_with(r#"{"lastItemId":12,"epics":{"1":"#));
+        assert_eq!(
+            serde_json::from_str::<DatabaseState>(&first).unwrap(),
+            state
+        );
+    }
+
+    #[test]
+    fn next_should_cycle_through_statuses() {
+        assert_eq!(Status::Open.next(), Status::Blocked);
+        assert_eq!(Status::Blocked.next(), Status::InProgress);
+        assert_eq!(Status::InProgress.next(), Status::Resolved);
+        assert_eq!(Status::Resolved.next(), Status::Closed);
+        assert_eq!(Status::Closed.next(), Status::Open);
+    }
+
+    #[test]
+    fn prev_should_be_inverse_of_next() {
+        let statuses = [
+            Status::Open,
+            Status::Blocked,
+            Status::InProgress,
+            Status::Resolved,
+            Status::Closed,
+        ];
+        for status in statuses {
+            assert_eq!(status.next().prev(), status);
+            assert_eq!(status.prev().next(), status);
+        }
+        assert_eq!(Status::Open.prev(), Status::Closed);
+    }
+}
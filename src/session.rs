@@ -0,0 +1,78 @@
+use std::{fs, io::ErrorKind, path::Path};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::models::RecentItem;
+
+/// `Session` is where the user was on a board when clira last exited: the epics and stories
+/// open on top of the home page, bottom first. It is stored as JSON next to the board file.
+#[derive(Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct Session {
+    pub pages: Vec<RecentItem>,
+}
+
+impl Session {
+    /// `path` returns where the session of the board at `db_path` is stored, e.g.
+    /// `db.session.json` for `db.json`.
+    pub fn path(db_path: &str) -> String {
+        let path = Path::new(db_path);
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        path.with_file_name(format!("{}.session.json", stem))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// `load` reads the session from the file at `path`. A missing file means there is no
+    /// session to restore. Returns `Err` if the file could not be read or parsed.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(session) => Ok(serde_json::from_str(&session)?),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// `save` writes the session to the file at `path`. Returns `Err` if the file could not
+    /// be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_should_be_next_to_board() {
+        assert_eq!(
+            Session::path("/boards/work.json"),
+            "/boards/work.session.json"
+        );
+        assert_eq!(Session::path("db.json"), "db.session.json");
+    }
+
+    #[test]
+    fn save_and_load_should_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("db.session.json");
+        assert_eq!(Session::load(&path).unwrap(), Session::default());
+
+        let session = Session {
+            pages: vec![
+                RecentItem::Epic(1),
+                RecentItem::Story {
+                    story_id: 2,
+                    epic_id: 1,
+                },
+            ],
+        };
+        session.save(&path).unwrap();
+        assert_eq!(Session::load(&path).unwrap(), session);
+
+        fs::write(&path, "[").unwrap();
+        assert!(Session::load(&path).is_err());
+    }
+}
@@ -0,0 +1,321 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr, sync::RwLock};
+
+use anyhow::Result;
+use owo_colors::AnsiColors;
+use serde::Deserialize;
+use tabled::settings::Color;
+
+use crate::models::Status;
+
+/// A `ThemeColor` is a terminal color that can be picked in the theme, or `Default` for the
+/// terminal's own color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThemeColor {
+    Default,
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+/// `THEME_COLORS` pairs each `ThemeColor` with its name in the config file.
+const THEME_COLORS: [(ThemeColor, &str); 17] = [
+    (ThemeColor::Default, "default"),
+    (ThemeColor::Black, "black"),
+    (ThemeColor::Red, "red"),
+    (ThemeColor::Green, "green"),
+    (ThemeColor::Yellow, "yellow"),
+    (ThemeColor::Blue, "blue"),
+    (ThemeColor::Magenta, "magenta"),
+    (ThemeColor::Cyan, "cyan"),
+    (ThemeColor::White, "white"),
+    (ThemeColor::BrightBlack, "bright black"),
+    (ThemeColor::BrightRed, "bright red"),
+    (ThemeColor::BrightGreen, "bright green"),
+    (ThemeColor::BrightYellow, "bright yellow"),
+    (ThemeColor::BrightBlue, "bright blue"),
+    (ThemeColor::BrightMagenta, "bright magenta"),
+    (ThemeColor::BrightCyan, "bright cyan"),
+    (ThemeColor::BrightWhite, "bright white"),
+];
+
+impl ThemeColor {
+    /// `ansi` returns the color to paint text with, or `None` for `Default`.
+    pub fn ansi(self) -> Option<AnsiColors> {
+        match self {
+            Self::Default => None,
+            Self::Black => Some(AnsiColors::Black),
+            Self::Red => Some(AnsiColors::Red),
+            Self::Green => Some(AnsiColors::Green),
+            Self::Yellow => Some(AnsiColors::Yellow),
+            Self::Blue => Some(AnsiColors::Blue),
+            Self::Magenta => Some(AnsiColors::Magenta),
+            Self::Cyan => Some(AnsiColors::Cyan),
+            Self::White => Some(AnsiColors::White),
+            Self::BrightBlack => Some(AnsiColors::BrightBlack),
+            Self::BrightRed => Some(AnsiColors::BrightRed),
+            Self::BrightGreen => Some(AnsiColors::BrightGreen),
+            Self::BrightYellow => Some(AnsiColors::BrightYellow),
+            Self::BrightBlue => Some(AnsiColors::BrightBlue),
+            Self::BrightMagenta => Some(AnsiColors::BrightMagenta),
+            Self::BrightCyan => Some(AnsiColors::BrightCyan),
+            Self::BrightWhite => Some(AnsiColors::BrightWhite),
+        }
+    }
+
+    /// `table_color` returns the color for table borders and titles.
+    pub fn table_color(self) -> Color {
+        match self {
+            Self::Default => Color::empty(),
+            Self::Black => Color::FG_BLACK,
+            Self::Red => Color::FG_RED,
+            Self::Green => Color::FG_GREEN,
+            Self::Yellow => Color::FG_YELLOW,
+            Self::Blue => Color::FG_BLUE,
+            Self::Magenta => Color::FG_MAGENTA,
+            Self::Cyan => Color::FG_CYAN,
+            Self::White => Color::FG_WHITE,
+            Self::BrightBlack => Color::FG_BRIGHT_BLACK,
+            Self::BrightRed => Color::FG_BRIGHT_RED,
+            Self::BrightGreen => Color::FG_BRIGHT_GREEN,
+            Self::BrightYellow => Color::FG_BRIGHT_YELLOW,
+            Self::BrightBlue => Color::FG_BRIGHT_BLUE,
+            Self::BrightMagenta => Color::FG_BRIGHT_MAGENTA,
+            Self::BrightCyan => Color::FG_BRIGHT_CYAN,
+            Self::BrightWhite => Color::FG_BRIGHT_WHITE,
+        }
+    }
+}
+
+impl Display for ThemeColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (_, name) = THEME_COLORS
+            .iter()
+            .find(|(color, _)| color == self)
+            .expect("every color has a name");
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for ThemeColor {
+    type Err = String;
+
+    /// `from_str` parses a color name such as "cyan" or "bright red", ignoring case. Words
+    /// may also be separated by `_` or `-`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace(['_', '-'], " ");
+        THEME_COLORS
+            .iter()
+            .find(|(_, known)| *known == name)
+            .map(|(color, _)| *color)
+            .ok_or_else(|| format!("unknown color {:?}", s))
+    }
+}
+
+/// `Theme` holds the color of each `Status` in tables. It is read from the `theme` section
+/// of the config file, such as:
+/// ```toml
+/// [theme]
+/// resolved = "cyan"
+/// closed = "bright magenta"
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
+    pub open: ThemeColor,
+    pub in_progress: ThemeColor,
+    pub blocked: ThemeColor,
+    pub resolved: ThemeColor,
+    pub closed: ThemeColor,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            open: ThemeColor::Default,
+            in_progress: ThemeColor::Yellow,
+            blocked: ThemeColor::Red,
+            resolved: ThemeColor::Blue,
+            closed: ThemeColor::Green,
+        }
+    }
+}
+
+/// `Config` is the part of the config file read by `Theme`.
+#[derive(Default, Deserialize)]
+struct Config {
+    #[serde(default)]
+    theme: BTreeMap<String, String>,
+}
+
+/// `THEME` is the theme of the program, set by `Theme::set`.
+static THEME: RwLock<Option<Theme>> = RwLock::new(None);
+
+/// `THEME_STATUSES` pairs each `Status` with its key in the theme section of the config file.
+const THEME_STATUSES: [(Status, &str); 5] = [
+    (Status::Open, "open"),
+    (Status::InProgress, "in_progress"),
+    (Status::Blocked, "blocked"),
+    (Status::Resolved, "resolved"),
+    (Status::Closed, "closed"),
+];
+
+impl Theme {
+    /// `set` sets the theme for the rest of the program, e.g. after it was changed in the
+    /// settings.
+    pub fn set(theme: Theme) {
+        *THEME.write().expect("theme lock poisoned") = Some(theme);
+    }
+
+    /// `current` returns the theme set by `set`, or the default theme if `set` was not
+    /// called.
+    pub fn current() -> Self {
+        THEME
+            .read()
+            .expect("theme lock poisoned")
+            .unwrap_or_default()
+    }
+
+    /// `set_color` paints `status` in `color`.
+    pub fn set_color(&mut self, status: Status, color: ThemeColor) {
+        match status {
+            Status::Open => self.open = color,
+            Status::InProgress => self.in_progress = color,
+            Status::Blocked => self.blocked = color,
+            Status::Resolved => self.resolved = color,
+            Status::Closed => self.closed = color,
+        }
+    }
+
+    /// `entries` returns the theme as it is written in the config file: the key of each
+    /// status along with the name of its color.
+    pub fn entries(&self) -> BTreeMap<String, String> {
+        THEME_STATUSES
+            .iter()
+            .map(|(status, key)| (key.to_string(), self.color(status.clone()).to_string()))
+            .collect()
+    }
+
+    /// `color` returns the color of `status`.
+    pub fn color(&self, status: Status) -> ThemeColor {
+        match status {
+            Status::Open => self.open,
+            Status::InProgress => self.in_progress,
+            Status::Blocked => self.blocked,
+            Status::Resolved => self.resolved,
+            Status::Closed => self.closed,
+        }
+    }
+
+    /// `parse` reads the theme from the TOML `config`, along with warnings about the
+    /// entries that were ignored. Statuses are named as in the UI or in snake case, e.g.
+    /// `in_progress`. Statuses missing from the theme, and those with unknown colors, keep
+    /// their default color. Returns `Err` if `config` is not valid TOML.
+    pub fn parse(config: &str) -> Result<(Self, Vec<String>)> {
+        let config: Config = toml::from_str(config)?;
+        let mut theme = Self::default();
+        let mut warnings = vec![];
+        for (key, value) in config.theme {
+            let status = match key.replace('_', " ").parse::<Status>() {
+                Ok(status) => status,
+                Err(error) => {
+                    warnings.push(format!("theme: {}, ignored", error));
+                    continue;
+                }
+            };
+            let color = match value.parse() {
+                Ok(color) => color,
+                Err(error) => {
+                    warnings.push(format!(
+                        "theme: {} for {}, using {}",
+                        error,
+                        status,
+                        theme.color(status.clone())
+                    ));
+                    continue;
+                }
+            };
+            theme.set_color(status, color);
+        }
+        Ok((theme, warnings))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_should_map_statuses_to_colors() {
+        let (theme, warnings) = Theme::parse(
+            r#"
+            [theme]
+            open = "white"
+            in_progress = "bright yellow"
+            Blocked = "MAGENTA"
+            resolved = "cyan"
+            closed = "bright_blue"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            theme,
+            Theme {
+                open: ThemeColor::White,
+                in_progress: ThemeColor::BrightYellow,
+                blocked: ThemeColor::Magenta,
+                resolved: ThemeColor::Cyan,
+                closed: ThemeColor::BrightBlue,
+            }
+        );
+        assert!(warnings.is_empty());
+        assert!(Theme::parse("[theme").is_err());
+    }
+
+    #[test]
+    fn parse_should_keep_defaults_for_missing_and_unknown_entries() {
+        let (theme, warnings) = Theme::parse(
+            r#"
+            [theme]
+            resolved = "teal"
+            closed = "magenta"
+            done = "red"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            theme,
+            Theme {
+                closed: ThemeColor::Magenta,
+                ..Theme::default()
+            }
+        );
+        assert_eq!(
+            warnings,
+            vec![
+                "theme: unknown status \"done\", ignored",
+                "theme: unknown color \"teal\" for Resolved, using blue",
+            ]
+        );
+        assert_eq!(Theme::parse("").unwrap(), (Theme::default(), vec![]));
+    }
+
+    #[test]
+    fn theme_color_should_round_trip_names() {
+        for (color, name) in THEME_COLORS {
+            assert_eq!(color.to_string(), name);
+            assert_eq!(name.parse(), Ok(color));
+        }
+    }
+}
@@ -0,0 +1,395 @@
+use std::{
+    io::{self, Write},
+    rc::Rc,
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::Duration,
+};
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    terminal,
+};
+
+use crate::{
+    ui::io::{Io, StdIo},
+    utils::Line,
+};
+
+/// An `Input` is a single input from the user: either a typed command, or a key moving or
+/// opening the highlighted row of the current page. `Eof` means that there will be no more
+/// input, and ends the main loop.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Input {
+    Command(String),
+    Up,
+    Down,
+    Open,
+    Eof,
+}
+
+/// An `InputSource` reads the user's input for the main loop. It is a level of indirection
+/// so that the navigation keys can be replaced by typed commands, e.g. with piped input.
+pub trait InputSource {
+    /// `next_input` blocks until the user entered something. `None` is returned if nothing
+    /// was entered.
+    fn next_input(&mut self) -> Option<Input>;
+
+    /// `is_reading` returns whether an input is still being read in the background after
+    /// `next_input` returned. The main loop must then not read from the terminal itself,
+    /// e.g. to pause on an error, since the line would go to the wrong reader.
+    fn is_reading(&self) -> bool {
+        false
+    }
+}
+
+/// `LineInput` reads whole lines from an `Io`, so that every input is a typed command. A
+/// read error is treated like the end of the input.
+pub struct LineInput {
+    io: Rc<dyn Io>,
+}
+
+impl LineInput {
+    /// `new` returns a `LineInput` reading from `io`.
+    pub fn new(io: Rc<dyn Io>) -> Self {
+        Self { io }
+    }
+}
+
+impl InputSource for LineInput {
+    fn next_input(&mut self) -> Option<Input> {
+        match self.io.read_line() {
+            Ok(Line::Text(line)) => Some(Input::Command(line)),
+            Ok(Line::Empty) => None,
+            Ok(Line::Eof) | Err(_) => Some(Input::Eof),
+        }
+    }
+}
+
+/// `KeyInput` reads single keys from the terminal in raw mode. `j`/`k` and the arrow keys
+/// move the selection and `enter` opens it. Any other key starts a typed command, which is
+/// echoed and ended with `enter`. Raw mode is only on while reading, so prompts still read
+/// whole lines.
+pub struct KeyInput;
+
+impl InputSource for KeyInput {
+    fn next_input(&mut self) -> Option<Input> {
+        if terminal::enable_raw_mode().is_err() {
+            return LineInput::new(Rc::new(StdIo)).next_input();
+        }
+        let input = read_keys();
+        let _ = terminal::disable_raw_mode();
+        println!();
+        match input {
+            KeyOutcome::Input(input) => Some(input),
+            KeyOutcome::Interrupt => std::process::exit(130),
+            KeyOutcome::Pending | KeyOutcome::Redraw => None,
+        }
+    }
+}
+
+/// `WatchInput` reads the inputs of another `InputSource` on a thread of its own, so that
+/// the main loop can redraw the current page every `interval` while waiting for them.
+/// `next_input` returns `None` once `interval` passed without an input. The thread only
+/// reads when asked to by `next_input`, so that it does not take the answers to prompts
+/// shown while an input is handled.
+pub struct WatchInput {
+    interval: Duration,
+    requests: Sender<()>,
+    inputs: Receiver<Option<Input>>,
+    /// `reading` is set while the thread reads an input that was not returned yet.
+    reading: bool,
+}
+
+impl WatchInput {
+    /// `spawn` starts reading from the source made by `source` on a new thread, and returns
+    /// a `WatchInput` waiting at most `interval` for each input. The source is made on the
+    /// thread, so that it need not be `Send`.
+    pub fn spawn<S: InputSource>(
+        interval: Duration,
+        source: impl FnOnce() -> S + Send + 'static,
+    ) -> Self {
+        let (requests, pending) = mpsc::channel::<()>();
+        let (sender, inputs) = mpsc::channel();
+        thread::spawn(move || {
+            let mut source = source();
+            for () in pending {
+                let input = source.next_input();
+                let eof = input == Some(Input::Eof);
+                if sender.send(input).is_err() || eof {
+                    break;
+                }
+            }
+        });
+        Self {
+            interval,
+            requests,
+            inputs,
+            reading: false,
+        }
+    }
+}
+
+impl InputSource for WatchInput {
+    fn next_input(&mut self) -> Option<Input> {
+        if !self.reading {
+            if self.requests.send(()).is_err() {
+                return Some(Input::Eof);
+            }
+            self.reading = true;
+        }
+        match self.inputs.recv_timeout(self.interval) {
+            Ok(input) => {
+                self.reading = false;
+                input
+            }
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => {
+                self.reading = false;
+                Some(Input::Eof)
+            }
+        }
+    }
+
+    fn is_reading(&self) -> bool {
+        self.reading
+    }
+}
+
+/// `KeyOutcome` is the result of a single key press while reading keys.
+#[derive(Debug, PartialEq, Eq)]
+enum KeyOutcome {
+    Input(Input),
+    /// `Interrupt` is ctrl-c, which would otherwise be swallowed by raw mode.
+    Interrupt,
+    /// `Pending` means that more keys are needed to make an input.
+    Pending,
+    /// `Redraw` means that the page should be drawn again without any input, e.g. because
+    /// the terminal was resized.
+    Redraw,
+}
+
+/// `read_keys` reads key presses until they make an input, echoing typed commands. Resizing
+/// the terminal before anything was typed asks for a redraw, so that the page fits the new
+/// width.
+fn read_keys() -> KeyOutcome {
+    let mut line = String::new();
+    loop {
+        let key = match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => key,
+            Ok(Event::Resize(..)) if line.is_empty() => return KeyOutcome::Redraw,
+            Ok(_) => continue,
+            Err(_) => return KeyOutcome::Pending,
+        };
+        match apply_key(&mut line, &key) {
+            KeyOutcome::Pending => {
+                print!("\r\x1b[K{}", line);
+                let _ = io::stdout().flush();
+            }
+            outcome => return outcome,
+        }
+    }
+}
+
+/// `apply_key` applies the key press `key` to the command typed so far in `line`. The
+/// navigation keys, and ctrl-d to end the input, only apply while nothing has been typed, so
+/// that commands can still contain `j` and `k`.
+fn apply_key(line: &mut String, key: &KeyEvent) -> KeyOutcome {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        return KeyOutcome::Interrupt;
+    }
+    if line.is_empty() {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('d') {
+            return KeyOutcome::Input(Input::Eof);
+        }
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => return KeyOutcome::Input(Input::Up),
+            KeyCode::Down | KeyCode::Char('j') => return KeyOutcome::Input(Input::Down),
+            KeyCode::Enter => return KeyOutcome::Input(Input::Open),
+            _ => {}
+        }
+    }
+    match key.code {
+        KeyCode::Enter => KeyOutcome::Input(Input::Command(line.trim().to_owned())),
+        KeyCode::Backspace => {
+            line.pop();
+            KeyOutcome::Pending
+        }
+        KeyCode::Char(c) => {
+            line.push(c);
+            KeyOutcome::Pending
+        }
+        _ => KeyOutcome::Pending,
+    }
+}
+
+/// `move_selection` returns the index highlighted after `input` in a list of `len` rows,
+/// where `current` is highlighted now. The selection starts at the top when moving down and
+/// at the bottom when moving up, and stops at either end. Inputs other than `Up` and `Down`
+/// keep the selection, clamped to the list.
+pub fn move_selection(len: usize, current: Option<usize>, input: &Input) -> Option<usize> {
+    let last = len.checked_sub(1)?;
+    let current = current.map(|index| index.min(last));
+    match (input, current) {
+        (Input::Down, None) => Some(0),
+        (Input::Down, Some(index)) => Some((index + 1).min(last)),
+        (Input::Up, None) => Some(last),
+        (Input::Up, Some(index)) => Some(index.saturating_sub(1)),
+        (_, current) => current,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::{test_utils::MockDatabase, JiraDatabase},
+        models::Action,
+        ui::{
+            io::ScriptedIo,
+            pages::{HomePage, Page},
+        },
+    };
+
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn line_input_should_end_with_eof() {
+        let mut input = LineInput::new(Rc::new(ScriptedIo::new(&["q", ""])));
+        assert_eq!(input.next_input(), Some(Input::Command("q".to_owned())));
+        assert_eq!(input.next_input(), None);
+        assert_eq!(input.next_input(), Some(Input::Eof));
+        assert_eq!(input.next_input(), Some(Input::Eof));
+    }
+
+    /// `ChannelInput` returns the inputs sent to it, blocking until one is.
+    struct ChannelInput(Receiver<Input>);
+
+    impl InputSource for ChannelInput {
+        fn next_input(&mut self) -> Option<Input> {
+            Some(self.0.recv().unwrap_or(Input::Eof))
+        }
+    }
+
+    #[test]
+    fn watch_input_should_pass_on_inputs_until_eof() {
+        let mut input = WatchInput::spawn(Duration::from_secs(60), || {
+            LineInput::new(Rc::new(ScriptedIo::new(&["1", "", "q"])))
+        });
+        assert_eq!(input.next_input(), Some(Input::Command("1".to_owned())));
+        assert_eq!(input.next_input(), None);
+        assert_eq!(input.next_input(), Some(Input::Command("q".to_owned())));
+        assert_eq!(input.next_input(), Some(Input::Eof));
+        assert_eq!(input.next_input(), Some(Input::Eof));
+        assert!(!input.is_reading());
+    }
+
+    #[test]
+    fn watch_input_should_time_out_while_reading() {
+        let (sender, receiver) = mpsc::channel();
+        let mut input =
+            WatchInput::spawn(Duration::from_millis(10), move || ChannelInput(receiver));
+        assert_eq!(input.next_input(), None);
+        assert!(input.is_reading());
+        assert_eq!(input.next_input(), None);
+
+        sender.send(Input::Down).unwrap();
+        sender.send(Input::Open).unwrap();
+        let mut inputs = vec![];
+        while inputs.len() < 2 {
+            inputs.extend(input.next_input());
+        }
+        assert_eq!(inputs, vec![Input::Down, Input::Open]);
+        assert_eq!(input.next_input(), None);
+    }
+
+    #[test]
+    fn apply_key_should_reach_json_lines_export_from_home_page() {
+        let mut line = String::new();
+        for c in "ej".chars() {
+            assert_eq!(
+                apply_key(&mut line, &key(KeyCode::Char(c))),
+                KeyOutcome::Pending
+            );
+        }
+        let KeyOutcome::Input(Input::Command(command)) = apply_key(&mut line, &key(KeyCode::Enter))
+        else {
+            panic!("{:?} was not read as a command", line);
+        };
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let page = HomePage::new(db);
+        assert_eq!(
+            page.action_from(&command, &ScriptedIo::default()).unwrap(),
+            Some(Action::ExportStoriesJsonl)
+        );
+    }
+
+    #[test]
+    fn move_selection_should_start_at_either_end() {
+        assert_eq!(move_selection(3, None, &Input::Down), Some(0));
+        assert_eq!(move_selection(3, None, &Input::Up), Some(2));
+        assert_eq!(move_selection(0, None, &Input::Down), None);
+        assert_eq!(move_selection(0, Some(1), &Input::Up), None);
+    }
+
+    #[test]
+    fn move_selection_should_stop_at_either_end() {
+        assert_eq!(move_selection(3, Some(0), &Input::Down), Some(1));
+        assert_eq!(move_selection(3, Some(2), &Input::Down), Some(2));
+        assert_eq!(move_selection(3, Some(1), &Input::Up), Some(0));
+        assert_eq!(move_selection(3, Some(0), &Input::Up), Some(0));
+    }
+
+    #[test]
+    fn move_selection_should_clamp_to_shorter_list() {
+        assert_eq!(move_selection(2, Some(5), &Input::Open), Some(1));
+        assert_eq!(move_selection(2, Some(5), &Input::Up), Some(0));
+        assert_eq!(move_selection(2, None, &Input::Open), None);
+    }
+
+    #[test]
+    fn apply_key_should_navigate_only_before_typing() {
+        let mut line = String::new();
+        assert_eq!(
+            apply_key(&mut line, &key(KeyCode::Char('j'))),
+            KeyOutcome::Input(Input::Down)
+        );
+        assert_eq!(
+            apply_key(&mut line, &key(KeyCode::Up)),
+            KeyOutcome::Input(Input::Up)
+        );
+        assert_eq!(
+            apply_key(&mut line, &key(KeyCode::Enter)),
+            KeyOutcome::Input(Input::Open)
+        );
+
+        for c in "mdk".chars() {
+            assert_eq!(
+                apply_key(&mut line, &key(KeyCode::Char(c))),
+                KeyOutcome::Pending
+            );
+        }
+        apply_key(&mut line, &key(KeyCode::Backspace));
+        assert_eq!(
+            apply_key(&mut line, &key(KeyCode::Enter)),
+            KeyOutcome::Input(Input::Command("md".to_owned()))
+        );
+        assert_eq!(
+            apply_key(
+                &mut String::new(),
+                &KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)
+            ),
+            KeyOutcome::Interrupt
+        );
+        assert_eq!(
+            apply_key(
+                &mut String::new(),
+                &KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL)
+            ),
+            KeyOutcome::Input(Input::Eof)
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use std::cell::RefCell;
+#[cfg(test)]
+use std::{cell::Cell, collections::VecDeque};
+
+use crate::{
+    ui::pager,
+    utils::{read_line, stdin_closed, Line},
+};
+
+/// An `Io` is where the UI reads its input from and writes its output to. Pages, prompts
+/// and the main loop all go through it, so that a whole session can be run without a
+/// terminal.
+pub trait Io {
+    /// `read_line` reads a line of input. Returns `Err` if it could not be read.
+    fn read_line(&self) -> std::io::Result<Line>;
+    /// `write` writes `text` as is.
+    fn write(&self, text: &str);
+    /// `clear` clears the screen before a page is drawn.
+    fn clear(&self);
+    /// `is_closed` returns whether the end of the input was reached, after which nothing
+    /// more can be read.
+    fn is_closed(&self) -> bool;
+
+    /// `println` writes `text` followed by a line break.
+    fn println(&self, text: &str) {
+        self.write(text);
+        self.write("\n");
+    }
+
+    /// `page` writes `text`, a whole screen, as is. Terminals may show it through a pager
+    /// instead when it does not fit.
+    fn page(&self, text: &str) {
+        self.write(text);
+    }
+
+    /// `prompt_line` reads the answer to a prompt. `None` is returned for an empty line, and
+    /// also at the end of the input or on an error, so that the prompt is cancelled.
+    fn prompt_line(&self) -> Option<String> {
+        self.read_line().ok().and_then(Line::text)
+    }
+}
+
+/// `StdIo` reads from stdin and writes to stdout.
+pub struct StdIo;
+
+impl Io for StdIo {
+    fn read_line(&self) -> std::io::Result<Line> {
+        read_line()
+    }
+
+    fn write(&self, text: &str) {
+        print!("{}", text);
+    }
+
+    fn clear(&self) {
+        clearscreen::clear().expect("failed to clear the screen");
+    }
+
+    fn is_closed(&self) -> bool {
+        stdin_closed()
+    }
+
+    fn page(&self, text: &str) {
+        if !pager::show(text) {
+            self.write(text);
+        }
+    }
+}
+
+/// `BufferedIo` holds what is written to it until it is `flush`ed or read from, and then
+/// hands it to `inner` as a whole screen, see `Io::page`.
+pub struct BufferedIo<'a> {
+    inner: &'a dyn Io,
+    output: RefCell<String>,
+}
+
+impl<'a> BufferedIo<'a> {
+    /// `new` returns a `BufferedIo` in front of `inner`.
+    pub fn new(inner: &'a dyn Io) -> Self {
+        Self {
+            inner,
+            output: RefCell::default(),
+        }
+    }
+
+    /// `flush` writes what was held to `inner`, as a screen.
+    pub fn flush(&self) {
+        let text = self.output.take();
+        if !text.is_empty() {
+            self.inner.page(&text);
+        }
+    }
+}
+
+impl Io for BufferedIo<'_> {
+    fn read_line(&self) -> std::io::Result<Line> {
+        self.flush();
+        self.inner.read_line()
+    }
+
+    fn write(&self, text: &str) {
+        self.output.borrow_mut().push_str(text);
+    }
+
+    fn clear(&self) {
+        self.output.borrow_mut().clear();
+        self.inner.clear();
+    }
+
+    fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+}
+
+/// `ScriptedIo` reads canned lines of input and keeps the output, for tests. Lines are read
+/// as is, except that a blank line is read as `Line::Empty`. The end of the script is read
+/// as `Line::Eof`.
+#[cfg(test)]
+#[derive(Default)]
+pub struct ScriptedIo {
+    input: RefCell<VecDeque<String>>,
+    output: RefCell<String>,
+    closed: Cell<bool>,
+}
+
+#[cfg(test)]
+impl ScriptedIo {
+    /// `new` returns a `ScriptedIo` that reads `lines` in order.
+    pub fn new(lines: &[&str]) -> Self {
+        Self {
+            input: RefCell::new(lines.iter().map(|line| line.to_string()).collect()),
+            ..Self::default()
+        }
+    }
+
+    /// `output` returns everything written so far.
+    pub fn output(&self) -> String {
+        self.output.borrow().clone()
+    }
+}
+
+#[cfg(test)]
+impl Io for ScriptedIo {
+    fn read_line(&self) -> std::io::Result<Line> {
+        match self.input.borrow_mut().pop_front() {
+            Some(line) if line.trim().is_empty() => Ok(Line::Empty),
+            Some(line) => Ok(Line::Text(line)),
+            None => {
+                self.closed.set(true);
+                Ok(Line::Eof)
+            }
+        }
+    }
+
+    fn write(&self, text: &str) {
+        self.output.borrow_mut().push_str(text);
+    }
+
+    fn clear(&self) {}
+
+    fn is_closed(&self) -> bool {
+        self.closed.get()
+    }
+}
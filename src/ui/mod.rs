@@ -1,2 +1,7 @@
+pub mod input;
+pub mod io;
 pub mod navigator;
+pub mod pager;
 pub mod pages;
+pub mod palette;
+pub mod views;
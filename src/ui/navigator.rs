@@ -1,11 +1,35 @@
-use std::rc::Rc;
+use std::{
+    cell::RefCell,
+    fs::{self, File},
+    path::Path,
+    rc::Rc,
+};
 
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
+use chrono::Local;
+use itertools::Itertools;
 
 use crate::{
-    db::JiraDatabase,
-    models::{Action, Status},
-    ui::pages::{prompts::Prompt, EpicDetail, HomePage, Page, StoryDetail},
+    config::Config,
+    db::{error::DbError, validate, JiraDatabase},
+    models::{
+        derive_status, normalize_tag, Action, DatabaseState, EpicBundle, RecentItem, Setting,
+        Status, Story,
+    },
+    theme::Theme,
+    ui::{
+        input::{move_selection, Input, InputSource},
+        io::{BufferedIo, Io, StdIo},
+        pages::{
+            prompts::{BlockerChange, EpicStatusChoice, Prompt, SprintChange, TagChange},
+            DescriptionPage, EpicDetail, HistoryPage, HomePage, MissingEntity, ModifiedPage, Page,
+            RecentPage, SettingsPage, SprintDetail, SprintPage, StoryDetail, TrashPage,
+            WorkspacePicker,
+        },
+        palette::{self, PaletteTarget},
+    },
+    utils::{format_duration, fuzzy_score, rows_per_page},
+    workspaces::Workspaces,
 };
 
 use super::pages::TaskDetail;
@@ -15,6 +39,55 @@ pub trait NavigationManager {
     fn current_page(&self) -> Option<&dyn Page>;
     /// `dispatch_action` commits the `action` to the database.
     fn dispatch_action(&mut self, action: Action) -> anyhow::Result<()>;
+    /// `handle_draw_error` recovers from `error` raised while drawing the current page. If
+    /// the page's epic, story or task no longer exists, the page is popped and `true` is
+    /// returned. Other errors are left to the caller.
+    fn handle_draw_error(&mut self, error: &anyhow::Error) -> bool;
+    /// `io` returns where pages and prompts read their input from and write to.
+    fn io(&self) -> Rc<dyn Io>;
+    /// `handle_input` moves the highlighted row of the current page for the navigation keys,
+    /// or turns `input` into an action for the current page and dispatches it. Opening the
+    /// highlighted row acts as if its id was typed. `z`, `g` and `:`, optionally followed by
+    /// a query, work on every page.
+    fn handle_input(&mut self, input: Input) -> anyhow::Result<()> {
+        let io = self.io();
+        let Some(page) = self.current_page() else {
+            return Ok(());
+        };
+        let action = match input {
+            // `z` undoes the last change from every page, so that no page can miss it.
+            Input::Command(line) if line.trim().eq_ignore_ascii_case("z") => Some(Action::Undo),
+            // `g` opens an epic or story by id from every page, so it is handled before the
+            // page's own commands.
+            Input::Command(line) if line.trim().eq_ignore_ascii_case("g") => {
+                Some(Action::JumpToItem)
+            }
+            // So does `:`, which opens the command palette.
+            Input::Command(line) if line.trim().starts_with(':') => Some(Action::OpenPalette {
+                query: line.trim()[1..].trim().to_owned(),
+            }),
+            Input::Command(line) => page.action_from(&line, &*io)?,
+            Input::Up | Input::Down => {
+                let len = page.selectable_ids()?.len();
+                page.set_selection(move_selection(len, page.selection(), &input));
+                None
+            }
+            Input::Open => {
+                let ids = page.selectable_ids()?;
+                match page.selection().and_then(|index| ids.get(index)) {
+                    Some(id) => page.action_from(&id.to_string(), &*io)?,
+                    None => None,
+                }
+            }
+            // The main loop ends on `Eof` without asking for confirmation, since no answer
+            // could be read.
+            Input::Eof => None,
+        };
+        match action {
+            Some(action) => self.dispatch_action(action),
+            None => Ok(()),
+        }
+    }
 }
 
 /// `Navigator` manages the navigation stack between different pages.
@@ -22,6 +95,24 @@ pub struct Navigator {
     pages: Vec<Box<dyn Page>>,
     prompts: Prompt,
     db: Rc<JiraDatabase>,
+    /// `last_message` describes the outcome of the last successful action.
+    last_message: Option<String>,
+    /// `saved` is set when the last action saved a change to the board.
+    saved: bool,
+    workspaces: Workspaces,
+    /// `dry_run` is set when changes are described instead of saved.
+    dry_run: bool,
+    /// `user` is the default assignee, see `utils::clira_user`.
+    user: Option<String>,
+    /// `config` holds the user's settings, see `Config`. It is shared with the settings
+    /// page.
+    config: Rc<RefCell<Config>>,
+    /// `config_path` is where changed settings are saved, if anywhere.
+    config_path: Option<String>,
+    /// `board_path` is the file of the board being shown, if it is stored in one. It changes
+    /// when switching workspaces.
+    board_path: Option<String>,
+    io: Rc<dyn Io>,
 }
 
 /// A Feature represents the different types of features in the program.
@@ -30,16 +121,280 @@ pub struct Navigator {
 enum Feature {
     Epic(u32),
     Story(u32),
+    Task(u32),
 }
 
 impl Navigator {
     /// `new` returns a new instance of `Navigator` ready to use.
     pub fn new(db: Rc<JiraDatabase>) -> Self {
         Self {
-            pages: vec![Box::new(HomePage { db: db.clone() })],
+            pages: vec![Box::new(HomePage::new(db.clone()))],
             prompts: Prompt::new(),
             db: db.clone(),
+            last_message: None,
+            saved: false,
+            workspaces: Workspaces::default(),
+            dry_run: false,
+            user: None,
+            config: Rc::new(RefCell::new(Config::default())),
+            config_path: None,
+            board_path: None,
+            io: Rc::new(StdIo),
+        }
+    }
+
+    /// `set_io` makes the pages and prompts read from and write to `io`. The prompts are
+    /// replaced by prompts using `io`.
+    #[allow(dead_code)]
+    // `set_io` is used for testing. If `warn(dead_code)` is enabled, then cargo check will incorrectly
+    // report unused code.
+    pub fn set_io(&mut self, io: Rc<dyn Io>) {
+        self.prompts = Prompt::with_config(io.clone(), &self.config.borrow());
+        self.io = io;
+    }
+
+    /// `run` draws the current page and handles the next input from `input` until the last
+    /// page is closed or the input ends. The page is drawn again whenever `input` returns
+    /// without an input, e.g. every few seconds with `WatchInput`, without asking for
+    /// anything. `banner` is shown above every page, and `messages` once above the first one.
+    /// Returns the file of the board shown on exit, see `set_board_path`, along with the
+    /// pages that were open on it, see `open_pages`. The recently viewed items are saved on
+    /// exit, see `JiraDatabase::record_recent`.
+    pub fn run(
+        &mut self,
+        input: &mut dyn InputSource,
+        banner: Option<&str>,
+        mut messages: Vec<String>,
+    ) -> (Option<String>, Vec<RecentItem>) {
+        let io = self.io.clone();
+        let mut open_pages = (self.board_path.clone(), vec![]);
+        loop {
+            io.clear();
+            if self.current_page().is_none() {
+                break;
+            }
+            open_pages = (self.board_path.clone(), self.open_pages());
+            // The screen is written at once, so that it can be paged when it is too long.
+            let screen = BufferedIo::new(&*io);
+            if let Some(banner) = banner {
+                screen.println(&format!("{}\n", banner));
+            }
+            for message in messages.drain(..) {
+                screen.println(&format!("{}\n", message));
+            }
+            // Recreating a deleted board is asked for below what was written so far.
+            screen.flush();
+            // Nothing may be read while `input` is still reading, so a deleted board is only
+            // offered to be recreated once it returned an input.
+            if let Err(error) = self.refresh_database() {
+                if input.is_reading() {
+                    screen.println(&format!("Error reloading the board: {}\n", error));
+                } else if !self.recover_from_refresh_error(&error) {
+                    break;
+                }
+            }
+            if let Some(message) = self.take_message() {
+                screen.println(&format!("{}\n", message));
+            }
+            if self.take_saved() {
+                screen.println("Saved.\n");
+            }
+            if let Err(error) = self.current_page().unwrap().draw(&screen) {
+                if self.handle_draw_error(&error) {
+                    continue;
+                }
+                screen.println(&format!("Error rendering page: {}", error));
+                if !input.is_reading() {
+                    screen.println("Press (enter) to continue..");
+                    let _ = screen.read_line();
+                }
+            }
+            screen.flush();
+            match input.next_input() {
+                Some(Input::Eof) => break,
+                Some(input) => {
+                    if let Err(error) = self.handle_input(input) {
+                        io.println(&error_message(&error));
+                        io.println("Press (enter) to continue..");
+                        let _ = io.read_line();
+                    }
+                }
+                None => {}
+            }
+        }
+        if let Err(error) = self.db.save_recent() {
+            io.println(&format!(
+                "Error saving the recently viewed items: {}",
+                error
+            ));
+        }
+        open_pages
+    }
+
+    /// `set_user` sets the user that stories are assigned to with "assign to me". Without a
+    /// user, the assignee is asked for instead.
+    pub fn set_user(&mut self, user: Option<String>) {
+        self.user = user;
+    }
+
+    /// `set_dry_run` sets whether the navigator runs on a dry run database, see
+    /// `JiraDatabase::dry_run`. Instead of the outcome of an action, the changes it would
+    /// have made are reported, and switched workspaces are opened as dry runs too.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    /// `set_config` applies the user's settings in `config`. The prompts are replaced by
+    /// prompts following them.
+    pub fn set_config(&mut self, config: Config) {
+        self.prompts = Prompt::with_config(self.io.clone(), &config);
+        *self.config.borrow_mut() = config;
+    }
+
+    /// `set_config_path` sets where the settings changed on the settings page are saved.
+    /// Without a path, changes only last until clira exits.
+    pub fn set_config_path(&mut self, path: Option<String>) {
+        self.config_path = path;
+    }
+
+    /// `epic_page` returns the page of the epic `epic_id`, listing as many stories per page
+    /// as configured.
+    fn epic_page(&self, epic_id: u32) -> Box<EpicDetail> {
+        let config = self.config.borrow();
+        let mut page = EpicDetail::new(epic_id, self.db.clone());
+        page.rows_per_page = rows_per_page(config.rows_per_page);
+        page.sort_by_status = config.sort_by_status;
+        Box::new(page)
+    }
+
+    /// `set_workspaces` sets the workspaces that can be switched to from the workspace
+    /// picker.
+    pub fn set_workspaces(&mut self, workspaces: Workspaces) {
+        self.workspaces = workspaces;
+    }
+
+    /// `set_board_path` sets the file of the board the navigator was created with, so that
+    /// `run` can tell which board the open pages belong to.
+    pub fn set_board_path(&mut self, path: Option<String>) {
+        self.board_path = path;
+    }
+
+    /// `switch_database` replaces the database of the navigator with `db`, stored in the file
+    /// at `path`. The recently viewed items of the previous database are saved first. The
+    /// navigation stack is reset to a new home page.
+    pub fn switch_database(&mut self, db: Rc<JiraDatabase>, path: &str) {
+        if let Err(error) = self.db.save_recent() {
+            self.last_message = Some(format!("Error saving the recently viewed items: {}", error));
+        }
+        self.db = db;
+        self.board_path = Some(path.to_owned());
+        self.pages = vec![Box::new(HomePage::new(self.db.clone()))];
+    }
+
+    /// `refresh_database` reloads the board if it was changed by another program, closing
+    /// the pages of items that no longer exist. Returns `Err` if the changed board could not
+    /// be read.
+    pub fn refresh_database(&mut self) -> anyhow::Result<()> {
+        if self.db.refresh_if_changed()? {
+            self.last_message =
+                Some("The board was changed outside of clira and has been reloaded".to_owned());
+            self.prune_stale_pages()?;
+        }
+        Ok(())
+    }
+
+    /// `recover_from_refresh_error` handles `error` raised by `refresh_database`. If the
+    /// board file went missing, the user is offered to recreate it as an empty board, and
+    /// `false` is returned if they declined. Other errors are shown, and `true` returned.
+    fn recover_from_refresh_error(&mut self, error: &anyhow::Error) -> bool {
+        let Some(DbError::NotFound { path }) = error.downcast_ref::<DbError>() else {
+            self.io
+                .println(&format!("Error reloading the board: {}\n", error));
+            return true;
+        };
+        if !(self.prompts.confirm_recreate_board)(path) {
+            return false;
+        }
+        match self.db.reset_to_empty() {
+            Ok(()) => {
+                self.pages = vec![Box::new(HomePage::new(self.db.clone()))];
+                self.last_message = Some(format!("Created an empty board at {}", path));
+            }
+            Err(error) => self
+                .io
+                .println(&format!("Error creating an empty board: {}\n", error)),
+        }
+        true
+    }
+
+    /// `open_pages` returns the epics and stories open on top of the home page, bottom
+    /// first, so that they can be reopened with `restore_pages`.
+    pub fn open_pages(&self) -> Vec<RecentItem> {
+        self.pages
+            .iter()
+            .filter_map(|page| {
+                let page = page.as_any();
+                if let Some(page) = page.downcast_ref::<EpicDetail>() {
+                    Some(RecentItem::Epic(page.epic_id))
+                } else {
+                    page.downcast_ref::<StoryDetail>()
+                        .map(|page| RecentItem::Story {
+                            story_id: page.story_id,
+                            epic_id: page.epic_id,
+                        })
+                }
+            })
+            .collect()
+    }
+
+    /// `restore_pages` reopens the epics and stories in `items` on top of a new home page.
+    /// Restoring stops at the first item that no longer exists, or whose story is no longer
+    /// in its epic, leaving the pages before it open. Returns `Err` if there was an error
+    /// reading the database.
+    pub fn restore_pages(&mut self, items: &[RecentItem]) -> anyhow::Result<()> {
+        let state = self.db.read()?;
+        self.pages.truncate(1);
+        for item in items {
+            let action = match *item {
+                RecentItem::Epic(epic_id) if state.epics.contains_key(&epic_id) => {
+                    Action::NavigateToEpicDetail { epic_id }
+                }
+                RecentItem::Story { story_id, epic_id }
+                    if state
+                        .epics
+                        .get(&epic_id)
+                        .is_some_and(|epic| epic.story_ids.contains(&story_id)) =>
+                {
+                    Action::NavigateToStoryDetail { story_id, epic_id }
+                }
+                _ => break,
+            };
+            self.handle_action(action)?;
         }
+        Ok(())
+    }
+
+    /// `take_message` returns the message describing the last successful action, if any,
+    /// and clears it so that it is only shown once.
+    pub fn take_message(&mut self) -> Option<String> {
+        self.last_message.take()
+    }
+
+    /// `checklist_owner` names the story or task `id` whose checklist was changed, e.g.
+    /// "task #3". Returns `Err` if there was an error reading the database.
+    fn checklist_owner(&self, id: u32) -> anyhow::Result<String> {
+        let kind = if self.db.read()?.tasks.contains_key(&id) {
+            "task"
+        } else {
+            "story"
+        };
+        Ok(format!("{} #{}", kind, id))
+    }
+
+    /// `take_saved` returns whether the last action saved a change to the board, and clears
+    /// it so that "Saved." is only shown once.
+    pub fn take_saved(&mut self) -> bool {
+        std::mem::take(&mut self.saved)
     }
 
     #[allow(dead_code)]
@@ -56,72 +411,140 @@ impl Navigator {
         self.prompts = prompt;
     }
 
-    /// `auto_update_epic_status` updates an Epic's status based on its children Stories.
-    /// Epics are updated based on the `feature`'s id. The status of the Epic is updated
-    /// based on the following conditions, where the higher conditions have higher
-    /// precedence:
-    /// - All stories Closed => Closed
-    /// - All stories Resolved or Closed => Resolved
-    /// - All stories Open => Open
-    /// - Otherwise => In Progress
-    fn auto_update_epic_status(&self, feat: Feature) -> anyhow::Result<()> {
+    /// `set_epic_status` sets the status of the epic `epic_id` manually, which stops it from
+    /// being derived from its stories. The user is asked to confirm statuses that are ahead
+    /// of or behind the epic's stories.
+    fn set_epic_status(&mut self, epic_id: u32, status: Status) -> anyhow::Result<()> {
         let state = self.db.read()?;
-        let (epic_id, epic) = match feat {
-            Feature::Epic(ref epic_id) => (
-                epic_id,
-                state.epics.get(epic_id).ok_or(anyhow!("epic not found"))?,
-            ),
-            Feature::Story(story_id) => state
-                .epics
-                .iter()
-                .find(|(_, epic)| epic.story_ids.contains(&story_id))
-                .ok_or(anyhow!("epic not found"))?,
-        };
+        let epic = state
+            .epics
+            .get(&epic_id)
+            .ok_or(DbError::EpicNotFound(epic_id))?;
         let stories: Vec<_> = epic
             .story_ids
             .iter()
             .filter_map(|id| state.stories.get(id))
             .collect();
-        if stories.is_empty() {
-            self.db.update_epic_status(*epic_id, Status::Open)?;
-            return Ok(());
+        if let Err(warning) = check_epic_status(&status, &stories) {
+            if !(self.prompts.confirm_epic_status)(&warning.to_string()) {
+                return Ok(());
+            }
         }
-        let status = if stories
-            .iter()
-            .all(|story| matches!(story.status, Status::Closed))
-        {
-            Status::Closed
-        } else if stories
-            .iter()
-            .all(|story| matches!(story.status, Status::Resolved | Status::Closed))
-        {
-            Status::Resolved
-        } else if stories
-            .iter()
-            .all(|story| matches!(story.status, Status::Open))
-        {
-            Status::Open
-        } else {
-            Status::InProgress
+        let derived = derive_epic_status(&stories);
+        if !stories.is_empty() && status < derived {
+            let message = format!(
+                "The stories in this epic are {}, but the requested status is {}",
+                derived, status
+            );
+            if !(self.prompts.confirm_status_downgrade)(&message) {
+                return Ok(());
+            }
+        }
+        let action = format!("Updated epic status to {}", status);
+        self.last_message = Some(format!("Epic #{} status → {}", epic_id, status));
+        self.db.update_epic_status(epic_id, status)?;
+        self.db.set_epic_status_override(epic_id, true)?;
+        self.db.record_audit(&action, Some(epic_id))?;
+        Ok(())
+    }
+
+    /// `set_story_status` sets the status of the story `story_id` and derives the status of
+    /// its epic again.
+    fn set_story_status(&mut self, story_id: u32, status: Status) -> anyhow::Result<()> {
+        let action = format!("Updated story status to {}", status);
+        self.last_message = Some(format!("Story #{} status → {}", story_id, status));
+        self.db.update_story_status(story_id, status)?;
+        self.db.record_audit(&action, Some(story_id))?;
+        Ok(())
+    }
+
+    /// `auto_update_epic_status` updates an Epic's status based on its children Stories, see
+    /// `JiraDatabase::update_derived_epic_status`. Epics are updated based on the `feature`'s
+    /// id.
+    fn auto_update_epic_status(&self, feat: Feature) -> anyhow::Result<()> {
+        let epic_id = match feat {
+            Feature::Epic(epic_id) => epic_id,
+            Feature::Story(story_id) => self
+                .db
+                .epic_id_for_story(story_id)?
+                .ok_or(DbError::StoryNotFound(story_id))?,
+            Feature::Task(task_id) => {
+                let story_id = self
+                    .db
+                    .story_id_for_task(task_id)?
+                    .ok_or(DbError::TaskNotFound(task_id))?;
+                self.db
+                    .epic_id_for_story(story_id)?
+                    .ok_or(DbError::StoryNotFound(story_id))?
+            }
         };
-        self.db.update_epic_status(*epic_id, status)?;
+        self.db.update_derived_epic_status(epic_id)?;
         Ok(())
     }
-}
 
-impl NavigationManager for Navigator {
-    fn current_page(&self) -> Option<&dyn Page> {
-        self.pages.last().map(|page| page.as_ref())
+    /// `auto_update_story_status` updates a Story's status based on its children Tasks, with
+    /// the same precedence as `auto_update_epic_status`, and then updates the status of the
+    /// Story's Epic. Stories are updated based on the `feature`'s id, which is either the
+    /// Story or one of its Tasks.
+    ///
+    /// Stories without tasks keep the status they were given.
+    fn auto_update_story_status(&self, feat: Feature) -> anyhow::Result<()> {
+        let state = self.db.read()?;
+        let story_id = match feat {
+            Feature::Epic(_) => bail!("epics have no parent story"),
+            Feature::Story(story_id) => story_id,
+            Feature::Task(task_id) => self
+                .db
+                .story_id_for_task(task_id)?
+                .ok_or(DbError::TaskNotFound(task_id))?,
+        };
+        let story = state
+            .stories
+            .get(&story_id)
+            .ok_or(DbError::StoryNotFound(story_id))?;
+        if story.task_ids.is_empty() {
+            return Ok(());
+        }
+        let statuses: Vec<_> = story
+            .task_ids
+            .iter()
+            .filter_map(|id| state.tasks.get(id))
+            .map(|task| task.status.clone())
+            .collect();
+        self.db
+            .update_story_status(story_id, derive_status(&statuses))?;
+        self.auto_update_epic_status(Feature::Story(story_id))
     }
 
-    fn dispatch_action(&mut self, action: Action) -> anyhow::Result<()> {
+    /// `prune_stale_pages` removes the pages for epics, stories and tasks that no longer
+    /// exist in the database, e.g. after an undo.
+    fn prune_stale_pages(&mut self) -> anyhow::Result<()> {
+        let state = self.db.read()?;
+        self.pages.retain(|page| {
+            let page = page.as_any();
+            if let Some(page) = page.downcast_ref::<EpicDetail>() {
+                state.epics.contains_key(&page.epic_id)
+            } else if let Some(page) = page.downcast_ref::<StoryDetail>() {
+                state.stories.contains_key(&page.story_id)
+            } else if let Some(page) = page.downcast_ref::<TaskDetail>() {
+                state.tasks.contains_key(&page.task_id)
+            } else if let Some(page) = page.downcast_ref::<SprintDetail>() {
+                state.sprints.contains_key(&page.sprint_id)
+            } else {
+                true
+            }
+        });
+        Ok(())
+    }
+
+    /// `handle_action` commits the `action` to the database. Use `dispatch_action` so that
+    /// the writes of the action are undone together.
+    fn handle_action(&mut self, action: Action) -> anyhow::Result<()> {
         match action {
             Action::NavigateToEpicDetail { epic_id } => {
-                let page = Box::new(EpicDetail {
-                    epic_id,
-                    db: self.db.clone(),
-                });
+                let page = self.epic_page(epic_id);
                 self.pages.push(page);
+                self.db.record_recent(RecentItem::Epic(epic_id))?;
             }
             Action::NavigateToStoryDetail { story_id, epic_id } => {
                 let page = Box::new(StoryDetail {
@@ -130,6 +553,8 @@ impl NavigationManager for Navigator {
                     db: self.db.clone(),
                 });
                 self.pages.push(page);
+                self.db
+                    .record_recent(RecentItem::Story { story_id, epic_id })?;
             }
             Action::NavigateToTaskDetail { task_id, story_id } => {
                 let page = Box::new(TaskDetail {
@@ -139,94 +564,1108 @@ impl NavigationManager for Navigator {
                 });
                 self.pages.push(page);
             }
+            Action::NavigateToDescription { id } => {
+                let page = Box::new(DescriptionPage {
+                    id,
+                    db: self.db.clone(),
+                });
+                self.pages.push(page);
+            }
+            Action::NavigateToHistory => {
+                let page = Box::new(HistoryPage {
+                    db: self.db.clone(),
+                });
+                self.pages.push(page);
+            }
+            Action::NavigateToSprints => {
+                let page = Box::new(SprintPage {
+                    db: self.db.clone(),
+                });
+                self.pages.push(page);
+            }
+            Action::NavigateToSprintDetail { sprint_id } => {
+                let page = Box::new(SprintDetail {
+                    sprint_id,
+                    db: self.db.clone(),
+                });
+                self.pages.push(page);
+            }
+            Action::NavigateToRecent => {
+                let page = Box::new(RecentPage {
+                    items: self.db.read()?.recent,
+                    db: self.db.clone(),
+                });
+                self.pages.push(page);
+            }
+            Action::NavigateToModified => {
+                let page = Box::new(ModifiedPage {
+                    db: self.db.clone(),
+                });
+                self.pages.push(page);
+            }
+            Action::OpenPalette { query } => {
+                let mut entries = palette::matching(&query);
+                if entries.is_empty() {
+                    self.last_message = Some(format!("No action matches {:?}", query));
+                    return Ok(());
+                }
+                let labels: Vec<String> =
+                    entries.iter().map(|(label, _)| label.to_string()).collect();
+                let Some(index) = (self.prompts.select_palette_entry)(&labels) else {
+                    return Ok(());
+                };
+                match entries.swap_remove(index).1 {
+                    PaletteTarget::Action(action) => self.handle_action(action)?,
+                    PaletteTarget::HomeCommand(command) => {
+                        self.pages.truncate(1);
+                        let io = self.io.clone();
+                        let action = match self.pages.first() {
+                            Some(home) => home.action_from(command, &*io)?,
+                            None => None,
+                        };
+                        if let Some(action) = action {
+                            self.handle_action(action)?;
+                        }
+                    }
+                }
+            }
+            Action::JumpToItem => {
+                let Some(query) = (self.prompts.goto_query)() else {
+                    return Ok(());
+                };
+                let state = self.db.read()?;
+                let target = if let Ok(id) = query.parse::<u32>() {
+                    let target = state.jump_target(id);
+                    if target.is_none() {
+                        self.last_message = Some(format!("No epic or story #{}", id));
+                    }
+                    target
+                } else {
+                    let mut matches = goto_matches(&state, &query);
+                    let labels: Vec<String> =
+                        matches.iter().map(|(_, label)| label.clone()).collect();
+                    if labels.is_empty() {
+                        self.last_message =
+                            Some(format!("No epic or story name matches {:?}", query));
+                        None
+                    } else {
+                        (self.prompts.select_goto_match)(&labels)
+                            .map(|index| matches.swap_remove(index).0)
+                    }
+                };
+                match target {
+                    Some(Action::NavigateToStoryDetail { story_id, epic_id }) => {
+                        self.handle_action(Action::NavigateToEpicDetail { epic_id })?;
+                        self.handle_action(Action::NavigateToStoryDetail { story_id, epic_id })?;
+                    }
+                    Some(action) => self.handle_action(action)?,
+                    None => {}
+                }
+            }
+            Action::NavigateToWorkspaces => {
+                let page = Box::new(WorkspacePicker {
+                    workspaces: self.workspaces.clone(),
+                });
+                self.pages.push(page);
+            }
+            Action::NavigateToSettings => {
+                let page = Box::new(SettingsPage {
+                    config: self.config.clone(),
+                });
+                self.pages.push(page);
+            }
+            Action::NavigateToTrash => {
+                let page = Box::new(TrashPage {
+                    db: self.db.clone(),
+                });
+                self.pages.push(page);
+            }
+            Action::RestoreFromTrash { id } => {
+                let epic_id = self.db.restore_from_trash(id)?;
+                self.db.record_audit("Restored from trash", Some(id))?;
+                if epic_id == id {
+                    self.last_message = Some(format!("Restored epic #{}", id));
+                } else {
+                    self.auto_update_epic_status(Feature::Epic(epic_id))?;
+                    self.last_message =
+                        Some(format!("Restored story #{} to epic #{}", id, epic_id));
+                }
+            }
+            Action::PurgeTrash => {
+                let state = self.db.read()?;
+                let count = state.trash_epics.len() + state.trash_stories.len();
+                if count == 0 {
+                    self.last_message = Some("The trash is already empty".to_owned());
+                } else if (self.prompts.confirm_purge_trash)(count) {
+                    self.db.purge_trash()?;
+                    self.db.record_audit("Emptied trash", None)?;
+                    self.last_message = Some(format!(
+                        "Permanently deleted {} {}",
+                        count,
+                        if count == 1 { "item" } else { "items" }
+                    ));
+                }
+            }
+            Action::UpdateSetting { setting } => {
+                let mut config = self.config.borrow().clone();
+                match setting {
+                    Setting::RowsPerPage => match (self.prompts.update_rows_per_page)() {
+                        Some(rows) => config.rows_per_page = rows,
+                        None => return Ok(()),
+                    },
+                    Setting::ConfirmExit => config.confirm_exit = !config.confirm_exit,
+                    Setting::SortByStatus => config.sort_by_status = !config.sort_by_status,
+                    Setting::Theme => match (self.prompts.update_theme_color)() {
+                        Some((status, color)) => config.theme.set_color(status, color),
+                        None => return Ok(()),
+                    },
+                }
+                if setting == Setting::Theme {
+                    Theme::set(config.theme);
+                }
+                *self.config.borrow_mut() = config.clone();
+                self.last_message = Some(match &self.config_path {
+                    Some(path) => {
+                        config.save(Path::new(path))?;
+                        format!("Saved the settings to {}", path)
+                    }
+                    None => "Changed the settings until clira exits".to_owned(),
+                });
+            }
+            Action::SwitchWorkspace { name } => {
+                let path = self
+                    .workspaces
+                    .get(&name)
+                    .ok_or(anyhow!("no workspace named {}", name))?
+                    .to_owned();
+                let db = if self.dry_run {
+                    JiraDatabase::open_existing(&path)?.dry_run()
+                } else {
+                    JiraDatabase::new(&path)?
+                };
+                self.switch_database(Rc::new(db), &path);
+                self.last_message = Some(format!("Switched to workspace {} ({})", name, path));
+            }
             Action::NavigateToPreviousPage => {
                 self.pages.pop();
             }
+            Action::NavigateHome => self.pages.truncate(1),
             Action::CreateEpic => {
                 if let Some(epic) = (self.prompts.create_epic)() {
-                    self.db.create_epic(&epic)?;
+                    let epic_id = self.db.create_epic(&epic)?;
+                    self.db.record_audit("Created epic", Some(epic_id))?;
+                    self.last_message = Some(format!("Created epic #{}", epic_id));
+                }
+            }
+            Action::CreateEpicFromTemplate => {
+                let templates = self.config.borrow().templates.clone();
+                if templates.is_empty() {
+                    self.last_message = Some(
+                        "There are no templates, add them to the config file under [[templates]]"
+                            .to_owned(),
+                    );
+                } else if let Some(index) = (self.prompts.select_template)(&templates) {
+                    let template = templates[index].fill_placeholders(Local::now().date_naive());
+                    let epic_id = self.db.apply_template(&template)?;
+                    self.db
+                        .record_audit("Created epic from template", Some(epic_id))?;
+                    let count = template.stories.len();
+                    self.last_message = Some(format!(
+                        "Created epic #{} with {} {} from template {:?}",
+                        epic_id,
+                        count,
+                        if count == 1 { "story" } else { "stories" },
+                        templates[index].name
+                    ));
                 }
             }
             Action::CreateStory { epic_id } => {
-                if let Some(story) = (self.prompts.create_story)() {
-                    self.db.create_story(&story, epic_id)?;
+                if let Some(story_id) = create_story_in_epic(&self.db, &self.prompts, epic_id)? {
+                    self.auto_update_epic_status(Feature::Epic(epic_id))?;
+                    self.db.record_audit("Created story", Some(story_id))?;
+                    self.last_message = Some(format!("Created story #{}", story_id));
+                }
+            }
+            Action::CreateStoryNamed { epic_id, name } => {
+                let story = Story::new(&validate::name(&name)?, "");
+                let story_ids = create_in_epic(&self.db, &self.prompts, epic_id, || {
+                    Ok(vec![self.db.create_story(&story, epic_id)?])
+                })?;
+                if let Some(story_id) = story_ids.first() {
+                    self.auto_update_epic_status(Feature::Epic(epic_id))?;
+                    self.db.record_audit("Created story", Some(*story_id))?;
+                    self.last_message = Some(format!("Created story #{}", story_id));
+                }
+            }
+            Action::CreateStories { epic_id } => {
+                let story_ids = create_stories_in_epic(&self.db, &self.prompts, epic_id)?;
+                if !story_ids.is_empty() {
+                    self.auto_update_epic_status(Feature::Epic(epic_id))?;
+                    for story_id in &story_ids {
+                        self.db.record_audit("Created story", Some(*story_id))?;
+                    }
+                    self.last_message = Some(format!(
+                        "Created {} {}",
+                        story_ids.len(),
+                        if story_ids.len() == 1 {
+                            "story"
+                        } else {
+                            "stories"
+                        }
+                    ));
                 }
             }
             Action::CreateTask { story_id } => {
                 if let Some(task) = (self.prompts.create_task)() {
-                    self.db.create_task(&task, story_id)?;
+                    let task_id = self.db.create_task(&task, story_id)?;
+                    self.db.record_audit("Created task", Some(task_id))?;
+                    self.last_message = Some(format!("Created task #{}", task_id));
                 }
             }
+            Action::CreateSprint => {
+                if let Some(sprint) = (self.prompts.create_sprint)() {
+                    let sprint_id = self.db.create_sprint(&sprint)?;
+                    self.db.record_audit("Created sprint", Some(sprint_id))?;
+                    self.last_message = Some(format!("Created sprint #{}", sprint_id));
+                }
+            }
+            Action::UpdateSprintStories { sprint_id } => {
+                match (self.prompts.update_sprint_stories)() {
+                    Some(SprintChange::Add(story_id)) => {
+                        self.db.add_story_to_sprint(sprint_id, story_id)?;
+                        self.db
+                            .record_audit("Added story to sprint", Some(sprint_id))?;
+                        self.last_message = Some(format!(
+                            "Story #{} added to sprint #{}",
+                            story_id, sprint_id
+                        ));
+                    }
+                    Some(SprintChange::Remove(story_id)) => {
+                        self.db.remove_story_from_sprint(sprint_id, story_id)?;
+                        self.db
+                            .record_audit("Removed story from sprint", Some(sprint_id))?;
+                        self.last_message = Some(format!(
+                            "Story #{} removed from sprint #{}",
+                            story_id, sprint_id
+                        ));
+                    }
+                    None => {}
+                }
+            }
+            Action::DuplicateEpic { epic_id } => {
+                let copy_id = self.db.duplicate_epic(epic_id)?;
+                self.db.record_audit("Duplicated epic", Some(copy_id))?;
+                self.last_message = Some(format!("Duplicated epic #{} as #{}", epic_id, copy_id));
+                let page = self.epic_page(copy_id);
+                self.pages.push(page);
+            }
             Action::UpdateEpicName { epic_id } => {
-                let name = (self.prompts.update_name)();
-                self.db.update_epic_name(epic_id, &name)?;
+                let current = self
+                    .db
+                    .read()?
+                    .epics
+                    .get(&epic_id)
+                    .ok_or(DbError::EpicNotFound(epic_id))?
+                    .name
+                    .clone();
+                let Some(name) = (self.prompts.update_name)(&current) else {
+                    return Ok(());
+                };
+                if name == current {
+                    self.last_message = Some(format!("Epic #{} name unchanged", epic_id));
+                } else {
+                    self.db.update_epic_name(epic_id, &name)?;
+                    self.db.record_audit("Updated epic name", Some(epic_id))?;
+                    self.last_message = Some(format!("Epic #{} renamed to {}", epic_id, name));
+                }
             }
             Action::UpdateEpicDescription { epic_id } => {
-                let description = (self.prompts.update_description)();
-                self.db.update_epic_description(epic_id, &description)?;
+                let current = self
+                    .db
+                    .read()?
+                    .epics
+                    .get(&epic_id)
+                    .ok_or(DbError::EpicNotFound(epic_id))?
+                    .description
+                    .clone();
+                let Some(description) = (self.prompts.update_description)(&current) else {
+                    return Ok(());
+                };
+                if description == current {
+                    self.last_message = Some(format!("Epic #{} description unchanged", epic_id));
+                } else {
+                    self.db.update_epic_description(epic_id, &description)?;
+                    self.db
+                        .record_audit("Updated epic description", Some(epic_id))?;
+                    self.last_message = Some(format!("Epic #{} description updated", epic_id));
+                }
+            }
+            Action::UpdateEpicStatus { epic_id } => match (self.prompts.update_epic_status)() {
+                Some(EpicStatusChoice::Manual(status)) => self.set_epic_status(epic_id, status)?,
+                Some(EpicStatusChoice::Auto) => {
+                    self.db.set_epic_status_override(epic_id, false)?;
+                    self.auto_update_epic_status(Feature::Epic(epic_id))?;
+                    self.db
+                        .record_audit("Derived epic status from stories", Some(epic_id))?;
+                    let status = &self.db.read()?.epics[&epic_id].status;
+                    self.last_message = Some(format!(
+                        "Epic #{} status → {} (derived from stories)",
+                        epic_id, status
+                    ));
+                }
+                None => {}
+            },
+            Action::SetEpicStatus { epic_id, status } => self.set_epic_status(epic_id, status)?,
+            Action::UpdateEpicAssignee { epic_id } => {
+                let assignee = (self.prompts.update_assignee)();
+                self.db.update_epic_assignee(epic_id, assignee.as_deref())?;
+                self.db
+                    .record_audit("Updated epic assignee", Some(epic_id))?;
+                self.last_message = Some(assignee_message("Epic", epic_id, &assignee));
+            }
+            Action::ToggleEpicArchived { epic_id } => {
+                let archived = !self
+                    .db
+                    .read()?
+                    .epics
+                    .get(&epic_id)
+                    .ok_or(DbError::EpicNotFound(epic_id))?
+                    .archived;
+                self.db.set_epic_archived(epic_id, archived)?;
+                let action = if archived { "Archived" } else { "Unarchived" };
+                self.db
+                    .record_audit(&format!("{} epic", action), Some(epic_id))?;
+                self.last_message = Some(format!("{} epic #{}", action, epic_id));
             }
-            Action::UpdateEpicStatus { epic_id } => {
+            Action::UpdateEpicStoriesStatus { epic_id } => {
                 if let Some(status) = (self.prompts.update_status)() {
-                    self.db.update_epic_status(epic_id, status)?;
+                    let action = format!("Updated all story statuses to {}", status);
+                    let count = self.db.bulk_update_story_status(epic_id, status.clone())?;
+                    self.auto_update_epic_status(Feature::Epic(epic_id))?;
+                    self.db.record_audit(&action, Some(epic_id))?;
+                    self.last_message = Some(format!(
+                        "{} {} in epic #{} → {}",
+                        count,
+                        if count == 1 { "story" } else { "stories" },
+                        epic_id,
+                        status
+                    ));
+                }
+            }
+            Action::CloseEpicStories { epic_id } => {
+                let count = self
+                    .db
+                    .read()?
+                    .epics
+                    .get(&epic_id)
+                    .ok_or(DbError::EpicNotFound(epic_id))?
+                    .story_ids
+                    .len();
+                if count == 0 {
+                    self.last_message = Some(format!("Epic #{} has no stories to close", epic_id));
+                } else if (self.prompts.confirm_close_stories)(count) {
+                    let count = self.db.bulk_update_story_status(epic_id, Status::Closed)?;
+                    self.auto_update_epic_status(Feature::Epic(epic_id))?;
+                    self.db.record_audit("Closed all stories", Some(epic_id))?;
+                    self.last_message = Some(format!(
+                        "Closed {} {} in epic #{}",
+                        count,
+                        if count == 1 { "story" } else { "stories" },
+                        epic_id
+                    ));
                 }
             }
             Action::UpdateStoryName { story_id } => {
-                let name = (self.prompts.update_name)();
-                self.db.update_story_name(story_id, &name)?;
+                let current = self
+                    .db
+                    .read()?
+                    .stories
+                    .get(&story_id)
+                    .ok_or(DbError::StoryNotFound(story_id))?
+                    .name
+                    .clone();
+                let Some(name) = (self.prompts.update_name)(&current) else {
+                    return Ok(());
+                };
+                if name == current {
+                    self.last_message = Some(format!("Story #{} name unchanged", story_id));
+                } else {
+                    self.db.update_story_name(story_id, &name)?;
+                    self.db.record_audit("Updated story name", Some(story_id))?;
+                    self.last_message = Some(format!("Story #{} renamed to {}", story_id, name));
+                }
             }
             Action::UpdateStoryDescription { story_id } => {
-                let description = (self.prompts.update_description)();
-                self.db.update_story_description(story_id, &description)?;
+                let current = self
+                    .db
+                    .read()?
+                    .stories
+                    .get(&story_id)
+                    .ok_or(DbError::StoryNotFound(story_id))?
+                    .description
+                    .clone();
+                let Some(description) = (self.prompts.update_description)(&current) else {
+                    return Ok(());
+                };
+                if description == current {
+                    self.last_message = Some(format!("Story #{} description unchanged", story_id));
+                } else {
+                    self.db.update_story_description(story_id, &description)?;
+                    self.db
+                        .record_audit("Updated story description", Some(story_id))?;
+                    self.last_message = Some(format!("Story #{} description updated", story_id));
+                }
             }
             Action::UpdateStoryStatus { story_id } => {
                 if let Some(status) = (self.prompts.update_status)() {
-                    self.db.update_story_status(story_id, status)?;
-                    self.auto_update_epic_status(Feature::Story(story_id))?;
+                    self.set_story_status(story_id, status)?;
+                }
+            }
+            Action::SetStoryStatus { story_id, status } => {
+                self.set_story_status(story_id, status)?
+            }
+            Action::MoveStory {
+                epic_id,
+                story_id,
+                direction,
+            } => {
+                self.db.reorder_story(epic_id, story_id, direction)?;
+                self.db.record_audit("Moved story", Some(story_id))?;
+                self.last_message = Some(format!("Moved story #{} {}", story_id, direction));
+            }
+            Action::UpdateStoryAssignee { story_id } => {
+                let assignee = (self.prompts.update_assignee)();
+                self.db
+                    .update_story_assignee(story_id, assignee.as_deref())?;
+                self.db
+                    .record_audit("Updated story assignee", Some(story_id))?;
+                self.last_message = Some(assignee_message("Story", story_id, &assignee));
+            }
+            Action::AssignStoryToMe { story_id } => {
+                let Some(user) = self.user.clone() else {
+                    return self.handle_action(Action::UpdateStoryAssignee { story_id });
+                };
+                self.db.update_story_assignee(story_id, Some(&user))?;
+                self.db
+                    .record_audit("Updated story assignee", Some(story_id))?;
+                self.last_message = Some(assignee_message("Story", story_id, &Some(user)));
+            }
+            Action::UpdateStoryPoints { story_id } => {
+                if let Some(points) = (self.prompts.update_points)() {
+                    self.db.update_story_points(story_id, points)?;
+                    self.db
+                        .record_audit("Updated story points", Some(story_id))?;
+                    self.last_message = Some(match points {
+                        Some(points) => {
+                            format!("Story #{} estimated at {} points", story_id, points)
+                        }
+                        None => format!("Story #{} estimate cleared", story_id),
+                    });
+                }
+            }
+            Action::UpdateStoryTags { story_id } => match (self.prompts.update_tags)() {
+                Some(TagChange::Add(tag)) => {
+                    self.db.add_story_tag(story_id, &tag)?;
+                    self.db.record_audit("Added story tag", Some(story_id))?;
+                    self.last_message = Some(format!(
+                        "Tagged story #{} with {}",
+                        story_id,
+                        normalize_tag(&tag)
+                    ));
+                }
+                Some(TagChange::Remove(tag)) => {
+                    self.db.remove_story_tag(story_id, &tag)?;
+                    self.db.record_audit("Removed story tag", Some(story_id))?;
+                    self.last_message = Some(format!(
+                        "Removed tag {} from story #{}",
+                        normalize_tag(&tag),
+                        story_id
+                    ));
+                }
+                None => {}
+            },
+            Action::UpdateStoryBlockers { story_id } => match (self.prompts.update_blockers)() {
+                Some(BlockerChange::Add(blocker_id)) => {
+                    self.db.add_story_dependency(story_id, blocker_id)?;
+                    self.db
+                        .record_audit("Added story blocker", Some(story_id))?;
+                    self.last_message = Some(format!(
+                        "Story #{} is blocked by story #{}",
+                        story_id, blocker_id
+                    ));
+                }
+                Some(BlockerChange::Remove(blocker_id)) => {
+                    self.db.remove_story_dependency(story_id, blocker_id)?;
+                    self.db
+                        .record_audit("Removed story blocker", Some(story_id))?;
+                    self.last_message = Some(format!(
+                        "Story #{} is no longer blocked by story #{}",
+                        story_id, blocker_id
+                    ));
+                }
+                None => {}
+            },
+            Action::LogWork { story_id } => {
+                if let Some(entry) = (self.prompts.log_work)() {
+                    self.db.log_work(story_id, &entry)?;
+                    self.db.record_audit("Logged work", Some(story_id))?;
+                    self.last_message = Some(format!(
+                        "Logged {} on story #{}",
+                        format_duration(entry.minutes),
+                        story_id
+                    ));
+                }
+            }
+            Action::AddComment { story_id } => {
+                if let Some(comment) = (self.prompts.add_comment)() {
+                    self.db.add_comment(story_id, &comment)?;
+                    self.db.record_audit("Commented", Some(story_id))?;
+                    self.last_message = Some(format!("Commented on story #{}", story_id));
                 }
             }
+            Action::AddChecklistItem { id, text } => {
+                let number = self.db.add_checklist_item(id, &text)?;
+                self.db.record_audit("Added checklist item", Some(id))?;
+                self.last_message = Some(format!(
+                    "Added checklist item {} to {}",
+                    number,
+                    self.checklist_owner(id)?
+                ));
+            }
+            Action::ToggleChecklistItem { id, index } => {
+                let done = self.db.toggle_checklist_item(id, index)?;
+                let action = if done { "Checked" } else { "Unchecked" };
+                self.db
+                    .record_audit(&format!("{} checklist item", action), Some(id))?;
+                self.last_message = Some(format!(
+                    "{} checklist item {} of {}",
+                    action,
+                    index + 1,
+                    self.checklist_owner(id)?
+                ));
+            }
+            Action::RemoveChecklistItem { id, index } => {
+                let item = self.db.remove_checklist_item(id, index)?;
+                self.db.record_audit("Removed checklist item", Some(id))?;
+                self.last_message = Some(format!(
+                    "Removed checklist item {} ({}) from {}",
+                    index + 1,
+                    item.text,
+                    self.checklist_owner(id)?
+                ));
+            }
             Action::UpdateTaskName { task_id } => {
-                let name = (self.prompts.update_name)();
-                self.db.update_task_name(task_id, &name)?;
+                let current = self
+                    .db
+                    .read()?
+                    .tasks
+                    .get(&task_id)
+                    .ok_or(DbError::TaskNotFound(task_id))?
+                    .name
+                    .clone();
+                let Some(name) = (self.prompts.update_name)(&current) else {
+                    return Ok(());
+                };
+                if name == current {
+                    self.last_message = Some(format!("Task #{} name unchanged", task_id));
+                } else {
+                    self.db.update_task_name(task_id, &name)?;
+                    self.db.record_audit("Updated task name", Some(task_id))?;
+                    self.last_message = Some(format!("Task #{} renamed to {}", task_id, name));
+                }
             }
             Action::UpdateTaskDescription { task_id } => {
-                let description = (self.prompts.update_description)();
-                self.db.update_task_description(task_id, &description)?;
+                let current = self
+                    .db
+                    .read()?
+                    .tasks
+                    .get(&task_id)
+                    .ok_or(DbError::TaskNotFound(task_id))?
+                    .description
+                    .clone();
+                let Some(description) = (self.prompts.update_description)(&current) else {
+                    return Ok(());
+                };
+                if description == current {
+                    self.last_message = Some(format!("Task #{} description unchanged", task_id));
+                } else {
+                    self.db.update_task_description(task_id, &description)?;
+                    self.db
+                        .record_audit("Updated task description", Some(task_id))?;
+                    self.last_message = Some(format!("Task #{} description updated", task_id));
+                }
             }
             Action::UpdateTaskStatus { task_id } => {
                 if let Some(status) = (self.prompts.update_status)() {
+                    let action = format!("Updated task status to {}", status);
+                    self.last_message = Some(format!("Task #{} status → {}", task_id, status));
                     self.db.update_task_status(task_id, status)?;
-                    // TODO: Auto update the parent Story's status
+                    self.auto_update_story_status(Feature::Task(task_id))?;
+                    self.db.record_audit(&action, Some(task_id))?;
                 }
             }
             Action::DeleteEpic { epic_id } => {
                 if (self.prompts.delete_epic)() {
+                    let story_count = self
+                        .db
+                        .read()?
+                        .epics
+                        .get(&epic_id)
+                        .map_or(0, |epic| epic.story_ids.len());
                     self.db.delete_epic(epic_id)?;
+                    self.db.record_audit("Deleted epic", Some(epic_id))?;
+                    self.last_message = Some(format!(
+                        "Deleted epic #{} and {} {}",
+                        epic_id,
+                        story_count,
+                        if story_count == 1 { "story" } else { "stories" }
+                    ));
                     self.pages.pop();
                 }
             }
             Action::DeleteStory { story_id, epic_id } => {
                 if (self.prompts.delete_story)() {
+                    let task_count = self
+                        .db
+                        .read()?
+                        .stories
+                        .get(&story_id)
+                        .map_or(0, |story| story.task_ids.len());
+                    self.last_message = Some(format!(
+                        "Deleted story #{} and {} {}",
+                        story_id,
+                        task_count,
+                        if task_count == 1 { "task" } else { "tasks" }
+                    ));
                     self.db.delete_story(story_id, epic_id)?;
                     self.auto_update_epic_status(Feature::Epic(epic_id))?;
+                    self.db.record_audit("Deleted story", Some(story_id))?;
                     self.pages.pop();
                 }
             }
+            Action::DeleteClosedStories { epic_id } => {
+                let state = self.db.read()?;
+                let story_ids: Vec<u32> = state
+                    .epics
+                    .get(&epic_id)
+                    .ok_or(DbError::EpicNotFound(epic_id))?
+                    .story_ids
+                    .iter()
+                    .filter(|id| {
+                        state
+                            .stories
+                            .get(id)
+                            .is_some_and(|story| story.status == Status::Closed)
+                    })
+                    .copied()
+                    .collect();
+                if story_ids.is_empty() {
+                    self.last_message =
+                        Some(format!("Epic #{} has no closed stories to delete", epic_id));
+                } else if (self.prompts.confirm_delete_stories)(story_ids.len()) {
+                    let deleted = self.db.delete_stories(&story_ids, epic_id)?;
+                    self.auto_update_epic_status(Feature::Epic(epic_id))?;
+                    for story_id in &deleted {
+                        self.db.record_audit("Deleted story", Some(*story_id))?;
+                    }
+                    self.last_message = Some(format!(
+                        "Deleted {} {}",
+                        deleted.len(),
+                        if deleted.len() == 1 {
+                            "story"
+                        } else {
+                            "stories"
+                        }
+                    ));
+                }
+            }
+            Action::DeleteStories { epic_id } => {
+                let Some(entries) = (self.prompts.select_story_ids)() else {
+                    return Ok(());
+                };
+                let epic_story_ids = self
+                    .db
+                    .read()?
+                    .epics
+                    .get(&epic_id)
+                    .ok_or(DbError::EpicNotFound(epic_id))?
+                    .story_ids
+                    .clone();
+                let (mut story_ids, mut skipped) = (vec![], vec![]);
+                for entry in entries {
+                    match entry.parse::<u32>() {
+                        Ok(id) if epic_story_ids.contains(&id) => {
+                            if !story_ids.contains(&id) {
+                                story_ids.push(id);
+                            }
+                        }
+                        _ => skipped.push(entry),
+                    }
+                }
+                let skipped = if skipped.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        " (skipped {}: not stories of this epic)",
+                        skipped.join(", ")
+                    )
+                };
+                if story_ids.is_empty() {
+                    self.last_message = Some(format!("Deleted no stories{}", skipped));
+                    return Ok(());
+                }
+                if (self.prompts.confirm_delete_stories)(story_ids.len()) {
+                    let deleted = self.db.delete_stories(&story_ids, epic_id)?;
+                    self.auto_update_epic_status(Feature::Epic(epic_id))?;
+                    for story_id in &deleted {
+                        self.db.record_audit("Deleted story", Some(*story_id))?;
+                    }
+                    self.last_message = Some(format!(
+                        "Deleted {} {}{}",
+                        deleted.len(),
+                        if deleted.len() == 1 {
+                            "story"
+                        } else {
+                            "stories"
+                        },
+                        skipped
+                    ));
+                }
+            }
             Action::DeleteTask { task_id, story_id } => {
                 if (self.prompts.delete_task)() {
                     self.db.delete_task(task_id, story_id)?;
-                    // TODO: Auto update the parent Story's status
+                    self.auto_update_story_status(Feature::Story(story_id))?;
+                    self.db.record_audit("Deleted task", Some(task_id))?;
+                    self.last_message = Some(format!("Deleted task #{}", task_id));
                     self.pages.pop();
                 }
             }
-            Action::Exit => self.pages.clear(),
-        }
-        Ok(())
-    }
-}
-
-pub mod test_utils {
-    use std::cell::RefCell;
-
+            Action::ConvertStoryToTask { story_id, epic_id } => {
+                if let Some(target_story_id) = (self.prompts.convert_target)("story") {
+                    let task_id = self.db.story_to_task(story_id, target_story_id)?;
+                    self.auto_update_story_status(Feature::Story(target_story_id))?;
+                    self.auto_update_epic_status(Feature::Epic(epic_id))?;
+                    self.db
+                        .record_audit("Converted story to task", Some(task_id))?;
+                    self.last_message = Some(format!(
+                        "Converted story #{} into task #{} of story #{}",
+                        story_id, task_id, target_story_id
+                    ));
+                    self.pages.pop();
+                }
+            }
+            Action::ConvertTaskToStory { task_id, story_id } => {
+                if let Some(target_epic_id) = (self.prompts.convert_target)("epic") {
+                    let new_story_id = self.db.task_to_story(task_id, target_epic_id)?;
+                    self.auto_update_story_status(Feature::Story(story_id))?;
+                    self.auto_update_epic_status(Feature::Epic(target_epic_id))?;
+                    self.db
+                        .record_audit("Converted task to story", Some(new_story_id))?;
+                    self.last_message = Some(format!(
+                        "Converted task #{} into story #{} of epic #{}",
+                        task_id, new_story_id, target_epic_id
+                    ));
+                    self.pages.pop();
+                }
+            }
+            Action::ExportCsv => {
+                if let Some(path) = (self.prompts.export_path)() {
+                    let mut file = File::create(&path)?;
+                    let rows = self.db.export_csv(&mut file)?;
+                    self.last_message = Some(format!(
+                        "Exported {} {} to {}",
+                        rows,
+                        if rows == 1 { "row" } else { "rows" },
+                        path
+                    ));
+                }
+            }
+            Action::ExportStoriesJsonl => {
+                if let Some(path) = (self.prompts.export_path)() {
+                    let mut file = File::create(&path)?;
+                    let stories = self.db.export_stories_jsonl(&mut file)?;
+                    self.last_message = Some(format!(
+                        "Exported {} {} to {}",
+                        stories,
+                        if stories == 1 { "story" } else { "stories" },
+                        path
+                    ));
+                }
+            }
+            Action::ExportHtml => {
+                if let Some(path) = (self.prompts.export_path)() {
+                    self.db.export_html(&mut File::create(&path)?)?;
+                    self.last_message = Some(format!("Exported the report to {}", path));
+                }
+            }
+            Action::ExportEpic { epic_id } => {
+                let path = format!("epic-{}.md", epic_id);
+                fs::write(&path, self.db.export_epic_markdown(epic_id)?)?;
+                let bundle_path = format!("epic-{}.bundle.json", epic_id);
+                let bundle = serde_json::to_string_pretty(&self.db.export_epic(epic_id)?)?;
+                fs::write(&bundle_path, bundle)?;
+                self.last_message = Some(format!(
+                    "Exported epic #{} to {} and {}",
+                    epic_id, path, bundle_path
+                ));
+            }
+            Action::ImportEpicBundle { path } => {
+                let bundle: EpicBundle = serde_json::from_str(&fs::read_to_string(&path)?)
+                    .map_err(|error| anyhow!("{} is not an epic bundle: {}", path, error))?;
+                let epic_id = self.db.import_epic(&bundle)?;
+                self.db.record_audit("Imported epic", Some(epic_id))?;
+                let count = bundle.epic.story_ids.len();
+                self.last_message = Some(format!(
+                    "Imported epic #{} with {} {} from {}",
+                    epic_id,
+                    count,
+                    if count == 1 { "story" } else { "stories" },
+                    path
+                ));
+            }
+            Action::ImportCsv => {
+                if let Some((path, skip_existing)) = (self.prompts.import_path)() {
+                    let summary = self.db.import_csv(File::open(&path)?, skip_existing)?;
+                    self.db.record_audit("Imported CSV", None)?;
+                    let mut message = format!(
+                        "Imported {} epics and {} stories from {}",
+                        summary.epics_created, summary.stories_created, path
+                    );
+                    if summary.stories_skipped > 0 {
+                        message +=
+                            &format!(" ({} existing stories skipped)", summary.stories_skipped);
+                    }
+                    for warning in summary.warnings {
+                        message += &format!("\n  warning: {}", warning);
+                    }
+                    self.last_message = Some(message);
+                }
+            }
+            Action::ReplaceInDescriptions => {
+                if let Some((find, replace)) = (self.prompts.replace_in_descriptions)() {
+                    let changed = self.db.replace_in_descriptions(&find, &replace)?;
+                    if changed == 0 {
+                        (self.prompts.notify)(&format!("No descriptions contain {:?}.", find));
+                    } else {
+                        self.db
+                            .record_audit("Replaced text in descriptions", None)?;
+                        self.last_message = Some(format!(
+                            "Replaced {:?} with {:?} in {} descriptions",
+                            find, replace, changed
+                        ));
+                    }
+                }
+            }
+            Action::Undo => {
+                if self.db.undo()? {
+                    self.prune_stale_pages()?;
+                    self.db.record_audit("Undid the last change", None)?;
+                    self.last_message = Some("Undid the last change".to_owned());
+                } else {
+                    (self.prompts.notify)("Nothing to undo.");
+                }
+            }
+            Action::Exit => {
+                if !self.config.borrow().confirm_exit || (self.prompts.confirm_exit)() {
+                    self.pages.clear();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `error_message` describes `error`, raised while handling input. A change that could not
+/// be saved is told apart from a board that could not be read.
+fn error_message(error: &anyhow::Error) -> String {
+    match error.downcast_ref::<DbError>() {
+        Some(DbError::Write { source }) => {
+            format!("Save failed, the change was not saved: {}", source)
+        }
+        Some(DbError::NotFound { .. } | DbError::Parse { .. } | DbError::Io { .. }) => {
+            format!("Error reading the board: {}", error)
+        }
+        _ => format!("Error processing request: {}", error),
+    }
+}
+
+/// `GOTO_MATCH_LIMIT` is the number of epics and stories offered when going to an item by
+/// name, numbered from 1.
+const GOTO_MATCH_LIMIT: usize = 9;
+
+/// `goto_matches` returns the epics and stories of `state` whose names match `query`, see
+/// `fuzzy_score`, along with a label naming each. Matches are ranked by score, then shorter
+/// names first, and only the best `GOTO_MATCH_LIMIT` are returned, as the actions opening
+/// them. Stories not in an epic are left out, since they cannot be opened.
+fn goto_matches(state: &DatabaseState, query: &str) -> Vec<(Action, String)> {
+    let epics = state.epics.iter().filter_map(|(id, epic)| {
+        let score = fuzzy_score(query, &epic.name)?;
+        let label = format!("Epic #{} {}", id, epic.name);
+        Some((score, epic.name.len(), *id, label))
+    });
+    let stories = state.stories.iter().filter_map(|(id, story)| {
+        let score = fuzzy_score(query, &story.name)?;
+        let epic_id = state.epic_id_for_story(*id)?;
+        let label = format!("Story #{} {} (epic #{})", id, story.name, epic_id);
+        Some((score, story.name.len(), *id, label))
+    });
+    epics
+        .chain(stories)
+        .sorted_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)))
+        .take(GOTO_MATCH_LIMIT)
+        .filter_map(|(_, _, id, label)| Some((state.jump_target(id)?, label)))
+        .collect()
+}
+
+/// `create_story_in_epic` creates a story in the epic `epic_id` from the `create_story`
+/// prompt. Adding a story to a Closed epic has to be confirmed first, and reopens the epic so
+/// that its status is derived from its stories again. Returns the id of the new story, or
+/// `None` if the user cancelled.
+fn create_story_in_epic(
+    db: &JiraDatabase,
+    prompts: &Prompt,
+    epic_id: u32,
+) -> anyhow::Result<Option<u32>> {
+    let story_ids = create_in_epic(db, prompts, epic_id, || match (prompts.create_story)() {
+        Some(story) => Ok(vec![db.create_story(&story, epic_id)?]),
+        None => Ok(vec![]),
+    })?;
+    Ok(story_ids.first().copied())
+}
+
+/// `create_stories_in_epic` prompts for the names of several stories and creates them in the
+/// epic `epic_id`, like `create_story_in_epic`. Returns the ids of the new stories, which is
+/// empty if the user cancelled.
+fn create_stories_in_epic(
+    db: &JiraDatabase,
+    prompts: &Prompt,
+    epic_id: u32,
+) -> anyhow::Result<Vec<u32>> {
+    create_in_epic(db, prompts, epic_id, || match (prompts.create_stories)() {
+        Some(names) => Ok(db.create_stories_bulk(epic_id, &names)?),
+        None => Ok(vec![]),
+    })
+}
+
+/// `create_in_epic` runs `create` to add stories to the epic `epic_id`. The user is asked
+/// to confirm adding stories to a Closed epic, which is reopened once they are created.
+/// Returns the ids of the new stories.
+fn create_in_epic(
+    db: &JiraDatabase,
+    prompts: &Prompt,
+    epic_id: u32,
+    create: impl FnOnce() -> anyhow::Result<Vec<u32>>,
+) -> anyhow::Result<Vec<u32>> {
+    let state = db.read()?;
+    let epic = state
+        .epics
+        .get(&epic_id)
+        .ok_or(DbError::EpicNotFound(epic_id))?;
+    let closed = epic.status == Status::Closed;
+    if closed && !(prompts.confirm_closed_epic)(epic_id) {
+        return Ok(vec![]);
+    }
+    let story_ids = create()?;
+    if closed && !story_ids.is_empty() {
+        db.set_epic_status_override(epic_id, false)?;
+        db.update_epic_status(epic_id, Status::Open)?;
+    }
+    Ok(story_ids)
+}
+
+/// `assignee_message` describes the assignee of the `kind` item `id` being changed to
+/// `assignee`.
+fn assignee_message(kind: &str, id: u32, assignee: &Option<String>) -> String {
+    match assignee {
+        Some(assignee) => format!("{} #{} assigned to {}", kind, id, assignee),
+        None => format!("{} #{} unassigned", kind, id),
+    }
+}
+
+/// `derive_epic_status` returns the status an epic should have given its `stories`, see
+/// `derive_status`.
+fn derive_epic_status(stories: &[&Story]) -> Status {
+    let statuses: Vec<_> = stories.iter().map(|story| story.status.clone()).collect();
+    derive_status(&statuses)
+}
+
+/// `check_epic_status` checks that the `stories` of an epic support setting the epic's
+/// status to `status`. An epic can only be Closed once all its stories are Closed, and only
+/// be Resolved once all its stories are Resolved or Closed.
+///
+/// `Err` explains which stories are holding the epic back.
+fn check_epic_status(status: &Status, stories: &[&Story]) -> anyhow::Result<()> {
+    let pending = match status {
+        Status::Closed => stories
+            .iter()
+            .filter(|story| !matches!(story.status, Status::Closed))
+            .count(),
+        Status::Resolved => stories
+            .iter()
+            .filter(|story| !matches!(story.status, Status::Resolved | Status::Closed))
+            .count(),
+        _ => 0,
+    };
+    if pending > 0 {
+        bail!(
+            "{} of {} stories in this epic are not {} yet",
+            pending,
+            stories.len(),
+            status
+        );
+    }
+    Ok(())
+}
+
+impl NavigationManager for Navigator {
+    fn current_page(&self) -> Option<&dyn Page> {
+        self.pages.last().map(|page| page.as_ref())
+    }
+
+    fn dispatch_action(&mut self, action: Action) -> anyhow::Result<()> {
+        self.last_message = None;
+        // The action may switch the database, so the group is ended on the one it began on.
+        let db = self.db.clone();
+        let saves = db.save_count();
+        db.begin_undo_group();
+        let res = self.handle_action(action);
+        db.end_undo_group();
+        if res.is_err() {
+            self.last_message = None;
+        }
+        self.saved = res.is_ok() && !self.dry_run && db.save_count() > saves;
+        if self.dry_run {
+            let changes = db.discard_changes()?;
+            if res.is_ok() && !changes.is_empty() {
+                self.last_message = Some(format!(
+                    "Dry run, nothing was saved:\n  {}",
+                    changes.join("\n  ")
+                ));
+            }
+        }
+        res
+    }
+
+    fn io(&self) -> Rc<dyn Io> {
+        self.io.clone()
+    }
+
+    fn handle_draw_error(&mut self, error: &anyhow::Error) -> bool {
+        match error.downcast_ref::<MissingEntity>() {
+            Some(missing) => {
+                self.pages.pop();
+                self.last_message = Some(format!("Went back because {}", missing));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+pub mod test_utils {
+    use std::cell::RefCell;
+
     use crate::db::test_utils::MockDatabase;
 
     use super::*;
@@ -238,15 +1677,14 @@ pub mod test_utils {
         pub prompts: Prompt,
         pub db: Rc<JiraDatabase>,
         pub state: Rc<MockDatabase>,
+        pub io: Rc<dyn Io>,
     }
 
     impl MockNavigator {
         /// `new` creates a new instance of `MockNavigator`. Pass an instance of `JiraDatabase`
         /// instantiated like below:
         /// ```
-        /// let db = Rc::new(JiraDatabase {
-        ///     db: Box::new(MockDatabase::new()),
-        /// });
+        /// let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
         /// ```
         /// NOTE: If you do multiple tests in a single `test` function, you will need to shadow the `db`
         /// variable to create a new instance of `Rc<JiraDatabase>`,
@@ -267,12 +1705,13 @@ pub mod test_utils {
         // report unused code.
         pub fn new(db: Rc<JiraDatabase>) -> Self {
             Self {
-                pages: vec![Box::new(HomePage { db: db.clone() })],
+                pages: vec![Box::new(HomePage::new(db.clone()))],
                 prompts: Prompt::new(),
                 db: db.clone(),
                 state: Rc::new(MockDatabase {
                     last_written_state: RefCell::new(db.read().unwrap()),
                 }),
+                io: Rc::new(StdIo),
             }
         }
 
@@ -290,13 +1729,22 @@ pub mod test_utils {
             self.pages.last().map(|page| page.as_ref())
         }
 
+        fn io(&self) -> Rc<dyn Io> {
+            self.io.clone()
+        }
+
+        fn handle_draw_error(&mut self, error: &anyhow::Error) -> bool {
+            if error.downcast_ref::<MissingEntity>().is_some() {
+                self.pages.pop();
+                return true;
+            }
+            false
+        }
+
         fn dispatch_action(&mut self, action: Action) -> anyhow::Result<()> {
             match action {
                 Action::NavigateToEpicDetail { epic_id } => {
-                    let page = Box::new(EpicDetail {
-                        epic_id,
-                        db: self.db.clone(),
-                    });
+                    let page = Box::new(EpicDetail::new(epic_id, self.db.clone()));
                     self.pages.push(page);
                 }
                 Action::NavigateToStoryDetail { story_id, epic_id } => {
@@ -315,9 +1763,107 @@ pub mod test_utils {
                     });
                     self.pages.push(page);
                 }
+                Action::NavigateToDescription { id } => {
+                    let page = Box::new(DescriptionPage {
+                        id,
+                        db: self.db.clone(),
+                    });
+                    self.pages.push(page);
+                }
+                Action::NavigateToHistory => {
+                    let page = Box::new(HistoryPage {
+                        db: self.db.clone(),
+                    });
+                    self.pages.push(page);
+                }
+                Action::NavigateToSprints => {
+                    let page = Box::new(SprintPage {
+                        db: self.db.clone(),
+                    });
+                    self.pages.push(page);
+                }
+                Action::NavigateToSprintDetail { sprint_id } => {
+                    let page = Box::new(SprintDetail {
+                        sprint_id,
+                        db: self.db.clone(),
+                    });
+                    self.pages.push(page);
+                }
+                Action::NavigateToRecent => {
+                    let page = Box::new(RecentPage {
+                        items: vec![],
+                        db: self.db.clone(),
+                    });
+                    self.pages.push(page);
+                }
+                Action::NavigateToModified => {
+                    let page = Box::new(ModifiedPage {
+                        db: self.db.clone(),
+                    });
+                    self.pages.push(page);
+                }
+                Action::JumpToItem => {
+                    let target = (self.prompts.goto_query)()
+                        .and_then(|query| query.parse().ok())
+                        .and_then(|id| self.db.read().ok()?.jump_target(id));
+                    if let Some(Action::NavigateToStoryDetail { epic_id, .. }) = target {
+                        self.dispatch_action(Action::NavigateToEpicDetail { epic_id })?;
+                    }
+                    if let Some(action) = target {
+                        self.dispatch_action(action)?;
+                    }
+                }
+                Action::OpenPalette { query } => {
+                    let mut entries = palette::matching(&query);
+                    let labels: Vec<String> =
+                        entries.iter().map(|(label, _)| label.to_string()).collect();
+                    let chosen = (self.prompts.select_palette_entry)(&labels)
+                        .map(|index| entries.swap_remove(index).1);
+                    if let Some(PaletteTarget::Action(action)) = chosen {
+                        self.dispatch_action(action)?;
+                    }
+                }
+                Action::NavigateToWorkspaces => {
+                    let page = Box::new(WorkspacePicker {
+                        workspaces: Workspaces::default(),
+                    });
+                    self.pages.push(page);
+                }
+                Action::NavigateToSettings => {
+                    let page = Box::new(SettingsPage {
+                        config: Rc::new(RefCell::new(Config::default())),
+                    });
+                    self.pages.push(page);
+                }
+                Action::NavigateToTrash => {
+                    let page = Box::new(TrashPage {
+                        db: self.db.clone(),
+                    });
+                    self.pages.push(page);
+                }
+                Action::RestoreFromTrash { id } => {
+                    self.db.restore_from_trash(id)?;
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
+                Action::PurgeTrash => {
+                    let state = self.db.read()?;
+                    let count = state.trash_epics.len() + state.trash_stories.len();
+                    if (self.prompts.confirm_purge_trash)(count) {
+                        self.db.purge_trash()?;
+                        self.state = Rc::new(MockDatabase {
+                            last_written_state: RefCell::new(self.db.read()?),
+                        });
+                    }
+                }
+                Action::SwitchWorkspace { .. }
+                | Action::UpdateSetting { .. }
+                | Action::CreateEpicFromTemplate => {}
                 Action::NavigateToPreviousPage => {
                     self.pages.pop();
                 }
+                Action::NavigateHome => self.pages.truncate(1),
                 Action::CreateEpic => {
                     if let Some(epic) = (self.prompts.create_epic)() {
                         self.db.create_epic(&epic)?;
@@ -327,8 +1873,25 @@ pub mod test_utils {
                     }
                 }
                 Action::CreateStory { epic_id } => {
-                    if let Some(story) = (self.prompts.create_story)() {
-                        self.db.create_story(&story, epic_id)?;
+                    if create_story_in_epic(&self.db, &self.prompts, epic_id)?.is_some() {
+                        self.state = Rc::new(MockDatabase {
+                            last_written_state: RefCell::new(self.db.read()?),
+                        });
+                    }
+                }
+                Action::CreateStoryNamed { epic_id, name } => {
+                    let story = Story::new(&name, "");
+                    let story_ids = create_in_epic(&self.db, &self.prompts, epic_id, || {
+                        Ok(vec![self.db.create_story(&story, epic_id)?])
+                    })?;
+                    if !story_ids.is_empty() {
+                        self.state = Rc::new(MockDatabase {
+                            last_written_state: RefCell::new(self.db.read()?),
+                        });
+                    }
+                }
+                Action::CreateStories { epic_id } => {
+                    if !create_stories_in_epic(&self.db, &self.prompts, epic_id)?.is_empty() {
                         self.state = Rc::new(MockDatabase {
                             last_written_state: RefCell::new(self.db.read()?),
                         });
@@ -342,38 +1905,185 @@ pub mod test_utils {
                         });
                     }
                 }
+                Action::CreateSprint => {
+                    if let Some(sprint) = (self.prompts.create_sprint)() {
+                        self.db.create_sprint(&sprint)?;
+                        self.state = Rc::new(MockDatabase {
+                            last_written_state: RefCell::new(self.db.read()?),
+                        });
+                    }
+                }
+                Action::UpdateSprintStories { sprint_id } => {
+                    match (self.prompts.update_sprint_stories)() {
+                        Some(SprintChange::Add(story_id)) => {
+                            self.db.add_story_to_sprint(sprint_id, story_id)?
+                        }
+                        Some(SprintChange::Remove(story_id)) => {
+                            self.db.remove_story_from_sprint(sprint_id, story_id)?
+                        }
+                        None => {}
+                    }
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
+                Action::DuplicateEpic { epic_id } => {
+                    self.db.duplicate_epic(epic_id)?;
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
                 Action::UpdateEpicName { epic_id } => {
-                    let name = (self.prompts.update_name)();
-                    self.db.update_epic_name(epic_id, &name)?;
+                    let current = self
+                        .db
+                        .read()?
+                        .epics
+                        .get(&epic_id)
+                        .map(|item| item.name.clone())
+                        .unwrap_or_default();
+                    let Some(name) = (self.prompts.update_name)(&current) else {
+                        return Ok(());
+                    };
+                    if name != current {
+                        self.db.update_epic_name(epic_id, &name)?;
+                    }
                     self.state = Rc::new(MockDatabase {
                         last_written_state: RefCell::new(self.db.read()?),
                     });
                 }
                 Action::UpdateEpicDescription { epic_id } => {
-                    let description = (self.prompts.update_description)();
-                    self.db.update_epic_description(epic_id, &description)?;
+                    let current = self
+                        .db
+                        .read()?
+                        .epics
+                        .get(&epic_id)
+                        .map(|item| item.description.clone())
+                        .unwrap_or_default();
+                    let Some(description) = (self.prompts.update_description)(&current) else {
+                        return Ok(());
+                    };
+                    if description != current {
+                        self.db.update_epic_description(epic_id, &description)?;
+                    }
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
+                Action::SetEpicStatus { epic_id, status } => {
+                    self.db.update_epic_status(epic_id, status)?;
+                    self.db.set_epic_status_override(epic_id, true)?;
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
+                Action::SetStoryStatus { story_id, status } => {
+                    self.db.update_story_status(story_id, status)?;
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
+                Action::MoveStory {
+                    epic_id,
+                    story_id,
+                    direction,
+                } => {
+                    self.db.reorder_story(epic_id, story_id, direction)?;
                     self.state = Rc::new(MockDatabase {
                         last_written_state: RefCell::new(self.db.read()?),
                     });
                 }
                 Action::UpdateEpicStatus { epic_id } => {
+                    match (self.prompts.update_epic_status)() {
+                        Some(EpicStatusChoice::Manual(status)) => {
+                            self.db.update_epic_status(epic_id, status)?;
+                            self.db.set_epic_status_override(epic_id, true)?;
+                        }
+                        Some(EpicStatusChoice::Auto) => {
+                            self.db.set_epic_status_override(epic_id, false)?;
+                            self.db.update_derived_epic_status(epic_id)?;
+                        }
+                        None => return Ok(()),
+                    }
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
+                Action::UpdateEpicAssignee { epic_id } => {
+                    let assignee = (self.prompts.update_assignee)();
+                    self.db.update_epic_assignee(epic_id, assignee.as_deref())?;
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
+                Action::ToggleEpicArchived { epic_id } => {
+                    let archived = self
+                        .db
+                        .read()?
+                        .epics
+                        .get(&epic_id)
+                        .ok_or(DbError::EpicNotFound(epic_id))?
+                        .archived;
+                    self.db.set_epic_archived(epic_id, !archived)?;
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
+                Action::UpdateEpicStoriesStatus { epic_id } => {
                     if let Some(status) = (self.prompts.update_status)() {
-                        self.db.update_epic_status(epic_id, status)?;
+                        self.db.bulk_update_story_status(epic_id, status)?;
+                        self.state = Rc::new(MockDatabase {
+                            last_written_state: RefCell::new(self.db.read()?),
+                        });
+                    }
+                }
+                Action::CloseEpicStories { epic_id } => {
+                    let count = self
+                        .db
+                        .read()?
+                        .epics
+                        .get(&epic_id)
+                        .ok_or(DbError::EpicNotFound(epic_id))?
+                        .story_ids
+                        .len();
+                    if (self.prompts.confirm_close_stories)(count) {
+                        self.db.bulk_update_story_status(epic_id, Status::Closed)?;
                         self.state = Rc::new(MockDatabase {
                             last_written_state: RefCell::new(self.db.read()?),
                         });
                     }
                 }
                 Action::UpdateStoryName { story_id } => {
-                    let name = (self.prompts.update_name)();
-                    self.db.update_story_name(story_id, &name)?;
+                    let current = self
+                        .db
+                        .read()?
+                        .stories
+                        .get(&story_id)
+                        .map(|item| item.name.clone())
+                        .unwrap_or_default();
+                    let Some(name) = (self.prompts.update_name)(&current) else {
+                        return Ok(());
+                    };
+                    if name != current {
+                        self.db.update_story_name(story_id, &name)?;
+                    }
                     self.state = Rc::new(MockDatabase {
                         last_written_state: RefCell::new(self.db.read()?),
                     });
                 }
                 Action::UpdateStoryDescription { story_id } => {
-                    let description = (self.prompts.update_description)();
-                    self.db.update_story_description(story_id, &description)?;
+                    let current = self
+                        .db
+                        .read()?
+                        .stories
+                        .get(&story_id)
+                        .map(|item| item.description.clone())
+                        .unwrap_or_default();
+                    let Some(description) = (self.prompts.update_description)(&current) else {
+                        return Ok(());
+                    };
+                    if description != current {
+                        self.db.update_story_description(story_id, &description)?;
+                    }
                     self.state = Rc::new(MockDatabase {
                         last_written_state: RefCell::new(self.db.read()?),
                     });
@@ -386,16 +2096,112 @@ pub mod test_utils {
                         });
                     }
                 }
+                Action::UpdateStoryAssignee { story_id } | Action::AssignStoryToMe { story_id } => {
+                    let assignee = (self.prompts.update_assignee)();
+                    self.db
+                        .update_story_assignee(story_id, assignee.as_deref())?;
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
+                Action::UpdateStoryPoints { story_id } => {
+                    if let Some(points) = (self.prompts.update_points)() {
+                        self.db.update_story_points(story_id, points)?;
+                        self.state = Rc::new(MockDatabase {
+                            last_written_state: RefCell::new(self.db.read()?),
+                        });
+                    }
+                }
+                Action::UpdateStoryTags { story_id } => {
+                    match (self.prompts.update_tags)() {
+                        Some(TagChange::Add(tag)) => self.db.add_story_tag(story_id, &tag)?,
+                        Some(TagChange::Remove(tag)) => self.db.remove_story_tag(story_id, &tag)?,
+                        None => {}
+                    }
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
+                Action::UpdateStoryBlockers { story_id } => {
+                    match (self.prompts.update_blockers)() {
+                        Some(BlockerChange::Add(blocker_id)) => {
+                            self.db.add_story_dependency(story_id, blocker_id)?
+                        }
+                        Some(BlockerChange::Remove(blocker_id)) => {
+                            self.db.remove_story_dependency(story_id, blocker_id)?
+                        }
+                        None => {}
+                    }
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
+                Action::LogWork { story_id } => {
+                    if let Some(entry) = (self.prompts.log_work)() {
+                        self.db.log_work(story_id, &entry)?;
+                        self.state = Rc::new(MockDatabase {
+                            last_written_state: RefCell::new(self.db.read()?),
+                        });
+                    }
+                }
+                Action::AddComment { story_id } => {
+                    if let Some(comment) = (self.prompts.add_comment)() {
+                        self.db.add_comment(story_id, &comment)?;
+                        self.state = Rc::new(MockDatabase {
+                            last_written_state: RefCell::new(self.db.read()?),
+                        });
+                    }
+                }
+                Action::AddChecklistItem { id, text } => {
+                    self.db.add_checklist_item(id, &text)?;
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
+                Action::ToggleChecklistItem { id, index } => {
+                    self.db.toggle_checklist_item(id, index)?;
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
+                Action::RemoveChecklistItem { id, index } => {
+                    self.db.remove_checklist_item(id, index)?;
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
                 Action::UpdateTaskName { task_id } => {
-                    let name = (self.prompts.update_name)();
-                    self.db.update_task_name(task_id, &name)?;
+                    let current = self
+                        .db
+                        .read()?
+                        .tasks
+                        .get(&task_id)
+                        .map(|item| item.name.clone())
+                        .unwrap_or_default();
+                    let Some(name) = (self.prompts.update_name)(&current) else {
+                        return Ok(());
+                    };
+                    if name != current {
+                        self.db.update_task_name(task_id, &name)?;
+                    }
                     self.state = Rc::new(MockDatabase {
                         last_written_state: RefCell::new(self.db.read()?),
                     });
                 }
                 Action::UpdateTaskDescription { task_id } => {
-                    let description = (self.prompts.update_description)();
-                    self.db.update_task_description(task_id, &description)?;
+                    let current = self
+                        .db
+                        .read()?
+                        .tasks
+                        .get(&task_id)
+                        .map(|item| item.description.clone())
+                        .unwrap_or_default();
+                    let Some(description) = (self.prompts.update_description)(&current) else {
+                        return Ok(());
+                    };
+                    if description != current {
+                        self.db.update_task_description(task_id, &description)?;
+                    }
                     self.state = Rc::new(MockDatabase {
                         last_written_state: RefCell::new(self.db.read()?),
                     });
@@ -426,36 +2232,126 @@ pub mod test_utils {
                         self.pages.pop();
                     }
                 }
-                Action::DeleteTask { task_id, story_id } => {
-                    if (self.prompts.delete_task)() {
-                        self.db.delete_task(task_id, story_id)?;
+                Action::DeleteStories { epic_id } => {
+                    let Some(entries) = (self.prompts.select_story_ids)() else {
+                        return Ok(());
+                    };
+                    let story_ids: Vec<u32> = entries
+                        .iter()
+                        .filter_map(|entry| entry.parse().ok())
+                        .collect();
+                    if (self.prompts.confirm_delete_stories)(story_ids.len()) {
+                        self.db.delete_stories(&story_ids, epic_id)?;
                         self.state = Rc::new(MockDatabase {
                             last_written_state: RefCell::new(self.db.read()?),
                         });
-                        self.pages.pop();
                     }
                 }
-                Action::Exit => self.pages.clear(),
-            }
-            Ok(())
-        }
+                Action::DeleteClosedStories { epic_id } => {
+                    let state = self.db.read()?;
+                    let story_ids: Vec<u32> = state
+                        .epics
+                        .get(&epic_id)
+                        .ok_or(DbError::EpicNotFound(epic_id))?
+                        .story_ids
+                        .iter()
+                        .filter(|id| {
+                            state
+                                .stories
+                                .get(id)
+                                .is_some_and(|story| story.status == Status::Closed)
+                        })
+                        .copied()
+                        .collect();
+                    if (self.prompts.confirm_delete_stories)(story_ids.len()) {
+                        self.db.delete_stories(&story_ids, epic_id)?;
+                        self.state = Rc::new(MockDatabase {
+                            last_written_state: RefCell::new(self.db.read()?),
+                        });
+                    }
+                }
+                Action::DeleteTask { task_id, story_id } => {
+                    if (self.prompts.delete_task)() {
+                        self.db.delete_task(task_id, story_id)?;
+                        self.state = Rc::new(MockDatabase {
+                            last_written_state: RefCell::new(self.db.read()?),
+                        });
+                        self.pages.pop();
+                    }
+                }
+                Action::ConvertStoryToTask { story_id, .. } => {
+                    if let Some(target_story_id) = (self.prompts.convert_target)("story") {
+                        self.db.story_to_task(story_id, target_story_id)?;
+                        self.state = Rc::new(MockDatabase {
+                            last_written_state: RefCell::new(self.db.read()?),
+                        });
+                        self.pages.pop();
+                    }
+                }
+                Action::ConvertTaskToStory { task_id, .. } => {
+                    if let Some(target_epic_id) = (self.prompts.convert_target)("epic") {
+                        self.db.task_to_story(task_id, target_epic_id)?;
+                        self.state = Rc::new(MockDatabase {
+                            last_written_state: RefCell::new(self.db.read()?),
+                        });
+                        self.pages.pop();
+                    }
+                }
+                Action::ExportCsv
+                | Action::ExportEpic { .. }
+                | Action::ExportHtml
+                | Action::ExportStoriesJsonl
+                | Action::ImportCsv
+                | Action::ImportEpicBundle { .. } => {}
+                Action::ReplaceInDescriptions => {
+                    if let Some((find, replace)) = (self.prompts.replace_in_descriptions)() {
+                        self.db.replace_in_descriptions(&find, &replace)?;
+                        self.state = Rc::new(MockDatabase {
+                            last_written_state: RefCell::new(self.db.read()?),
+                        });
+                    }
+                }
+                Action::Undo => {
+                    self.db.undo()?;
+                    self.state = Rc::new(MockDatabase {
+                        last_written_state: RefCell::new(self.db.read()?),
+                    });
+                }
+                Action::Exit => {
+                    if (self.prompts.confirm_exit)() {
+                        self.pages.clear();
+                    }
+                }
+            }
+            Ok(())
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        io::Write,
+        time::Duration,
+    };
+
+    use chrono::NaiveDate;
+
     use crate::{
-        db::test_utils::MockDatabase,
-        models::{Epic, Status, Story},
+        db::{test_utils::MockDatabase, validate::MAX_NAME_LENGTH, Database, RECENT_LIMIT},
+        models::{DatabaseState, Epic, Sprint, Status, Story, StoryStub, Task, Template},
+        ui::{
+            input::{LineInput, WatchInput},
+            io::ScriptedIo,
+        },
     };
 
-    use super::*;
+    use super::{test_utils::MockNavigator, *};
 
     #[test]
     fn should_start_on_home_page() {
-        let db = Rc::new(JiraDatabase {
-            db: Box::new(MockDatabase::new()),
-        });
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
         let nav = Navigator::new(db.clone());
         assert!(nav.current_page().is_some());
         assert!(nav
@@ -468,10 +2364,8 @@ mod tests {
 
     #[test]
     fn should_navigate_to_epic_detail() {
-        let db = Rc::new(JiraDatabase {
-            db: Box::new(MockDatabase::new()),
-        });
-        let epic_id = db.create_epic(&Epic::new("", "")).unwrap();
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
         let mut nav = Navigator::new(db.clone());
 
         let res = nav.dispatch_action(Action::NavigateToEpicDetail { epic_id });
@@ -483,12 +2377,254 @@ mod tests {
     }
 
     #[test]
-    fn should_navigate_to_story_detail() {
-        let db = Rc::new(JiraDatabase {
-            db: Box::new(MockDatabase::new()),
+    fn handle_input_should_open_selected_epic() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let second_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let mut nav = Navigator::new(db.clone());
+
+        nav.handle_input(Input::Open).unwrap();
+        assert_eq!(nav.page_count(), 1usize);
+
+        for input in [
+            Input::Down,
+            Input::Down,
+            Input::Down,
+            Input::Up,
+            Input::Down,
+        ] {
+            nav.handle_input(input).unwrap();
+        }
+        nav.handle_input(Input::Open).unwrap();
+        assert_eq!(nav.page_count(), 2usize);
+
+        let current_page = nav.current_page().unwrap();
+        let epic_detail = current_page.as_any().downcast_ref::<EpicDetail>().unwrap();
+        assert_eq!(epic_detail.epic_id, second_id);
+    }
+
+    #[test]
+    fn handle_input_should_run_typed_command() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let mut nav = Navigator::new(db.clone());
+
+        nav.handle_input(Input::Command(epic_id.to_string()))
+            .unwrap();
+        assert_eq!(nav.page_count(), 2usize);
+        nav.handle_input(Input::Command("b".to_owned())).unwrap();
+        assert_eq!(nav.page_count(), 1usize);
+    }
+
+    #[test]
+    fn handle_input_should_undo_from_any_page() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let mut nav = Navigator::new(db.clone());
+        nav.dispatch_action(Action::NavigateToSprints).unwrap();
+
+        nav.handle_input(Input::Command(" Z ".to_owned())).unwrap();
+        assert!(db.read().unwrap().epics.is_empty());
+        assert_eq!(nav.take_message(), Some("Undid the last change".to_owned()));
+        assert_eq!(nav.page_count(), 2usize);
+    }
+
+    #[test]
+    fn handle_input_should_jump_to_story_from_any_page() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("Story 1", ""), epic_id)
+            .unwrap();
+        let mut prompts = Prompt::new();
+        prompts.goto_query = Box::new(move || Some(story_id.to_string()));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+        nav.dispatch_action(Action::NavigateToHistory).unwrap();
+
+        nav.handle_input(Input::Command(" G ".to_owned())).unwrap();
+        assert_eq!(
+            nav.open_pages(),
+            vec![
+                RecentItem::Epic(epic_id),
+                RecentItem::Story { story_id, epic_id }
+            ]
+        );
+        assert_eq!(nav.page_count(), 4usize);
+
+        let mut prompts = Prompt::new();
+        prompts.goto_query = Box::new(|| Some("999".to_owned()));
+        nav.set_prompts(prompts);
+        nav.handle_input(Input::Command("g".to_owned())).unwrap();
+        assert_eq!(nav.page_count(), 4usize);
+        assert_eq!(nav.take_message(), Some("No epic or story #999".to_owned()));
+    }
+
+    #[test]
+    fn handle_input_should_run_chosen_palette_entry_from_any_page() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let offered = Rc::new(RefCell::new(vec![]));
+        let mut prompts = Prompt::new();
+        prompts.select_palette_entry = {
+            let offered = offered.clone();
+            Box::new(move |labels| {
+                *offered.borrow_mut() = labels.to_vec();
+                Some(0)
+            })
+        };
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+
+        nav.handle_input(Input::Command(": trash".to_owned()))
+            .unwrap();
+        assert_eq!(offered.borrow()[0], "Trash");
+        assert!(nav
+            .current_page()
+            .unwrap()
+            .as_any()
+            .downcast_ref::<TrashPage>()
+            .is_some());
+
+        nav.handle_input(Input::Command(":zzz".to_owned())).unwrap();
+        assert_eq!(
+            nav.take_message(),
+            Some("No action matches \"zzz\"".to_owned())
+        );
+
+        let mut prompts = Prompt::new();
+        prompts.select_palette_entry = Box::new(|labels| {
+            labels
+                .iter()
+                .position(|label| label == "Show or hide archived epics")
         });
-        let epic_id = db.create_epic(&Epic::new("", "")).unwrap();
-        let story_id = db.create_story(&Story::new("", ""), epic_id).unwrap();
+        nav.set_prompts(prompts);
+        nav.handle_input(Input::Command(":".to_owned())).unwrap();
+        assert_eq!(nav.page_count(), 1);
+        let home = nav.current_page().unwrap().as_any();
+        assert!(home.downcast_ref::<HomePage>().unwrap().show_archived.get());
+    }
+
+    #[test]
+    fn handle_input_should_go_to_chosen_match_by_name() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Release 2", "")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("Release notes", ""), epic_id)
+            .unwrap();
+        db.create_story(&Story::new("Unrelated", ""), epic_id)
+            .unwrap();
+        db.create_story(&Story::new("Docs", ""), epic_id).unwrap();
+        let offered = Rc::new(RefCell::new(vec![]));
+        let mut prompts = Prompt::new();
+        prompts.goto_query = Box::new(|| Some("REL".to_owned()));
+        prompts.select_goto_match = {
+            let offered = offered.clone();
+            Box::new(move |matches| {
+                *offered.borrow_mut() = matches.to_vec();
+                Some(1)
+            })
+        };
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.handle_input(Input::Command("g".to_owned())).unwrap();
+        assert_eq!(
+            *offered.borrow(),
+            vec![
+                format!("Epic #{} Release 2", epic_id),
+                format!("Story #{} Release notes (epic #{})", story_id, epic_id),
+                format!("Story #{} Unrelated (epic #{})", story_id + 1, epic_id),
+            ]
+        );
+        assert_eq!(
+            nav.open_pages(),
+            vec![
+                RecentItem::Epic(epic_id),
+                RecentItem::Story { story_id, epic_id }
+            ]
+        );
+
+        let mut prompts = Prompt::new();
+        prompts.goto_query = Box::new(|| Some("zzz".to_owned()));
+        nav.set_prompts(prompts);
+        nav.handle_input(Input::Command("g".to_owned())).unwrap();
+        assert_eq!(
+            nav.take_message(),
+            Some("No epic or story name matches \"zzz\"".to_owned())
+        );
+    }
+
+    #[test]
+    fn open_pages_should_restore_epic_and_story_pages() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("Story 1", ""), epic_id)
+            .unwrap();
+        let mut nav = Navigator::new(db.clone());
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+        nav.dispatch_action(Action::NavigateToHistory).unwrap();
+        nav.dispatch_action(Action::NavigateToStoryDetail { story_id, epic_id })
+            .unwrap();
+        let open_pages = nav.open_pages();
+        assert_eq!(
+            open_pages,
+            vec![
+                RecentItem::Epic(epic_id),
+                RecentItem::Story { story_id, epic_id }
+            ]
+        );
+
+        let mut restored = Navigator::new(db.clone());
+        restored.restore_pages(&open_pages).unwrap();
+        assert_eq!(restored.page_count(), 3usize);
+        assert_eq!(restored.open_pages(), open_pages);
+        let current_page = restored.current_page().unwrap();
+        assert!(current_page
+            .as_any()
+            .downcast_ref::<StoryDetail>()
+            .is_some());
+    }
+
+    #[test]
+    fn restore_pages_should_stop_at_stale_items() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("Story 1", ""), epic_id)
+            .unwrap();
+        let other_epic_id = db.create_epic(&Epic::new("Epic 2", "")).unwrap();
+        let mut nav = Navigator::new(db.clone());
+
+        nav.restore_pages(&[
+            RecentItem::Epic(other_epic_id),
+            RecentItem::Story {
+                story_id,
+                epic_id: other_epic_id,
+            },
+            RecentItem::Epic(epic_id),
+        ])
+        .unwrap();
+        assert_eq!(nav.open_pages(), vec![RecentItem::Epic(other_epic_id)]);
+
+        nav.restore_pages(&[RecentItem::Epic(999), RecentItem::Epic(epic_id)])
+            .unwrap();
+        assert_eq!(nav.page_count(), 1usize);
+        let current_page = nav.current_page().unwrap();
+        assert!(current_page.as_any().downcast_ref::<HomePage>().is_some());
+    }
+
+    #[test]
+    fn should_navigate_to_story_detail() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("Story 1", ""), epic_id)
+            .unwrap();
         let mut nav = Navigator::new(db.clone());
 
         let res = nav.dispatch_action(Action::NavigateToStoryDetail { story_id, epic_id });
@@ -504,12 +2640,10 @@ mod tests {
 
     #[test]
     fn should_navigate_to_previous_page() {
-        let db = Rc::new(JiraDatabase {
-            db: Box::new(MockDatabase::new()),
-        });
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
         let mut nav = Navigator::new(db.clone());
 
-        let epic_id = db.create_epic(&Epic::new("", "")).unwrap();
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
         nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
             .unwrap();
 
@@ -523,9 +2657,7 @@ mod tests {
 
     #[test]
     fn should_create_epic() {
-        let db = Rc::new(JiraDatabase {
-            db: Box::new(MockDatabase::new()),
-        });
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
         let mut prompts = Prompt::new();
         prompts.create_epic = Box::new(|| Some(Epic::new("name", "description")));
         let mut nav = Navigator::new(db.clone());
@@ -544,9 +2676,7 @@ mod tests {
 
     #[test]
     fn should_no_op_on_create_epic_cancelled() {
-        let db = Rc::new(JiraDatabase {
-            db: Box::new(MockDatabase::new()),
-        });
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
         let mut prompts = Prompt::new();
         prompts.create_epic = Box::new(|| None);
         let mut nav = Navigator::new(db.clone());
@@ -559,11 +2689,25 @@ mod tests {
         assert!(epics.is_empty());
     }
 
+    #[test]
+    fn should_replace_in_descriptions() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "old term")).unwrap();
+        let mut prompts = Prompt::new();
+        prompts.replace_in_descriptions = Box::new(|| Some(("old".to_owned(), "new".to_owned())));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::ReplaceInDescriptions).unwrap();
+        assert_eq!(db.read().unwrap().epics[&epic_id].description, "new term");
+
+        nav.dispatch_action(Action::Undo).unwrap();
+        assert_eq!(db.read().unwrap().epics[&epic_id].description, "old term");
+    }
+
     #[test]
     fn should_create_story() {
-        let db = Rc::new(JiraDatabase {
-            db: Box::new(MockDatabase::new()),
-        });
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
         let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
         let mut prompts = Prompt::new();
         prompts.create_story = Box::new(|| Some(Story::new("name", "description")));
@@ -583,9 +2727,7 @@ mod tests {
 
     #[test]
     fn should_no_op_on_create_story_cancelled() {
-        let db = Rc::new(JiraDatabase {
-            db: Box::new(MockDatabase::new()),
-        });
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
         let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
         let mut prompts = Prompt::new();
         prompts.create_story = Box::new(|| None);
@@ -600,102 +2742,1808 @@ mod tests {
     }
 
     #[test]
-    fn should_update_epic_status() {
-        let db = Rc::new(JiraDatabase {
-            db: Box::new(MockDatabase::new()),
-        });
+    fn should_reopen_closed_epic_on_create_story() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
         let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("name", "description"), epic_id)
+            .unwrap();
         let mut prompts = Prompt::new();
-        prompts.update_status = Box::new(|| Some(Status::InProgress));
+        prompts.update_status = Box::new(|| Some(Status::Closed));
+        prompts.create_story = Box::new(|| Some(Story::new("name", "description")));
+        prompts.confirm_closed_epic = Box::new(|_| true);
         let mut nav = Navigator::new(db.clone());
         nav.set_prompts(prompts);
 
-        let res = nav.dispatch_action(Action::UpdateEpicStatus { epic_id });
+        nav.dispatch_action(Action::UpdateStoryStatus { story_id })
+            .unwrap();
+        assert_eq!(db.read().unwrap().epics[&epic_id].status, Status::Closed);
+
+        let res = nav.dispatch_action(Action::CreateStory { epic_id });
         assert!(res.is_ok());
+        assert_eq!(
+            db.read().unwrap().epics[&epic_id].status,
+            Status::InProgress
+        );
+    }
+
+    #[test]
+    fn should_create_stories_in_bulk() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let mut prompts = Prompt::new();
+        prompts.create_stories = Box::new(|| Some(vec!["one".to_owned(), "two".to_owned()]));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
 
+        let res = nav.dispatch_action(Action::CreateStories { epic_id });
+        assert!(res.is_ok());
+        assert_eq!(nav.last_message.as_deref(), Some("Created 2 stories"));
         let state = db.read().unwrap();
-        let epic = state.epics.get(&epic_id);
-        assert!(epic.is_some());
-        assert_eq!(epic.unwrap().status, Status::InProgress);
+        assert_eq!(state.epics[&epic_id].story_ids.len(), 2);
+        assert_eq!(state.audit.len(), 2);
     }
 
     #[test]
-    fn should_update_story_status() {
-        let db = Rc::new(JiraDatabase {
-            db: Box::new(MockDatabase::new()),
-        });
+    fn should_create_named_story_without_prompting() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
         let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
-        let story_id = db
-            .create_story(&Story::new("name", "description"), epic_id)
-            .unwrap();
         let mut prompts = Prompt::new();
-        prompts.update_status = Box::new(|| Some(Status::InProgress));
+        prompts.create_story = Box::new(|| panic!("the story prompt was not expected"));
         let mut nav = Navigator::new(db.clone());
         nav.set_prompts(prompts);
 
-        let res = nav.dispatch_action(Action::UpdateStoryStatus { story_id });
+        let res = nav.dispatch_action(Action::CreateStoryNamed {
+            epic_id,
+            name: "Fix the login bug".to_owned(),
+        });
         assert!(res.is_ok());
+        let state = db.read().unwrap();
+        let story_id = state.epics[&epic_id].story_ids[0];
+        assert_eq!(
+            state.stories[&story_id],
+            Story::new("Fix the login bug", "")
+        );
+
+        let res = nav.dispatch_action(Action::CreateStoryNamed {
+            epic_id,
+            name: "a".repeat(MAX_NAME_LENGTH + 1),
+        });
+        assert!(res.is_err());
+        assert_eq!(db.read().unwrap().stories.len(), 1);
+    }
+
+    #[test]
+    fn should_not_create_story_in_closed_epic_unless_confirmed() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        db.update_epic_status(epic_id, Status::Closed).unwrap();
+        db.set_epic_status_override(epic_id, true).unwrap();
+        let mut prompts = Prompt::new();
+        prompts.create_story = Box::new(|| Some(Story::new("name", "description")));
+        prompts.confirm_closed_epic = Box::new(|_| false);
+        let mut nav = MockNavigator::new(db.clone());
+        nav.set_prompts(prompts);
 
+        nav.dispatch_action(Action::CreateStory { epic_id })
+            .unwrap();
         let state = db.read().unwrap();
-        let story = state.stories.get(&story_id);
-        assert!(story.is_some());
-        assert_eq!(story.unwrap().status, Status::InProgress);
+        assert!(state.stories.is_empty());
+        assert_eq!(state.epics[&epic_id].status, Status::Closed);
+
+        let mut prompts = Prompt::new();
+        prompts.create_story = Box::new(|| Some(Story::new("name", "description")));
+        prompts.confirm_closed_epic = Box::new(|_| true);
+        nav.set_prompts(prompts);
+        nav.dispatch_action(Action::CreateStory { epic_id })
+            .unwrap();
+        let state = db.read().unwrap();
+        assert_eq!(state.stories.len(), 1);
+        assert_eq!(state.epics[&epic_id].status, Status::Open);
+        assert!(!state.epics[&epic_id].status_override);
     }
 
     #[test]
-    fn should_delete_epic() {
-        let db = Rc::new(JiraDatabase {
-            db: Box::new(MockDatabase::new()),
-        });
+    fn should_update_epic_status() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
         let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
         let mut prompts = Prompt::new();
-        prompts.delete_epic = Box::new(|| true);
+        prompts.update_epic_status =
+            Box::new(|| Some(EpicStatusChoice::Manual(Status::InProgress)));
         let mut nav = Navigator::new(db.clone());
         nav.set_prompts(prompts);
 
-        let res = nav.dispatch_action(Action::DeleteEpic { epic_id });
+        let res = nav.dispatch_action(Action::UpdateEpicStatus { epic_id });
         assert!(res.is_ok());
 
         let state = db.read().unwrap();
-        assert!(state.epics.is_empty());
+        let epic = state.epics.get(&epic_id);
+        assert!(epic.is_some());
+        assert_eq!(epic.unwrap().status, Status::InProgress);
+        assert!(epic.unwrap().status_override);
     }
 
     #[test]
-    fn should_delete_story() {
-        let db = Rc::new(JiraDatabase {
-            db: Box::new(MockDatabase::new()),
-        });
+    fn derive_epic_status_should_prefer_blocked_unless_all_closed() {
+        let story = |status| {
+            let mut story = Story::new("name", "description");
+            story.status = status;
+            story
+        };
+        let (blocked, resolved, closed) = (
+            story(Status::Blocked),
+            story(Status::Resolved),
+            story(Status::Closed),
+        );
+
+        assert_eq!(
+            derive_epic_status(&[&blocked, &resolved, &closed]),
+            Status::Blocked
+        );
+        assert_eq!(derive_epic_status(&[&blocked]), Status::Blocked);
+        assert_eq!(derive_epic_status(&[&resolved, &closed]), Status::Resolved);
+        assert_eq!(derive_epic_status(&[&closed, &closed]), Status::Closed);
+    }
+
+    #[test]
+    fn check_epic_status_should_reject_unfinished_stories() {
+        let mut open = Story::new("name", "description");
+        open.status = Status::Open;
+        let mut resolved = Story::new("name", "description");
+        resolved.status = Status::Resolved;
+        let mut closed = Story::new("name", "description");
+        closed.status = Status::Closed;
+
+        assert!(check_epic_status(&Status::Closed, &[&closed, &resolved]).is_err());
+        assert!(check_epic_status(&Status::Resolved, &[&closed, &open]).is_err());
+        assert!(check_epic_status(&Status::Resolved, &[&closed, &resolved]).is_ok());
+        assert!(check_epic_status(&Status::Closed, &[&closed]).is_ok());
+        assert!(check_epic_status(&Status::Closed, &[]).is_ok());
+        assert!(check_epic_status(&Status::InProgress, &[&open]).is_ok());
+    }
+
+    #[test]
+    fn should_not_close_epic_with_open_stories_unless_confirmed() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
         let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
-        let story_id = db
-            .create_story(&Story::new("name", "description"), epic_id)
+        db.create_story(&Story::new("name", "description"), epic_id)
             .unwrap();
         let mut prompts = Prompt::new();
-        prompts.delete_story = Box::new(|| true);
+        prompts.update_epic_status = Box::new(|| Some(EpicStatusChoice::Manual(Status::Closed)));
+        prompts.confirm_epic_status = Box::new(|_| false);
         let mut nav = Navigator::new(db.clone());
         nav.set_prompts(prompts);
 
-        let res = nav.dispatch_action(Action::DeleteStory { story_id, epic_id });
+        let res = nav.dispatch_action(Action::UpdateEpicStatus { epic_id });
         assert!(res.is_ok());
+        assert_eq!(db.read().unwrap().epics[&epic_id].status, Status::Open);
 
-        let state = db.read().unwrap();
-        assert!(state.stories.is_empty());
+        let mut prompts = Prompt::new();
+        prompts.update_epic_status = Box::new(|| Some(EpicStatusChoice::Manual(Status::Closed)));
+        prompts.confirm_epic_status = Box::new(|_| true);
+        nav.set_prompts(prompts);
+
+        let res = nav.dispatch_action(Action::UpdateEpicStatus { epic_id });
+        assert!(res.is_ok());
+        assert_eq!(db.read().unwrap().epics[&epic_id].status, Status::Closed);
     }
 
     #[test]
-    fn should_exit() {
-        let db = Rc::new(JiraDatabase {
-            db: Box::new(MockDatabase::new()),
+    fn should_confirm_epic_status_downgrade() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("name", "description"), epic_id)
+            .unwrap();
+        db.update_story_status(story_id, Status::Resolved).unwrap();
+        db.update_epic_status(epic_id, Status::Resolved).unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_epic_status = Box::new(|| Some(EpicStatusChoice::Manual(Status::Open)));
+        prompts.confirm_status_downgrade = Box::new(|message| {
+            assert!(message.contains("Resolved") && message.contains("Open"));
+            false
         });
         let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
 
-        let epic_id = db.create_epic(&Epic::new("", "")).unwrap();
-        nav.pages.push(Box::new(EpicDetail {
-            epic_id,
-            db: db.clone(),
-        }));
-
-        let res = nav.dispatch_action(Action::Exit);
+        let res = nav.dispatch_action(Action::UpdateEpicStatus { epic_id });
+        assert!(res.is_ok());
+        let state = db.read().unwrap();
+        assert_eq!(state.epics[&epic_id].status, Status::Resolved);
+        assert!(!state.epics[&epic_id].status_override);
+
+        let mut prompts = Prompt::new();
+        prompts.update_epic_status = Box::new(|| Some(EpicStatusChoice::Manual(Status::Open)));
+        prompts.confirm_status_downgrade = Box::new(|_| true);
+        nav.set_prompts(prompts);
+
+        let res = nav.dispatch_action(Action::UpdateEpicStatus { epic_id });
+        assert!(res.is_ok());
+        let state = db.read().unwrap();
+        assert_eq!(state.epics[&epic_id].status, Status::Open);
+        assert!(state.epics[&epic_id].status_override);
+    }
+
+    #[test]
+    fn should_not_confirm_epic_status_without_stories() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        db.update_epic_status(epic_id, Status::InProgress).unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_epic_status = Box::new(|| Some(EpicStatusChoice::Manual(Status::Open)));
+        prompts.confirm_status_downgrade = Box::new(|_| panic!("no confirmation expected"));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        assert!(nav
+            .dispatch_action(Action::UpdateEpicStatus { epic_id })
+            .is_ok());
+        assert_eq!(db.read().unwrap().epics[&epic_id].status, Status::Open);
+    }
+
+    #[test]
+    fn should_close_epic_with_closed_stories() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("name", "description"), epic_id)
+            .unwrap();
+        db.update_story_status(story_id, Status::Closed).unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_epic_status = Box::new(|| Some(EpicStatusChoice::Manual(Status::Closed)));
+        prompts.confirm_epic_status = Box::new(|_| panic!("no confirmation expected"));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        let res = nav.dispatch_action(Action::UpdateEpicStatus { epic_id });
+        assert!(res.is_ok());
+        assert_eq!(db.read().unwrap().epics[&epic_id].status, Status::Closed);
+    }
+
+    #[test]
+    fn should_not_auto_update_overridden_epic_status() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("name", "description"), epic_id)
+            .unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_epic_status = Box::new(|| Some(EpicStatusChoice::Manual(Status::Closed)));
+        prompts.confirm_epic_status = Box::new(|_| true);
+        prompts.update_status = Box::new(|| Some(Status::InProgress));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::UpdateEpicStatus { epic_id })
+            .unwrap();
+        let res = nav.dispatch_action(Action::UpdateStoryStatus { story_id });
+        assert!(res.is_ok());
+
+        let state = db.read().unwrap();
+        assert_eq!(state.stories[&story_id].status, Status::InProgress);
+        assert_eq!(state.epics[&epic_id].status, Status::Closed);
+    }
+
+    #[test]
+    fn should_clear_epic_status_override_on_auto() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("name", "description"), epic_id)
+            .unwrap();
+        db.update_story_status(story_id, Status::InProgress)
+            .unwrap();
+        db.update_epic_status(epic_id, Status::Closed).unwrap();
+        db.set_epic_status_override(epic_id, true).unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_epic_status = Box::new(|| Some(EpicStatusChoice::Auto));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        let res = nav.dispatch_action(Action::UpdateEpicStatus { epic_id });
+        assert!(res.is_ok());
+
+        let state = db.read().unwrap();
+        assert!(!state.epics[&epic_id].status_override);
+        assert_eq!(state.epics[&epic_id].status, Status::InProgress);
+    }
+
+    #[test]
+    fn mock_navigator_should_clear_epic_status_override_on_auto() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("name", "description"), epic_id)
+            .unwrap();
+        db.update_story_status(story_id, Status::InProgress)
+            .unwrap();
+        db.update_epic_status(epic_id, Status::Closed).unwrap();
+        db.set_epic_status_override(epic_id, true).unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_epic_status = Box::new(|| Some(EpicStatusChoice::Auto));
+        let mut nav = MockNavigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::UpdateEpicStatus { epic_id })
+            .unwrap();
+
+        let state = nav.state.read().unwrap();
+        assert!(!state.epics[&epic_id].status_override);
+        assert_eq!(state.epics[&epic_id].status, Status::InProgress);
+    }
+
+    #[test]
+    fn should_update_story_status() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("name", "description"), epic_id)
+            .unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_status = Box::new(|| Some(Status::InProgress));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        let res = nav.dispatch_action(Action::UpdateStoryStatus { story_id });
+        assert!(res.is_ok());
+
+        let state = db.read().unwrap();
+        let story = state.stories.get(&story_id);
+        assert!(story.is_some());
+        assert_eq!(story.unwrap().status, Status::InProgress);
+    }
+
+    #[test]
+    fn should_set_story_status_and_update_epic() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("name", "description"), epic_id)
+            .unwrap();
+        let mut nav = Navigator::new(db.clone());
+
+        let res = nav.dispatch_action(Action::SetStoryStatus {
+            story_id,
+            status: Status::Closed,
+        });
+        assert!(res.is_ok());
+
+        let state = db.read().unwrap();
+        assert_eq!(state.stories[&story_id].status, Status::Closed);
+        assert_eq!(state.epics[&epic_id].status, Status::Closed);
+    }
+
+    #[test]
+    fn should_toggle_epic_archived() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let mut nav = Navigator::new(db.clone());
+
+        nav.dispatch_action(Action::ToggleEpicArchived { epic_id })
+            .unwrap();
+        assert!(db.read().unwrap().epics[&epic_id].archived);
+        assert_eq!(
+            nav.take_message(),
+            Some(format!("Archived epic #{}", epic_id))
+        );
+        assert_eq!(
+            db.read().unwrap().audit.last().unwrap().action,
+            "Archived epic"
+        );
+
+        nav.dispatch_action(Action::ToggleEpicArchived { epic_id })
+            .unwrap();
+        assert!(!db.read().unwrap().epics[&epic_id].archived);
+        assert!(nav
+            .dispatch_action(Action::ToggleEpicArchived { epic_id: 999 })
+            .is_err());
+    }
+
+    #[test]
+    fn should_roll_task_statuses_up_to_story_and_epic() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("name", "description"), epic_id)
+            .unwrap();
+        let done_id = db
+            .create_task(&Task::new("name", "description"), story_id)
+            .unwrap();
+        let open_id = db
+            .create_task(&Task::new("name", "description"), story_id)
+            .unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_status = Box::new(|| Some(Status::Closed));
+        prompts.delete_task = Box::new(|| true);
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::UpdateTaskStatus { task_id: done_id })
+            .unwrap();
+        let state = db.read().unwrap();
+        assert_eq!(state.stories[&story_id].status, Status::InProgress);
+        assert_eq!(state.epics[&epic_id].status, Status::InProgress);
+
+        nav.dispatch_action(Action::DeleteTask {
+            task_id: open_id,
+            story_id,
+        })
+        .unwrap();
+        let state = db.read().unwrap();
+        assert_eq!(state.stories[&story_id].status, Status::Closed);
+        assert_eq!(state.epics[&epic_id].status, Status::Closed);
+    }
+
+    #[test]
+    fn should_delete_epic() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let mut prompts = Prompt::new();
+        prompts.delete_epic = Box::new(|| true);
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        let res = nav.dispatch_action(Action::DeleteEpic { epic_id });
+        assert!(res.is_ok());
+
+        let state = db.read().unwrap();
+        assert!(state.epics.is_empty());
+    }
+
+    #[test]
+    fn should_delete_story() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("name", "description"), epic_id)
+            .unwrap();
+        let mut prompts = Prompt::new();
+        prompts.delete_story = Box::new(|| true);
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        let res = nav.dispatch_action(Action::DeleteStory { story_id, epic_id });
+        assert!(res.is_ok());
+
+        let state = db.read().unwrap();
+        assert!(state.stories.is_empty());
+    }
+
+    #[test]
+    fn should_delete_listed_stories_and_report_skipped_ids() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let story_ids: Vec<u32> = (0..3)
+            .map(|_| db.create_story(&Story::new("name", ""), epic_id).unwrap())
+            .collect();
+        let entries = vec![
+            story_ids[0].to_string(),
+            "999".to_owned(),
+            story_ids[2].to_string(),
+            "abc".to_owned(),
+        ];
+        let confirmed = Rc::new(Cell::new(0));
+        let mut prompts = Prompt::new();
+        prompts.select_story_ids = Box::new(move || Some(entries.clone()));
+        prompts.confirm_delete_stories = {
+            let confirmed = confirmed.clone();
+            Box::new(move |count| {
+                confirmed.set(count);
+                true
+            })
+        };
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::DeleteStories { epic_id })
+            .unwrap();
+        assert_eq!(confirmed.get(), 2);
+        assert_eq!(
+            nav.take_message().as_deref(),
+            Some("Deleted 2 stories (skipped 999, abc: not stories of this epic)")
+        );
+        let state = db.read().unwrap();
+        assert_eq!(state.epics[&epic_id].story_ids, vec![story_ids[1]]);
+        assert_eq!(state.stories.len(), 1);
+
+        nav.dispatch_action(Action::Undo).unwrap();
+        assert_eq!(db.read().unwrap().stories.len(), 3);
+    }
+
+    #[test]
+    fn should_restore_and_purge_trash() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let story_id = db.create_story(&Story::new("name", ""), epic_id).unwrap();
+        let other_id = db.create_story(&Story::new("name", ""), epic_id).unwrap();
+        let confirmed = Rc::new(Cell::new(0));
+        let mut prompts = Prompt::new();
+        prompts.delete_story = Box::new(|| true);
+        prompts.confirm_purge_trash = {
+            let confirmed = confirmed.clone();
+            Box::new(move |count| {
+                confirmed.set(count);
+                true
+            })
+        };
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::DeleteStory { story_id, epic_id })
+            .unwrap();
+        nav.dispatch_action(Action::NavigateToTrash).unwrap();
+        assert!(nav.current_page().unwrap().as_any().is::<TrashPage>());
+        nav.dispatch_action(Action::RestoreFromTrash { id: story_id })
+            .unwrap();
+        assert_eq!(
+            nav.take_message(),
+            Some(format!("Restored story #{} to epic #{}", story_id, epic_id))
+        );
+        assert!(db.read().unwrap().stories.contains_key(&story_id));
+
+        nav.dispatch_action(Action::DeleteStory {
+            story_id: other_id,
+            epic_id,
+        })
+        .unwrap();
+        nav.dispatch_action(Action::PurgeTrash).unwrap();
+        assert_eq!(confirmed.get(), 1);
+        assert_eq!(
+            nav.take_message(),
+            Some("Permanently deleted 1 item".to_owned())
+        );
+        assert!(db.read().unwrap().trash_stories.is_empty());
+
+        nav.dispatch_action(Action::PurgeTrash).unwrap();
+        assert_eq!(
+            nav.take_message(),
+            Some("The trash is already empty".to_owned())
+        );
+    }
+
+    #[test]
+    fn should_undo_epic_deletion() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("name", "description"), epic_id)
+            .unwrap();
+        let mut prompts = Prompt::new();
+        prompts.delete_epic = Box::new(|| true);
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+
+        nav.dispatch_action(Action::DeleteEpic { epic_id }).unwrap();
+        assert!(db.read().unwrap().epics.is_empty());
+
+        let res = nav.dispatch_action(Action::Undo);
+        assert!(res.is_ok());
+        let state = db.read().unwrap();
+        assert_eq!(state.epics[&epic_id].story_ids, vec![story_id]);
+        assert!(state.stories.contains_key(&story_id));
+    }
+
+    #[test]
+    fn should_undo_actions_in_reverse_order() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let mut prompts = Prompt::new();
+        prompts.create_story = Box::new(|| Some(Story::new("name", "description")));
+        prompts.update_name = Box::new(|_| Some("new name".to_owned()));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::CreateStory { epic_id })
+            .unwrap();
+        nav.dispatch_action(Action::UpdateEpicName { epic_id })
+            .unwrap();
+
+        nav.dispatch_action(Action::Undo).unwrap();
+        let state = db.read().unwrap();
+        assert_eq!(state.epics[&epic_id].name, "name");
+        assert_eq!(state.stories.len(), 1);
+
+        nav.dispatch_action(Action::Undo).unwrap();
+        let state = db.read().unwrap();
+        assert!(state.stories.is_empty());
+        assert!(state.epics[&epic_id].story_ids.is_empty());
+    }
+
+    #[test]
+    fn should_pop_stale_pages_on_undo() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let mut prompts = Prompt::new();
+        prompts.create_epic = Box::new(|| Some(Epic::new("name", "description")));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::CreateEpic).unwrap();
+        let epic_id = *db.read().unwrap().epics.keys().next().unwrap();
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+
+        nav.dispatch_action(Action::Undo).unwrap();
+        assert_eq!(nav.page_count(), 1);
+        assert!(nav
+            .current_page()
+            .unwrap()
+            .as_any()
+            .downcast_ref::<HomePage>()
+            .is_some());
+    }
+
+    #[test]
+    fn should_notify_when_nothing_to_undo() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let notified = Rc::new(RefCell::new(None));
+        let mut prompts = Prompt::new();
+        let message = notified.clone();
+        prompts.notify = Box::new(move |msg| *message.borrow_mut() = Some(msg.to_owned()));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        let res = nav.dispatch_action(Action::Undo);
+        assert!(res.is_ok());
+        assert_eq!(*notified.borrow(), Some("Nothing to undo.".to_owned()));
+    }
+
+    #[test]
+    fn should_record_audit_entries() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let mut prompts = Prompt::new();
+        prompts.create_epic = Box::new(|| Some(Epic::new("name", "description")));
+        prompts.update_name = Box::new(|_| Some("new name".to_owned()));
+        prompts.delete_epic = Box::new(|| true);
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::CreateEpic).unwrap();
+        let epic_id = *db.read().unwrap().epics.keys().next().unwrap();
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+        nav.dispatch_action(Action::UpdateEpicName { epic_id })
+            .unwrap();
+        nav.dispatch_action(Action::DeleteEpic { epic_id }).unwrap();
+
+        let audit: Vec<_> = db
+            .read()
+            .unwrap()
+            .audit
+            .into_iter()
+            .map(|entry| (entry.action, entry.id))
+            .collect();
+        assert_eq!(
+            audit,
+            vec![
+                ("Created epic".to_owned(), Some(epic_id)),
+                ("Updated epic name".to_owned(), Some(epic_id)),
+                ("Deleted epic".to_owned(), Some(epic_id)),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_not_record_audit_entries_for_navigation() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let mut nav = Navigator::new(db.clone());
+
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+        nav.dispatch_action(Action::NavigateToPreviousPage).unwrap();
+        nav.dispatch_action(Action::NavigateToHistory).unwrap();
+
+        assert!(db.read().unwrap().audit.is_empty());
+        assert!(nav
+            .current_page()
+            .unwrap()
+            .as_any()
+            .downcast_ref::<HistoryPage>()
+            .is_some());
+    }
+
+    #[test]
+    fn should_remember_recently_viewed_items() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let first_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let second_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let mut nav = Navigator::new(db.clone());
+
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id: first_id })
+            .unwrap();
+        nav.dispatch_action(Action::NavigateToPreviousPage).unwrap();
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id: second_id })
+            .unwrap();
+        assert_eq!(
+            db.read().unwrap().recent,
+            vec![RecentItem::Epic(second_id), RecentItem::Epic(first_id)]
+        );
+
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id: first_id })
+            .unwrap();
+        assert_eq!(
+            db.read().unwrap().recent,
+            vec![RecentItem::Epic(first_id), RecentItem::Epic(second_id)]
+        );
+
+        nav.dispatch_action(Action::NavigateHome).unwrap();
+        nav.dispatch_action(Action::NavigateToRecent).unwrap();
+        let page = nav.current_page().unwrap();
+        assert_eq!(
+            page.action_from(&second_id.to_string(), &ScriptedIo::default())
+                .unwrap(),
+            Some(Action::NavigateToEpicDetail { epic_id: second_id })
+        );
+    }
+
+    #[test]
+    fn should_limit_recently_viewed_items() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let mut nav = Navigator::new(db.clone());
+        for _ in 0..RECENT_LIMIT + 2 {
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            nav.dispatch_action(Action::NavigateToStoryDetail { story_id, epic_id })
+                .unwrap();
+        }
+        assert_eq!(db.read().unwrap().recent.len(), RECENT_LIMIT);
+    }
+
+    #[test]
+    fn run_should_save_recently_viewed_items_on_exit() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let io = Rc::new(ScriptedIo::new(&[&epic_id.to_string(), "b"]));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_io(io.clone());
+
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+        assert!(db.db.read().unwrap().recent.is_empty());
+
+        nav.run(&mut LineInput::new(io.clone()), None, vec![]);
+        assert_eq!(
+            db.db.read().unwrap().recent,
+            vec![RecentItem::Epic(epic_id)]
+        );
+    }
+
+    #[test]
+    fn should_switch_database() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        db.create_epic(&Epic::new("First", "")).unwrap();
+        let epic_id = db.create_epic(&Epic::new("Second", "")).unwrap();
+        let other_db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let other_epic_id = other_db.create_epic(&Epic::new("Other", "")).unwrap();
+        let mut nav = Navigator::new(db.clone());
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+
+        nav.switch_database(other_db.clone(), "other.json");
+        assert_eq!(nav.board_path.as_deref(), Some("other.json"));
+        assert_eq!(nav.page_count(), 1);
+        assert!(other_db.read().unwrap().recent.is_empty());
+        let home = nav.current_page().unwrap();
+        assert_eq!(
+            home.action_from(&epic_id.to_string(), &ScriptedIo::default())
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            home.action_from(&other_epic_id.to_string(), &ScriptedIo::default())
+                .unwrap(),
+            Some(Action::NavigateToEpicDetail {
+                epic_id: other_epic_id
+            })
+        );
+
+        let mut prompts = Prompt::new();
+        prompts.create_epic = Box::new(|| Some(Epic::new("Created", "")));
+        nav.set_prompts(prompts);
+        nav.dispatch_action(Action::CreateEpic).unwrap();
+        assert_eq!(db.read().unwrap().epics.len(), 2);
+    }
+
+    #[test]
+    fn should_create_sprint_and_plan_stories() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("Story 1", ""), epic_id)
+            .unwrap();
+        let mut prompts = Prompt::new();
+        prompts.create_sprint = Box::new(|| {
+            let date = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+            Some(Sprint::new("Sprint 1", date, date))
+        });
+        prompts.update_sprint_stories = Box::new(move || Some(SprintChange::Add(story_id)));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::NavigateToSprints).unwrap();
+        nav.dispatch_action(Action::CreateSprint).unwrap();
+        let sprint_id = *db.read().unwrap().sprints.keys().next().unwrap();
+        assert_eq!(
+            nav.take_message(),
+            Some(format!("Created sprint #{}", sprint_id))
+        );
+
+        nav.dispatch_action(Action::NavigateToSprintDetail { sprint_id })
+            .unwrap();
+        nav.dispatch_action(Action::UpdateSprintStories { sprint_id })
+            .unwrap();
+        assert_eq!(
+            db.read().unwrap().sprints[&sprint_id].story_ids,
+            vec![story_id]
+        );
+        assert_eq!(nav.page_count(), 3);
+
+        nav.dispatch_action(Action::Undo).unwrap();
+        nav.dispatch_action(Action::Undo).unwrap();
+        assert_eq!(nav.page_count(), 2);
+    }
+
+    #[test]
+    fn should_switch_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("work").join("db.json");
+        let path = path.to_str().unwrap().to_owned();
+        let work = JiraDatabase::new(&path).unwrap();
+        let epic_id = work.create_epic(&Epic::new("Work", "")).unwrap();
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let mut nav = Navigator::new(db);
+        nav.set_workspaces(Workspaces([("work".to_owned(), path.clone())].into()));
+
+        nav.dispatch_action(Action::NavigateToWorkspaces).unwrap();
+        let action = nav
+            .current_page()
+            .unwrap()
+            .action_from("1", &ScriptedIo::default())
+            .unwrap();
+        assert_eq!(
+            action,
+            Some(Action::SwitchWorkspace {
+                name: "work".to_owned()
+            })
+        );
+        nav.dispatch_action(action.unwrap()).unwrap();
+        assert_eq!(
+            nav.take_message(),
+            Some(format!("Switched to workspace work ({})", path))
+        );
+
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+        let page = nav
+            .current_page()
+            .unwrap()
+            .as_any()
+            .downcast_ref::<EpicDetail>()
+            .unwrap();
+        assert_eq!(page.db.read().unwrap().epics[&epic_id].name, "Work");
+        assert!(nav
+            .dispatch_action(Action::SwitchWorkspace {
+                name: "missing".to_owned()
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn should_update_story_points() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("name", "description"), epic_id)
+            .unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_points = Box::new(|| Some(Some(5)));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::UpdateStoryPoints { story_id })
+            .unwrap();
+        assert_eq!(db.read().unwrap().stories[&story_id].points, Some(5));
+        assert_eq!(
+            nav.take_message().as_deref(),
+            Some(format!("Story #{} estimated at 5 points", story_id).as_str())
+        );
+
+        let mut prompts = Prompt::new();
+        prompts.update_points = Box::new(|| Some(None));
+        nav.set_prompts(prompts);
+        nav.dispatch_action(Action::UpdateStoryPoints { story_id })
+            .unwrap();
+        assert_eq!(db.read().unwrap().stories[&story_id].points, None);
+    }
+
+    #[test]
+    fn should_update_story_tags() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("Story 1", ""), epic_id)
+            .unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_tags = Box::new(|| Some(TagChange::Add("Bug".to_owned())));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::UpdateStoryTags { story_id })
+            .unwrap();
+        assert_eq!(db.read().unwrap().stories[&story_id].tags, vec!["bug"]);
+
+        let mut prompts = Prompt::new();
+        prompts.update_tags = Box::new(|| Some(TagChange::Remove("bug".to_owned())));
+        nav.set_prompts(prompts);
+        nav.dispatch_action(Action::UpdateStoryTags { story_id })
+            .unwrap();
+        assert!(db.read().unwrap().stories[&story_id].tags.is_empty());
+    }
+
+    #[test]
+    fn should_not_close_blocked_story() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("Story 1", ""), epic_id)
+            .unwrap();
+        let blocker_id = db
+            .create_story(&Story::new("Story 1", ""), epic_id)
+            .unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_blockers = Box::new(move || Some(BlockerChange::Add(blocker_id)));
+        prompts.update_status = Box::new(|| Some(Status::Closed));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::UpdateStoryBlockers { story_id })
+            .unwrap();
+        assert_eq!(
+            db.read().unwrap().stories[&story_id].blocked_by,
+            vec![blocker_id]
+        );
+
+        assert!(nav
+            .dispatch_action(Action::UpdateStoryStatus { story_id })
+            .is_err());
+        assert_eq!(nav.take_message(), None);
+        assert_eq!(db.read().unwrap().stories[&story_id].status, Status::Open);
+    }
+
+    #[test]
+    fn should_set_message_on_create() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let mut prompts = Prompt::new();
+        prompts.create_epic = Box::new(|| Some(Epic::new("name", "description")));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::CreateEpic).unwrap();
+        let epic_id = *db.read().unwrap().epics.keys().next().unwrap();
+        assert_eq!(
+            nav.take_message(),
+            Some(format!("Created epic #{}", epic_id))
+        );
+        assert_eq!(nav.take_message(), None);
+    }
+
+    #[test]
+    fn should_set_message_on_update() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("Story 1", ""), epic_id)
+            .unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_status = Box::new(|| Some(Status::Closed));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::UpdateStoryStatus { story_id })
+            .unwrap();
+        assert_eq!(
+            nav.take_message(),
+            Some(format!("Story #{} status → Closed", story_id))
+        );
+    }
+
+    #[test]
+    fn should_set_message_on_delete() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        for _ in 0..4 {
+            db.create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+        }
+        let mut prompts = Prompt::new();
+        prompts.delete_epic = Box::new(|| true);
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+
+        nav.dispatch_action(Action::DeleteEpic { epic_id }).unwrap();
+        assert_eq!(
+            nav.take_message(),
+            Some(format!("Deleted epic #{} and 4 stories", epic_id))
+        );
+        assert!(db.read().unwrap().stories.is_empty());
+    }
+
+    #[test]
+    fn run_should_drive_a_whole_session() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let io = Rc::new(ScriptedIo::new(&[
+            "n",
+            "Epic 1",
+            "First epic",
+            "0",
+            "n",
+            "Story 1",
+            "First story",
+            "1",
+            "u",
+            "3",
+            "4",
+            "b",
+            "b",
+            "q",
+            "y",
+        ]));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_io(io.clone());
+
+        let (_, open_pages) = nav.run(&mut LineInput::new(io.clone()), Some("Banner"), vec![]);
+        assert!(nav.current_page().is_none());
+        assert!(open_pages.is_empty());
+
+        let state = db.read().unwrap();
+        assert_eq!(state.epics[&0].name, "Epic 1");
+        assert_eq!(state.epics[&0].story_ids, vec![1]);
+        assert_eq!(state.stories[&1].name, "Story 1");
+        assert_eq!(state.stories[&1].status, Status::Closed);
+
+        let output = io.output();
+        for snippet in [
+            "Banner",
+            "Created epic #0",
+            "Created story #1",
+            "│ 1  │ Story 1",
+            "Closed",
+            "Quit Clira?",
+        ] {
+            assert!(output.contains(snippet), "{:?} not in output", snippet);
+        }
+    }
+
+    /// `ReadOnlyDatabase` holds a board that can be read but never saved.
+    struct ReadOnlyDatabase(DatabaseState);
+
+    impl Database for ReadOnlyDatabase {
+        fn read(&self) -> Result<DatabaseState, DbError> {
+            Ok(self.0.clone())
+        }
+
+        fn write(&self, _: &DatabaseState) -> anyhow::Result<()> {
+            bail!("disk full")
+        }
+    }
+
+    #[test]
+    fn dispatch_action_should_report_saved_changes_only() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let mut prompts = Prompt::new();
+        prompts.create_epic = Box::new(|| Some(Epic::new("Epic 1", "")));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::CreateEpic).unwrap();
+        assert!(nav.take_saved());
+        assert!(!nav.take_saved());
+
+        let epic_id = *db.read().unwrap().epics.keys().next().unwrap();
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+        assert!(!nav.take_saved());
+    }
+
+    #[test]
+    fn dispatch_action_should_report_failed_saves() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(ReadOnlyDatabase(
+            DatabaseState::default(),
+        ))));
+        let mut prompts = Prompt::new();
+        prompts.create_epic = Box::new(|| Some(Epic::new("Epic 1", "")));
+        let mut nav = Navigator::new(db);
+        nav.set_prompts(prompts);
+
+        let error = nav.dispatch_action(Action::CreateEpic).unwrap_err();
+        assert!(!nav.take_saved());
+        assert_eq!(nav.take_message(), None);
+        assert_eq!(
+            error_message(&error),
+            "Save failed, the change was not saved: disk full"
+        );
+
+        let io = Rc::new(ScriptedIo::new(&["n", "", "q", "y"]));
+        nav.set_io(io.clone());
+        let mut prompts = Prompt::new();
+        prompts.create_epic = Box::new(|| Some(Epic::new("Epic 1", "")));
+        nav.set_prompts(prompts);
+        nav.run(&mut LineInput::new(io.clone()), None, vec![]);
+        assert!(io.output().contains("Save failed"));
+        assert!(!io.output().contains("Saved."));
+    }
+
+    #[test]
+    fn run_should_offer_to_recreate_deleted_board() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("db.json");
+        let db = Rc::new(JiraDatabase::new(path.to_str().unwrap()).unwrap());
+        db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let io = Rc::new(ScriptedIo::new(&["n"]));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_io(io.clone());
+        nav.run(&mut LineInput::new(io.clone()), None, vec![]);
+        assert!(io.output().contains("was deleted"));
+        assert!(!path.exists());
+
+        let io = Rc::new(ScriptedIo::new(&["y", "q", "y"]));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_io(io.clone());
+        nav.run(&mut LineInput::new(io.clone()), None, vec![]);
+        assert!(io.output().contains("Created an empty board"));
+        assert_eq!(db.read().unwrap(), DatabaseState::default());
+    }
+
+    #[test]
+    fn run_should_dispatch_actions_in_watch_mode() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let io = Rc::new(ScriptedIo::new(&["Epic 1", "First epic"]));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_io(io.clone());
+        let mut input = WatchInput::spawn(Duration::from_millis(10), || {
+            LineInput::new(Rc::new(ScriptedIo::new(&["n", "0"])))
+        });
+
+        let (_, open_pages) = nav.run(&mut input, None, vec![]);
+        assert_eq!(open_pages, vec![RecentItem::Epic(0)]);
+        assert_eq!(db.read().unwrap().epics[&0].name, "Epic 1");
+        let output = io.output();
+        assert!(output.contains("Created epic #0"));
+        assert!(output.contains("Saved."));
+    }
+
+    #[test]
+    fn run_should_end_without_confirmation_at_end_of_input() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let io = Rc::new(ScriptedIo::new(&[&epic_id.to_string()]));
+        let mut nav = Navigator::new(db);
+        nav.set_io(io.clone());
+
+        let (_, open_pages) = nav.run(&mut LineInput::new(io.clone()), None, vec![]);
+        assert_eq!(open_pages, vec![RecentItem::Epic(epic_id)]);
+        assert!(!io.output().contains("Quit Clira?"));
+    }
+
+    #[test]
+    fn run_should_return_board_of_switched_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("b.json");
+        let path = path.to_str().unwrap().to_owned();
+        let work = JiraDatabase::new(&path).unwrap();
+        let epic_id = work.create_epic(&Epic::new("Work", "")).unwrap();
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let io = Rc::new(ScriptedIo::new(&["w", "1", &epic_id.to_string()]));
+        let mut nav = Navigator::new(db);
+        nav.set_io(io.clone());
+        nav.set_board_path(Some("a.json".to_owned()));
+        nav.set_workspaces(Workspaces([("b".to_owned(), path.clone())].into()));
+
+        let (board_path, open_pages) = nav.run(&mut LineInput::new(io.clone()), None, vec![]);
+        assert_eq!(board_path, Some(path));
+        assert_eq!(open_pages, vec![RecentItem::Epic(epic_id)]);
+    }
+
+    #[test]
+    fn dry_run_delete_should_leave_state_unchanged() {
+        let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        for _ in 0..4 {
+            db.create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+        }
+        let before = db.read().unwrap();
+        let db = Rc::new(db.dry_run());
+        let mut prompts = Prompt::new();
+        prompts.delete_epic = Box::new(|| true);
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+        nav.set_dry_run(true);
+
+        let res = nav.dispatch_action(Action::DeleteEpic { epic_id });
+        assert!(res.is_ok());
+        assert_eq!(db.read().unwrap(), before);
+        assert_eq!(
+            nav.take_message(),
+            Some(format!(
+                "Dry run, nothing was saved:\n  would delete epic #{} and its 4 stories",
+                epic_id
+            ))
+        );
+    }
+
+    #[test]
+    fn unchanged_name_and_description_should_not_be_written() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "Old")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("Story 1", ""), epic_id)
+            .unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_name = Box::new(|current| Some(current.to_owned()));
+        prompts.update_description = Box::new(|current| Some(current.to_owned()));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+        let before = db.read().unwrap();
+
+        nav.dispatch_action(Action::UpdateEpicName { epic_id })
+            .unwrap();
+        assert_eq!(
+            nav.take_message(),
+            Some(format!("Epic #{} name unchanged", epic_id))
+        );
+        nav.dispatch_action(Action::UpdateEpicDescription { epic_id })
+            .unwrap();
+        nav.dispatch_action(Action::UpdateStoryDescription { story_id })
+            .unwrap();
+        assert_eq!(
+            nav.take_message(),
+            Some(format!("Story #{} description unchanged", story_id))
+        );
+        assert_eq!(db.read().unwrap(), before);
+    }
+
+    #[test]
+    fn canceled_updates_should_not_be_written() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "Old")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("Story 1", "Old"), epic_id)
+            .unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_name = Box::new(|_| None);
+        prompts.update_description = Box::new(|_| None);
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+        let before = db.read().unwrap();
+
+        for action in [
+            Action::UpdateEpicName { epic_id },
+            Action::UpdateEpicDescription { epic_id },
+            Action::UpdateStoryName { story_id },
+            Action::UpdateStoryDescription { story_id },
+        ] {
+            nav.dispatch_action(action).unwrap();
+            assert_eq!(nav.take_message(), None);
+        }
+        assert_eq!(db.read().unwrap(), before);
+    }
+
+    #[test]
+    fn update_name_should_prompt_with_current_name() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_name = Box::new(|current| Some(format!("{} renamed", current)));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::UpdateEpicName { epic_id })
+            .unwrap();
+        assert_eq!(db.read().unwrap().epics[&epic_id].name, "Epic 1 renamed");
+    }
+
+    #[test]
+    fn assign_story_to_me_should_use_default_user() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("Story 1", ""), epic_id)
+            .unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_assignee = Box::new(|| Some("bob".to_owned()));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.set_user(Some("alice".to_owned()));
+        nav.dispatch_action(Action::AssignStoryToMe { story_id })
+            .unwrap();
+        assert_eq!(
+            db.read().unwrap().stories[&story_id].assignee.as_deref(),
+            Some("alice")
+        );
+
+        nav.set_user(None);
+        nav.dispatch_action(Action::AssignStoryToMe { story_id })
+            .unwrap();
+        assert_eq!(
+            db.read().unwrap().stories[&story_id].assignee.as_deref(),
+            Some("bob")
+        );
+    }
+
+    #[test]
+    fn should_not_set_message_on_error() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let mut prompts = Prompt::new();
+        prompts.update_name = Box::new(|_| Some("name".to_owned()));
+        let mut nav = Navigator::new(db);
+        nav.set_prompts(prompts);
+
+        let res = nav.dispatch_action(Action::UpdateEpicName { epic_id: 999 });
+        assert!(res.is_err());
+        assert_eq!(nav.take_message(), None);
+    }
+
+    #[test]
+    fn should_explain_invalid_names() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+        let mut prompts = Prompt::new();
+        prompts.update_name = Box::new(|_| Some("a".repeat(40)));
+        prompts.create_story = Box::new(|| Some(Story::new("  ", "description")));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        let error = nav
+            .dispatch_action(Action::UpdateEpicName { epic_id })
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "a name can be at most 30 characters long, this one is 40"
+        );
+        let error = nav
+            .dispatch_action(Action::CreateStory { epic_id })
+            .unwrap_err();
+        assert_eq!(error.to_string(), "a name cannot be empty");
+
+        let state = db.read().unwrap();
+        assert_eq!(state.epics[&epic_id].name, "name");
+        assert!(state.stories.is_empty());
+        assert_eq!(nav.take_message(), None);
+    }
+
+    #[test]
+    fn should_update_all_story_statuses_and_roll_up_epic() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        for _ in 0..3 {
+            db.create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+        }
+        let mut prompts = Prompt::new();
+        prompts.update_status = Box::new(|| Some(Status::Resolved));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::UpdateEpicStoriesStatus { epic_id })
+            .unwrap();
+        let state = db.read().unwrap();
+        assert!(state
+            .stories
+            .values()
+            .all(|story| story.status == Status::Resolved));
+        assert_eq!(state.epics[&epic_id].status, Status::Resolved);
+        assert_eq!(
+            nav.take_message(),
+            Some(format!("3 stories in epic #{} → Resolved", epic_id))
+        );
+    }
+
+    #[test]
+    fn should_close_all_stories_and_epic_once_confirmed() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        for status in [Status::Open, Status::InProgress, Status::Resolved] {
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            db.update_story_status(story_id, status).unwrap();
+        }
+        let confirmed = Rc::new(Cell::new(0));
+        let mut prompts = Prompt::new();
+        prompts.confirm_close_stories = {
+            let confirmed = confirmed.clone();
+            Box::new(move |count| {
+                confirmed.set(count);
+                count == 3
+            })
+        };
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::CloseEpicStories { epic_id })
+            .unwrap();
+        assert_eq!(confirmed.get(), 3);
+        let state = db.read().unwrap();
+        assert!(state
+            .stories
+            .values()
+            .all(|story| story.status == Status::Closed));
+        assert_eq!(state.epics[&epic_id].status, Status::Closed);
+        assert_eq!(
+            nav.take_message(),
+            Some(format!("Closed 3 stories in epic #{}", epic_id))
+        );
+    }
+
+    #[test]
+    fn should_delete_only_closed_stories_once_confirmed() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let mut kept = vec![];
+        for status in [
+            Status::Closed,
+            Status::Open,
+            Status::Closed,
+            Status::Resolved,
+            Status::Closed,
+        ] {
+            let story_id = db.create_story(&Story::new("Story", ""), epic_id).unwrap();
+            if status != Status::Closed {
+                kept.push(story_id);
+            }
+            db.update_story_status(story_id, status).unwrap();
+        }
+        let before = db.read().unwrap();
+        let confirmed = Rc::new(Cell::new(0));
+        let answer = Rc::new(Cell::new(false));
+        let mut prompts = Prompt::new();
+        prompts.confirm_delete_stories = {
+            let (confirmed, answer) = (confirmed.clone(), answer.clone());
+            Box::new(move |count| {
+                confirmed.set(count);
+                answer.get()
+            })
+        };
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::DeleteClosedStories { epic_id })
+            .unwrap();
+        assert_eq!(confirmed.get(), 3);
+        assert_eq!(db.read().unwrap(), before);
+        assert_eq!(nav.take_message(), None);
+
+        answer.set(true);
+        nav.dispatch_action(Action::DeleteClosedStories { epic_id })
+            .unwrap();
+        let state = db.read().unwrap();
+        assert_eq!(state.epics[&epic_id].story_ids, kept);
+        assert_eq!(state.stories.len(), 2);
+        assert_eq!(state.trash_stories.len(), 3);
+        assert_eq!(state.epics[&epic_id].status, Status::InProgress);
+        assert_eq!(nav.take_message(), Some("Deleted 3 stories".to_owned()));
+
+        nav.dispatch_action(Action::DeleteClosedStories { epic_id })
+            .unwrap();
+        assert_eq!(
+            nav.take_message(),
+            Some(format!("Epic #{} has no closed stories to delete", epic_id))
+        );
+    }
+
+    #[test]
+    fn should_import_epic_bundle_from_path() {
+        let source = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+        let epic_id = source.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        for name in ["Story 1", "Story 2"] {
+            source.create_story(&Story::new(name, ""), epic_id).unwrap();
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Epic-0.bundle.json");
+        let path = path.to_str().unwrap().to_owned();
+        let bundle = source.export_epic(epic_id).unwrap();
+        fs::write(&path, serde_json::to_string(&bundle).unwrap()).unwrap();
+
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        db.create_epic(&Epic::new("Existing", "")).unwrap();
+        let mut nav = Navigator::new(db.clone());
+        let action = HomePage::new(db.clone())
+            .action_from(&format!(" IB {} ", path), &ScriptedIo::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(action, Action::ImportEpicBundle { path: path.clone() });
+        nav.dispatch_action(action).unwrap();
+
+        let state = db.read().unwrap();
+        assert_eq!(state.epics.len(), 2);
+        assert_eq!(state.stories.len(), 2);
+        let message = nav.take_message().unwrap();
+        assert!(message.ends_with(&format!("with 2 stories from {}", path)));
+
+        fs::write(&path, "{}").unwrap();
+        let error = nav
+            .dispatch_action(Action::ImportEpicBundle { path: path.clone() })
+            .unwrap_err();
+        assert!(error.to_string().contains("is not an epic bundle"));
+    }
+
+    #[test]
+    fn should_create_epic_from_chosen_template() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let mut nav = Navigator::new(db.clone());
+        nav.dispatch_action(Action::CreateEpicFromTemplate).unwrap();
+        assert!(db.read().unwrap().epics.is_empty());
+        assert!(nav
+            .take_message()
+            .unwrap()
+            .starts_with("There are no templates"));
+
+        let stub = |name: &str| StoryStub {
+            name: name.to_owned(),
+            description: "".to_owned(),
+        };
+        nav.set_config(Config {
+            templates: vec![
+                Template {
+                    name: "Onboarding".to_owned(),
+                    description: "".to_owned(),
+                    stories: vec![stub("Laptop")],
+                },
+                Template {
+                    name: "Release {date}".to_owned(),
+                    description: "".to_owned(),
+                    stories: vec![stub("Freeze"), stub("Tag {date}")],
+                },
+            ],
+            ..Config::default()
+        });
+        let mut prompts = Prompt::new();
+        prompts.select_template = Box::new(|templates| {
+            assert_eq!(templates.len(), 2);
+            Some(1)
+        });
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::CreateEpicFromTemplate).unwrap();
+        let state = db.read().unwrap();
+        let (epic_id, epic) = state.epics.iter().next().unwrap();
+        let today = Local::now().date_naive().format("%Y-%m-%d").to_string();
+        assert_eq!(epic.name, format!("Release {}", today));
+        assert_eq!(
+            state.stories[&epic.story_ids[1]].name,
+            format!("Tag {}", today)
+        );
+        assert_eq!(
+            nav.take_message(),
+            Some(format!(
+                "Created epic #{} with 2 stories from template \"Release {{date}}\"",
+                epic_id
+            ))
+        );
+    }
+
+    #[test]
+    fn should_recover_from_stale_page() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let mut nav = MockNavigator::new(db.clone());
+        nav.pages.push(Box::new(EpicDetail::new(999, db.clone())));
+
+        let error = nav
+            .current_page()
+            .unwrap()
+            .draw(&ScriptedIo::default())
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<MissingEntity>(),
+            Some(&MissingEntity::Epic(999))
+        );
+        assert!(nav.handle_draw_error(&error));
+        assert_eq!(nav.pages.len(), 1);
+        assert!(nav
+            .current_page()
+            .unwrap()
+            .as_any()
+            .downcast_ref::<HomePage>()
+            .is_some());
+    }
+
+    #[test]
+    fn should_not_recover_from_other_draw_errors() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let mut nav = Navigator::new(db.clone());
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+
+        assert!(!nav.handle_draw_error(&anyhow!("failed to read database")));
+        assert_eq!(nav.page_count(), 2);
+    }
+
+    #[test]
+    fn should_export_csv() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        db.create_story(&Story::new("Story 1", ""), epic_id)
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.csv");
+        let path = path.to_str().unwrap().to_owned();
+        let mut prompts = Prompt::new();
+        let export_path = path.clone();
+        prompts.export_path = Box::new(move || Some(export_path.clone()));
+        let mut nav = Navigator::new(db);
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::ExportCsv).unwrap();
+        assert_eq!(
+            nav.take_message(),
+            Some(format!("Exported 1 row to {}", path))
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+    }
+
+    #[test]
+    fn should_import_csv() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            "Epic Name,Story Name,Description,Status\nEpic 1,Story 1,,Later\n"
+        )
+        .unwrap();
+        let path = file.path().to_str().unwrap().to_owned();
+        let mut prompts = Prompt::new();
+        let import_path = path.clone();
+        prompts.import_path = Box::new(move || Some((import_path.clone(), false)));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::ImportCsv).unwrap();
+        assert_eq!(db.read().unwrap().stories.len(), 1);
+        assert_eq!(
+            nav.take_message(),
+            Some(format!(
+                "Imported 1 epics and 1 stories from {}\n  warning: row 1: unknown status \"Later\", using Open",
+                path
+            ))
+        );
+    }
+
+    #[test]
+    fn should_exit() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let mut nav = Navigator::new(db.clone());
+
+        let mut prompts = Prompt::new();
+        prompts.confirm_exit = Box::new(|| true);
+        nav.set_prompts(prompts);
+
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        nav.pages
+            .push(Box::new(EpicDetail::new(epic_id, db.clone())));
+
+        let res = nav.dispatch_action(Action::Exit);
+        assert!(res.is_ok());
+        assert!(nav.pages.is_empty());
+    }
+
+    #[test]
+    fn should_not_exit_when_declined() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let mut nav = Navigator::new(db.clone());
+        let mut prompts = Prompt::new();
+        prompts.confirm_exit = Box::new(|| false);
+        nav.set_prompts(prompts);
+
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        nav.pages
+            .push(Box::new(EpicDetail::new(epic_id, db.clone())));
+
+        let res = nav.dispatch_action(Action::Exit);
         assert!(res.is_ok());
+        assert_eq!(nav.page_count(), 2);
+    }
+
+    #[test]
+    fn should_update_and_save_settings() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let mut prompts = Prompt::new();
+        prompts.update_rows_per_page = Box::new(|| Some(Some(4)));
+        let mut nav = Navigator::new(db.clone());
+        nav.set_prompts(prompts);
+        nav.set_config_path(Some(path.to_string_lossy().into_owned()));
+
+        nav.dispatch_action(Action::NavigateToSettings).unwrap();
+        nav.dispatch_action(Action::UpdateSetting {
+            setting: Setting::RowsPerPage,
+        })
+        .unwrap();
+        nav.dispatch_action(Action::UpdateSetting {
+            setting: Setting::SortByStatus,
+        })
+        .unwrap();
+        assert!(nav
+            .take_message()
+            .unwrap()
+            .starts_with("Saved the settings"));
+        let (saved, warnings) = Config::load(&path);
+        assert!(warnings.is_empty());
+        assert_eq!(saved.rows_per_page, Some(4));
+        assert!(saved.sort_by_status);
+        let page = nav.current_page().unwrap().as_any();
+        let page = page.downcast_ref::<SettingsPage>().unwrap();
+        assert_eq!(*page.config.borrow(), saved);
+
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+        let page = nav.current_page().unwrap().as_any();
+        assert!(page.downcast_ref::<EpicDetail>().unwrap().sort_by_status);
+    }
+
+    #[test]
+    fn should_follow_config_settings() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let mut nav = Navigator::new(db.clone());
+        nav.set_config(Config {
+            rows_per_page: Some(3),
+            confirm_exit: false,
+            ..Config::default()
+        });
+        let mut prompts = Prompt::new();
+        prompts.confirm_exit = Box::new(|| false);
+        nav.set_prompts(prompts);
+
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+        let page = nav.current_page().unwrap().as_any();
+        if std::env::var("CLIRA_ROWS_PER_PAGE").is_err() {
+            assert_eq!(page.downcast_ref::<EpicDetail>().unwrap().rows_per_page, 3);
+        }
+
+        nav.dispatch_action(Action::Exit).unwrap();
         assert!(nav.pages.is_empty());
     }
+
+    #[test]
+    fn should_navigate_home() {
+        let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+        let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+        let story_id = db
+            .create_story(&Story::new("Story 1", ""), epic_id)
+            .unwrap();
+        let mut nav = Navigator::new(db.clone());
+        nav.dispatch_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+        nav.dispatch_action(Action::NavigateToStoryDetail { story_id, epic_id })
+            .unwrap();
+
+        let action = nav
+            .current_page()
+            .unwrap()
+            .action_from("h", &ScriptedIo::default())
+            .unwrap();
+        assert_eq!(action, Some(Action::NavigateHome));
+        nav.dispatch_action(action.unwrap()).unwrap();
+        assert_eq!(nav.page_count(), 1);
+        assert!(nav
+            .current_page()
+            .unwrap()
+            .as_any()
+            .downcast_ref::<HomePage>()
+            .is_some());
+    }
 }
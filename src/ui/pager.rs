@@ -0,0 +1,107 @@
+use std::{
+    env,
+    io::{stdout, IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+use crossterm::terminal;
+use tabled::grid::util::string::string_width;
+
+/// `PAGER_ENV_VAR` names the environment variable that turns paging on, e.g. `CLIRA_PAGER=1`.
+pub const PAGER_ENV_VAR: &str = "CLIRA_PAGER";
+
+/// `DEFAULT_PAGER` is the pager used when `$PAGER` is not set. `-R` keeps the colors and `-X`
+/// leaves the last screen up on exit, so that the menu stays above the prompt.
+const DEFAULT_PAGER: &str = "less -RX";
+
+/// `show` shows `text` through the user's pager, if paging is turned on with `CLIRA_PAGER`,
+/// stdout is a terminal and `text` does not fit in it. Returns whether `text` was shown, so
+/// that the caller prints it otherwise, e.g. when the pager could not be launched.
+pub fn show(text: &str) -> bool {
+    if !is_enabled(env::var(PAGER_ENV_VAR).ok().as_deref()) || !stdout().is_terminal() {
+        return false;
+    }
+    let Ok((columns, rows)) = terminal::size() else {
+        return false;
+    };
+    if !exceeds_window(text, columns.into(), rows.into()) {
+        return false;
+    }
+    let pager = env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PAGER.to_owned());
+    run_pager(&pager, text).is_ok()
+}
+
+/// `is_enabled` reads the `CLIRA_PAGER` toggle from its `value`. Paging is off unless it is
+/// set to "1", "true", "yes" or "on".
+fn is_enabled(value: Option<&str>) -> bool {
+    value.is_some_and(|value| {
+        matches!(
+            value.trim().to_lowercase().as_str(),
+            "1" | "true" | "yes" | "on"
+        )
+    })
+}
+
+/// `exceeds_window` returns whether `text` takes more than `rows` lines on a terminal
+/// `columns` wide, counting the lines that wrap and leaving a line for the prompt. Color
+/// codes take no room.
+pub fn exceeds_window(text: &str, columns: usize, rows: usize) -> bool {
+    let columns = columns.max(1);
+    let lines: usize = text
+        .lines()
+        .map(|line| string_width(line).div_ceil(columns).max(1))
+        .sum();
+    lines >= rows
+}
+
+/// `run_pager` pipes `text` into the `pager` command, which may include arguments such as
+/// `less -R`, and waits for the user to close it. Returns `Err` if the pager could not be
+/// launched.
+fn run_pager(pager: &str, text: &str) -> std::io::Result<()> {
+    stdout().flush()?;
+    let mut args = pager.split_whitespace();
+    let program = args.next().unwrap_or(DEFAULT_PAGER);
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager may be closed before it read everything, which is not an error.
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exceeds_window_should_count_wrapped_lines() {
+        assert!(!exceeds_window("one\ntwo\nthree\n", 80, 4));
+        assert!(exceeds_window("one\ntwo\nthree\n", 80, 3));
+        assert!(!exceeds_window(&"x".repeat(160), 80, 3));
+        assert!(exceeds_window(&"x".repeat(161), 80, 3));
+        assert!(exceeds_window("\n\n", 80, 2));
+        assert!(!exceeds_window("", 0, 1));
+    }
+
+    #[test]
+    fn exceeds_window_should_ignore_color_codes() {
+        let colored = format!("\u{1b}[32m{}\u{1b}[0m", "x".repeat(80));
+        assert!(!exceeds_window(&colored, 80, 2));
+    }
+
+    #[test]
+    fn is_enabled_should_only_accept_truthy_values() {
+        assert!(is_enabled(Some("1")));
+        assert!(is_enabled(Some(" On ")));
+        assert!(!is_enabled(Some("0")));
+        assert!(!is_enabled(Some("")));
+        assert!(!is_enabled(None));
+    }
+}
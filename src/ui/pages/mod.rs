@@ -1,9 +1,22 @@
 pub mod prompts;
 
-use std::{any::Any, rc::Rc};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    env,
+    error::Error,
+    fmt::Display,
+    io::IsTerminal,
+    rc::Rc,
+};
 
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
+use chrono::Local;
+use crossterm::terminal;
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
 use itertools::Itertools;
+use regex::Regex;
 use tabled::{
     builder::{self, Builder},
     settings::{
@@ -15,20 +28,69 @@ use tabled::{
 };
 
 use crate::{
-    db::JiraDatabase,
-    models::Action,
-    utils::{color_for_table_header, color_table_column, constrain_text, read_line},
+    config::Config,
+    db::{error::DbError, validate::MAX_NAME_LENGTH, JiraDatabase},
+    models::{
+        normalize_tag, Action, ChecklistItem, DatabaseState, Direction, Epic, ModifiedItem,
+        RecentItem, Setting, Status, Story, EDITABLE_SETTINGS,
+    },
+    theme::Theme,
+    ui::io::Io,
+    utils::{
+        color_for_table_header, color_table_column, constrain_text, dim, format_duration,
+        page_count, paginate, preview_text, progress_bar, progress_bar_with_partial, rows_per_page,
+        ColorMode,
+    },
+    workspaces::Workspaces,
 };
 
+/// `MissingEntity` is the error returned when drawing a page for an epic, story, task or
+/// sprint that no longer exists in the database.
+#[derive(Debug, PartialEq, Eq)]
+pub enum MissingEntity {
+    Epic(u32),
+    Story(u32),
+    Task(u32),
+    Sprint(u32),
+    /// `Item` is an epic or story, for pages showing either.
+    Item(u32),
+}
+
+impl Display for MissingEntity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Epic(id) => write!(f, "epic #{} no longer exists", id),
+            Self::Story(id) => write!(f, "story #{} no longer exists", id),
+            Self::Task(id) => write!(f, "task #{} no longer exists", id),
+            Self::Sprint(id) => write!(f, "sprint #{} no longer exists", id),
+            Self::Item(id) => write!(f, "epic or story #{} no longer exists", id),
+        }
+    }
+}
+
+impl Error for MissingEntity {}
+
 /// A `Page` is a view that can be drawn on the terminal.
 pub trait Page {
-    /// `draw` prints the page to the `stdout`.
-    fn draw(&self) -> anyhow::Result<()>;
+    /// `draw` prints the page to `io`.
+    fn draw(&self, io: &dyn Io) -> anyhow::Result<()>;
     /// `draw_menu` draws a menu to the page with all the actions available
     /// to the user.
-    fn draw_menu(&self);
-    /// `action_from` returns an action, depending on the `input`.
-    fn action_from(&self, input: &str) -> anyhow::Result<Option<Action>>;
+    fn draw_menu(&self, io: &dyn Io);
+    /// `action_from` returns an action, depending on the `input`. Pages that need more
+    /// input, such as a search text, read it from `io`.
+    fn action_from(&self, input: &str, io: &dyn Io) -> anyhow::Result<Option<Action>>;
+    /// `selectable_ids` returns the ids of the rows that can be highlighted and opened with
+    /// the navigation keys, in the order they are shown.
+    fn selectable_ids(&self) -> anyhow::Result<Vec<u32>> {
+        Ok(vec![])
+    }
+    /// `selection` returns the index of the highlighted row in `selectable_ids`, if any.
+    fn selection(&self) -> Option<usize> {
+        None
+    }
+    /// `set_selection` highlights the row at `index` in `selectable_ids`.
+    fn set_selection(&self, _index: Option<usize>) {}
     /// `as_any` is used to prepare to downcast a trait object to a concrete type.
     fn as_any(&self) -> &dyn Any;
 }
@@ -36,12 +98,108 @@ pub trait Page {
 /// `HomePage` is the first page that a user sees when running the application.
 pub struct HomePage {
     pub db: Rc<JiraDatabase>,
+    /// `assignee_filter` limits the epics shown to those involving a single assignee.
+    pub assignee_filter: RefCell<Option<String>>,
+    /// `search` limits the epics shown to those whose name or description matches.
+    pub search: RefCell<Option<EpicSearch>>,
+    /// `selection` is the index of the epic highlighted with the navigation keys.
+    pub selection: Cell<Option<usize>>,
+    /// `show_archived` is whether archived epics are listed, dimmed, along with the others.
+    pub show_archived: Cell<bool>,
+}
+
+/// An `EpicSearch` is what the epics on the home page are searched for: either text matched
+/// fuzzily, plain text matched exactly, both ignoring case, or a regular expression.
+pub enum EpicSearch {
+    /// `Fuzzy` tolerates typos such as dropped characters, and lists the best matches first.
+    Fuzzy(String),
+    Text(String),
+    Pattern(Regex),
+}
+
+impl EpicSearch {
+    /// `from_query` returns the search for the `query` typed after `/`. Queries starting with
+    /// `'` are matched exactly, as in fzf, and the others fuzzily.
+    pub fn from_query(query: &str) -> Self {
+        match query.strip_prefix('\'') {
+            Some(text) => Self::Text(text.to_owned()),
+            None => Self::Fuzzy(query.to_owned()),
+        }
+    }
+}
+
+impl Display for EpicSearch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fuzzy(text) => write!(f, "{:?}, best match first", text),
+            Self::Text(text) => write!(f, "{:?} exactly", text),
+            Self::Pattern(pattern) => write!(f, "/{}/", pattern),
+        }
+    }
+}
+
+/// `HistoryPage` is a page listing the most recent changes made to the database.
+pub struct HistoryPage {
+    pub db: Rc<JiraDatabase>,
+}
+
+/// `RecentPage` is a page listing the epics and stories viewed most recently, most recent
+/// first, so that the user can jump back to them.
+pub struct RecentPage {
+    pub items: Vec<RecentItem>,
+    pub db: Rc<JiraDatabase>,
+}
+
+/// `ModifiedPage` is a page listing the epics and stories changed most recently, most recent
+/// first, so that the user can resume their work.
+pub struct ModifiedPage {
+    pub db: Rc<JiraDatabase>,
+}
+
+/// `WorkspacePicker` is a page listing the configured workspaces, to switch to another
+/// board.
+pub struct WorkspacePicker {
+    pub workspaces: Workspaces,
+}
+
+/// `SettingsPage` is a page listing the user's settings, to change them.
+pub struct SettingsPage {
+    /// `config` is shared with the navigator, which changes it.
+    pub config: Rc<RefCell<Config>>,
+}
+
+/// `TrashPage` is a page listing the deleted epics and stories, most recently deleted first,
+/// to restore them or empty the trash.
+pub struct TrashPage {
+    pub db: Rc<JiraDatabase>,
+}
+
+/// `SprintPage` is a page listing the sprints, earliest first.
+pub struct SprintPage {
+    pub db: Rc<JiraDatabase>,
+}
+
+/// `SprintDetail` is a page listing the stories planned in a sprint, across epics.
+pub struct SprintDetail {
+    pub sprint_id: u32,
+    pub db: Rc<JiraDatabase>,
 }
 
 /// `EpicDetail` is a page with the details of an epic.
 pub struct EpicDetail {
     pub epic_id: u32,
     pub db: Rc<JiraDatabase>,
+    /// `tag_filter` limits the stories shown to those with a single tag.
+    pub tag_filter: RefCell<Option<String>>,
+    /// `page` is the zero-based page of the stories table being shown.
+    pub page: Cell<usize>,
+    /// `selection` is the index of the story highlighted with the navigation keys, on the
+    /// page being shown.
+    pub selection: Cell<Option<usize>>,
+    pub rows_per_page: usize,
+    /// `sort_by_status` lists the stories by status, from Open to Closed, instead of in the
+    /// epic's order.
+    pub sort_by_status: bool,
 }
 
 /// `StoryDetail` is a page with details of a story.
@@ -51,6 +209,13 @@ pub struct StoryDetail {
     pub db: Rc<JiraDatabase>,
 }
 
+/// `DescriptionPage` is a page showing the whole description of the epic or story `id`,
+/// which the detail pages cut to `DESCRIPTION_PREVIEW_LINES`.
+pub struct DescriptionPage {
+    pub id: u32,
+    pub db: Rc<JiraDatabase>,
+}
+
 /// `TaskDetail` is a page with details of a task.
 pub struct TaskDetail {
     pub task_id: u32,
@@ -58,169 +223,515 @@ pub struct TaskDetail {
     pub db: Rc<JiraDatabase>,
 }
 
-pub const MAX_NAME_LENGTH: usize = 30;
 pub const MAX_DESCRIPTION_LENGTH: usize = 55;
+pub const HISTORY_PAGE_LENGTH: usize = 50;
 
-impl Page for HomePage {
-    fn draw(&self) -> anyhow::Result<()> {
-        let db = self.db.read()?;
-        if db.epics.is_empty() {
-            println!("\n  There are no epics. Create a new epic with `n`.");
-            self.draw_menu();
-            return Ok(());
-        }
+/// `MODIFIED_PAGE_LENGTH` is the number of items listed on the recently modified page.
+pub const MODIFIED_PAGE_LENGTH: usize = 10;
 
-        let mut builder = builder::Builder::new();
-        builder.push_record(["ID", "Name", "Status"]);
+/// `WORK_LOG_LENGTH` is the number of latest work log entries shown on a story's page.
+pub const WORK_LOG_LENGTH: usize = 3;
 
-        for id in db.epics.keys().sorted() {
-            let epic = &db.epics[id];
-            builder.push_record([id.to_string(), epic.name.clone(), epic.status.to_string()]);
+/// `HOME_RECENT_LENGTH` is the number of recently viewed items shown on the home page, which
+/// are opened with the shortcuts `r1` and up.
+pub const HOME_RECENT_LENGTH: usize = 5;
+
+/// `DESCRIPTION_PREVIEW_LINES` is the number of lines of a description shown on the page
+/// of an epic or story. The rest is shown with `v`.
+pub const DESCRIPTION_PREVIEW_LINES: usize = 4;
+
+/// `PROGRESS_BAR_WIDTH` is the number of segments in the progress bar of an epic on the home
+/// page.
+const PROGRESS_BAR_WIDTH: usize = 8;
+
+/// `MIN_COLUMN_WIDTH` is the narrowest a name or description column gets.
+const MIN_COLUMN_WIDTH: usize = 10;
+
+/// `MAX_COLUMN_WIDTH` is the widest a description column gets when fitted to the terminal,
+/// so that descriptions stay readable on very wide terminals.
+const MAX_COLUMN_WIDTH: usize = 100;
+
+/// `DEFAULT_MENU_WIDTH` is the width menus are laid out to when the terminal width is
+/// unknown, e.g. when the output is piped.
+const DEFAULT_MENU_WIDTH: usize = 80;
+
+/// `COLUMN_MARGIN` is the room taken by the borders and the other columns of a detail
+/// table, next to the name and description columns.
+const COLUMN_MARGIN: usize = 25;
+
+/// `ColumnWidths` holds the width at which names and descriptions are wrapped in tables.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColumnWidths {
+    pub name: usize,
+    pub description: usize,
+}
+
+impl Default for ColumnWidths {
+    fn default() -> Self {
+        Self {
+            name: MAX_NAME_LENGTH,
+            description: MAX_DESCRIPTION_LENGTH,
         }
+    }
+}
 
-        let table = builder
-            .build()
-            .with(settings::Style::rounded())
-            .with(
-                LineText::new(
-                    format!("Epics ({})", &db.epics.keys().count()),
-                    Rows::first(),
-                )
-                .offset(2),
-            )
-            .modify(Columns::single(2), Format::content(color_table_column))
-            .to_string();
+impl ColumnWidths {
+    /// `from_env` reads the column widths from the `CLIRA_NAME_WIDTH` and `CLIRA_DESC_WIDTH`
+    /// environment variables and the terminal width. The width is read from `COLUMNS`, or
+    /// detected from the terminal. Pages call it on every draw, so that they follow the
+    /// terminal as it is resized. Without a terminal, e.g. when the output is piped, the
+    /// default widths are kept.
+    pub fn from_env() -> Self {
+        let var = |name| env::var(name).ok();
+        Self::from_vars(
+            var("CLIRA_NAME_WIDTH").as_deref(),
+            var("CLIRA_DESC_WIDTH").as_deref(),
+            terminal_columns(),
+        )
+    }
 
-        println!("{}", table);
-        self.draw_menu();
-        Ok(())
+    /// `from_vars` picks the column widths. Explicit `name` and `description` widths take
+    /// precedence. Otherwise the widths are shared out of the terminal `columns` by
+    /// `column_widths`. Missing or invalid values keep the defaults.
+    fn from_vars(name: Option<&str>, description: Option<&str>, columns: Option<usize>) -> Self {
+        let parse = |value: Option<&str>| {
+            value
+                .and_then(|value| value.trim().parse::<usize>().ok())
+                .filter(|width| *width > 0)
+        };
+        let fitted = columns.filter(|columns| *columns > 0).map(column_widths);
+        let name = parse(name)
+            .or(fitted.map(|widths| widths.name))
+            .unwrap_or(MAX_NAME_LENGTH);
+        let description = parse(description)
+            .or_else(|| {
+                columns.filter(|columns| *columns > 0).map(|columns| {
+                    columns
+                        .saturating_sub(name + COLUMN_MARGIN)
+                        .clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH)
+                })
+            })
+            .unwrap_or(MAX_DESCRIPTION_LENGTH);
+        Self { name, description }
     }
+}
 
-    fn draw_menu(&self) {
-        let menu = into_table(&["(q) quit", "(n) new epic", "<ID> view epic"]);
-        println!("\n\n{}\n\nEnter command:", menu);
+/// `terminal_columns` returns the width of the terminal, read from `COLUMNS` or detected
+/// from the terminal, or `None` without a terminal.
+fn terminal_columns() -> Option<usize> {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse::<usize>().ok())
+        .or_else(|| {
+            std::io::stdout()
+                .is_terminal()
+                .then(terminal::size)?
+                .ok()
+                .map(|(columns, _)| columns.into())
+        })
+}
+
+/// `column_widths` shares a terminal `term_width` columns wide between the name and
+/// description columns of a table, after the room taken by the other columns. Names get
+/// two fifths, up to `MAX_NAME_LENGTH`, and descriptions the rest, up to
+/// `MAX_COLUMN_WIDTH`. Neither column gets narrower than `MIN_COLUMN_WIDTH`.
+pub fn column_widths(term_width: usize) -> ColumnWidths {
+    let available = term_width.saturating_sub(COLUMN_MARGIN);
+    let name = (available * 2 / 5).clamp(MIN_COLUMN_WIDTH, MAX_NAME_LENGTH);
+    let description = available
+        .saturating_sub(name)
+        .clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH);
+    ColumnWidths { name, description }
+}
+
+impl HomePage {
+    /// `new` returns a new instance of `HomePage` with no filters applied.
+    pub fn new(db: Rc<JiraDatabase>) -> Self {
+        Self {
+            db,
+            assignee_filter: RefCell::new(None),
+            search: RefCell::new(None),
+            selection: Cell::new(None),
+            show_archived: Cell::new(false),
+        }
     }
 
-    fn action_from(&self, input: &str) -> anyhow::Result<Option<Action>> {
-        match input {
-            "q" => Ok(Some(Action::Exit)),
-            "n" => Ok(Some(Action::CreateEpic)),
-            other => {
-                if let Ok(epic_id) = other.parse::<u32>() {
-                    if self.db.read()?.epics.contains_key(&epic_id) {
-                        return Ok(Some(Action::NavigateToEpicDetail { epic_id }));
-                    }
-                }
-                Ok(None)
-            }
+    /// `menu_entries` returns the commands listed in the menu of the home page.
+    fn menu_entries(&self) -> Vec<&'static str> {
+        vec![
+            "(q) quit",
+            "(n) new epic",
+            "(nt) new epic from template",
+            "(a) filter by assignee",
+            if self.show_archived.get() {
+                "(A) hide archived"
+            } else {
+                "(A) show archived"
+            },
+            "(/) search",
+            "(~) search by regex",
+            "(h) history",
+            "(v) recently viewed",
+            "(m) recently modified",
+            "(w) workspaces",
+            "(,) settings",
+            "(T) trash",
+            "(e) export csv",
+            "(ej) export stories as json lines",
+            "(i) import csv",
+            "(ib) <path> import epic bundle",
+            "(r) html report",
+            "(s) sprints",
+            "(f) find and replace in descriptions",
+            "(z) undo",
+            "(g) go to ID or name",
+            "(:) command palette",
+            "<ID> view epic",
+        ]
+    }
+
+    /// `epic_ids` returns the sorted ids of the epics that pass the filters. Archived epics
+    /// are left out unless `show_archived` is set.
+    fn epic_ids(&self, state: &DatabaseState) -> Vec<u32> {
+        let mut ids = match self.assignee_filter.borrow().as_deref() {
+            Some(assignee) => filter_epics_by_assignee(state, assignee),
+            None => state.epics.keys().copied().collect(),
+        };
+        if !self.show_archived.get() {
+            ids.retain(|id| !state.epics[id].archived);
         }
+        let matching = match self.search.borrow().as_ref() {
+            Some(EpicSearch::Fuzzy(text)) => {
+                return rank_epics_fuzzily(state, text)
+                    .into_iter()
+                    .map(|(id, _)| id)
+                    .filter(|id| ids.contains(id))
+                    .collect();
+            }
+            Some(EpicSearch::Text(text)) => filter_epics_by_text(state, text),
+            Some(EpicSearch::Pattern(pattern)) => filter_epics_by_pattern(state, pattern),
+            None => return ids,
+        };
+        ids.retain(|id| matching.contains(id));
+        ids
     }
 
-    fn as_any(&self) -> &dyn Any {
-        self
+    /// `hidden_archived_count` returns how many archived epics are left out of the listing.
+    fn hidden_archived_count(&self, state: &DatabaseState) -> usize {
+        if self.show_archived.get() {
+            return 0;
+        }
+        state.epics.values().filter(|epic| epic.archived).count()
     }
 }
 
-impl Page for EpicDetail {
-    fn draw(&self) -> anyhow::Result<()> {
-        let db = self.db.read()?;
-        let mut builder = builder::Builder::new();
-        builder.push_record(["Name", "Description"]);
+impl EpicDetail {
+    /// `new` returns a new instance of `EpicDetail` for the epic `epic_id` with no filters
+    /// applied.
+    pub fn new(epic_id: u32, db: Rc<JiraDatabase>) -> Self {
+        Self {
+            epic_id,
+            db,
+            tag_filter: RefCell::new(None),
+            page: Cell::new(0),
+            selection: Cell::new(None),
+            rows_per_page: rows_per_page(None),
+            sort_by_status: false,
+        }
+    }
 
-        let epic = db
-            .epics
-            .get(&self.epic_id)
-            .ok_or_else(|| anyhow!("could not find epic"))?;
+    /// `header` renders the table with the name, description and assignee of `epic`. The
+    /// status in the title is colored according to `colors`.
+    fn header(
+        &self,
+        epic: &Epic,
+        stories: &BTreeMap<u32, Story>,
+        widths: ColumnWidths,
+        colors: ColorMode,
+    ) -> String {
+        let (total, completed) = epic_points(epic, stories);
+        let mut builder = builder::Builder::new();
+        builder.push_record(["Name", "Description", "Assignee", "Points"]);
         builder.push_record([
-            &constrain_text(&epic.name, MAX_NAME_LENGTH),
-            &constrain_text(&epic.description, MAX_DESCRIPTION_LENGTH),
+            constrain_text(&epic.name, widths.name),
+            preview_text(
+                &epic.description,
+                widths.description,
+                DESCRIPTION_PREVIEW_LINES,
+            ),
+            display_assignee(&epic.assignee),
+            format!("{}/{} done", completed, total),
         ]);
 
-        let table = builder
+        builder
             .build()
             .with(settings::Style::rounded())
             .with(LineText::new(format!("Epic #{} (", &self.epic_id), Rows::first()).offset(2))
             .with(
                 LineText::new(format!("{}", &epic.status), Rows::first())
-                    .color(color_for_table_header(&epic.status.to_string()))
+                    .color(color_for_table_header(
+                        &epic.status.to_string(),
+                        colors,
+                        &Theme::current(),
+                    ))
                     .offset(2 + format!("Epic #{} (", &self.epic_id).len()),
             )
             .with(
-                LineText::new(")", Rows::first())
+                LineText::new(if epic.status_override { "*)" } else { ")" }, Rows::first())
                     .offset(2 + format!("Epic #{} ({}", &self.epic_id, &epic.status).len()),
             )
-            .to_string();
-        println!("{}", table);
+            .to_string()
+    }
+
+    /// `story_ids` returns the ids of the epic's stories that pass the filters, in the epic's
+    /// order, or by status if `sort_by_status` is set.
+    fn story_ids(&self, state: &DatabaseState) -> anyhow::Result<Vec<u32>> {
+        let epic = state
+            .epics
+            .get(&self.epic_id)
+            .ok_or(MissingEntity::Epic(self.epic_id))?;
+        let mut ids = match self.tag_filter.borrow().as_deref() {
+            Some(tag) => filter_stories_by_tag(&epic.story_ids, &state.stories, tag),
+            None => epic.story_ids.clone(),
+        };
+        if self.sort_by_status {
+            ids.sort_by_key(|id| state.stories.get(id).map(|story| story.status.clone()));
+        }
+        Ok(ids)
+    }
+
+    /// `menu` returns the menu entries that apply to the epic in `state`. Commands acting on
+    /// stories are left out while the epic has none, and paging while there is one page.
+    fn menu(&self, state: &DatabaseState) -> Vec<String> {
+        let epic = state.epics.get(&self.epic_id);
+        let story_count = epic.map_or(0, |epic| epic.story_ids.len());
+        let archived = epic.is_some_and(|epic| epic.archived);
+        let visible_count = self.story_ids(state).map_or(0, |ids| ids.len());
+        let has_stories = story_count > 0;
+
+        let mut menu = vec![
+            "(b) back",
+            "(h) home",
+            "(u) update",
+            "(<<)/(>>) previous/next status",
+        ];
+        if has_stories {
+            menu.extend(["(U) update all stories", "(C) close all stories"]);
+        }
+        menu.push("(a) assign");
+        if has_stories {
+            menu.push("(t) filter by tag");
+        }
+        if has_stories {
+            menu.push("(o)/(p)/(r)/(cl) <ID> set story open/in progress/resolved/closed");
+        }
+        menu.extend([
+            "(v) view description",
+            "(c) duplicate",
+            "(x) export",
+            "(d) delete",
+        ]);
+        if has_stories {
+            menu.extend(["(D) delete stories", "(dc) delete closed stories"]);
+        }
+        menu.extend([
+            if archived {
+                "(A) unarchive"
+            } else {
+                "(A) archive"
+            },
+            "(n) [name] new story",
+            "(N) new stories",
+        ]);
+        if page_count(visible_count, self.rows_per_page) > 1 {
+            menu.push("(<)/(>) page");
+        }
+        if story_count > 1 {
+            menu.push("(mu)/(md) <ID> move story up/down");
+        }
+        let mut menu: Vec<String> = menu.into_iter().map(str::to_owned).collect();
+        if has_stories {
+            menu.push(format!("<ID> view story ({})", story_count));
+        }
+        menu
+    }
+}
 
-        let mut story_ids = epic.story_ids.clone();
-        if story_ids.is_empty() {
-            println!("\n  This epic has no stories.");
-            self.draw_menu();
+impl Page for HomePage {
+    fn draw(&self, io: &dyn Io) -> anyhow::Result<()> {
+        let db = self.db.read()?;
+        if db.epics.is_empty() {
+            io.println("\n  There are no epics. Create a new epic with `n`.");
+            self.draw_menu(io);
             return Ok(());
         }
 
+        if let Some(assignee) = self.assignee_filter.borrow().as_deref() {
+            io.println(&format!("\n  Showing epics assigned to {}.", assignee));
+        }
+        if let Some(search) = self.search.borrow().as_ref() {
+            io.println(&format!("\n  Showing epics matching {}.", search));
+        }
+        let epic_ids = self.epic_ids(&db);
+        let widths = ColumnWidths::from_env();
+
         let mut builder = builder::Builder::new();
-        builder.push_record(["ID", "Name", "Status"]);
+        builder.push_record(["ID", "Name", "Status", "Stories", "Progress"]);
 
-        story_ids.sort();
-        for id in story_ids {
-            let story = db
-                .stories
-                .get(&id)
-                .ok_or_else(|| anyhow!("could not find story"))?;
+        for (index, id) in epic_ids.iter().enumerate() {
+            let epic = &db.epics[id];
+            let (closed, resolved, total) = epic_progress(epic, &db.stories);
+            // Resolved stories are shaded as half done, which needs colors to tell apart.
+            let progress = match ColorMode::current() {
+                ColorMode::Enabled => {
+                    progress_bar_with_partial(closed, resolved, total, PROGRESS_BAR_WIDTH)
+                }
+                ColorMode::Disabled => progress_bar(closed, total, PROGRESS_BAR_WIDTH),
+            };
+            let name = constrain_text(&epic.name, widths.name);
             builder.push_record([
-                id.to_string(),
-                constrain_text(story.name.as_str(), MAX_NAME_LENGTH),
-                constrain_text(&story.status.to_string(), MAX_DESCRIPTION_LENGTH),
+                row_id(*id, index, self.selection.get()),
+                if epic.archived {
+                    dim(&name, ColorMode::current())
+                } else {
+                    name
+                },
+                epic.status.to_string(),
+                epic.story_ids.len().to_string(),
+                progress,
             ]);
         }
 
         let table = builder
             .build()
             .with(settings::Style::rounded())
+            .with(LineText::new(format!("Epics ({})", epic_ids.len()), Rows::first()).offset(2))
             .with(
                 LineText::new(
-                    format!("Stories ({} total)", &epic.story_ids.len()),
-                    Rows::first(),
+                    format!("Stories ({})", story_total(&db, &epic_ids)),
+                    Rows::last(),
                 )
                 .offset(2),
             )
-            .modify(Columns::single(2), Format::content(color_table_column))
+            .modify(
+                Columns::single(2),
+                Format::content(|status| {
+                    color_table_column(status, ColorMode::current(), &Theme::current())
+                }),
+            )
             .to_string();
 
-        println!("\n{}", table);
-        self.draw_menu();
+        io.println(&table);
+        if let Some(legend) = status_legend(ColorMode::current(), &Theme::current()) {
+            io.println(&legend);
+        }
+        match self.hidden_archived_count(&db) {
+            0 => {}
+            1 => io.println("  1 archived epic hidden. Show it with `A`."),
+            hidden => io.println(&format!(
+                "  {} archived epics hidden. Show them with `A`.",
+                hidden
+            )),
+        }
+        let recent = home_recent(&db);
+        if !recent.is_empty() {
+            let mut builder = builder::Builder::new();
+            builder.push_record(["", "Type", "Name", "Status"]);
+            for (index, item) in recent.iter().enumerate() {
+                if let Some((_, kind, name, status)) = recent_record(&db, item) {
+                    builder.push_record([
+                        format!("r{}", index + 1),
+                        kind.to_owned(),
+                        constrain_text(name, widths.name),
+                        status.to_string(),
+                    ]);
+                }
+            }
+            let table = builder
+                .build()
+                .with(settings::Style::rounded())
+                .with(LineText::new("Recent", Rows::first()).offset(2))
+                .modify(
+                    Columns::single(3),
+                    Format::content(|status| {
+                        color_table_column(status, ColorMode::current(), &Theme::current())
+                    }),
+                )
+                .to_string();
+            io.println(&format!("\n{}", table));
+        }
+        self.draw_menu(io);
         Ok(())
     }
 
-    fn draw_menu(&self) {
-        let menu = into_table(&[
-            "(b) back",
-            "(u) update",
-            "(d) delete",
-            "(n) new story",
-            "<ID> view story",
-        ]);
-        println!("\n\n{}\n\nEnter command:", menu);
+    fn draw_menu(&self, io: &dyn Io) {
+        let menu = into_table(&self.menu_entries());
+        io.println(&format!("\n\n{}\n\nEnter command:", menu));
     }
 
-    fn action_from(&self, input: &str) -> anyhow::Result<Option<Action>> {
-        match input {
-            "b" => Ok(Some(Action::NavigateToPreviousPage)),
-            "u" => Ok(update_epic(self.epic_id)),
-            "d" => Ok(Some(Action::DeleteEpic {
-                epic_id: self.epic_id,
-            })),
-            "n" => Ok(Some(Action::CreateStory {
-                epic_id: self.epic_id,
-            })),
+    fn action_from(&self, input: &str, io: &dyn Io) -> anyhow::Result<Option<Action>> {
+        // `A` and `T` are told apart from `a` and `t` by their case, so they are matched
+        // before normalizing.
+        match input.trim() {
+            "A" => {
+                self.show_archived.set(!self.show_archived.get());
+                self.selection.set(None);
+                return Ok(None);
+            }
+            "T" => return Ok(Some(Action::NavigateToTrash)),
+            _ => {}
+        }
+        // The path after `ib` keeps its case, so it is also read before normalizing.
+        if let Some((command, path)) = input.trim().split_once(char::is_whitespace) {
+            if command.eq_ignore_ascii_case("ib") {
+                return Ok(Some(Action::ImportEpicBundle {
+                    path: path.trim().to_owned(),
+                }));
+            }
+        }
+        match normalize_command(input).as_str() {
+            "q" => Ok(Some(Action::Exit)),
+            "n" => Ok(Some(Action::CreateEpic)),
+            "nt" => Ok(Some(Action::CreateEpicFromTemplate)),
+            "h" => Ok(Some(Action::NavigateToHistory)),
+            "v" => Ok(Some(Action::NavigateToRecent)),
+            "m" => Ok(Some(Action::NavigateToModified)),
+            "w" => Ok(Some(Action::NavigateToWorkspaces)),
+            "," => Ok(Some(Action::NavigateToSettings)),
+            "e" => Ok(Some(Action::ExportCsv)),
+            "ej" => Ok(Some(Action::ExportStoriesJsonl)),
+            "i" => Ok(Some(Action::ImportCsv)),
+            "r" => Ok(Some(Action::ExportHtml)),
+            "s" => Ok(Some(Action::NavigateToSprints)),
+            "f" => Ok(Some(Action::ReplaceInDescriptions)),
+            "a" => {
+                *self.assignee_filter.borrow_mut() = filter_assignee(io);
+                self.selection.set(None);
+                Ok(None)
+            }
+            "/" => {
+                *self.search.borrow_mut() =
+                    search_text(io).map(|query| EpicSearch::from_query(&query));
+                self.selection.set(None);
+                Ok(None)
+            }
+            "~" => {
+                let search = match search_pattern(io) {
+                    Some(pattern) => Some(EpicSearch::Pattern(compile_pattern(&pattern)?)),
+                    None => None,
+                };
+                *self.search.borrow_mut() = search;
+                self.selection.set(None);
+                Ok(None)
+            }
             other => {
-                if let Ok(story_id) = other.parse::<u32>() {
-                    if self.db.read()?.stories.contains_key(&story_id) {
-                        return Ok(Some(Action::NavigateToStoryDetail {
-                            story_id,
-                            epic_id: self.epic_id,
-                        }));
+                if let Some(index) = recent_shortcut(other) {
+                    let recent = home_recent(&self.db.read()?);
+                    return Ok(recent.get(index).map(|item| view_recent(*item)));
+                }
+                if let Ok(epic_id) = other.parse::<u32>() {
+                    if self.db.read()?.epics.contains_key(&epic_id) {
+                        return Ok(Some(Action::NavigateToEpicDetail { epic_id }));
                     }
                 }
                 Ok(None)
@@ -228,118 +739,146 @@ impl Page for EpicDetail {
         }
     }
 
+    fn selectable_ids(&self) -> anyhow::Result<Vec<u32>> {
+        Ok(self.epic_ids(&self.db.read()?))
+    }
+
+    fn selection(&self) -> Option<usize> {
+        self.selection.get()
+    }
+
+    fn set_selection(&self, index: Option<usize>) {
+        self.selection.set(index);
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
 }
 
-impl Page for StoryDetail {
-    fn draw(&self) -> anyhow::Result<()> {
+impl Page for HistoryPage {
+    fn draw(&self, io: &dyn Io) -> anyhow::Result<()> {
         let db = self.db.read()?;
-        let mut builder = builder::Builder::new();
-        builder.push_record(["Name", "Description"]);
+        if db.audit.is_empty() {
+            io.println("\n  There is no history yet.");
+            self.draw_menu(io);
+            return Ok(());
+        }
 
-        let story = db
-            .stories
-            .get(&self.story_id)
-            .ok_or_else(|| anyhow!("could not find story"))?;
-        builder.push_record([
-            constrain_text(&story.name, MAX_NAME_LENGTH),
-            constrain_text(&story.description, MAX_DESCRIPTION_LENGTH),
-        ]);
+        let mut builder = builder::Builder::new();
+        builder.push_record(["When", "Action", "ID"]);
+        for entry in db.audit.iter().rev().take(HISTORY_PAGE_LENGTH) {
+            builder.push_record([
+                entry
+                    .at
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string(),
+                entry.action.clone(),
+                entry.id.map(|id| id.to_string()).unwrap_or("—".to_owned()),
+            ]);
+        }
 
         let table = builder
             .build()
             .with(settings::Style::rounded())
-            .with(LineText::new(format!("Story #{} (", &self.story_id), Rows::first()).offset(2))
-            .with(
-                LineText::new(format!("{}", &story.status), Rows::first())
-                    .color(color_for_table_header(&story.status.to_string()))
-                    .offset(2 + format!("Story #{} (", &self.story_id).len()),
-            )
             .with(
-                LineText::new(")", Rows::first())
-                    .offset(2 + format!("Story #{} ({}", &self.story_id, &story.status).len()),
+                LineText::new(
+                    format!(
+                        "History (latest {} of {})",
+                        db.audit.len().min(HISTORY_PAGE_LENGTH),
+                        db.audit.len()
+                    ),
+                    Rows::first(),
+                )
+                .offset(2),
             )
             .to_string();
+        io.println(&table);
+        self.draw_menu(io);
+        Ok(())
+    }
 
-        println!("{}", table);
+    fn draw_menu(&self, io: &dyn Io) {
+        let menu = into_table(&["(b) back", "(z) undo"]);
+        io.println(&format!("\n\n{}\n\nEnter command:", menu));
+    }
 
-        let mut task_ids = story.task_ids.clone();
-        if task_ids.is_empty() {
-            println!("\n  This Story has no Tasks. Create a new Task with `n`.");
-            self.draw_menu();
-            return Ok(());
+    fn action_from(&self, input: &str, _io: &dyn Io) -> anyhow::Result<Option<Action>> {
+        match normalize_command(input).as_str() {
+            "b" => Ok(Some(Action::NavigateToPreviousPage)),
+            _ => Ok(None),
         }
+    }
 
-        let mut builder = builder::Builder::new();
-        builder.push_record(["ID", "Name", "Status"]);
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
 
-        task_ids.sort();
-        for id in task_ids {
-            let task = db
-                .tasks
-                .get(&id)
-                .ok_or_else(|| anyhow!("could not find task"))?;
+impl Page for RecentPage {
+    fn draw(&self, io: &dyn Io) -> anyhow::Result<()> {
+        let widths = ColumnWidths::from_env();
+        let db = self.db.read()?;
+        let mut builder = builder::Builder::new();
+        builder.push_record(["ID", "Type", "Name", "Status"]);
+        for item in &self.items {
+            let Some((id, kind, name, status)) = recent_record(&db, item) else {
+                continue;
+            };
             builder.push_record([
                 id.to_string(),
-                constrain_text(task.name.as_str(), MAX_NAME_LENGTH),
-                constrain_text(&task.status.to_string(), MAX_DESCRIPTION_LENGTH),
+                kind.to_owned(),
+                constrain_text(name, widths.name),
+                status.to_string(),
             ]);
         }
+        if builder.count_records() == 1 {
+            io.println("\n  Nothing has been viewed yet.");
+            self.draw_menu(io);
+            return Ok(());
+        }
 
         let table = builder
             .build()
             .with(settings::Style::rounded())
-            .with(
-                LineText::new(
-                    format!("Tasks ({} total)", &story.task_ids.len()),
-                    Rows::first(),
-                )
-                .offset(2),
+            .with(LineText::new("Recently viewed", Rows::first()).offset(2))
+            .modify(
+                Columns::single(3),
+                Format::content(|status| {
+                    color_table_column(status, ColorMode::current(), &Theme::current())
+                }),
             )
-            .modify(Columns::single(2), Format::content(color_table_column))
             .to_string();
-
-        println!("\n{}", table);
-        self.draw_menu();
+        io.println(&table);
+        self.draw_menu(io);
         Ok(())
     }
 
-    fn draw_menu(&self) {
-        let menu = into_table(&[
-            "(b) back",
-            "(u) update",
-            "(n) new task",
-            "(d) delete",
-            "<ID> view task",
-        ]);
-        println!("\n\n{}\n\nEnter command:", menu);
+    fn draw_menu(&self, io: &dyn Io) {
+        let menu = into_table(&["(b) back", "<ID> view epic or story"]);
+        io.println(&format!("\n\n{}\n\nEnter command:", menu));
     }
 
-    fn action_from(&self, input: &str) -> anyhow::Result<Option<Action>> {
-        match input {
-            "b" => Ok(Some(Action::NavigateToPreviousPage)),
-            "u" => Ok(update_story(self.story_id)),
-            "n" => Ok(Some(Action::CreateTask {
-                story_id: self.story_id,
-            })),
-            "d" => Ok(Some(Action::DeleteStory {
-                story_id: self.story_id,
-                epic_id: self.epic_id,
-            })),
-            id => {
-                if let Ok(task_id) = id.parse::<u32>() {
-                    if self.db.read()?.tasks.contains_key(&task_id) {
-                        return Ok(Some(Action::NavigateToTaskDetail {
-                            task_id,
-                            story_id: self.story_id,
-                        }));
-                    }
-                }
-                Ok(None)
-            }
+    fn action_from(&self, input: &str, _io: &dyn Io) -> anyhow::Result<Option<Action>> {
+        let input = normalize_command(input);
+        if input == "b" {
+            return Ok(Some(Action::NavigateToPreviousPage));
         }
+        let Ok(id) = input.parse::<u32>() else {
+            return Ok(None);
+        };
+        let db = self.db.read()?;
+        let action = self
+            .items
+            .iter()
+            .filter(|item| db.is_viewable(item))
+            .find(|item| match **item {
+                RecentItem::Epic(epic_id) => epic_id == id,
+                RecentItem::Story { story_id, .. } => story_id == id,
+            })
+            .map(|item| view_recent(*item));
+        Ok(action)
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -347,53 +886,151 @@ impl Page for StoryDetail {
     }
 }
 
-impl Page for TaskDetail {
-    fn draw(&self) -> anyhow::Result<()> {
+impl Page for ModifiedPage {
+    fn draw(&self, io: &dyn Io) -> anyhow::Result<()> {
+        let widths = ColumnWidths::from_env();
         let db = self.db.read()?;
+        let modified = db.recently_modified(MODIFIED_PAGE_LENGTH);
+        if modified.is_empty() {
+            io.println("\n  Nothing has been modified yet.");
+            self.draw_menu(io);
+            return Ok(());
+        }
+
         let mut builder = builder::Builder::new();
-        builder.push_record(["Name", "Description"]);
-        let task = db
-            .tasks
-            .get(&self.task_id)
-            .ok_or_else(|| anyhow!("could not find task"))?;
+        builder.push_record(["ID", "Type", "Name", "Status", "Modified"]);
+        for item in &modified {
+            let (kind, name, status) = match item {
+                ModifiedItem::Epic { epic, .. } => ("Epic", &epic.name, &epic.status),
+                ModifiedItem::Story { story, .. } => ("Story", &story.name, &story.status),
+            };
+            builder.push_record([
+                item.id().to_string(),
+                kind.to_owned(),
+                constrain_text(name, widths.name),
+                status.to_string(),
+                item.at()
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string(),
+            ]);
+        }
+
+        let table = builder
+            .build()
+            .with(settings::Style::rounded())
+            .with(LineText::new("Recently modified", Rows::first()).offset(2))
+            .modify(
+                Columns::single(3),
+                Format::content(|status| {
+                    color_table_column(status, ColorMode::current(), &Theme::current())
+                }),
+            )
+            .to_string();
+        io.println(&table);
+        self.draw_menu(io);
+        Ok(())
+    }
+
+    fn draw_menu(&self, io: &dyn Io) {
+        let menu = into_table(&["(b) back", "<ID> view epic or story"]);
+        io.println(&format!("\n\n{}\n\nEnter command:", menu));
+    }
+
+    fn action_from(&self, input: &str, _io: &dyn Io) -> anyhow::Result<Option<Action>> {
+        let input = normalize_command(input);
+        if input == "b" {
+            return Ok(Some(Action::NavigateToPreviousPage));
+        }
+        let Ok(id) = input.parse::<u32>() else {
+            return Ok(None);
+        };
+        let db = self.db.read()?;
+        let action = db
+            .recently_modified(MODIFIED_PAGE_LENGTH)
+            .iter()
+            .find(|item| item.id() == id)
+            .map(|item| view_recent(item.recent_item()));
+        Ok(action)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// `setting_record` returns the name of `setting` and its value in `config`.
+fn setting_record(setting: Setting, config: &Config) -> (&'static str, String) {
+    let yes_no = |value: bool| if value { "yes" } else { "no" }.to_owned();
+    match setting {
+        Setting::RowsPerPage => (
+            "Rows per page",
+            config
+                .rows_per_page
+                .map_or("fit to terminal".to_owned(), |rows| rows.to_string()),
+        ),
+        Setting::ConfirmExit => ("Confirm on exit", yes_no(config.confirm_exit)),
+        Setting::SortByStatus => ("Sort stories by status", yes_no(config.sort_by_status)),
+        Setting::Theme => (
+            "Theme",
+            [
+                Status::Open,
+                Status::InProgress,
+                Status::Blocked,
+                Status::Resolved,
+                Status::Closed,
+            ]
+            .into_iter()
+            .map(|status| format!("{}: {}", status, config.theme.color(status.clone())))
+            .join("\n"),
+        ),
+    }
+}
+
+impl Page for SettingsPage {
+    fn draw(&self, io: &dyn Io) -> anyhow::Result<()> {
+        let config = self.config.borrow();
+        let mut builder = builder::Builder::new();
+        builder.push_record(["#", "Setting", "Value"]);
+        for (index, setting) in EDITABLE_SETTINGS.iter().enumerate() {
+            let (name, value) = setting_record(*setting, &config);
+            builder.push_record([(index + 1).to_string(), name.to_owned(), value]);
+        }
         builder.push_record([
-            constrain_text(&task.name, MAX_NAME_LENGTH),
-            constrain_text(&task.description, MAX_DESCRIPTION_LENGTH),
+            String::new(),
+            "Board file".to_owned(),
+            config.db_path.clone().unwrap_or("—".to_owned()),
+        ]);
+        builder.push_record([
+            String::new(),
+            "Date format".to_owned(),
+            config.date_format.clone(),
         ]);
         let table = builder
             .build()
             .with(settings::Style::rounded())
-            .with(LineText::new(format!("Task #{} (", &self.task_id), Rows::first()).offset(2))
-            .with(
-                LineText::new(format!("{}", &task.status), Rows::first())
-                    .color(color_for_table_header(&task.status.to_string()))
-                    .offset(2 + format!("Task #{} (", &self.task_id).len()),
-            )
-            .with(
-                LineText::new(")", Rows::first())
-                    .offset(2 + format!("Task #{} ({}", &self.task_id, &task.status).len()),
-            )
+            .with(LineText::new("Settings", Rows::first()).offset(2))
             .to_string();
-        println!("{}", table);
-        self.draw_menu();
+        io.println(&table);
+        self.draw_menu(io);
         Ok(())
     }
 
-    fn draw_menu(&self) {
-        let menu = into_table(&["(b) back", "(u) update", "(d) delete"]);
-        println!("\n\n{}\n\nEnter command:", menu);
+    fn draw_menu(&self, io: &dyn Io) {
+        let menu = into_table(&["(b) back", "<#> change setting"]);
+        io.println(&format!("\n\n{}\n\nEnter command:", menu));
     }
 
-    fn action_from(&self, input: &str) -> anyhow::Result<Option<Action>> {
-        match input {
-            "b" => Ok(Some(Action::NavigateToPreviousPage)),
-            "u" => Ok(update_task(self.task_id)),
-            "d" => Ok(Some(Action::DeleteTask {
-                task_id: self.task_id,
-                story_id: self.story_id,
-            })),
-            _ => Ok(None),
+    fn action_from(&self, input: &str, _io: &dyn Io) -> anyhow::Result<Option<Action>> {
+        let command = normalize_command(input);
+        if command == "b" {
+            return Ok(Some(Action::NavigateToPreviousPage));
         }
+        let setting = command
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| EDITABLE_SETTINGS.get(number.checked_sub(1)?));
+        Ok(setting.map(|setting| Action::UpdateSetting { setting: *setting }))
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -401,181 +1038,2478 @@ impl Page for TaskDetail {
     }
 }
 
-fn into_table(opts: &[&str]) -> String {
-    let mut builder = Builder::new();
-    builder.push_record(opts.iter().map(|s| s.to_owned()));
-    builder
-        .build()
-        .with(settings::Modify::new(Segment::all()).with(Alignment::center()))
-        .with(settings::Style::modern_rounded())
-        .to_string()
+/// `trash_records` returns a row for each epic and story in the trash of `state`, most
+/// recently deleted first: the id, type, name, the epic a story was deleted from, and when
+/// it was deleted.
+fn trash_records(state: &DatabaseState) -> Vec<[String; 5]> {
+    let epics = state.trash_epics.iter().map(|(id, trashed)| {
+        let stories = trashed.stories.len();
+        (
+            trashed.deleted_at,
+            [
+                id.to_string(),
+                "Epic".to_owned(),
+                trashed.epic.name.clone(),
+                format!(
+                    "{} {}",
+                    stories,
+                    if stories == 1 { "story" } else { "stories" }
+                ),
+            ],
+        )
+    });
+    let stories = state.trash_stories.iter().map(|(id, trashed)| {
+        let epic = match state.epics.get(&trashed.epic_id) {
+            Some(epic) => format!("#{} {}", trashed.epic_id, epic.name),
+            None => format!("#{} (deleted)", trashed.epic_id),
+        };
+        (
+            trashed.deleted_at,
+            [
+                id.to_string(),
+                "Story".to_owned(),
+                trashed.story.name.clone(),
+                epic,
+            ],
+        )
+    });
+    epics
+        .chain(stories)
+        .sorted_by(|a, b| b.0.cmp(&a.0))
+        .map(|(deleted_at, [id, kind, name, from])| {
+            let deleted_at = deleted_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string();
+            [id, kind, name, from, deleted_at]
+        })
+        .collect()
 }
 
-fn update_epic(epic_id: u32) -> Option<Action> {
-    println!("Update which field?\n\t(1) Name\n\t(2) Description\n\t(3) Status");
-    println!("(x) cancel");
+impl Page for TrashPage {
+    fn draw(&self, io: &dyn Io) -> anyhow::Result<()> {
+        let db = self.db.read()?;
+        let records = trash_records(&db);
+        if records.is_empty() {
+            io.println("\n  The trash is empty.");
+            self.draw_menu(io);
+            return Ok(());
+        }
 
-    match read_line().unwrap_or("".into()).as_str() {
-        "1" => Some(Action::UpdateEpicName { epic_id }),
-        "2" => Some(Action::UpdateEpicDescription { epic_id }),
-        "3" => Some(Action::UpdateEpicStatus { epic_id }),
-        _ => None,
+        let widths = ColumnWidths::from_env();
+        let mut builder = builder::Builder::new();
+        builder.push_record(["ID", "Type", "Name", "Epic / Contents", "Deleted"]);
+        for [id, kind, name, from, deleted_at] in &records {
+            builder.push_record([
+                id.clone(),
+                kind.clone(),
+                constrain_text(name, widths.name),
+                constrain_text(from, widths.name),
+                deleted_at.clone(),
+            ]);
+        }
+        let table = builder
+            .build()
+            .with(settings::Style::rounded())
+            .with(LineText::new(format!("Trash ({})", records.len()), Rows::first()).offset(2))
+            .to_string();
+        io.println(&table);
+        self.draw_menu(io);
+        Ok(())
+    }
+
+    fn draw_menu(&self, io: &dyn Io) {
+        let menu = into_table(&["(b) back", "(r) <ID> restore", "(purge) empty trash"]);
+        io.println(&format!("\n\n{}\n\nEnter command:", menu));
+    }
+
+    fn action_from(&self, input: &str, _io: &dyn Io) -> anyhow::Result<Option<Action>> {
+        match normalize_command(input).as_str() {
+            "b" => Ok(Some(Action::NavigateToPreviousPage)),
+            "h" => Ok(Some(Action::NavigateHome)),
+            "purge" => Ok(Some(Action::PurgeTrash)),
+            other => {
+                let Some(id) = other.strip_prefix('r') else {
+                    return Ok(None);
+                };
+                let Ok(id) = id.trim().parse::<u32>() else {
+                    return Ok(None);
+                };
+                let db = self.db.read()?;
+                if db.trash_epics.contains_key(&id) || db.trash_stories.contains_key(&id) {
+                    return Ok(Some(Action::RestoreFromTrash { id }));
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
     }
 }
 
-fn update_story(story_id: u32) -> Option<Action> {
-    println!("Update which field?\n\t(1) Name\n\t(2) Description\n\t(3) Status");
-    println!("(x) cancel");
+impl Page for WorkspacePicker {
+    fn draw(&self, io: &dyn Io) -> anyhow::Result<()> {
+        if self.workspaces.0.is_empty() {
+            io.println(
+                "\n  No workspaces are configured. Add them to ~/.config/clira/workspaces.json.",
+            );
+            self.draw_menu(io);
+            return Ok(());
+        }
 
-    match read_line().unwrap_or("".into()).as_str() {
-        "1" => Some(Action::UpdateStoryName { story_id }),
-        "2" => Some(Action::UpdateStoryDescription { story_id }),
-        "3" => Some(Action::UpdateStoryStatus { story_id }),
-        _ => None,
+        let mut builder = builder::Builder::new();
+        builder.push_record(["#", "Name", "Board file"]);
+        for (index, (name, path)) in self.workspaces.0.iter().enumerate() {
+            builder.push_record([(index + 1).to_string(), name.clone(), path.clone()]);
+        }
+        let table = builder
+            .build()
+            .with(settings::Style::rounded())
+            .with(LineText::new("Workspaces", Rows::first()).offset(2))
+            .to_string();
+        io.println(&table);
+        self.draw_menu(io);
+        Ok(())
+    }
+
+    fn draw_menu(&self, io: &dyn Io) {
+        let menu = into_table(&["(b) back", "<#> or <name> switch workspace"]);
+        io.println(&format!("\n\n{}\n\nEnter command:", menu));
+    }
+
+    fn action_from(&self, input: &str, _io: &dyn Io) -> anyhow::Result<Option<Action>> {
+        let input = input.trim();
+        if self.workspaces.get(input).is_some() {
+            return Ok(Some(Action::SwitchWorkspace {
+                name: input.to_owned(),
+            }));
+        }
+        if normalize_command(input) == "b" {
+            return Ok(Some(Action::NavigateToPreviousPage));
+        }
+        let name = input
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| self.workspaces.0.keys().nth(number.checked_sub(1)?));
+        Ok(name.map(|name| Action::SwitchWorkspace { name: name.clone() }))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
     }
 }
 
-fn update_task(task_id: u32) -> Option<Action> {
-    println!("Update which field?\n\t(1) Name\n\t(2) Description\n\t(3) Status");
-    println!("(x) cancel");
+impl Page for SprintPage {
+    fn draw(&self, io: &dyn Io) -> anyhow::Result<()> {
+        let widths = ColumnWidths::from_env();
+        let db = self.db.read()?;
+        if db.sprints.is_empty() {
+            io.println("\n  There are no sprints. Create a new sprint with `n`.");
+            self.draw_menu(io);
+            return Ok(());
+        }
+
+        let mut builder = builder::Builder::new();
+        builder.push_record(["ID", "Name", "Start", "End", "Stories"]);
+        for (id, sprint) in db
+            .sprints
+            .iter()
+            .sorted_by_key(|(id, sprint)| (sprint.start, **id))
+        {
+            builder.push_record([
+                id.to_string(),
+                constrain_text(&sprint.name, widths.name),
+                sprint.start.to_string(),
+                sprint.end.to_string(),
+                sprint.story_ids.len().to_string(),
+            ]);
+        }
 
-    match read_line().unwrap_or("".into()).as_str() {
-        "1" => Some(Action::UpdateTaskName { task_id }),
-        "2" => Some(Action::UpdateTaskDescription { task_id }),
-        "3" => Some(Action::UpdateTaskStatus { task_id }),
-        _ => None,
+        let table = builder
+            .build()
+            .with(settings::Style::rounded())
+            .with(LineText::new(format!("Sprints ({})", db.sprints.len()), Rows::first()).offset(2))
+            .to_string();
+        io.println(&table);
+        self.draw_menu(io);
+        Ok(())
+    }
+
+    fn draw_menu(&self, io: &dyn Io) {
+        let menu = into_table(&["(b) back", "(n) new sprint", "<ID> view sprint"]);
+        io.println(&format!("\n\n{}\n\nEnter command:", menu));
+    }
+
+    fn action_from(&self, input: &str, _io: &dyn Io) -> anyhow::Result<Option<Action>> {
+        match normalize_command(input).as_str() {
+            "b" => Ok(Some(Action::NavigateToPreviousPage)),
+            "n" => Ok(Some(Action::CreateSprint)),
+            other => {
+                if let Ok(sprint_id) = other.parse::<u32>() {
+                    if self.db.read()?.sprints.contains_key(&sprint_id) {
+                        return Ok(Some(Action::NavigateToSprintDetail { sprint_id }));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::db::test_utils::MockDatabase;
+impl Page for SprintDetail {
+    fn draw(&self, io: &dyn Io) -> anyhow::Result<()> {
+        let widths = ColumnWidths::from_env();
+        let db = self.db.read()?;
+        let sprint = db
+            .sprints
+            .get(&self.sprint_id)
+            .ok_or(MissingEntity::Sprint(self.sprint_id))?;
+        io.println(&format!(
+            "\n  Sprint #{}: {} ({} to {})",
+            self.sprint_id, sprint.name, sprint.start, sprint.end
+        ));
+        if sprint.story_ids.is_empty() {
+            io.println("\n  This sprint has no stories. Add a story with `s`.");
+            self.draw_menu(io);
+            return Ok(());
+        }
 
-    mod home_page {
-        use crate::models::Epic;
+        let mut builder = builder::Builder::new();
+        builder.push_record(["ID", "Name", "Status", "Epic"]);
+        for id in &sprint.story_ids {
+            let Some(story) = db.stories.get(id) else {
+                continue;
+            };
+            let epic = match db.epic_id_for_story(*id) {
+                Some(epic_id) => format!(
+                    "#{} {}",
+                    epic_id,
+                    constrain_text(&db.epics[&epic_id].name, widths.name)
+                ),
+                None => "—".to_owned(),
+            };
+            builder.push_record([
+                id.to_string(),
+                constrain_text(&story.name, widths.name),
+                story.status.to_string(),
+                epic,
+            ]);
+        }
 
-        use super::*;
+        let table = builder
+            .build()
+            .with(settings::Style::rounded())
+            .with(
+                LineText::new(
+                    format!("Stories ({} total)", sprint.story_ids.len()),
+                    Rows::first(),
+                )
+                .offset(2),
+            )
+            .modify(
+                Columns::single(2),
+                Format::content(|status| {
+                    color_table_column(status, ColorMode::current(), &Theme::current())
+                }),
+            )
+            .to_string();
+        io.println(&format!("\n{}", table));
+        self.draw_menu(io);
+        Ok(())
+    }
 
-        #[test]
-        fn draw_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
-            let page = HomePage { db };
-            assert!(page.draw().is_ok());
+    fn draw_menu(&self, io: &dyn Io) {
+        let menu = into_table(&["(b) back", "(s) add/remove stories", "<ID> view story"]);
+        io.println(&format!("\n\n{}\n\nEnter command:", menu));
+    }
+
+    fn action_from(&self, input: &str, _io: &dyn Io) -> anyhow::Result<Option<Action>> {
+        match normalize_command(input).as_str() {
+            "b" => Ok(Some(Action::NavigateToPreviousPage)),
+            "s" => Ok(Some(Action::UpdateSprintStories {
+                sprint_id: self.sprint_id,
+            })),
+            other => {
+                let Ok(story_id) = other.parse::<u32>() else {
+                    return Ok(None);
+                };
+                let db = self.db.read()?;
+                let planned = db
+                    .sprints
+                    .get(&self.sprint_id)
+                    .is_some_and(|sprint| sprint.story_ids.contains(&story_id));
+                Ok(db
+                    .epic_id_for_story(story_id)
+                    .filter(|_| planned)
+                    .map(|epic_id| Action::NavigateToStoryDetail { story_id, epic_id }))
+            }
         }
+    }
 
-        #[test]
-        fn action_from_quit_action_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
-            let page = HomePage { db: db.clone() };
-            let quit_action = page.action_from("q");
-            assert!(quit_action.is_ok());
-            assert_eq!(quit_action.unwrap(), Some(Action::Exit));
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Page for EpicDetail {
+    fn draw(&self, io: &dyn Io) -> anyhow::Result<()> {
+        let db = self.db.read()?;
+        let epic = db
+            .epics
+            .get(&self.epic_id)
+            .ok_or(MissingEntity::Epic(self.epic_id))?;
+        let widths = ColumnWidths::from_env();
+        let table = self.header(epic, &db.stories, widths, ColorMode::current());
+        io.println(&table);
+        if epic.status_override {
+            io.println("  * Status was set manually and is not derived from stories.");
+        }
+        if epic.archived {
+            io.println("  This epic is archived and hidden from the home page.");
         }
 
-        #[test]
-        fn action_from_new_action_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
-            let page = HomePage { db: db.clone() };
-            let create_action = page.action_from("n");
-            assert_eq!(create_action.unwrap(), Some(Action::CreateEpic));
+        if epic.story_ids.is_empty() {
+            io.println("\n  This epic has no stories.");
+            self.draw_menu(io);
+            return Ok(());
         }
 
-        #[test]
-        fn action_from_view_epic_action_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
-            let page = HomePage { db: db.clone() };
+        if let Some(tag) = self.tag_filter.borrow().as_deref() {
+            io.println(&format!("\n  Showing stories tagged {}.", tag));
+        }
+        let story_ids = self.story_ids(&db)?;
+        let pages = page_count(story_ids.len(), self.rows_per_page);
+        self.page.set(self.page.get().min(pages - 1));
+
+        let mut builder = builder::Builder::new();
+        builder.push_record(["ID", "Name", "Status", "Assignee", "Points", "Logged"]);
+
+        let visible_ids = paginate(&story_ids, self.page.get(), self.rows_per_page);
+        for (index, id) in visible_ids.iter().enumerate() {
+            let story = db.stories.get(id).ok_or(DbError::StoryNotFound(*id))?;
+            builder.push_record([
+                row_id(*id, index, self.selection.get()),
+                constrain_text(story.name.as_str(), widths.name),
+                constrain_text(&story.status.to_string(), widths.description),
+                display_assignee(&story.assignee),
+                display_points(story.points),
+                format_duration(story.logged_minutes()),
+            ]);
+        }
+        let logged = epic
+            .story_ids
+            .iter()
+            .filter_map(|id| db.stories.get(id))
+            .map(Story::logged_minutes)
+            .fold(0, u32::saturating_add);
+
+        let table = builder
+            .build()
+            .with(settings::Style::rounded())
+            .with(
+                LineText::new(
+                    format!(
+                        "Stories ({} total, {} logged)",
+                        &epic.story_ids.len(),
+                        format_duration(logged)
+                    ),
+                    Rows::first(),
+                )
+                .offset(2),
+            )
+            .modify(
+                Columns::single(2),
+                Format::content(|status| {
+                    color_table_column(status, ColorMode::current(), &Theme::current())
+                }),
+            )
+            .to_string();
+
+        io.println(&format!("\n{}", table));
+        io.println(&format!("  Page {}/{}", self.page.get() + 1, pages));
+        self.draw_menu(io);
+        Ok(())
+    }
+
+    fn draw_menu(&self, io: &dyn Io) {
+        let state = self.db.read().unwrap_or_default();
+        let menu = self.menu(&state);
+        let menu = into_table(&menu.iter().map(String::as_str).collect_vec());
+        io.println(&format!("\n\n{}\n\nEnter command:", menu));
+    }
+
+    fn action_from(&self, input: &str, io: &dyn Io) -> anyhow::Result<Option<Action>> {
+        // `U`, `N`, `A`, `D` and `C` are told apart from `u`, `n`, `a`, `d` and `c` by their
+        // case, so they are matched before normalizing.
+        match input.trim() {
+            "U" => {
+                return Ok(Some(Action::UpdateEpicStoriesStatus {
+                    epic_id: self.epic_id,
+                }))
+            }
+            "N" => {
+                return Ok(Some(Action::CreateStories {
+                    epic_id: self.epic_id,
+                }))
+            }
+            "A" => {
+                return Ok(Some(Action::ToggleEpicArchived {
+                    epic_id: self.epic_id,
+                }))
+            }
+            "D" => {
+                return Ok(Some(Action::DeleteStories {
+                    epic_id: self.epic_id,
+                }))
+            }
+            "C" => {
+                return Ok(Some(Action::CloseEpicStories {
+                    epic_id: self.epic_id,
+                }))
+            }
+            _ => {}
+        }
+        // The name after `n` keeps its case, so it is also read before normalizing.
+        if let Some((command, name)) = input.trim().split_once(char::is_whitespace) {
+            if command.eq_ignore_ascii_case("n") {
+                return Ok(Some(Action::CreateStoryNamed {
+                    epic_id: self.epic_id,
+                    name: name.trim().to_owned(),
+                }));
+            }
+        }
+        match normalize_command(input).as_str() {
+            "b" => Ok(Some(Action::NavigateToPreviousPage)),
+            "h" => Ok(Some(Action::NavigateHome)),
+            "u" => Ok(update_epic(self.epic_id, io)),
+            command @ (">>" | "<<") => {
+                let status = self.db.read()?.epics.get(&self.epic_id).map(|epic| {
+                    if command == ">>" {
+                        epic.status.next()
+                    } else {
+                        epic.status.prev()
+                    }
+                });
+                Ok(status.map(|status| Action::SetEpicStatus {
+                    epic_id: self.epic_id,
+                    status,
+                }))
+            }
+            "c" => Ok(Some(Action::DuplicateEpic {
+                epic_id: self.epic_id,
+            })),
+            "dc" => Ok(Some(Action::DeleteClosedStories {
+                epic_id: self.epic_id,
+            })),
+            "v" => Ok(Some(Action::NavigateToDescription { id: self.epic_id })),
+            "x" => Ok(Some(Action::ExportEpic {
+                epic_id: self.epic_id,
+            })),
+            "a" => Ok(Some(Action::UpdateEpicAssignee {
+                epic_id: self.epic_id,
+            })),
+            "t" => {
+                *self.tag_filter.borrow_mut() = filter_tag(io);
+                self.page.set(0);
+                self.selection.set(None);
+                Ok(None)
+            }
+            ">" => {
+                let pages = page_count(self.story_ids(&self.db.read()?)?.len(), self.rows_per_page);
+                self.page.set((self.page.get() + 1).min(pages - 1));
+                self.selection.set(None);
+                Ok(None)
+            }
+            "<" => {
+                self.page.set(self.page.get().saturating_sub(1));
+                self.selection.set(None);
+                Ok(None)
+            }
+            "d" => Ok(Some(Action::DeleteEpic {
+                epic_id: self.epic_id,
+            })),
+            "n" => Ok(Some(Action::CreateStory {
+                epic_id: self.epic_id,
+            })),
+            other => {
+                if let Some((command, id)) = other.split_once(char::is_whitespace) {
+                    if let Some(status) = status_shortcut(command) {
+                        let story_id = id.trim().parse::<u32>().ok().filter(|story_id| {
+                            self.db.read().is_ok_and(|state| {
+                                state
+                                    .epics
+                                    .get(&self.epic_id)
+                                    .is_some_and(|epic| epic.story_ids.contains(story_id))
+                            })
+                        });
+                        return Ok(
+                            story_id.map(|story_id| Action::SetStoryStatus { story_id, status })
+                        );
+                    }
+                    let direction = match command {
+                        "mu" => Direction::Up,
+                        "md" => Direction::Down,
+                        _ => return Ok(None),
+                    };
+                    return Ok(id.trim().parse().ok().map(|story_id| Action::MoveStory {
+                        epic_id: self.epic_id,
+                        story_id,
+                        direction,
+                    }));
+                }
+                if let Ok(story_id) = other.parse::<u32>() {
+                    if self.db.read()?.stories.contains_key(&story_id) {
+                        return Ok(Some(Action::NavigateToStoryDetail {
+                            story_id,
+                            epic_id: self.epic_id,
+                        }));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn selectable_ids(&self) -> anyhow::Result<Vec<u32>> {
+        let story_ids = self.story_ids(&self.db.read()?)?;
+        Ok(paginate(&story_ids, self.page.get(), self.rows_per_page).to_vec())
+    }
+
+    fn selection(&self) -> Option<usize> {
+        self.selection.get()
+    }
+
+    fn set_selection(&self, index: Option<usize>) {
+        self.selection.set(index);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Page for StoryDetail {
+    fn draw(&self, io: &dyn Io) -> anyhow::Result<()> {
+        let widths = ColumnWidths::from_env();
+        let db = self.db.read()?;
+        let mut builder = builder::Builder::new();
+        builder.push_record([
+            "Name",
+            "Description",
+            "Assignee",
+            "Points",
+            "Tags",
+            "Blocked by",
+        ]);
+
+        let story = db
+            .stories
+            .get(&self.story_id)
+            .ok_or(MissingEntity::Story(self.story_id))?;
+        builder.push_record([
+            constrain_text(&story.name, widths.name),
+            preview_text(
+                &story.description,
+                widths.description,
+                DESCRIPTION_PREVIEW_LINES,
+            ),
+            display_assignee(&story.assignee),
+            display_points(story.points),
+            story.tags.join(", "),
+            story
+                .blocked_by
+                .iter()
+                .map(|id| match db.stories.get(id) {
+                    Some(blocker) => {
+                        format!("#{} {}", id, constrain_text(&blocker.name, widths.name))
+                    }
+                    None => format!("#{}", id),
+                })
+                .join("\n"),
+        ]);
+
+        let table = builder
+            .build()
+            .with(settings::Style::rounded())
+            .with(LineText::new(format!("Story #{} (", &self.story_id), Rows::first()).offset(2))
+            .with(
+                LineText::new(format!("{}", &story.status), Rows::first())
+                    .color(color_for_table_header(
+                        &story.status.to_string(),
+                        ColorMode::current(),
+                        &Theme::current(),
+                    ))
+                    .offset(2 + format!("Story #{} (", &self.story_id).len()),
+            )
+            .with(
+                LineText::new(")", Rows::first())
+                    .offset(2 + format!("Story #{} ({}", &self.story_id, &story.status).len()),
+            )
+            .to_string();
+
+        io.println(&table);
+        if !story.checklist.is_empty() {
+            io.println(&format!("\n{}", checklist_lines(&story.checklist, widths)));
+        }
+        if !story.work_log.is_empty() {
+            io.println(&format!("\n{}", work_log_table(story, widths)));
+        }
+        if !story.comments.is_empty() {
+            io.println(&format!("\n{}", comments_table(story, widths)));
+        }
+
+        let mut task_ids = story.task_ids.clone();
+        if task_ids.is_empty() {
+            io.println("\n  This Story has no Tasks. Create a new Task with `n`.");
+            self.draw_menu(io);
+            return Ok(());
+        }
+
+        let mut builder = builder::Builder::new();
+        builder.push_record(["ID", "Name", "Status"]);
+
+        task_ids.sort();
+        for id in task_ids {
+            let task = db.tasks.get(&id).ok_or(DbError::TaskNotFound(id))?;
+            builder.push_record([
+                id.to_string(),
+                constrain_text(task.name.as_str(), widths.name),
+                constrain_text(&task.status.to_string(), widths.description),
+            ]);
+        }
+
+        let table = builder
+            .build()
+            .with(settings::Style::rounded())
+            .with(
+                LineText::new(
+                    format!("Tasks ({} total)", &story.task_ids.len()),
+                    Rows::first(),
+                )
+                .offset(2),
+            )
+            .modify(
+                Columns::single(2),
+                Format::content(|status| {
+                    color_table_column(status, ColorMode::current(), &Theme::current())
+                }),
+            )
+            .to_string();
+
+        io.println(&format!("\n{}", table));
+        self.draw_menu(io);
+        Ok(())
+    }
+
+    fn draw_menu(&self, io: &dyn Io) {
+        let menu = into_table(&[
+            "(b) back",
+            "(h) home",
+            "(u) update",
+            "(<<)/(>>) previous/next status",
+            "(o)/(p)/(r)/(cl) set open/in progress/resolved/closed",
+            "(v) view description",
+            "(a) assign",
+            "(m) assign to me",
+            "(l) tags",
+            "(e) estimate",
+            "(bl) blockers",
+            "(w) log work",
+            "(c) comment",
+            "(+) <text> add checklist item",
+            "(t) <n> check/uncheck item",
+            "(-) <n> remove item",
+            "(n) new task",
+            "(ct) convert to task",
+            "(d) delete",
+            "<ID> view task",
+        ]);
+        io.println(&format!("\n\n{}\n\nEnter command:", menu));
+    }
+
+    fn action_from(&self, input: &str, io: &dyn Io) -> anyhow::Result<Option<Action>> {
+        // The text of a checklist item keeps its case, so it is read before normalizing.
+        if let Some(action) = checklist_action(self.story_id, input)? {
+            return Ok(Some(action));
+        }
+        match normalize_command(input).as_str() {
+            "b" => Ok(Some(Action::NavigateToPreviousPage)),
+            "h" => Ok(Some(Action::NavigateHome)),
+            "u" => Ok(update_story(self.story_id, io)),
+            command @ (">>" | "<<") => {
+                let status = self.db.read()?.stories.get(&self.story_id).map(|story| {
+                    if command == ">>" {
+                        story.status.next()
+                    } else {
+                        story.status.prev()
+                    }
+                });
+                Ok(status.map(|status| Action::SetStoryStatus {
+                    story_id: self.story_id,
+                    status,
+                }))
+            }
+            // `c` adds a comment here, so only `cl` closes the story.
+            command @ ("o" | "p" | "r" | "cl") => {
+                Ok(
+                    status_shortcut(command).map(|status| Action::SetStoryStatus {
+                        story_id: self.story_id,
+                        status,
+                    }),
+                )
+            }
+            "a" => Ok(Some(Action::UpdateStoryAssignee {
+                story_id: self.story_id,
+            })),
+            "m" => Ok(Some(Action::AssignStoryToMe {
+                story_id: self.story_id,
+            })),
+            "v" => Ok(Some(Action::NavigateToDescription { id: self.story_id })),
+            "w" => Ok(Some(Action::LogWork {
+                story_id: self.story_id,
+            })),
+            "c" => Ok(Some(Action::AddComment {
+                story_id: self.story_id,
+            })),
+            "l" => Ok(Some(Action::UpdateStoryTags {
+                story_id: self.story_id,
+            })),
+            "e" => Ok(Some(Action::UpdateStoryPoints {
+                story_id: self.story_id,
+            })),
+            "bl" => Ok(Some(Action::UpdateStoryBlockers {
+                story_id: self.story_id,
+            })),
+            "n" => Ok(Some(Action::CreateTask {
+                story_id: self.story_id,
+            })),
+            "ct" => Ok(Some(Action::ConvertStoryToTask {
+                story_id: self.story_id,
+                epic_id: self.epic_id,
+            })),
+            "d" => Ok(Some(Action::DeleteStory {
+                story_id: self.story_id,
+                epic_id: self.epic_id,
+            })),
+            id => {
+                if let Ok(task_id) = id.parse::<u32>() {
+                    if self.db.read()?.tasks.contains_key(&task_id) {
+                        return Ok(Some(Action::NavigateToTaskDetail {
+                            task_id,
+                            story_id: self.story_id,
+                        }));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Page for DescriptionPage {
+    fn draw(&self, io: &dyn Io) -> anyhow::Result<()> {
+        let db = self.db.read()?;
+        let (title, description) = match (db.epics.get(&self.id), db.stories.get(&self.id)) {
+            (Some(epic), _) => (
+                format!("Epic #{} {}", self.id, epic.name),
+                &epic.description,
+            ),
+            (None, Some(story)) => (
+                format!("Story #{} {}", self.id, story.name),
+                &story.description,
+            ),
+            (None, None) => return Err(MissingEntity::Item(self.id).into()),
+        };
+        if description.is_empty() {
+            io.println(&format!("\n  {} has no description.", title));
+            self.draw_menu(io);
+            return Ok(());
+        }
+
+        // The whole description takes the room of both the name and description columns.
+        let widths = ColumnWidths::from_env();
+        let mut builder = builder::Builder::new();
+        builder.push_record([constrain_text(
+            description,
+            widths.name + widths.description,
+        )]);
+        let table = builder
+            .build()
+            .with(settings::Style::rounded())
+            .with(LineText::new(title, Rows::first()).offset(2))
+            .to_string();
+        io.println(&table);
+        self.draw_menu(io);
+        Ok(())
+    }
+
+    fn draw_menu(&self, io: &dyn Io) {
+        let menu = into_table(&["(b) back", "(h) home"]);
+        io.println(&format!("\n\n{}\n\nEnter command:", menu));
+    }
+
+    fn action_from(&self, input: &str, _io: &dyn Io) -> anyhow::Result<Option<Action>> {
+        match normalize_command(input).as_str() {
+            "b" => Ok(Some(Action::NavigateToPreviousPage)),
+            "h" => Ok(Some(Action::NavigateHome)),
+            _ => Ok(None),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Page for TaskDetail {
+    fn draw(&self, io: &dyn Io) -> anyhow::Result<()> {
+        let widths = ColumnWidths::from_env();
+        let db = self.db.read()?;
+        let mut builder = builder::Builder::new();
+        builder.push_record(["Name", "Description"]);
+        let task = db
+            .tasks
+            .get(&self.task_id)
+            .ok_or(MissingEntity::Task(self.task_id))?;
+        builder.push_record([
+            constrain_text(&task.name, widths.name),
+            constrain_text(&task.description, widths.description),
+        ]);
+        let table = builder
+            .build()
+            .with(settings::Style::rounded())
+            .with(LineText::new(format!("Task #{} (", &self.task_id), Rows::first()).offset(2))
+            .with(
+                LineText::new(format!("{}", &task.status), Rows::first())
+                    .color(color_for_table_header(
+                        &task.status.to_string(),
+                        ColorMode::current(),
+                        &Theme::current(),
+                    ))
+                    .offset(2 + format!("Task #{} (", &self.task_id).len()),
+            )
+            .with(
+                LineText::new(")", Rows::first())
+                    .offset(2 + format!("Task #{} ({}", &self.task_id, &task.status).len()),
+            )
+            .to_string();
+        io.println(&table);
+        if !task.checklist.is_empty() {
+            io.println(&format!("\n{}", checklist_lines(&task.checklist, widths)));
+        }
+        self.draw_menu(io);
+        Ok(())
+    }
+
+    fn draw_menu(&self, io: &dyn Io) {
+        let menu = into_table(&[
+            "(b) back",
+            "(u) update",
+            "(+) <text> add checklist item",
+            "(t) <n> check/uncheck item",
+            "(-) <n> remove item",
+            "(cs) convert to story",
+            "(d) delete",
+        ]);
+        io.println(&format!("\n\n{}\n\nEnter command:", menu));
+    }
+
+    fn action_from(&self, input: &str, io: &dyn Io) -> anyhow::Result<Option<Action>> {
+        if let Some(action) = checklist_action(self.task_id, input)? {
+            return Ok(Some(action));
+        }
+        match normalize_command(input).as_str() {
+            "b" => Ok(Some(Action::NavigateToPreviousPage)),
+            "u" => Ok(update_task(self.task_id, io)),
+            "cs" => Ok(Some(Action::ConvertTaskToStory {
+                task_id: self.task_id,
+                story_id: self.story_id,
+            })),
+            "d" => Ok(Some(Action::DeleteTask {
+                task_id: self.task_id,
+                story_id: self.story_id,
+            })),
+            _ => Ok(None),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// `status_legend` returns a line naming each status in its color, in the order of
+/// progress. `None` is returned when colors are disabled, since the legend would only
+/// explain colors.
+fn status_legend(colors: ColorMode, theme: &Theme) -> Option<String> {
+    if colors == ColorMode::Disabled {
+        return None;
+    }
+    let statuses = [
+        Status::Open,
+        Status::Blocked,
+        Status::InProgress,
+        Status::Resolved,
+        Status::Closed,
+    ];
+    let legend = statuses
+        .iter()
+        .map(|status| color_table_column(&status.to_string(), colors, theme))
+        .join(" · ");
+    Some(format!("  Status: {}", legend))
+}
+
+/// `epic_progress` returns how many of the `epic`'s stories, looked up in `stories`, are
+/// Closed and Resolved, along with the number of its stories.
+fn epic_progress(epic: &Epic, stories: &BTreeMap<u32, Story>) -> (usize, usize, usize) {
+    let statuses: Vec<_> = epic
+        .story_ids
+        .iter()
+        .filter_map(|id| stories.get(id))
+        .map(|story| &story.status)
+        .collect();
+    let count = |wanted: Status| statuses.iter().filter(|status| ***status == wanted).count();
+    (
+        count(Status::Closed),
+        count(Status::Resolved),
+        statuses.len(),
+    )
+}
+
+/// `epic_points` returns the total story points of the `epic`'s stories, looked up in
+/// `stories`, and how many of them are in Resolved or Closed stories. Stories without an
+/// estimate count as 0.
+fn epic_points(epic: &Epic, stories: &BTreeMap<u32, Story>) -> (u32, u32) {
+    epic.story_ids.iter().filter_map(|id| stories.get(id)).fold(
+        (0, 0),
+        |(total, completed), story| {
+            let points = story.points.unwrap_or(0);
+            let done = matches!(story.status, Status::Resolved | Status::Closed);
+            let completed = completed.saturating_add(if done { points } else { 0 });
+            (total.saturating_add(points), completed)
+        },
+    )
+}
+
+/// `recent_record` returns the id, type, name and status of the epic or story of `item` in
+/// `state`, or `None` if it no longer exists.
+fn recent_record<'a>(
+    state: &'a DatabaseState,
+    item: &RecentItem,
+) -> Option<(u32, &'static str, &'a String, &'a Status)> {
+    match *item {
+        RecentItem::Epic(id) => state
+            .epics
+            .get(&id)
+            .map(|epic| (id, "Epic", &epic.name, &epic.status)),
+        RecentItem::Story { story_id, .. } => state
+            .stories
+            .get(&story_id)
+            .map(|story| (story_id, "Story", &story.name, &story.status)),
+    }
+}
+
+/// `home_recent` returns the recently viewed items of `state` shown on the home page.
+fn home_recent(state: &DatabaseState) -> Vec<RecentItem> {
+    state
+        .recent
+        .iter()
+        .filter(|item| state.is_viewable(item))
+        .take(HOME_RECENT_LENGTH)
+        .copied()
+        .collect()
+}
+
+/// `recent_shortcut` parses a shortcut to a recently viewed item on the home page, such as
+/// "r2", into the index of the item. `None` is returned for anything else.
+fn recent_shortcut(command: &str) -> Option<usize> {
+    let number = command.strip_prefix('r')?.parse::<usize>().ok()?;
+    (1..=HOME_RECENT_LENGTH)
+        .contains(&number)
+        .then(|| number - 1)
+}
+
+/// `view_recent` returns the action navigating to the epic or story of `item`.
+fn view_recent(item: RecentItem) -> Action {
+    match item {
+        RecentItem::Epic(epic_id) => Action::NavigateToEpicDetail { epic_id },
+        RecentItem::Story { story_id, epic_id } => {
+            Action::NavigateToStoryDetail { story_id, epic_id }
+        }
+    }
+}
+
+/// `story_total` returns the number of stories in the epics `epic_ids` of `state`.
+fn story_total(state: &DatabaseState, epic_ids: &[u32]) -> usize {
+    epic_ids
+        .iter()
+        .filter_map(|id| state.epics.get(id))
+        .map(|epic| epic.story_ids.len())
+        .sum()
+}
+
+/// `filter_epics_by_assignee` returns the sorted ids of the epics in `state` that are
+/// assigned to `assignee`, or that have at least one story assigned to `assignee`.
+fn filter_epics_by_assignee(state: &DatabaseState, assignee: &str) -> Vec<u32> {
+    state
+        .epics
+        .iter()
+        .filter(|(_, epic)| {
+            epic.assignee.as_deref() == Some(assignee)
+                || epic.story_ids.iter().any(|id| {
+                    state
+                        .stories
+                        .get(id)
+                        .is_some_and(|story| story.assignee.as_deref() == Some(assignee))
+                })
+        })
+        .map(|(id, _)| *id)
+        .collect()
+}
+
+/// `filter_epics_by_text` returns the sorted ids of the epics whose name or description
+/// contains `text`, ignoring case.
+fn filter_epics_by_text(state: &DatabaseState, text: &str) -> Vec<u32> {
+    let text = text.to_lowercase();
+    state
+        .epics
+        .iter()
+        .filter(|(_, epic)| {
+            epic.name.to_lowercase().contains(&text)
+                || epic.description.to_lowercase().contains(&text)
+        })
+        .map(|(id, _)| *id)
+        .collect()
+}
+
+/// `rank_epics_fuzzily` returns the ids of the epics whose name or description fuzzily
+/// matches `text` ignoring case, with the score of their best match. The best matches come
+/// first, and epics with the same score are ordered by id so that the ranking is stable.
+pub fn rank_epics_fuzzily(state: &DatabaseState, text: &str) -> Vec<(u32, i64)> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+    state
+        .epics
+        .iter()
+        .filter_map(|(id, epic)| {
+            let name = matcher.fuzzy_match(&epic.name, text);
+            let description = matcher.fuzzy_match(&epic.description, text);
+            Some((*id, name.max(description)?))
+        })
+        .sorted_by(|(id, score), (other_id, other_score)| {
+            other_score.cmp(score).then(id.cmp(other_id))
+        })
+        .collect()
+}
+
+/// `filter_epics_by_pattern` returns the sorted ids of the epics whose name or description
+/// matches `pattern`.
+fn filter_epics_by_pattern(state: &DatabaseState, pattern: &Regex) -> Vec<u32> {
+    state
+        .epics
+        .iter()
+        .filter(|(_, epic)| pattern.is_match(&epic.name) || pattern.is_match(&epic.description))
+        .map(|(id, _)| *id)
+        .collect()
+}
+
+/// `compile_pattern` compiles the regular expression `pattern`. Returns `Err` explaining
+/// why the pattern is invalid.
+fn compile_pattern(pattern: &str) -> anyhow::Result<Regex> {
+    Regex::new(pattern).map_err(|error| anyhow!("invalid search pattern: {}", error))
+}
+
+/// `filter_stories_by_tag` returns the sorted ids in `story_ids` of the stories that are
+/// tagged with `tag`.
+fn filter_stories_by_tag(story_ids: &[u32], stories: &BTreeMap<u32, Story>, tag: &str) -> Vec<u32> {
+    story_ids
+        .iter()
+        .filter(|id| stories.get(id).is_some_and(|story| story.has_tag(tag)))
+        .copied()
+        .collect()
+}
+
+/// `checklist_lines` renders the items of `checklist` one per line, numbered from 1 and
+/// marked `[x]` once done, below a title counting how many are done.
+fn checklist_lines(checklist: &[ChecklistItem], widths: ColumnWidths) -> String {
+    let done = checklist.iter().filter(|item| item.done).count();
+    let mut lines = vec![format!("  Checklist ({}/{} done)", done, checklist.len())];
+    for (index, item) in checklist.iter().enumerate() {
+        let mark = if item.done { "[x]" } else { "[ ]" };
+        lines.push(format!(
+            "  {}. {} {}",
+            index + 1,
+            mark,
+            constrain_text(&item.text, widths.name + widths.description)
+        ));
+    }
+    lines.join("\n")
+}
+
+/// `checklist_action` returns the action for the checklist commands of the story or task
+/// `id`: `+ <text>` adds an item, `t <n>` checks or unchecks item `n`, and `- <n>`
+/// removes it. Items are numbered from 1. Returns `None` for other input, or `Err` if `n` is
+/// 0.
+fn checklist_action(id: u32, input: &str) -> anyhow::Result<Option<Action>> {
+    let input = input.trim();
+    if let Some(text) = input.strip_prefix('+') {
+        return Ok(Some(Action::AddChecklistItem {
+            id,
+            text: text.trim().to_owned(),
+        }));
+    }
+    let (remove, number) = match input.strip_prefix('-') {
+        Some(number) => (true, number),
+        None => match input.split_once(char::is_whitespace) {
+            Some((command, number)) if command.eq_ignore_ascii_case("t") => (false, number),
+            _ => return Ok(None),
+        },
+    };
+    let Ok(number) = number.trim().parse::<usize>() else {
+        return Ok(None);
+    };
+    let Some(index) = number.checked_sub(1) else {
+        bail!("checklist items are numbered from 1");
+    };
+    Ok(Some(if remove {
+        Action::RemoveChecklistItem { id, index }
+    } else {
+        Action::ToggleChecklistItem { id, index }
+    }))
+}
+
+/// `work_log_table` renders the latest entries of the work log of `story`, titled with the
+/// total time logged.
+fn work_log_table(story: &Story, widths: ColumnWidths) -> String {
+    let mut builder = builder::Builder::new();
+    builder.push_record(["Date", "Time", "Note"]);
+    for entry in story.work_log.iter().rev().take(WORK_LOG_LENGTH) {
+        builder.push_record([
+            entry.date.clone(),
+            format_duration(entry.minutes),
+            constrain_text(&entry.note, widths.description),
+        ]);
+    }
+    builder
+        .build()
+        .with(settings::Style::rounded())
+        .with(
+            LineText::new(
+                format!("Logged {}", format_duration(story.logged_minutes())),
+                Rows::first(),
+            )
+            .offset(2),
+        )
+        .to_string()
+}
+
+/// `comments_table` renders the comments on `story`, newest first, titled with their count.
+fn comments_table(story: &Story, widths: ColumnWidths) -> String {
+    let mut builder = builder::Builder::new();
+    builder.push_record(["When", "Author", "Comment"]);
+    for comment in story.comments.iter().rev() {
+        builder.push_record([
+            comment
+                .at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+            comment.author.clone(),
+            constrain_text(&comment.body, widths.description),
+        ]);
+    }
+    builder
+        .build()
+        .with(settings::Style::rounded())
+        .with(
+            LineText::new(
+                format!("Comments ({})", story.comments.len()),
+                Rows::first(),
+            )
+            .offset(2),
+        )
+        .to_string()
+}
+
+/// `row_id` renders the `id` of the row at `index` in a table, marked if the row is the
+/// `selection`.
+fn row_id(id: u32, index: usize, selection: Option<usize>) -> String {
+    if selection == Some(index) {
+        format!("> {}", id)
+    } else {
+        id.to_string()
+    }
+}
+
+/// `normalize_command` trims and lowercases the user's `input` so that commands match
+/// regardless of stray whitespace or case.
+fn normalize_command(input: &str) -> String {
+    input.trim().to_lowercase()
+}
+
+/// `status_shortcut` returns the status that the shortcut `command` sets a story to: `o` for
+/// Open, `p` for In Progress, `r` for Resolved, and `cl` for Closed. Closing is not `c`, which
+/// other commands use, e.g. to duplicate an epic, so that a forgotten ID cannot run them.
+fn status_shortcut(command: &str) -> Option<Status> {
+    match command {
+        "o" => Some(Status::Open),
+        "p" => Some(Status::InProgress),
+        "r" => Some(Status::Resolved),
+        "cl" => Some(Status::Closed),
+        _ => None,
+    }
+}
+
+/// `display_points` formats optional story points for a table column.
+fn display_points(points: Option<u32>) -> String {
+    points.map_or("—".to_owned(), |points| points.to_string())
+}
+
+/// `display_assignee` formats an optional assignee for a table column.
+fn display_assignee(assignee: &Option<String>) -> String {
+    assignee.clone().unwrap_or("—".to_owned())
+}
+
+/// `into_table` lays the menu `opts` out to the width of the terminal, see `menu_table`.
+fn into_table(opts: &[&str]) -> String {
+    let width = terminal_columns()
+        .filter(|columns| *columns > 0)
+        .unwrap_or(DEFAULT_MENU_WIDTH);
+    menu_table(opts, width)
+}
+
+/// `menu_table` lays the menu `opts` out in a table at most `width` columns wide, with as
+/// many options per row as fit. Options wider than `width` get a row each.
+fn menu_table(opts: &[&str], width: usize) -> String {
+    let table = |per_row: usize| {
+        let mut builder = Builder::new();
+        for row in opts.chunks(per_row) {
+            let mut row: Vec<_> = row.iter().map(|opt| opt.to_string()).collect();
+            row.resize(per_row, String::new());
+            builder.push_record(row);
+        }
+        builder
+            .build()
+            .with(settings::Modify::new(Segment::all()).with(Alignment::center()))
+            .with(settings::Style::modern_rounded())
+            .to_string()
+    };
+    let fits = |table: &String| table.lines().all(|line| line.chars().count() <= width);
+    (2..=opts.len())
+        .rev()
+        .map(table)
+        .find(fits)
+        .unwrap_or_else(|| table(1))
+}
+
+fn filter_assignee(io: &dyn Io) -> Option<String> {
+    io.println("Show epics for which assignee? (leave empty to show all)");
+    io.prompt_line()
+}
+
+fn search_text(io: &dyn Io) -> Option<String> {
+    io.println(
+        "Search epic names and descriptions for? (start with ' for an exact match | leave empty \
+         to show all)",
+    );
+    io.prompt_line()
+}
+
+fn search_pattern(io: &dyn Io) -> Option<String> {
+    io.println("Search epic names and descriptions by which regex? (leave empty to show all)");
+    io.prompt_line()
+}
+
+fn filter_tag(io: &dyn Io) -> Option<String> {
+    io.println("Show stories with which tag? (leave empty to show all)");
+    io.prompt_line().map(|tag| normalize_tag(&tag))
+}
+
+fn update_epic(epic_id: u32, io: &dyn Io) -> Option<Action> {
+    io.println("Update which field?\n\t(1) Name\n\t(2) Description\n\t(3) Status");
+    io.println("(x) cancel");
+
+    match io.prompt_line().unwrap_or("".into()).as_str() {
+        "1" => Some(Action::UpdateEpicName { epic_id }),
+        "2" => Some(Action::UpdateEpicDescription { epic_id }),
+        "3" => Some(Action::UpdateEpicStatus { epic_id }),
+        _ => None,
+    }
+}
+
+fn update_story(story_id: u32, io: &dyn Io) -> Option<Action> {
+    io.println("Update which field?\n\t(1) Name\n\t(2) Description\n\t(3) Status");
+    io.println("(x) cancel");
+
+    match io.prompt_line().unwrap_or("".into()).as_str() {
+        "1" => Some(Action::UpdateStoryName { story_id }),
+        "2" => Some(Action::UpdateStoryDescription { story_id }),
+        "3" => Some(Action::UpdateStoryStatus { story_id }),
+        _ => None,
+    }
+}
+
+fn update_task(task_id: u32, io: &dyn Io) -> Option<Action> {
+    io.println("Update which field?\n\t(1) Name\n\t(2) Description\n\t(3) Status");
+    io.println("(x) cancel");
+
+    match io.prompt_line().unwrap_or("".into()).as_str() {
+        "1" => Some(Action::UpdateTaskName { task_id }),
+        "2" => Some(Action::UpdateTaskDescription { task_id }),
+        "3" => Some(Action::UpdateTaskStatus { task_id }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::test_utils::MockDatabase, ui::io::ScriptedIo};
+
+    mod home_page {
+        use super::*;
+
+        #[test]
+        fn epic_ids_should_hide_archived_epics_until_shown() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let archived_id = db.create_epic(&Epic::new("Epic 2", "")).unwrap();
+            db.set_epic_archived(archived_id, true).unwrap();
+            let page = HomePage::new(db.clone());
+            let state = db.read().unwrap();
+
+            assert_eq!(page.epic_ids(&state), vec![epic_id]);
+            assert_eq!(page.hidden_archived_count(&state), 1);
+            let io = ScriptedIo::default();
+            page.draw(&io).unwrap();
+            assert!(io.output().contains("1 archived epic hidden"));
+            assert!(io.output().contains("(A) show archived"));
+
+            assert_eq!(page.action_from("A", &io).unwrap(), None);
+            assert_eq!(page.epic_ids(&state), vec![epic_id, archived_id]);
+            assert_eq!(page.hidden_archived_count(&state), 0);
+            assert_eq!(page.action_from("A", &io).unwrap(), None);
+            assert_eq!(page.epic_ids(&state), vec![epic_id]);
+        }
+
+        #[test]
+        fn action_from_recent_shortcut_should_open_recent_item() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            db.record_recent(RecentItem::Epic(epic_id)).unwrap();
+            db.record_recent(RecentItem::Story { story_id, epic_id })
+                .unwrap();
+            db.record_recent(RecentItem::Epic(999)).unwrap();
+            let page = HomePage::new(db.clone());
+            let io = ScriptedIo::default();
+
+            assert_eq!(
+                page.action_from("r1", &io).unwrap(),
+                Some(Action::NavigateToStoryDetail { story_id, epic_id })
+            );
+            assert_eq!(
+                page.action_from(" R2 ", &io).unwrap(),
+                Some(Action::NavigateToEpicDetail { epic_id })
+            );
+            assert_eq!(page.action_from("r3", &io).unwrap(), None);
+            assert_eq!(recent_shortcut("r0"), None);
+            assert_eq!(recent_shortcut("r6"), None);
+            assert_eq!(recent_shortcut("rx"), None);
+            assert_eq!(recent_shortcut("r5"), Some(4));
+
+            let output = ScriptedIo::default();
+            page.draw(&output).unwrap();
+            assert!(output.output().contains("r2 │ Epic"));
+        }
+
+        #[test]
+        fn draw_should_succeed() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let page = HomePage::new(db);
+            assert!(page.draw(&ScriptedIo::default()).is_ok());
+        }
+
+        #[test]
+        fn action_from_quit_action_should_succeed() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let page = HomePage::new(db.clone());
+            let quit_action = page.action_from("q", &ScriptedIo::default());
+            assert!(quit_action.is_ok());
+            assert_eq!(quit_action.unwrap(), Some(Action::Exit));
+        }
+
+        #[test]
+        fn action_from_new_action_should_succeed() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let page = HomePage::new(db.clone());
+            let create_action = page.action_from("n", &ScriptedIo::default());
+            assert_eq!(create_action.unwrap(), Some(Action::CreateEpic));
+        }
+
+        #[test]
+        fn menu_should_fit_width() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let page = HomePage::new(db);
+            let entries = page.menu_entries();
+            for width in [40, 80, 120, 200] {
+                let menu = menu_table(&entries, width);
+                assert!(menu.lines().all(|line| line.chars().count() <= width));
+                assert!(entries.iter().all(|entry| menu.contains(entry)));
+            }
+            assert!(menu_table(&entries, 80).lines().count() > 3);
+            assert_eq!(menu_table(&["(b) back", "(h) home"], 80).lines().count(), 3);
+        }
+
+        #[test]
+        fn action_from_should_ignore_case_and_whitespace() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+            let page = HomePage::new(db.clone());
+            assert_eq!(
+                page.action_from("Q", &ScriptedIo::default()).unwrap(),
+                Some(Action::Exit)
+            );
+            assert_eq!(
+                page.action_from(" N ", &ScriptedIo::default()).unwrap(),
+                Some(Action::CreateEpic)
+            );
+            assert_eq!(
+                page.action_from(&format!(" {} ", epic_id), &ScriptedIo::default())
+                    .unwrap(),
+                Some(Action::NavigateToEpicDetail { epic_id })
+            );
+        }
+
+        #[test]
+        fn action_from_replace_action_should_succeed() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let page = HomePage::new(db.clone());
+            assert_eq!(
+                page.action_from("f", &ScriptedIo::default()).unwrap(),
+                Some(Action::ReplaceInDescriptions)
+            );
+        }
+
+        #[test]
+        fn action_from_view_epic_action_should_succeed() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let page = HomePage::new(db.clone());
+
+            let epic = Epic::new("Epic 1", "Epic 1 description");
+            let epic_id = db.create_epic(&epic).unwrap();
+            let view_epic_action = page.action_from(&epic_id.to_string(), &ScriptedIo::default());
+            assert!(view_epic_action.is_ok());
+            assert_eq!(
+                view_epic_action.unwrap(),
+                Some(Action::NavigateToEpicDetail { epic_id })
+            );
+        }
+
+        #[test]
+        fn action_from_view_epic_action_should_fail_if_invalid_input() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let page = HomePage::new(db.clone());
+            let view_epic_action = page.action_from("invalid", &ScriptedIo::default());
+            assert!(view_epic_action.is_ok());
+            assert!(view_epic_action.unwrap().is_none());
+        }
+
+        #[test]
+        fn action_from_view_epic_action_should_fail_if_invalid_epic_id() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let page = HomePage::new(db.clone());
+
+            let invalid_epic_id = 999u32.to_string();
+            let view_epic_action = page.action_from(&invalid_epic_id, &ScriptedIo::default());
+            assert!(view_epic_action.is_ok());
+            assert!(view_epic_action.unwrap().is_none());
+        }
+
+        #[test]
+        fn filter_epics_by_assignee_should_match_epic_and_story_assignees() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let assigned_epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            db.update_epic_assignee(assigned_epic_id, Some("alice"))
+                .unwrap();
+            let story_epic_id = db.create_epic(&Epic::new("Epic 2", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), story_epic_id)
+                .unwrap();
+            db.update_story_assignee(story_id, Some("alice")).unwrap();
+            let other_epic_id = db.create_epic(&Epic::new("Epic 3", "")).unwrap();
+            db.update_epic_assignee(other_epic_id, Some("bob")).unwrap();
+            db.create_epic(&Epic::new("Epic 4", "")).unwrap();
+
+            let state = db.read().unwrap();
+            assert_eq!(
+                filter_epics_by_assignee(&state, "alice"),
+                vec![assigned_epic_id, story_epic_id]
+            );
+            assert_eq!(filter_epics_by_assignee(&state, "bob"), vec![other_epic_id]);
+            assert!(filter_epics_by_assignee(&state, "carol").is_empty());
+        }
+
+        #[test]
+        fn filter_epics_by_text_should_ignore_case() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let name_id = db.create_epic(&Epic::new("Login page", "")).unwrap();
+            let description_id = db
+                .create_epic(&Epic::new("Epic 2", "Fix the LOGIN flow"))
+                .unwrap();
+            db.create_epic(&Epic::new("Epic 3", "Billing")).unwrap();
+
+            let state = db.read().unwrap();
+            assert_eq!(
+                filter_epics_by_text(&state, "login"),
+                vec![name_id, description_id]
+            );
+            assert!(filter_epics_by_text(&state, "logout").is_empty());
+        }
+
+        #[test]
+        fn filter_epics_by_pattern_should_match_name_or_description() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let name_id = db.create_epic(&Epic::new("v2 API", "")).unwrap();
+            let description_id = db
+                .create_epic(&Epic::new("Epic 2", "Ship v10 of the CLI"))
+                .unwrap();
+            db.create_epic(&Epic::new("Epic 3", "v-next")).unwrap();
+
+            let state = db.read().unwrap();
+            let pattern = compile_pattern(r"^v\d+\b|\bv\d+ of").unwrap();
+            assert_eq!(
+                filter_epics_by_pattern(&state, &pattern),
+                vec![name_id, description_id]
+            );
+            let pattern = compile_pattern("^Epic 4$").unwrap();
+            assert!(filter_epics_by_pattern(&state, &pattern).is_empty());
+        }
+
+        #[test]
+        fn rank_epics_fuzzily_should_tolerate_dropped_characters() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let login_id = db.create_epic(&Epic::new("Login page", "")).unwrap();
+            let other_id = db
+                .create_epic(&Epic::new("Billing", "Invoices for logged in users"))
+                .unwrap();
+            db.create_epic(&Epic::new("Search", "")).unwrap();
+
+            let state = db.read().unwrap();
+            let ranked = rank_epics_fuzzily(&state, "LGIN");
+            assert_eq!(
+                ranked.iter().map(|(id, _)| *id).collect_vec(),
+                vec![login_id, other_id]
+            );
+            assert!(ranked[0].1 > ranked[1].1);
+            assert!(filter_epics_by_text(&state, "lgin").is_empty());
+        }
+
+        #[test]
+        fn rank_epics_fuzzily_should_order_ties_by_id() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let ids = (0..3)
+                .map(|_| db.create_epic(&Epic::new("Release", "")).unwrap())
+                .collect_vec();
+            let best_id = db.create_epic(&Epic::new("rel", "")).unwrap();
+
+            let state = db.read().unwrap();
+            let ranked = rank_epics_fuzzily(&state, "rel");
+            assert_eq!(ranked[0].0, best_id);
+            assert_eq!(ranked[1..].iter().map(|(id, _)| *id).collect_vec(), ids);
+            assert_eq!(rank_epics_fuzzily(&state, "rel"), ranked);
+        }
+
+        #[test]
+        fn epic_search_from_query_should_match_exactly_after_quote() {
+            assert!(matches!(
+                EpicSearch::from_query("lgin"),
+                EpicSearch::Fuzzy(text) if text == "lgin"
+            ));
+            assert!(matches!(
+                EpicSearch::from_query("'login"),
+                EpicSearch::Text(text) if text == "login"
+            ));
+        }
+
+        #[test]
+        fn compile_pattern_should_fail_on_invalid_pattern() {
+            let error = compile_pattern("(unclosed").unwrap_err();
+            assert!(error.to_string().starts_with("invalid search pattern: "));
+        }
+
+        #[test]
+        fn epic_ids_should_combine_assignee_filter_and_search() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Login page", "")).unwrap();
+            db.update_epic_assignee(epic_id, Some("alice")).unwrap();
+            let other_id = db.create_epic(&Epic::new("Login flow", "")).unwrap();
+            db.update_epic_assignee(other_id, Some("bob")).unwrap();
+            let page = HomePage::new(db.clone());
+            let state = db.read().unwrap();
+
+            *page.search.borrow_mut() = Some(EpicSearch::Text("LOGIN".to_owned()));
+            assert_eq!(page.epic_ids(&state), vec![epic_id, other_id]);
+            *page.assignee_filter.borrow_mut() = Some("bob".to_owned());
+            assert_eq!(page.epic_ids(&state), vec![other_id]);
+            *page.search.borrow_mut() =
+                Some(EpicSearch::Pattern(compile_pattern("page$").unwrap()));
+            assert!(page.epic_ids(&state).is_empty());
+            assert!(page.draw(&ScriptedIo::default()).is_ok());
+        }
+
+        #[test]
+        fn status_legend_should_name_every_status_in_its_color() {
+            let theme = Theme::default();
+            let legend = status_legend(ColorMode::Enabled, &theme).unwrap();
+            for status in ["Open", "Blocked", "In Progress", "Resolved", "Closed"] {
+                assert!(legend.contains(&color_table_column(status, ColorMode::Enabled, &theme)));
+            }
+            assert_eq!(status_legend(ColorMode::Disabled, &theme), None);
+        }
+
+        #[test]
+        fn story_total_should_count_stories_of_every_epic() {
+            let db = JiraDatabase::from_database(Box::new(MockDatabase::new()));
+            let big_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            for _ in 0..3 {
+                db.create_story(&Story::new("Story", ""), big_id).unwrap();
+            }
+            let small_id = db.create_epic(&Epic::new("Epic 2", "")).unwrap();
+            db.create_story(&Story::new("Story", ""), small_id).unwrap();
+            let empty_id = db.create_epic(&Epic::new("Epic 3", "")).unwrap();
+
+            let state = db.read().unwrap();
+            assert_eq!(state.epics[&big_id].story_ids.len(), 3);
+            assert_eq!(story_total(&state, &[big_id, small_id, empty_id]), 4);
+            assert_eq!(story_total(&state, &[small_id]), 1);
+        }
+
+        #[test]
+        fn epic_progress_should_be_empty_for_empty_epic() {
+            let epic = Epic::new("Epic 1", "Epic 1 description");
+            assert_eq!(epic_progress(&epic, &BTreeMap::new()), (0, 0, 0));
+        }
+
+        #[test]
+        fn epic_progress_should_count_closed_and_resolved_stories() {
+            let mut epic = Epic::new("Epic 1", "Epic 1 description");
+            let mut stories = BTreeMap::new();
+            for (id, status) in [
+                Status::Open,
+                Status::InProgress,
+                Status::Resolved,
+                Status::Closed,
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                let mut story = Story::new("Story", "Story description");
+                story.status = status;
+                stories.insert(id as u32, story);
+                epic.story_ids.push(id as u32);
+            }
+            assert_eq!(epic_progress(&epic, &stories), (1, 1, 4));
+        }
+
+        #[test]
+        fn epic_points_should_count_missing_estimates_as_zero() {
+            let mut epic = Epic::new("Epic 1", "Epic 1 description");
+            let mut stories = BTreeMap::new();
+            for (id, (status, points)) in [
+                (Status::Open, Some(5)),
+                (Status::InProgress, None),
+                (Status::Resolved, Some(3)),
+                (Status::Closed, Some(2)),
+                (Status::Closed, None),
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                let mut story = Story::new("Story", "Story description");
+                story.status = status;
+                story.points = points;
+                stories.insert(id as u32, story);
+                epic.story_ids.push(id as u32);
+            }
+            assert_eq!(epic_points(&epic, &stories), (10, 5));
+            assert_eq!(epic_points(&epic, &BTreeMap::new()), (0, 0));
+            assert_eq!(display_points(None), "—");
+            assert_eq!(display_points(Some(8)), "8");
+        }
+
+        #[test]
+        fn epic_points_should_not_overflow() {
+            let mut epic = Epic::new("Epic 1", "");
+            let mut stories = BTreeMap::new();
+            for (id, status) in [Status::Closed, Status::Resolved, Status::Open]
+                .into_iter()
+                .enumerate()
+            {
+                let mut story = Story::new("Story", "");
+                story.status = status;
+                story.points = Some(3000000000);
+                stories.insert(id as u32, story);
+                epic.story_ids.push(id as u32);
+            }
+            assert_eq!(epic_points(&epic, &stories), (u32::MAX, u32::MAX));
+        }
+
+        #[test]
+        fn epic_progress_should_be_complete_when_all_stories_closed() {
+            let mut epic = Epic::new("Epic 1", "Epic 1 description");
+            let mut stories = BTreeMap::new();
+            for id in 0..3u32 {
+                let mut story = Story::new("Story", "Story description");
+                story.status = Status::Closed;
+                stories.insert(id, story);
+                epic.story_ids.push(id);
+            }
+            assert_eq!(epic_progress(&epic, &stories), (3, 0, 3));
+        }
+    }
+
+    mod history_page {
+        use crate::models::Epic;
+
+        use super::*;
+
+        #[test]
+        fn draw_should_succeed() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let page = HistoryPage { db: db.clone() };
+            assert!(page.draw(&ScriptedIo::default()).is_ok());
+
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            db.record_audit("Created epic", Some(epic_id)).unwrap();
+            assert!(page.draw(&ScriptedIo::default()).is_ok());
+        }
+
+        #[test]
+        fn action_from_back_action_should_succeed() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let page = HistoryPage { db };
+            let back_action = page.action_from("b", &ScriptedIo::default());
+            assert_eq!(back_action.unwrap(), Some(Action::NavigateToPreviousPage));
+        }
+    }
+
+    mod modified_page {
+        use crate::models::Epic;
+
+        use super::*;
+
+        #[test]
+        fn draw_should_list_modified_items() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let page = ModifiedPage { db: db.clone() };
+            let io = ScriptedIo::default();
+            page.draw(&io).unwrap();
+            assert!(io.output().contains("Nothing has been modified yet."));
+
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            db.record_audit("Created epic", Some(epic_id)).unwrap();
+            db.record_audit("Created story", Some(story_id)).unwrap();
+            let io = ScriptedIo::default();
+            page.draw(&io).unwrap();
+            assert!(io.output().contains("Recently modified"));
+            assert!(io.output().contains("Story 1"));
+
+            assert_eq!(
+                page.action_from(&story_id.to_string(), &io).unwrap(),
+                Some(Action::NavigateToStoryDetail { story_id, epic_id })
+            );
+            assert_eq!(page.action_from("999", &io).unwrap(), None);
+        }
+    }
+
+    mod settings_page {
+        use super::*;
+
+        #[test]
+        fn action_from_should_pick_setting_by_number() {
+            let config = Rc::new(RefCell::new(Config::default()));
+            let page = SettingsPage {
+                config: config.clone(),
+            };
+            let io = ScriptedIo::default();
+            assert_eq!(
+                page.action_from("1", &io).unwrap(),
+                Some(Action::UpdateSetting {
+                    setting: Setting::RowsPerPage
+                })
+            );
+            assert_eq!(
+                page.action_from(" 4 ", &io).unwrap(),
+                Some(Action::UpdateSetting {
+                    setting: Setting::Theme
+                })
+            );
+            assert_eq!(page.action_from("0", &io).unwrap(), None);
+            assert_eq!(page.action_from("5", &io).unwrap(), None);
+            assert_eq!(
+                page.action_from("b", &io).unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+
+            config.borrow_mut().rows_per_page = Some(7);
+            page.draw(&io).unwrap();
+            assert!(io.output().contains("Rows per page"));
+            assert!(io.output().contains(" 7 "));
+        }
+    }
+
+    mod trash_page {
+        use super::*;
+
+        #[test]
+        fn action_from_should_restore_only_trashed_items() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let page = TrashPage { db: db.clone() };
+            let io = ScriptedIo::default();
+
+            page.draw(&io).unwrap();
+            assert!(io.output().contains("The trash is empty."));
+            assert_eq!(
+                page.action_from(&format!("r {}", story_id), &io).unwrap(),
+                None
+            );
+
+            db.delete_story(story_id, epic_id).unwrap();
+            assert_eq!(
+                page.action_from(&format!(" R {} ", story_id), &io).unwrap(),
+                Some(Action::RestoreFromTrash { id: story_id })
+            );
+            assert_eq!(page.action_from("r", &io).unwrap(), None);
+            assert_eq!(
+                page.action_from("purge", &io).unwrap(),
+                Some(Action::PurgeTrash)
+            );
+
+            page.draw(&io).unwrap();
+            assert!(io.output().contains("Story 1"));
+            assert!(io.output().contains(&format!("#{} Epic 1", epic_id)));
+        }
+    }
+
+    mod sprint_pages {
+        use chrono::NaiveDate;
+
+        use crate::models::{Epic, Sprint};
+
+        use super::*;
+
+        #[test]
+        fn sprint_detail_should_list_stories_across_epics() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let first_epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let second_epic_id = db.create_epic(&Epic::new("Epic 2", "")).unwrap();
+            let first_id = db
+                .create_story(&Story::new("Story 1", ""), first_epic_id)
+                .unwrap();
+            let second_id = db
+                .create_story(&Story::new("Story 2", ""), second_epic_id)
+                .unwrap();
+            let date = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+            let sprint_id = db
+                .create_sprint(&Sprint::new("Sprint 1", date, date))
+                .unwrap();
+            db.add_story_to_sprint(sprint_id, first_id).unwrap();
+            db.add_story_to_sprint(sprint_id, second_id).unwrap();
+
+            let page = SprintPage { db: db.clone() };
+            assert!(page.draw(&ScriptedIo::default()).is_ok());
+            assert_eq!(
+                page.action_from(&sprint_id.to_string(), &ScriptedIo::default())
+                    .unwrap(),
+                Some(Action::NavigateToSprintDetail { sprint_id })
+            );
+            assert_eq!(
+                page.action_from("999", &ScriptedIo::default()).unwrap(),
+                None
+            );
+
+            let page = SprintDetail {
+                sprint_id,
+                db: db.clone(),
+            };
+            assert!(page.draw(&ScriptedIo::default()).is_ok());
+            assert_eq!(
+                page.action_from(&second_id.to_string(), &ScriptedIo::default())
+                    .unwrap(),
+                Some(Action::NavigateToStoryDetail {
+                    story_id: second_id,
+                    epic_id: second_epic_id
+                })
+            );
+            assert_eq!(
+                page.action_from(&first_epic_id.to_string(), &ScriptedIo::default())
+                    .unwrap(),
+                None
+            );
+            assert_eq!(
+                page.action_from("s", &ScriptedIo::default()).unwrap(),
+                Some(Action::UpdateSprintStories { sprint_id })
+            );
+        }
+
+        #[test]
+        fn sprint_detail_draw_should_fail_for_missing_sprint() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let page = SprintDetail { sprint_id: 999, db };
+            let error = page.draw(&ScriptedIo::default()).unwrap_err();
+            assert_eq!(
+                error.downcast_ref::<MissingEntity>(),
+                Some(&MissingEntity::Sprint(999))
+            );
+        }
+    }
+
+    mod epic_detail {
+        use crate::{
+            models::{Epic, Status, Story},
+            ui::navigator::{test_utils::MockNavigator, NavigationManager},
+        };
+
+        use self::prompts::{EpicStatusChoice, Prompt};
+
+        use super::*;
+
+        #[test]
+        fn story_ids_should_sort_by_status_when_set() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let mut story_ids = vec![];
+            for status in [Status::Closed, Status::Open, Status::InProgress] {
+                let story_id = db.create_story(&Story::new("Story", ""), epic_id).unwrap();
+                db.update_story_status(story_id, status).unwrap();
+                story_ids.push(story_id);
+            }
+            let mut page = EpicDetail::new(epic_id, db.clone());
+            let state = db.read().unwrap();
+            assert_eq!(page.story_ids(&state).unwrap(), story_ids);
+
+            page.sort_by_status = true;
+            assert_eq!(
+                page.story_ids(&state).unwrap(),
+                vec![story_ids[1], story_ids[2], story_ids[0]]
+            );
+        }
+
+        #[test]
+        fn menu_should_omit_story_commands_for_empty_epic() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let page = EpicDetail::new(epic_id, db.clone());
+
+            let menu = page.menu(&db.read().unwrap());
+            assert!(menu.contains(&"(n) [name] new story".to_owned()));
+            assert!(menu.contains(&"(d) delete".to_owned()));
+            assert!(!menu.iter().any(|entry| entry.contains("view story")));
+            assert!(!menu.iter().any(|entry| entry.contains("all stories")));
+            assert!(!menu.iter().any(|entry| entry.contains("move story")));
+            assert!(!menu.contains(&"(D) delete stories".to_owned()));
+        }
+
+        #[test]
+        fn menu_should_count_stories() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            for _ in 0..3 {
+                db.create_story(&Story::new("Story", ""), epic_id).unwrap();
+            }
+            let mut page = EpicDetail::new(epic_id, db.clone());
+            page.rows_per_page = 2;
+
+            let menu = page.menu(&db.read().unwrap());
+            assert_eq!(menu.last().unwrap(), "<ID> view story (3)");
+            assert!(menu.contains(&"(<)/(>) page".to_owned()));
+            assert!(menu.contains(&"(mu)/(md) <ID> move story up/down".to_owned()));
+        }
+
+        #[test]
+        fn header_should_differ_only_by_escape_codes_without_colors() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let mut epic = Epic::new("Epic 1", "Epic 1 description");
+            epic.status = Status::InProgress;
+            let epic_id = db.create_epic(&epic).unwrap();
+            let page = EpicDetail::new(epic_id, db);
+
+            let stories = BTreeMap::new();
+            let colored = page.header(&epic, &stories, ColumnWidths::default(), ColorMode::Enabled);
+            let plain = page.header(
+                &epic,
+                &stories,
+                ColumnWidths::default(),
+                ColorMode::Disabled,
+            );
+            assert_ne!(colored, plain);
+            assert!(!plain.contains('\x1b'));
+            assert_eq!(strip_escape_codes(&colored), plain);
+        }
+
+        /// `strip_escape_codes` removes the ANSI color escape codes from `text`.
+        fn strip_escape_codes(text: &str) -> String {
+            let mut plain = String::new();
+            let mut chars = text.chars();
+            while let Some(c) = chars.next() {
+                if c == '\x1b' {
+                    chars.by_ref().find(|c| *c == 'm');
+                } else {
+                    plain.push(c);
+                }
+            }
+            plain
+        }
+
+        #[test]
+        fn header_should_wrap_description_at_configured_width() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic = Epic::new(
+                "Epic 1",
+                "A description long enough to need a few lines in a narrow column",
+            );
+            let epic_id = db.create_epic(&epic).unwrap();
+            let page = EpicDetail::new(epic_id, db);
+
+            let stories = BTreeMap::new();
+            let wide = page.header(&epic, &stories, ColumnWidths::default(), ColorMode::Enabled);
+            let narrow = page.header(
+                &epic,
+                &stories,
+                ColumnWidths {
+                    name: MAX_NAME_LENGTH,
+                    description: 20,
+                },
+                ColorMode::Enabled,
+            );
+            assert!(narrow.lines().count() > wide.lines().count());
+        }
+
+        #[test]
+        fn header_should_wrap_description_more_on_narrow_terminal() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic = Epic::new("Epic 1", &"a description that goes on ".repeat(6));
+            let epic_id = db.create_epic(&epic).unwrap();
+            let page = EpicDetail::new(epic_id, db);
+
+            let stories = BTreeMap::new();
+            let header = |columns| {
+                page.header(
+                    &epic,
+                    &stories,
+                    ColumnWidths::from_vars(None, None, Some(columns)),
+                    ColorMode::Disabled,
+                )
+            };
+            assert!(header(60).lines().count() > header(160).lines().count());
+            assert_eq!(
+                ColumnWidths::from_vars(None, None, Some(1000)).description,
+                MAX_COLUMN_WIDTH
+            );
+        }
+
+        #[test]
+        fn column_widths_should_fit_terminal() {
+            assert_eq!(
+                column_widths(60),
+                ColumnWidths {
+                    name: 14,
+                    description: 21
+                }
+            );
+            assert_eq!(
+                column_widths(80),
+                ColumnWidths {
+                    name: 22,
+                    description: 33
+                }
+            );
+            assert_eq!(
+                column_widths(150),
+                ColumnWidths {
+                    name: MAX_NAME_LENGTH,
+                    description: 150 - MAX_NAME_LENGTH - COLUMN_MARGIN
+                }
+            );
+            assert_eq!(
+                column_widths(400),
+                ColumnWidths {
+                    name: MAX_NAME_LENGTH,
+                    description: MAX_COLUMN_WIDTH
+                }
+            );
+            assert_eq!(
+                column_widths(20),
+                ColumnWidths {
+                    name: MIN_COLUMN_WIDTH,
+                    description: MIN_COLUMN_WIDTH
+                }
+            );
+        }
+
+        #[test]
+        fn column_widths_should_prefer_explicit_widths() {
+            assert_eq!(
+                ColumnWidths::from_vars(None, None, None),
+                ColumnWidths::default()
+            );
+            assert_eq!(
+                ColumnWidths::from_vars(Some("20"), Some("40"), Some(200)),
+                ColumnWidths {
+                    name: 20,
+                    description: 40
+                }
+            );
+            assert_eq!(
+                ColumnWidths::from_vars(None, Some("x"), Some(120)),
+                ColumnWidths {
+                    name: MAX_NAME_LENGTH,
+                    description: 120 - MAX_NAME_LENGTH - COLUMN_MARGIN
+                }
+            );
+            assert_eq!(
+                ColumnWidths::from_vars(None, None, Some(40)).description,
+                MIN_COLUMN_WIDTH
+            );
+            assert_eq!(
+                ColumnWidths::from_vars(None, None, Some(80)),
+                column_widths(80)
+            );
+            assert_eq!(
+                ColumnWidths::from_vars(Some("15"), None, Some(80)),
+                ColumnWidths {
+                    name: 15,
+                    description: 80 - 15 - COLUMN_MARGIN
+                }
+            );
+        }
+
+        #[test]
+        fn action_from_next_status_action_should_succeed() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            db.update_epic_status(epic_id, Status::Closed).unwrap();
+            let page = EpicDetail::new(epic_id, db);
+            assert_eq!(
+                page.action_from(">>", &ScriptedIo::default()).unwrap(),
+                Some(Action::SetEpicStatus {
+                    epic_id,
+                    status: Status::Open
+                })
+            );
+        }
+
+        #[test]
+        fn action_from_move_story_action_should_succeed() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let first_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let last_id = db
+                .create_story(&Story::new("Story 2", ""), epic_id)
+                .unwrap();
+            let page = EpicDetail::new(epic_id, db.clone());
+
+            let action = page
+                .action_from(&format!("MU {}", last_id), &ScriptedIo::default())
+                .unwrap();
+            assert_eq!(
+                action,
+                Some(Action::MoveStory {
+                    epic_id,
+                    story_id: last_id,
+                    direction: Direction::Up
+                })
+            );
+            assert_eq!(
+                page.action_from("md x", &ScriptedIo::default()).unwrap(),
+                None
+            );
+            assert_eq!(
+                page.action_from("mx 1", &ScriptedIo::default()).unwrap(),
+                None
+            );
+
+            let mut nav = MockNavigator::new(db.clone());
+            nav.dispatch_action(action.unwrap()).unwrap();
+            let state = db.read().unwrap();
+            assert_eq!(page.story_ids(&state).unwrap(), vec![last_id, first_id]);
+        }
+
+        #[test]
+        fn action_from_status_shortcut_should_set_story_status() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let other_epic_id = db.create_epic(&Epic::new("Epic 2", "")).unwrap();
+            let other_story_id = db
+                .create_story(&Story::new("Story 2", ""), other_epic_id)
+                .unwrap();
+            let page = EpicDetail::new(epic_id, db.clone());
+            let io = ScriptedIo::default();
 
-            let epic = Epic::new("Epic 1", "Epic 1 description");
-            let epic_id = db.create_epic(&epic).unwrap();
-            let view_epic_action = page.action_from(&epic_id.to_string());
-            assert!(view_epic_action.is_ok());
             assert_eq!(
-                view_epic_action.unwrap(),
-                Some(Action::NavigateToEpicDetail { epic_id })
+                page.action_from(&format!("CL {}", story_id), &io).unwrap(),
+                Some(Action::SetStoryStatus {
+                    story_id,
+                    status: Status::Closed
+                })
+            );
+            assert_eq!(
+                page.action_from(&format!("c {}", story_id), &io).unwrap(),
+                None
+            );
+            assert_eq!(
+                page.action_from(&format!("p {}", story_id), &io).unwrap(),
+                Some(Action::SetStoryStatus {
+                    story_id,
+                    status: Status::InProgress
+                })
+            );
+            assert_eq!(page.action_from("r x", &io).unwrap(), None);
+            assert_eq!(page.action_from("o 999", &io).unwrap(), None);
+            assert_eq!(
+                page.action_from(&format!("o {}", other_story_id), &io)
+                    .unwrap(),
+                None
+            );
+            assert_eq!(page.action_from("o", &io).unwrap(), None);
+            assert_eq!(
+                page.action_from("c", &io).unwrap(),
+                Some(Action::DuplicateEpic { epic_id })
             );
         }
 
         #[test]
-        fn action_from_view_epic_action_should_fail_if_invalid_input() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
-            let page = HomePage { db: db.clone() };
-            let view_epic_action = page.action_from("invalid");
-            assert!(view_epic_action.is_ok());
-            assert!(view_epic_action.unwrap().is_none());
+        fn action_from_previous_status_action_should_succeed() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            db.update_epic_status(epic_id, Status::Resolved).unwrap();
+            let page = EpicDetail::new(epic_id, db);
+            assert_eq!(
+                page.action_from("<<", &ScriptedIo::default()).unwrap(),
+                Some(Action::SetEpicStatus {
+                    epic_id,
+                    status: Status::InProgress
+                })
+            );
+            assert_eq!(page.action_from("<", &ScriptedIo::default()).unwrap(), None);
         }
 
         #[test]
-        fn action_from_view_epic_action_should_fail_if_invalid_epic_id() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
-            let page = HomePage { db: db.clone() };
+        fn action_from_page_actions_should_stay_in_bounds() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            for _ in 0..5 {
+                db.create_story(&Story::new("Story", ""), epic_id).unwrap();
+            }
+            let mut page = EpicDetail::new(epic_id, db);
+            page.rows_per_page = 2;
 
-            let invalid_epic_id = 999u32.to_string();
-            let view_epic_action = page.action_from(&invalid_epic_id);
-            assert!(view_epic_action.is_ok());
-            assert!(view_epic_action.unwrap().is_none());
+            assert_eq!(page.action_from("<", &ScriptedIo::default()).unwrap(), None);
+            assert_eq!(page.page.get(), 0);
+            for _ in 0..5 {
+                page.action_from(">", &ScriptedIo::default()).unwrap();
+            }
+            assert_eq!(page.page.get(), 2);
+            assert!(page.draw(&ScriptedIo::default()).is_ok());
+            page.action_from("<", &ScriptedIo::default()).unwrap();
+            assert_eq!(page.page.get(), 1);
         }
-    }
 
-    mod epic_detail {
-        use crate::{
-            models::{Epic, Status, Story},
-            ui::navigator::{test_utils::MockNavigator, NavigationManager},
-        };
+        #[test]
+        fn filter_stories_by_tag_should_ignore_case() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let bug_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            db.add_story_tag(bug_id, "bug").unwrap();
+            let other_id = db
+                .create_story(&Story::new("Story 2", ""), epic_id)
+                .unwrap();
+            db.add_story_tag(other_id, "backend").unwrap();
 
-        use self::prompts::Prompt;
+            let state = db.read().unwrap();
+            let story_ids = &state.epics[&epic_id].story_ids;
+            assert_eq!(
+                filter_stories_by_tag(story_ids, &state.stories, "BUG"),
+                vec![bug_id]
+            );
+            assert!(filter_stories_by_tag(story_ids, &state.stories, "ui").is_empty());
 
-        use super::*;
+            let page = EpicDetail::new(epic_id, db.clone());
+            *page.tag_filter.borrow_mut() = Some("bug".to_owned());
+            assert!(page.draw(&ScriptedIo::default()).is_ok());
+        }
 
         #[test]
         fn draw_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db
                 .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
                 .unwrap();
-            let page = EpicDetail { db, epic_id };
-            assert!(page.draw().is_ok());
+            let page = EpicDetail::new(epic_id, db);
+            assert!(page.draw(&ScriptedIo::default()).is_ok());
         }
 
         #[test]
         fn action_from_back_action_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db
                 .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
                 .unwrap();
-            let page = EpicDetail { db, epic_id };
+            let page = EpicDetail::new(epic_id, db);
 
-            let back_action = page.action_from("b");
+            let back_action = page.action_from("b", &ScriptedIo::default());
             assert!(back_action.is_ok());
             assert_eq!(back_action.unwrap(), Some(Action::NavigateToPreviousPage));
         }
 
+        #[test]
+        fn action_from_should_ignore_case_and_whitespace() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+            let page = EpicDetail::new(epic_id, db);
+            assert_eq!(
+                page.action_from(" B ", &ScriptedIo::default()).unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.action_from(" n", &ScriptedIo::default()).unwrap(),
+                Some(Action::CreateStory { epic_id })
+            );
+            assert_eq!(
+                page.action_from(" U ", &ScriptedIo::default()).unwrap(),
+                Some(Action::UpdateEpicStoriesStatus { epic_id })
+            );
+        }
+
+        #[test]
+        fn action_from_update_all_stories_action_should_succeed() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db
+                .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
+                .unwrap();
+            let page = EpicDetail::new(epic_id, db);
+
+            let action = page.action_from("U", &ScriptedIo::default());
+            assert_eq!(
+                action.unwrap(),
+                Some(Action::UpdateEpicStoriesStatus { epic_id })
+            );
+        }
+
+        #[test]
+        fn action_from_new_stories_action_should_succeed() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let page = EpicDetail::new(epic_id, db);
+            let io = ScriptedIo::default();
+
+            assert_eq!(
+                page.action_from(" N ", &io).unwrap(),
+                Some(Action::CreateStories { epic_id })
+            );
+            assert_eq!(
+                page.action_from("D", &io).unwrap(),
+                Some(Action::DeleteStories { epic_id })
+            );
+            assert_eq!(
+                page.action_from("DC", &io).unwrap(),
+                Some(Action::DeleteClosedStories { epic_id })
+            );
+            assert_eq!(
+                page.action_from(" C ", &io).unwrap(),
+                Some(Action::CloseEpicStories { epic_id })
+            );
+            assert_eq!(
+                page.action_from("c", &io).unwrap(),
+                Some(Action::DuplicateEpic { epic_id })
+            );
+            assert!(matches!(
+                page.action_from("d", &io).unwrap(),
+                Some(Action::DeleteEpic { .. })
+            ));
+            assert_eq!(
+                page.action_from("n", &io).unwrap(),
+                Some(Action::CreateStory { epic_id })
+            );
+        }
+
+        #[test]
+        fn action_from_new_story_with_name_should_keep_name() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let page = EpicDetail::new(epic_id, db);
+            let io = ScriptedIo::default();
+
+            assert_eq!(
+                page.action_from(" n  Fix the login bug ", &io).unwrap(),
+                Some(Action::CreateStoryNamed {
+                    epic_id,
+                    name: "Fix the login bug".to_owned()
+                })
+            );
+            assert_eq!(
+                page.action_from("n ", &io).unwrap(),
+                Some(Action::CreateStory { epic_id })
+            );
+        }
+
+        #[test]
+        fn new_story_with_name_should_confirm_and_reopen_closed_epic() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            db.update_epic_status(epic_id, Status::Closed).unwrap();
+            let page = EpicDetail::new(epic_id, db.clone());
+            let mut nav = MockNavigator::new(db.clone());
+
+            for confirmed in [false, true] {
+                let mut prompts = Prompt::new();
+                prompts.confirm_closed_epic = Box::new(move |_| confirmed);
+                nav.set_prompts(prompts);
+                let action = page
+                    .action_from("n Fix the login bug", &ScriptedIo::default())
+                    .unwrap()
+                    .unwrap();
+                nav.dispatch_action(action).unwrap();
+            }
+            let epic = &db.read().unwrap().epics[&epic_id];
+            assert_eq!(epic.story_ids.len(), 1);
+            assert_eq!(epic.status, Status::Open);
+        }
+
         #[test]
         fn action_from_update_action_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
             let mut nav = MockNavigator::new(db);
 
             let mut prompts = Prompt::new();
-            prompts.update_name = Box::new(|| "new name".to_string());
+            prompts.update_name = Box::new(|_| Some("new name".to_string()));
             nav.set_prompts(prompts);
             let res = nav.dispatch_action(Action::UpdateEpicName { epic_id });
             assert!(res.is_ok());
@@ -592,7 +3526,7 @@ mod tests {
             );
 
             let mut prompts = Prompt::new();
-            prompts.update_description = Box::new(|| "new description".to_string());
+            prompts.update_description = Box::new(|_| Some("new description".to_string()));
             nav.set_prompts(prompts);
             let res = nav.dispatch_action(Action::UpdateEpicDescription { epic_id });
             assert!(res.is_ok());
@@ -609,7 +3543,8 @@ mod tests {
             );
 
             let mut prompts = Prompt::new();
-            prompts.update_status = Box::new(|| Some(Status::Closed));
+            prompts.update_epic_status =
+                Box::new(|| Some(EpicStatusChoice::Manual(Status::Closed)));
             nav.set_prompts(prompts);
             let res = nav.dispatch_action(Action::UpdateEpicStatus { epic_id });
             assert!(res.is_ok());
@@ -628,33 +3563,29 @@ mod tests {
 
         #[test]
         fn action_from_new_story_action_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db
                 .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
                 .unwrap();
-            let page = EpicDetail { db, epic_id };
+            let page = EpicDetail::new(epic_id, db);
 
-            let new_action = page.action_from("n");
+            let new_action = page.action_from("n", &ScriptedIo::default());
             assert!(new_action.is_ok());
             assert_eq!(new_action.unwrap(), Some(Action::CreateStory { epic_id }));
         }
 
         #[test]
         fn action_from_view_story_action_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db
                 .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
                 .unwrap();
             let story_id = db
                 .create_story(&Story::new("Story 1", "Story 1 description"), epic_id)
                 .unwrap();
-            let page = EpicDetail { db, epic_id };
+            let page = EpicDetail::new(epic_id, db);
 
-            let view_story_action = page.action_from(&story_id.to_string());
+            let view_story_action = page.action_from(&story_id.to_string(), &ScriptedIo::default());
             assert!(view_story_action.is_ok());
             assert_eq!(
                 view_story_action.unwrap(),
@@ -664,30 +3595,26 @@ mod tests {
 
         #[test]
         fn action_from_view_story_action_should_fail_if_invalid_story_id() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db
                 .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
                 .unwrap();
-            let page = EpicDetail { db, epic_id };
+            let page = EpicDetail::new(epic_id, db);
 
-            let view_story_action = page.action_from("999");
+            let view_story_action = page.action_from("999", &ScriptedIo::default());
             assert!(view_story_action.is_ok());
             assert!(view_story_action.unwrap().is_none());
         }
 
         #[test]
         fn action_from_unknown_action_should_fail() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db
                 .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
                 .unwrap();
-            let page = EpicDetail { db, epic_id };
+            let page = EpicDetail::new(epic_id, db);
 
-            let view_story_action = page.action_from("invalid");
+            let view_story_action = page.action_from("invalid", &ScriptedIo::default());
             assert!(view_story_action.is_ok());
             assert!(view_story_action.unwrap().is_none());
         }
@@ -696,8 +3623,10 @@ mod tests {
     mod story_detail {
         use story_detail::tests::prompts::Prompt;
 
+        use chrono::Utc;
+
         use crate::{
-            models::{Epic, Status, Story},
+            models::{Comment, Epic, Status, Story, WorkEntry},
             ui::navigator::{test_utils::MockNavigator, NavigationManager},
         };
 
@@ -705,9 +3634,7 @@ mod tests {
 
         #[test]
         fn draw_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db
                 .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
                 .unwrap();
@@ -719,14 +3646,184 @@ mod tests {
                 epic_id,
                 db,
             };
-            assert!(page.draw().is_ok());
+            assert!(page.draw(&ScriptedIo::default()).is_ok());
         }
 
         #[test]
-        fn action_from_back_action_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
+        fn comments_should_show_newest_first() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let page = StoryDetail {
+                story_id,
+                epic_id,
+                db: db.clone(),
+            };
+            let action = page
+                .action_from("c", &ScriptedIo::default())
+                .unwrap()
+                .unwrap();
+            assert_eq!(action, Action::AddComment { story_id });
+
+            let mut nav = MockNavigator::new(db.clone());
+            for body in ["First", "Second"] {
+                let mut prompts = Prompt::new();
+                prompts.add_comment = Box::new(move || {
+                    Some(Comment {
+                        author: "alice".to_owned(),
+                        body: body.to_owned(),
+                        at: Utc::now(),
+                    })
+                });
+                nav.set_prompts(prompts);
+                nav.dispatch_action(Action::AddComment { story_id })
+                    .unwrap();
+            }
+
+            let story = &db.read().unwrap().stories[&story_id];
+            let table = comments_table(story, ColumnWidths::default());
+            assert!(table.contains("Comments (2)"));
+            assert!(table.find("Second").unwrap() < table.find("First").unwrap());
+            assert!(page.draw(&ScriptedIo::default()).is_ok());
+        }
+
+        #[test]
+        fn log_work_should_show_on_story_and_epic() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let page = StoryDetail {
+                story_id,
+                epic_id,
+                db: db.clone(),
+            };
+            let action = page
+                .action_from("w", &ScriptedIo::default())
+                .unwrap()
+                .unwrap();
+            assert_eq!(action, Action::LogWork { story_id });
+
+            let mut nav = MockNavigator::new(db.clone());
+            let mut prompts = Prompt::new();
+            prompts.log_work = Box::new(|| {
+                Some(WorkEntry {
+                    minutes: 90,
+                    note: "Pairing".to_owned(),
+                    date: "2024-06-03".to_owned(),
+                })
             });
+            nav.set_prompts(prompts);
+            nav.dispatch_action(action).unwrap();
+
+            let story = &db.read().unwrap().stories[&story_id];
+            assert_eq!(story.logged_minutes(), 90);
+            assert!(work_log_table(story, ColumnWidths::default()).contains("Logged 1h 30m"));
+            assert!(page.draw(&ScriptedIo::default()).is_ok());
+            assert!(EpicDetail::new(epic_id, db)
+                .draw(&ScriptedIo::default())
+                .is_ok());
+        }
+
+        #[test]
+        fn action_from_next_status_action_should_succeed() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("name", "description"), epic_id)
+                .unwrap();
+            let page = StoryDetail {
+                story_id,
+                epic_id,
+                db: db.clone(),
+            };
+            let action = page
+                .action_from(">>", &ScriptedIo::default())
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                action,
+                Action::SetStoryStatus {
+                    story_id,
+                    status: Status::Blocked
+                }
+            );
+
+            let mut nav = MockNavigator::new(db.clone());
+            nav.dispatch_action(action).unwrap();
+            assert_eq!(
+                db.read().unwrap().stories[&story_id].status,
+                Status::Blocked
+            );
+        }
+
+        #[test]
+        fn action_from_status_shortcut_should_set_status() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("name", "description"), epic_id)
+                .unwrap();
+            let page = StoryDetail {
+                story_id,
+                epic_id,
+                db: db.clone(),
+            };
+            let io = ScriptedIo::default();
+
+            for (command, status) in [
+                ("o", Status::Open),
+                ("P", Status::InProgress),
+                (" r ", Status::Resolved),
+                ("cl", Status::Closed),
+            ] {
+                assert_eq!(
+                    page.action_from(command, &io).unwrap(),
+                    Some(Action::SetStoryStatus { story_id, status })
+                );
+            }
+            assert_eq!(
+                page.action_from("c", &io).unwrap(),
+                Some(Action::AddComment { story_id })
+            );
+            assert_eq!(
+                page.action_from(&format!("o {}", story_id), &io).unwrap(),
+                None
+            );
+        }
+
+        #[test]
+        fn action_from_should_ignore_case_and_whitespace() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("name", "description"), epic_id)
+                .unwrap();
+            let page = StoryDetail {
+                story_id,
+                epic_id,
+                db,
+            };
+            assert_eq!(
+                page.action_from(" B ", &ScriptedIo::default()).unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.action_from("N", &ScriptedIo::default()).unwrap(),
+                Some(Action::CreateTask { story_id })
+            );
+            assert_eq!(
+                page.action_from("BL", &ScriptedIo::default()).unwrap(),
+                Some(Action::UpdateStoryBlockers { story_id })
+            );
+        }
+
+        #[test]
+        fn action_from_back_action_should_succeed() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db
                 .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
                 .unwrap();
@@ -739,16 +3836,14 @@ mod tests {
                 db,
             };
 
-            let back_action = page.action_from("b");
+            let back_action = page.action_from("b", &ScriptedIo::default());
             assert!(back_action.is_ok());
             assert_eq!(back_action.unwrap(), Some(Action::NavigateToPreviousPage));
         }
 
         #[test]
         fn action_from_update_action_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
 
             let epic_id = db.create_epic(&Epic::new("name", "description")).unwrap();
             let story_id = db
@@ -757,7 +3852,7 @@ mod tests {
             let mut nav = MockNavigator::new(db);
 
             let mut prompts = Prompt::new();
-            prompts.update_name = Box::new(|| "new name".to_string());
+            prompts.update_name = Box::new(|_| Some("new name".to_string()));
             nav.set_prompts(prompts);
             let res = nav.dispatch_action(Action::UpdateStoryName { story_id });
             assert!(res.is_ok());
@@ -774,7 +3869,7 @@ mod tests {
             );
 
             let mut prompts = Prompt::new();
-            prompts.update_description = Box::new(|| "new description".to_string());
+            prompts.update_description = Box::new(|_| Some("new description".to_string()));
             nav.set_prompts(prompts);
             let res = nav.dispatch_action(Action::UpdateStoryDescription { story_id });
             assert!(res.is_ok());
@@ -810,9 +3905,7 @@ mod tests {
 
         #[test]
         fn action_from_delete_action_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db
                 .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
                 .unwrap();
@@ -825,7 +3918,7 @@ mod tests {
                 db,
             };
 
-            let delete_action = page.action_from("d");
+            let delete_action = page.action_from("d", &ScriptedIo::default());
             assert!(delete_action.is_ok());
             assert_eq!(
                 delete_action.unwrap(),
@@ -835,9 +3928,7 @@ mod tests {
 
         #[test]
         fn action_from_unknown_action_should_do_nothing() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db
                 .create_epic(&Epic::new("Epic 1", "Epic 1 description"))
                 .unwrap();
@@ -850,10 +3941,144 @@ mod tests {
                 db,
             };
 
-            let unknown_action = page.action_from("unknown");
+            let unknown_action = page.action_from("unknown", &ScriptedIo::default());
             assert!(unknown_action.is_ok());
             assert!(unknown_action.unwrap().is_none());
         }
+
+        #[test]
+        fn checklist_commands_should_edit_and_show_checklist() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let page = StoryDetail {
+                story_id,
+                epic_id,
+                db: db.clone(),
+            };
+            let io = ScriptedIo::default();
+            assert_eq!(
+                page.action_from("+ Update the Docs", &io).unwrap(),
+                Some(Action::AddChecklistItem {
+                    id: story_id,
+                    text: "Update the Docs".to_owned()
+                })
+            );
+            assert_eq!(
+                page.action_from("T 2", &io).unwrap(),
+                Some(Action::ToggleChecklistItem {
+                    id: story_id,
+                    index: 1
+                })
+            );
+            assert_eq!(
+                page.action_from("- 3", &io).unwrap(),
+                Some(Action::RemoveChecklistItem {
+                    id: story_id,
+                    index: 2
+                })
+            );
+            assert!(page.action_from("t 0", &io).is_err());
+            assert_eq!(page.action_from("t x", &io).unwrap(), None);
+
+            let mut nav = MockNavigator::new(db.clone());
+            for input in ["+ Docs", "+ Tests", "+ Release", "t 2", "- 3"] {
+                let action = page.action_from(input, &io).unwrap().unwrap();
+                nav.dispatch_action(action).unwrap();
+            }
+            let action = page.action_from("t 3", &io).unwrap().unwrap();
+            assert!(nav.dispatch_action(action).is_err());
+
+            page.draw(&io).unwrap();
+            let output = io.output();
+            assert!(output.contains("Checklist (1/2 done)"));
+            assert!(output.contains("[ ] Docs"));
+            assert!(output.contains("[x] Tests"));
+            assert!(!output.contains("Release"));
+        }
+    }
+
+    mod description_page {
+        use super::*;
+
+        /// `long_description` returns a description of 499 characters made of distinct
+        /// words, so that a cut one is missing its last words.
+        fn long_description() -> String {
+            (0..100).map(|index| format!("w{:03}", index)).join(" ")
+        }
+
+        #[test]
+        fn description_should_be_whole_only_in_expanded_view() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic = Epic::new("Epic 1", &long_description());
+            let epic_id = db.create_epic(&epic).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", &long_description()), epic_id)
+                .unwrap();
+
+            let compact = EpicDetail::new(epic_id, db.clone()).header(
+                &epic,
+                &BTreeMap::new(),
+                ColumnWidths::default(),
+                ColorMode::Disabled,
+            );
+            assert!(compact.contains("w000") && compact.contains('…'));
+            assert!(!compact.contains("w099"));
+            let io = ScriptedIo::default();
+            let story_page = StoryDetail {
+                story_id,
+                epic_id,
+                db: db.clone(),
+            };
+            story_page.draw(&io).unwrap();
+            assert!(!io.output().contains("w099"));
+
+            for id in [epic_id, story_id] {
+                let io = ScriptedIo::default();
+                DescriptionPage { id, db: db.clone() }.draw(&io).unwrap();
+                for word in long_description().split(' ') {
+                    assert!(io.output().contains(word), "{} is missing", word);
+                }
+            }
+        }
+
+        #[test]
+        fn action_from_view_description_should_open_description_page() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let io = ScriptedIo::default();
+
+            assert_eq!(
+                EpicDetail::new(epic_id, db.clone())
+                    .action_from("v", &io)
+                    .unwrap(),
+                Some(Action::NavigateToDescription { id: epic_id })
+            );
+            let story_page = StoryDetail {
+                story_id,
+                epic_id,
+                db: db.clone(),
+            };
+            assert_eq!(
+                story_page.action_from(" V ", &io).unwrap(),
+                Some(Action::NavigateToDescription { id: story_id })
+            );
+
+            let page = DescriptionPage { id: story_id, db };
+            page.draw(&io).unwrap();
+            assert!(io
+                .output()
+                .contains(&format!("Story #{} Story 1 has no description.", story_id)));
+            assert_eq!(
+                page.action_from("b", &io).unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+        }
     }
 
     mod task_detail {
@@ -863,6 +4088,7 @@ mod tests {
             db::test_utils::MockDatabase,
             models::{Epic, Status, Story, Task},
             ui::{
+                io::ScriptedIo,
                 navigator::{test_utils::MockNavigator, NavigationManager},
                 pages::{tests::Action, Page},
             },
@@ -870,11 +4096,40 @@ mod tests {
 
         use super::{prompts::Prompt, JiraDatabase, TaskDetail};
 
+        #[test]
+        fn checklist_commands_should_toggle_task_items() {
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
+            let epic_id = db.create_epic(&Epic::new("Epic 1", "")).unwrap();
+            let story_id = db
+                .create_story(&Story::new("Story 1", ""), epic_id)
+                .unwrap();
+            let task_id = db.create_task(&Task::new("Task 1", ""), story_id).unwrap();
+            let page = TaskDetail {
+                task_id,
+                story_id,
+                db: db.clone(),
+            };
+            let io = ScriptedIo::default();
+            let mut nav = MockNavigator::new(db.clone());
+            for input in ["+ Write", "+ Review", "+ Merge", "t 1", "t 3"] {
+                let action = page.action_from(input, &io).unwrap().unwrap();
+                nav.dispatch_action(action).unwrap();
+            }
+            let action = page.action_from("t 4", &io).unwrap().unwrap();
+            assert!(nav.dispatch_action(action).is_err());
+            assert!(page.action_from("- 0", &io).is_err());
+
+            page.draw(&io).unwrap();
+            let output = io.output();
+            assert!(output.contains("Checklist (2/3 done)"));
+            assert!(output.contains("1. [x] Write"));
+            assert!(output.contains("2. [ ] Review"));
+            assert!(output.contains("3. [x] Merge"));
+        }
+
         #[test]
         fn draw_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db
                 .create_epic(&Epic::new("epic name", "epic description"))
                 .unwrap();
@@ -889,14 +4144,12 @@ mod tests {
                 story_id,
                 db,
             };
-            assert!(page.draw().is_ok());
+            assert!(page.draw(&ScriptedIo::default()).is_ok());
         }
 
         #[test]
         fn action_from_back_action_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db
                 .create_epic(&Epic::new("epic name", "epic description"))
                 .unwrap();
@@ -911,16 +4164,14 @@ mod tests {
                 story_id,
                 db,
             };
-            let back_action = page.action_from("b");
+            let back_action = page.action_from("b", &ScriptedIo::default());
             assert!(back_action.is_ok());
             assert_eq!(back_action.unwrap(), Some(Action::NavigateToPreviousPage));
         }
 
         #[test]
         fn action_from_update_action_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db
                 .create_epic(&Epic::new("epic name", "epic description"))
                 .unwrap();
@@ -933,7 +4184,7 @@ mod tests {
             let mut nav = MockNavigator::new(db);
 
             let mut prompts = Prompt::new();
-            prompts.update_name = Box::new(|| "new name".to_string());
+            prompts.update_name = Box::new(|_| Some("new name".to_string()));
             nav.set_prompts(prompts);
             let res = nav.dispatch_action(Action::UpdateTaskName { task_id });
             assert!(res.is_ok());
@@ -950,7 +4201,7 @@ mod tests {
             );
 
             let mut prompts = Prompt::new();
-            prompts.update_description = Box::new(|| "new description".to_string());
+            prompts.update_description = Box::new(|_| Some("new description".to_string()));
             nav.set_prompts(prompts);
             let res = nav.dispatch_action(Action::UpdateTaskDescription { task_id });
             assert!(res.is_ok());
@@ -986,9 +4237,7 @@ mod tests {
 
         #[test]
         fn action_from_delete_action_should_succeed() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db
                 .create_epic(&Epic::new("epic name", "epic description"))
                 .unwrap();
@@ -1003,7 +4252,7 @@ mod tests {
                 story_id,
                 db,
             };
-            let delete_action = page.action_from("d");
+            let delete_action = page.action_from("d", &ScriptedIo::default());
             assert!(delete_action.is_ok());
             assert_eq!(
                 delete_action.unwrap(),
@@ -1013,9 +4262,7 @@ mod tests {
 
         #[test]
         fn action_from_unknown_action_should_do_nothing() {
-            let db = Rc::new(JiraDatabase {
-                db: Box::new(MockDatabase::new()),
-            });
+            let db = Rc::new(JiraDatabase::from_database(Box::new(MockDatabase::new())));
             let epic_id = db
                 .create_epic(&Epic::new("epic name", "epic description"))
                 .unwrap();
@@ -1030,7 +4277,7 @@ mod tests {
                 story_id,
                 db,
             };
-            let unknown_action = page.action_from("unknown");
+            let unknown_action = page.action_from("unknown", &ScriptedIo::default());
             assert!(unknown_action.is_ok());
             assert!(unknown_action.unwrap().is_none());
         }
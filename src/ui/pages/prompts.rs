@@ -1,201 +1,1373 @@
+use std::{env, rc::Rc};
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+
 use crate::{
-    models::{Epic, Status, Story, Task},
-    ui::pages::MAX_NAME_LENGTH,
-    utils::read_line,
+    config::Config,
+    db::validate,
+    models::{Comment, Epic, Sprint, Status, Story, Task, Template, WorkEntry},
+    theme::ThemeColor,
+    ui::io::{Io, StdIo},
+    ui::pages::ColumnWidths,
+    utils::{clira_user, confirm, edit_in_editor, parse_date, parse_duration, DATE_FORMAT},
 };
 
+/// `Editor` opens a text in an editor and returns the edited text, or `Err` if the editor
+/// failed. Prompts take it as a parameter so that tests can stub the editor.
+type Editor<'a> = &'a mut dyn FnMut(&str) -> anyhow::Result<String>;
+
+/// `EpicStatusChoice` is the answer to the epic status prompt. An epic's status can
+/// either be set manually or handed back to the automatic derivation from its stories.
+pub enum EpicStatusChoice {
+    Manual(Status),
+    Auto,
+}
+
+/// `TagChange` is the answer to the story tags prompt.
+pub enum TagChange {
+    Add(String),
+    Remove(String),
+}
+
+/// `BlockerChange` is the answer to the story blockers prompt. It holds the id of the
+/// blocking story.
+pub enum BlockerChange {
+    Add(u32),
+    Remove(u32),
+}
+
+/// `SprintChange` is the answer to the sprint stories prompt. It holds the id of the story
+/// planned in or taken out of the sprint.
+pub enum SprintChange {
+    Add(u32),
+    Remove(u32),
+}
+
+/// `TextPrompt` asks for a new text given the current one. `None` means the user canceled.
+pub type TextPrompt = Box<dyn Fn(&str) -> Option<String>>;
+
+/// `IdPrompt` asks for an id given what it is the id of. `None` means the user canceled.
+pub type IdPrompt = Box<dyn Fn(&str) -> Option<u32>>;
+
+/// `SelectPrompt` asks to choose one of the given items and returns its index. `None` means
+/// the user canceled.
+pub type SelectPrompt<T> = Box<dyn Fn(&[T]) -> Option<usize>>;
+
 /// `Prompt` has different members to display prompts and read user input.
 /// It acts as a level of indirection for testability.
 pub struct Prompt {
     pub create_epic: Box<dyn Fn() -> Option<Epic>>,
     pub create_story: Box<dyn Fn() -> Option<Story>>,
+    pub create_stories: Box<dyn Fn() -> Option<Vec<String>>>,
     pub create_task: Box<dyn Fn() -> Option<Task>>,
+    pub create_sprint: Box<dyn Fn() -> Option<Sprint>>,
+    pub select_template: SelectPrompt<Template>,
     pub delete_epic: Box<dyn Fn() -> bool>,
     pub delete_story: Box<dyn Fn() -> bool>,
+    /// `select_story_ids` returns the comma-separated entries the user typed, which the
+    /// caller checks against the epic. `None` means the user canceled.
+    pub select_story_ids: Box<dyn Fn() -> Option<Vec<String>>>,
+    /// `confirm_delete_stories` is given the number of stories to delete.
+    pub confirm_delete_stories: Box<dyn Fn(usize) -> bool>,
     pub delete_task: Box<dyn Fn() -> bool>,
-    pub update_name: Box<dyn Fn() -> String>,
-    pub update_description: Box<dyn Fn() -> String>,
+    /// `convert_target` is given the kind of the new parent, e.g. "epic", and returns its id.
+    pub convert_target: IdPrompt,
+    /// `confirm_purge_trash` is given the number of epics and stories in the trash.
+    pub confirm_purge_trash: Box<dyn Fn(usize) -> bool>,
+    /// `update_name` is given the current name, which empty input keeps. `None` means the
+    /// update was canceled.
+    pub update_name: TextPrompt,
+    /// `update_description` is given the current description, which empty input keeps and
+    /// the editor starts from. `None` means the update was canceled.
+    pub update_description: TextPrompt,
     pub update_status: Box<dyn Fn() -> Option<Status>>,
+    pub update_epic_status: Box<dyn Fn() -> Option<EpicStatusChoice>>,
+    pub confirm_epic_status: Box<dyn Fn(&str) -> bool>,
+    pub confirm_status_downgrade: Box<dyn Fn(&str) -> bool>,
+    pub confirm_closed_epic: Box<dyn Fn(u32) -> bool>,
+    /// `confirm_close_stories` is given the number of stories to close.
+    pub confirm_close_stories: Box<dyn Fn(usize) -> bool>,
+    pub update_assignee: Box<dyn Fn() -> Option<String>>,
+    pub update_tags: Box<dyn Fn() -> Option<TagChange>>,
+    pub update_points: Box<dyn Fn() -> Option<Option<u32>>>,
+    /// `goto_query` returns the id or the part of a name that the user typed to go to.
+    pub goto_query: Box<dyn Fn() -> Option<String>>,
+    /// `select_goto_match` is given the epics and stories matching the `goto_query`.
+    pub select_goto_match: SelectPrompt<String>,
+    /// `select_palette_entry` is given the labels of the command palette entries to run.
+    pub select_palette_entry: SelectPrompt<String>,
+    pub update_rows_per_page: Box<dyn Fn() -> Option<Option<usize>>>,
+    pub update_theme_color: Box<dyn Fn() -> Option<(Status, ThemeColor)>>,
+    pub update_blockers: Box<dyn Fn() -> Option<BlockerChange>>,
+    pub update_sprint_stories: Box<dyn Fn() -> Option<SprintChange>>,
+    pub log_work: Box<dyn Fn() -> Option<WorkEntry>>,
+    pub add_comment: Box<dyn Fn() -> Option<Comment>>,
+    pub confirm_exit: Box<dyn Fn() -> bool>,
+    /// `confirm_recreate_board` is given the path of the board file that went missing.
+    pub confirm_recreate_board: Box<dyn Fn(&str) -> bool>,
+    pub export_path: Box<dyn Fn() -> Option<String>>,
+    pub import_path: Box<dyn Fn() -> Option<(String, bool)>>,
+    pub replace_in_descriptions: Box<dyn Fn() -> Option<(String, String)>>,
+    pub notify: Box<dyn Fn(&str)>,
 }
 
 impl Prompt {
-    /// `new` creates a new instance of `Prompt` with members ready to use.
+    /// `new` creates a new instance of `Prompt` with members ready to use. Every prompt reads
+    /// its input from stdin.
     pub fn new() -> Self {
+        Self::with_io(Rc::new(StdIo))
+    }
+
+    /// `with_io` creates a new instance of `Prompt` whose prompts read their input from and
+    /// write to `io`, with the default settings.
+    pub fn with_io(io: Rc<dyn Io>) -> Self {
+        Self::with_config(io, &Config::default())
+    }
+
+    /// `with_config` creates a new instance of `Prompt` whose prompts read their input from
+    /// and write to `io`, following the user's settings in `config`.
+    pub fn with_config(io: Rc<dyn Io>, config: &Config) -> Self {
+        let date_format = config.date_format.clone();
         Self {
-            create_epic: Box::new(create_epic),
-            create_story: Box::new(create_story),
-            create_task: Box::new(create_task),
-            delete_epic: Box::new(delete_epic),
-            delete_story: Box::new(delete_story),
-            delete_task: Box::new(delete_task),
-            update_name: Box::new(update_name),
-            update_description: Box::new(update_description),
-            update_status: Box::new(update_status),
-        }
-    }
-}
-
-fn create_epic() -> Option<Epic> {
-    println!("Enter Epic name: ((x) cancel and discard)");
-    let name: String = loop {
-        match read_line() {
-            Some(name) => {
-                if name.to_lowercase() == "x" {
-                    return None;
-                }
-                if name.len() >= MAX_NAME_LENGTH {
-                    println!(
-                        "Epic names should be short and meaningful. Please provide a shorter name:"
-                    );
-                } else {
-                    break name;
-                }
-            }
-            None => continue,
+            create_epic: {
+                let io = io.clone();
+                Box::new(move || create_epic(&*io, &mut edit_in_editor))
+            },
+            create_story: {
+                let io = io.clone();
+                Box::new(move || create_story(&*io, &mut edit_in_editor))
+            },
+            create_stories: {
+                let io = io.clone();
+                Box::new(move || create_stories(&*io))
+            },
+            create_task: {
+                let io = io.clone();
+                Box::new(move || create_task(&*io, &mut edit_in_editor))
+            },
+            create_sprint: {
+                let io = io.clone();
+                Box::new(move || create_sprint(&*io, &date_format))
+            },
+            select_template: {
+                let io = io.clone();
+                Box::new(move |templates| select_template(templates, &*io))
+            },
+            delete_epic: {
+                let io = io.clone();
+                Box::new(move || {
+                    confirm(
+                        "Delete this Epic? All Stories in this Epic will also be deleted.",
+                        false,
+                        &*io,
+                    )
+                })
+            },
+            delete_story: {
+                let io = io.clone();
+                Box::new(move || {
+                    confirm(
+                        "Delete this Story? All Tasks in this Story will also be deleted.",
+                        false,
+                        &*io,
+                    )
+                })
+            },
+            select_story_ids: {
+                let io = io.clone();
+                Box::new(move || select_story_ids(&*io))
+            },
+            confirm_delete_stories: {
+                let io = io.clone();
+                Box::new(move |count| {
+                    confirm(
+                        &format!(
+                            "Delete {} {}? All their Tasks will also be deleted.",
+                            count,
+                            if count == 1 { "Story" } else { "Stories" }
+                        ),
+                        false,
+                        &*io,
+                    )
+                })
+            },
+            delete_task: {
+                let io = io.clone();
+                Box::new(move || confirm("Delete this Task?", false, &*io))
+            },
+            convert_target: {
+                let io = io.clone();
+                Box::new(move |kind| convert_target(kind, &*io))
+            },
+            confirm_purge_trash: {
+                let io = io.clone();
+                Box::new(move |count| {
+                    confirm(
+                        &format!(
+                            "Permanently delete the {} {} in the trash?",
+                            count,
+                            if count == 1 { "item" } else { "items" }
+                        ),
+                        false,
+                        &*io,
+                    )
+                })
+            },
+            update_name: {
+                let io = io.clone();
+                Box::new(move |current| update_name(current, &*io))
+            },
+            update_description: {
+                let io = io.clone();
+                Box::new(move |current| update_description(current, &*io, &mut edit_in_editor))
+            },
+            update_status: {
+                let io = io.clone();
+                Box::new(move || update_status(&*io))
+            },
+            update_epic_status: {
+                let io = io.clone();
+                Box::new(move || update_epic_status(&*io))
+            },
+            confirm_epic_status: {
+                let io = io.clone();
+                Box::new(move |warning| confirm_epic_status(warning, &*io))
+            },
+            confirm_status_downgrade: {
+                let io = io.clone();
+                Box::new(move |message| confirm_status_downgrade(message, &*io))
+            },
+            confirm_closed_epic: {
+                let io = io.clone();
+                Box::new(move |epic_id| confirm_closed_epic(epic_id, &*io))
+            },
+            confirm_close_stories: {
+                let io = io.clone();
+                Box::new(move |count| {
+                    confirm(
+                        &format!(
+                            "Close all {} {} of this Epic?",
+                            count,
+                            if count == 1 { "Story" } else { "Stories" }
+                        ),
+                        false,
+                        &*io,
+                    )
+                })
+            },
+            update_assignee: {
+                let io = io.clone();
+                Box::new(move || update_assignee(&*io))
+            },
+            update_tags: {
+                let io = io.clone();
+                Box::new(move || update_tags(&*io))
+            },
+            update_points: {
+                let io = io.clone();
+                Box::new(move || update_points(&*io))
+            },
+            goto_query: {
+                let io = io.clone();
+                Box::new(move || goto_query(&*io))
+            },
+            select_goto_match: {
+                let io = io.clone();
+                Box::new(move |matches| select_entry(matches, "Number to open:", &*io))
+            },
+            select_palette_entry: {
+                let io = io.clone();
+                Box::new(move |labels| select_entry(labels, "Number to run:", &*io))
+            },
+            update_rows_per_page: {
+                let io = io.clone();
+                Box::new(move || update_rows_per_page(&*io))
+            },
+            update_theme_color: {
+                let io = io.clone();
+                Box::new(move || update_theme_color(&*io))
+            },
+            update_blockers: {
+                let io = io.clone();
+                Box::new(move || update_blockers(&*io))
+            },
+            update_sprint_stories: {
+                let io = io.clone();
+                Box::new(move || update_sprint_stories(&*io))
+            },
+            log_work: {
+                let io = io.clone();
+                Box::new(move || log_work(Local::now().date_naive(), &*io))
+            },
+            add_comment: {
+                let io = io.clone();
+                Box::new(move || add_comment(&comment_author(), Utc::now(), &*io))
+            },
+            confirm_exit: {
+                let io = io.clone();
+                Box::new(move || confirm("Quit Clira?", false, &*io))
+            },
+            confirm_recreate_board: {
+                let io = io.clone();
+                Box::new(move |path| confirm_recreate_board(path, &*io))
+            },
+            export_path: {
+                let io = io.clone();
+                Box::new(move || export_path(&*io))
+            },
+            import_path: {
+                let io = io.clone();
+                Box::new(move || import_path(&*io))
+            },
+            replace_in_descriptions: {
+                let io = io.clone();
+                Box::new(move || replace_in_descriptions(&*io))
+            },
+            notify: Box::new(move |message| notify(message, &*io)),
         }
-    };
-    println!("Enter Epic description: ((x) cancel and discard)");
-    let description: String = loop {
-        match read_line() {
-            Some(description) => {
-                if description.to_lowercase() == "x" {
-                    return None;
-                }
-                break description;
-            }
-            None => continue,
-        }
-    };
+    }
+}
+
+/// `create_epic` prompts for a new epic, reading each line of input from `io`. The
+/// description may be written in `edit`.
+fn create_epic(io: &dyn Io, edit: Editor) -> Option<Epic> {
+    let (name, description) = read_name_and_description("Epic", io, edit)?;
     Some(Epic::new(&name, &description))
 }
 
-fn create_story() -> Option<Story> {
-    println!("Enter Story name: ((x) cancel and discard)");
-    let name: String = loop {
-        match read_line() {
-            Some(name) => {
-                if name.to_lowercase() == "x" {
-                    return None;
-                }
-                if name.len() >= MAX_NAME_LENGTH {
-                    println!(
-                        "Story names should be short and meaningful. Please provide a shorter name:"
-                    );
-                } else {
-                    break name;
-                }
-            }
-            None => continue,
+/// `create_story` prompts for a new story, reading each line of input from `io`. The
+/// description may be written in `edit`.
+fn create_story(io: &dyn Io, edit: Editor) -> Option<Story> {
+    let (name, description) = read_name_and_description("Story", io, edit)?;
+    Some(Story::new(&name, &description))
+}
+
+/// `create_stories` prompts for the names of several stories, one per line, until an empty
+/// line or the end of the input. Lines that are not valid names, e.g. longer than
+/// `MAX_NAME_LENGTH`, are skipped with a warning. `None` is returned if the user cancels
+/// with "x" or no names were entered.
+fn create_stories(io: &dyn Io) -> Option<Vec<String>> {
+    io.println("Enter one Story name per line, then an empty line: ((x) cancel and discard)");
+    let mut names = vec![];
+    while let Some(line) = io.prompt_line() {
+        if line.trim().to_lowercase() == "x" {
+            return None;
+        }
+        match validate::name(&line) {
+            Ok(name) => names.push(name),
+            Err(error) => io.println(&format!("Skipped {:?}: {}", line.trim(), error)),
+        }
+    }
+    Some(names).filter(|names| !names.is_empty())
+}
+
+/// `select_story_ids` prompts for a comma-separated list of story ids, e.g. "3, 5, 8", and
+/// returns its non-empty entries. `None` is returned if the user cancels with "x" or enters
+/// nothing.
+fn select_story_ids(io: &dyn Io) -> Option<Vec<String>> {
+    io.println("Story IDs, separated by commas: ((x) cancel)");
+    let line = io.prompt_line()?;
+    if line.trim().to_lowercase() == "x" {
+        return None;
+    }
+    let entries: Vec<String> = line
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_owned)
+        .collect();
+    Some(entries).filter(|entries| !entries.is_empty())
+}
+
+/// `select_template` lists `templates` with their number of stories and asks for one, see
+/// `select_entry`.
+fn select_template(templates: &[Template], io: &dyn Io) -> Option<usize> {
+    let entries: Vec<String> = templates
+        .iter()
+        .map(|template| {
+            let stories = template.stories.len();
+            format!(
+                "{} - {} {}",
+                template.name,
+                stories,
+                if stories == 1 { "story" } else { "stories" }
+            )
+        })
+        .collect();
+    select_entry(&entries, "Template number:", io)
+}
+
+/// `select_entry` lists `entries`, numbered from 1, and asks for the number of one with
+/// `prompt`, again until it is listed. Returns the index of the chosen entry, or `None` if
+/// the user cancels with "x" or an empty line.
+fn select_entry(entries: &[String], prompt: &str, io: &dyn Io) -> Option<usize> {
+    for (number, entry) in (1..).zip(entries) {
+        io.println(&format!("({}) {}", number, entry));
+    }
+    io.println(&format!("{} ((x) cancel)", prompt));
+    loop {
+        let line = io.prompt_line()?;
+        if line.trim().to_lowercase() == "x" {
+            return None;
+        }
+        match parse_selection(&line, entries.len()) {
+            Some(index) => return Some(index),
+            None => io.println(&format!(
+                "Enter a number from 1 to {}. Please try again:",
+                entries.len()
+            )),
+        }
+    }
+}
+
+/// `parse_selection` reads the number of one of `count` listed entries, numbered from 1, and
+/// returns its index. `None` is returned if `line` is not a listed number.
+fn parse_selection(line: &str, count: usize) -> Option<usize> {
+    let number = line.trim().parse::<usize>().ok()?;
+    (1..=count).contains(&number).then(|| number - 1)
+}
+
+/// `create_task` prompts for a new task, reading each line of input from `io`. The
+/// description may be written in `edit`.
+fn create_task(io: &dyn Io, edit: Editor) -> Option<Task> {
+    let (name, description) = read_name_and_description("Task", io, edit)?;
+    Some(Task::new(&name, &description))
+}
+
+/// `create_sprint` prompts for a new sprint, reading each line of input from `io`. Dates
+/// are entered in `date_format`, and asked again until they are valid and the sprint does
+/// not end before it starts. `None` is returned if the user cancels with "x" or an empty
+/// line.
+fn create_sprint(io: &dyn Io, date_format: &str) -> Option<Sprint> {
+    io.println("Enter sprint name: ((x) cancel and discard)");
+    let name = read_name(io)?;
+    io.println(&format!("Enter start date ({}):", date_format));
+    let start = read_date(io, date_format)?;
+    io.println(&format!("Enter end date ({}):", date_format));
+    let end = loop {
+        let end = read_date(io, date_format)?;
+        if end >= start {
+            break end;
         }
+        io.println("The sprint cannot end before it starts. Please enter another end date:");
     };
-    println!("Enter Story description: ((x) cancel and discard)");
+    Some(Sprint::new(&name, start, end))
+}
+
+/// `read_date` reads a date in `format` from `io`, asking again until it is valid. `None`
+/// is returned if the user cancels with "x" or an empty line.
+fn read_date(io: &dyn Io, format: &str) -> Option<NaiveDate> {
+    let example = NaiveDate::from_ymd_opt(2024, 5, 31).expect("valid date");
+    loop {
+        let line = io.prompt_line().filter(|line| line.to_lowercase() != "x")?;
+        match parse_date(&line, format) {
+            Some(date) => return Some(date),
+            None => io.println(&format!(
+                "Dates look like {}. Please try again:",
+                example.format(format)
+            )),
+        }
+    }
+}
+
+/// `read_name_and_description` prompts for the name and description of a new `kind` of item,
+/// reading each line of input from `io`. Names and descriptions are asked again until
+/// they are valid. The description may be written in `edit` with "e" instead. Descriptions
+/// wider than the description column, see `ColumnWidths`, may be kept or re-entered. `None`
+/// is returned if the user cancels with "x".
+fn read_name_and_description(kind: &str, io: &dyn Io, edit: Editor) -> Option<(String, String)> {
+    io.println(&format!("Enter {} name: ((x) cancel and discard)", kind));
+    let name = read_name(io)?;
+    io.println(&format!(
+        "Enter {} description: ((e) open editor | (x) cancel and discard)",
+        kind
+    ));
     let description: String = loop {
-        match read_line() {
+        match io.prompt_line() {
             Some(description) => {
-                if description.to_lowercase() == "x" {
-                    return None;
+                let description = match description.to_lowercase().as_str() {
+                    "x" => return None,
+                    "e" => match edit("") {
+                        Ok(description) => description,
+                        Err(error) => {
+                            io.println(&format!("{}. Please enter the description:", error));
+                            continue;
+                        }
+                    },
+                    _ => description,
+                };
+                let description = match validate::description(&description) {
+                    Ok(description) => description,
+                    Err(error) => {
+                        io.println(&format!(
+                            "Invalid description: {}. Please enter another one:",
+                            error
+                        ));
+                        continue;
+                    }
+                };
+                let length = description.chars().count();
+                let width = ColumnWidths::from_env().description;
+                if length <= width {
+                    break description;
+                }
+                io.println(&format!(
+                    "This description is {} characters long, and only {} fit on a line.",
+                    length, width
+                ));
+                io.println("\t(c) continue | (r) re-enter | (x) cancel and discard");
+                match io
+                    .prompt_line()
+                    .unwrap_or("".into())
+                    .to_lowercase()
+                    .as_str()
+                {
+                    "x" => return None,
+                    "r" => io.println(&format!(
+                        "Enter {} description: ((e) open editor | (x) cancel and discard)",
+                        kind
+                    )),
+                    _ => break description,
                 }
-                break description;
             }
+            None if io.is_closed() => return None,
             None => continue,
         }
     };
-    Some(Story::new(&name, &description))
+    Some((name, description))
 }
 
-fn create_task() -> Option<Task> {
-    println!("Enter Task name: ((x) cancel and discard)");
-    let name: String = loop {
-        match read_line() {
-            Some(name) => {
-                if name.to_lowercase() == "x" {
-                    return None;
-                }
-                if name.len() >= MAX_NAME_LENGTH {
-                    println!(
-                        "Task names should be short and meaningful. Please provide a shorter name:"
-                    );
-                } else {
-                    break name;
-                }
+/// `read_name` reads a name from `io`, asking again until it is valid. `None` is returned
+/// if the user cancels with "x".
+fn read_name(io: &dyn Io) -> Option<String> {
+    loop {
+        let Some(name) = io.prompt_line() else {
+            if io.is_closed() {
+                return None;
             }
-            None => continue,
+            continue;
+        };
+        if name.to_lowercase() == "x" {
+            return None;
         }
-    };
-    println!("Enter Task description: ((x) cancel and discard)");
-    let description: String = loop {
-        match read_line() {
-            Some(description) => {
-                if description.to_lowercase() == "x" {
-                    return None;
+        match validate::name(&name) {
+            Ok(name) => return Some(name),
+            Err(error) => io.println(&format!(
+                "Invalid name: {}. Please enter another one:",
+                error
+            )),
+        }
+    }
+}
+
+/// `update_name` shows the `current` name and prompts for a new one, reading each line of
+/// input from `io`. The name is asked again until it is valid. Empty input keeps
+/// `current`. `None` is returned if the user cancels with "x".
+fn update_name(current: &str, io: &dyn Io) -> Option<String> {
+    io.println(&format!("Current name: {}", current));
+    io.println("New name: (leave empty to keep the current name | (x) cancel)");
+    loop {
+        let Some(name) = io.prompt_line() else {
+            return Some(current.to_owned());
+        };
+        if name.to_lowercase() == "x" {
+            return None;
+        }
+        match validate::name(&name) {
+            Ok(name) => return Some(name),
+            Err(error) => io.println(&format!(
+                "Invalid name: {}. Please enter another one:",
+                error
+            )),
+        }
+    }
+}
+
+/// `update_description` shows the `current` description and prompts for a new one, reading
+/// each line of input from `io`. With "e", the `current` description is opened in `edit`
+/// instead. The description is asked again until it is valid. Empty input, or an editor
+/// that failed, keeps `current`. `None` is returned if the user cancels with "x".
+fn update_description(current: &str, io: &dyn Io, edit: Editor) -> Option<String> {
+    if current.is_empty() {
+        io.println("Current description: (none)");
+    } else {
+        io.println(&format!("Current description:\n{}", current));
+    }
+    io.println("New description: ((e) open editor | leave empty to keep the current description | (x) cancel)");
+    loop {
+        let description = match io.prompt_line() {
+            Some(line) if line.to_lowercase() == "x" => return None,
+            Some(line) if line.to_lowercase() == "e" => match edit(current) {
+                Ok(description) => description,
+                Err(error) => {
+                    io.println(&format!("{}. The description was not changed.", error));
+                    return Some(current.to_owned());
                 }
-                break description;
-            }
-            None => continue,
+            },
+            Some(line) => line,
+            None => return Some(current.to_owned()),
+        };
+        match validate::description(&description) {
+            Ok(description) => return Some(description),
+            Err(error) => io.println(&format!(
+                "Invalid description: {}. Please enter another one:",
+                error
+            )),
         }
+    }
+}
+
+fn update_status(io: &dyn Io) -> Option<Status> {
+    io.println("New status:");
+    io.println("\t(1) Open\n\t(2) In Progress\n\t(3) Resolved\n\t(4) Closed\n\t(5) Blocked");
+    io.println("(x) cancel");
+    match io.prompt_line().unwrap_or("".into()).as_str() {
+        "1" => Some(Status::Open),
+        "2" => Some(Status::InProgress),
+        "3" => Some(Status::Resolved),
+        "4" => Some(Status::Closed),
+        "5" => Some(Status::Blocked),
+        _ => None,
+    }
+}
+
+fn update_epic_status(io: &dyn Io) -> Option<EpicStatusChoice> {
+    io.println("New status:");
+    io.println("\t(1) Open\n\t(2) In Progress\n\t(3) Resolved\n\t(4) Closed\n\t(5) Blocked");
+    io.println("\t(auto) Derive from stories");
+    io.println("(x) cancel");
+    match io.prompt_line().unwrap_or("".into()).as_str() {
+        "1" => Some(EpicStatusChoice::Manual(Status::Open)),
+        "2" => Some(EpicStatusChoice::Manual(Status::InProgress)),
+        "3" => Some(EpicStatusChoice::Manual(Status::Resolved)),
+        "4" => Some(EpicStatusChoice::Manual(Status::Closed)),
+        "5" => Some(EpicStatusChoice::Manual(Status::Blocked)),
+        "auto" => Some(EpicStatusChoice::Auto),
+        _ => None,
+    }
+}
+
+fn confirm_epic_status(warning: &str, io: &dyn Io) -> bool {
+    io.println(&format!("Warning: {}.", warning));
+    confirm("Set the status anyway?", false, io)
+}
+
+fn confirm_status_downgrade(message: &str, io: &dyn Io) -> bool {
+    io.println(&format!("{}.", message));
+    confirm("Downgrade the status anyway?", false, io)
+}
+
+fn confirm_closed_epic(epic_id: u32, io: &dyn Io) -> bool {
+    io.println(&format!("Epic #{} is closed.", epic_id));
+    confirm("Add a story and reopen it?", false, io)
+}
+
+fn update_assignee(io: &dyn Io) -> Option<String> {
+    io.println("New assignee: (leave empty to unassign)");
+    io.prompt_line()
+}
+
+fn update_tags(io: &dyn Io) -> Option<TagChange> {
+    io.println("Update tags:");
+    io.println("\t(a) add | (r) remove");
+    io.println("(x) cancel");
+    let add = match io.prompt_line().unwrap_or("".into()).as_str() {
+        "a" => true,
+        "r" => false,
+        _ => return None,
     };
-    Some(Task::new(&name, &description))
+    io.println("Tag:");
+    let tag = io.prompt_line()?;
+    if add {
+        Some(TagChange::Add(tag))
+    } else {
+        Some(TagChange::Remove(tag))
+    }
+}
+
+/// `update_points` asks for the story points of a story, asking again until they are a
+/// whole number. An empty line clears the estimate with `Some(None)`. `None` is returned if
+/// the user cancels with "x" or the input ends.
+fn update_points(io: &dyn Io) -> Option<Option<u32>> {
+    io.println("Story points: (leave empty to clear the estimate | (x) cancel)");
+    loop {
+        let Some(line) = io.prompt_line() else {
+            return if io.is_closed() { None } else { Some(None) };
+        };
+        if line.trim().to_lowercase() == "x" {
+            return None;
+        }
+        match line
+            .trim()
+            .parse()
+            .map(|points| validate::points(Some(points)))
+        {
+            Ok(Ok(points)) => return Some(points),
+            Ok(Err(error)) => io.println(&format!("{}. Please try again:", error)),
+            Err(_) => io.println("Story points are a whole number, e.g. 3. Please try again:"),
+        }
+    }
 }
 
-fn delete_epic() -> bool {
-    println!("Delete this Epic? All Stories in this Epic will also be deleted.");
-    println!("\t(y) yes | (n) no");
-    read_line()
-        .unwrap_or("".into())
-        .to_ascii_lowercase()
-        .contains('y')
+/// `goto_query` asks for the id of an epic or story to open, or a few characters of its
+/// name. `None` is returned if the user cancels with an empty line or "x".
+fn goto_query(io: &dyn Io) -> Option<String> {
+    io.println("Go to epic or story ID, or type part of its name: ((x) cancel)");
+    let line = io.prompt_line()?;
+    if line.trim().to_lowercase() == "x" {
+        return None;
+    }
+    Some(line.trim().to_owned())
 }
 
-fn delete_story() -> bool {
-    println!("Delete this Story? All Tasks in this Story will also be deleted.");
-    println!("\t(y) yes | (n) no");
-    read_line()
-        .unwrap_or("".into())
-        .to_ascii_lowercase()
-        .contains('y')
+/// `convert_target` asks for the id of the `kind` of item to convert an item into a child
+/// of, again until it is a number. `None` is returned if the user cancels with an empty line
+/// or "x".
+fn convert_target(kind: &str, io: &dyn Io) -> Option<u32> {
+    io.println(&format!("Convert it under the {} ID: ((x) cancel)", kind));
+    loop {
+        let line = io.prompt_line()?;
+        if line.trim().to_lowercase() == "x" {
+            return None;
+        }
+        match line.trim().parse() {
+            Ok(id) => return Some(id),
+            Err(_) => io.println("An ID is a whole number, e.g. 3. Please try again:"),
+        }
+    }
 }
 
-fn delete_task() -> bool {
-    println!("Delete this Task?");
-    println!("\t(y) yes | (n) no");
-    read_line()
-        .unwrap_or("".into())
-        .to_ascii_lowercase()
-        .contains('y')
+/// `update_rows_per_page` asks for the number of stories listed per page, again until it is
+/// a number of at least 1. `Some(None)` is returned for an empty line, to fit the pages to
+/// the terminal again, and `None` if the user cancels with "x".
+fn update_rows_per_page(io: &dyn Io) -> Option<Option<usize>> {
+    io.println("Rows per page: (leave empty to fit the terminal | (x) cancel)");
+    loop {
+        let Some(line) = io.prompt_line() else {
+            return if io.is_closed() { None } else { Some(None) };
+        };
+        if line.trim().to_lowercase() == "x" {
+            return None;
+        }
+        match line.trim().parse() {
+            Ok(rows) if rows > 0 => return Some(Some(rows)),
+            _ => io.println("Rows per page is a whole number of at least 1. Please try again:"),
+        }
+    }
 }
 
-fn update_name() -> String {
-    println!("New name:");
+/// `update_theme_color` asks for a status and the color to paint it in, each again until
+/// it is known. `None` is returned if the user cancels with "x" or an empty line.
+fn update_theme_color(io: &dyn Io) -> Option<(Status, ThemeColor)> {
+    io.println("Status to recolor, e.g. in progress: ((x) cancel)");
+    let status = loop {
+        let line = io.prompt_line().filter(|line| line.to_lowercase() != "x")?;
+        match line.parse::<Status>() {
+            Ok(status) => break status,
+            Err(error) => io.println(&format!("{}. Please try again:", error)),
+        }
+    };
+    io.println(&format!(
+        "Color for {}, e.g. bright blue: ((x) cancel)",
+        status
+    ));
     loop {
-        match read_line() {
-            Some(name) => {
-                if name.len() >= MAX_NAME_LENGTH {
-                    println!(
-                        "Story names should be short and meaningful. Please provide a shorter name:"
-                    );
-                } else {
-                    break name;
-                }
-            }
-            None => continue,
+        let line = io.prompt_line().filter(|line| line.to_lowercase() != "x")?;
+        match line.parse::<ThemeColor>() {
+            Ok(color) => return Some((status, color)),
+            Err(error) => io.println(&format!("{}. Please try again:", error)),
         }
     }
 }
 
-fn update_description() -> String {
-    println!("New description:");
-    read_line().unwrap_or("".into())
+/// `update_blockers` asks for a blocking story to add or remove. `None` is returned if the
+/// user cancels or enters an id that is not a number.
+fn update_blockers(io: &dyn Io) -> Option<BlockerChange> {
+    io.println("Update blockers:");
+    io.println("\t(a) add | (r) remove");
+    io.println("(x) cancel");
+    let add = match io.prompt_line().unwrap_or("".into()).as_str() {
+        "a" => true,
+        "r" => false,
+        _ => return None,
+    };
+    io.println("Blocking story ID:");
+    let id = io.prompt_line()?.trim().parse().ok()?;
+    if add {
+        Some(BlockerChange::Add(id))
+    } else {
+        Some(BlockerChange::Remove(id))
+    }
 }
 
-fn update_status() -> Option<Status> {
-    println!("New status:");
-    println!("\t(1) Open\n\t(2) In Progress\n\t(3) Resolved\n\t(4) Closed");
-    println!("(x) cancel");
-    match read_line().unwrap_or("".into()).as_str() {
-        "1" => Some(Status::Open),
-        "2" => Some(Status::InProgress),
-        "3" => Some(Status::Resolved),
-        "4" => Some(Status::Closed),
-        _ => None,
+/// `update_sprint_stories` asks for a story to plan in or take out of a sprint. `None` is
+/// returned if the user cancels or enters an id that is not a number.
+fn update_sprint_stories(io: &dyn Io) -> Option<SprintChange> {
+    io.println("Update sprint stories:");
+    io.println("\t(a) add | (r) remove");
+    io.println("(x) cancel");
+    let add = match io.prompt_line().unwrap_or("".into()).as_str() {
+        "a" => true,
+        "r" => false,
+        _ => return None,
+    };
+    io.println("Story ID:");
+    let id = io.prompt_line()?.trim().parse().ok()?;
+    if add {
+        Some(SprintChange::Add(id))
+    } else {
+        Some(SprintChange::Remove(id))
+    }
+}
+
+/// `log_work` prompts for the time spent on a story on the day `today` and a note about it.
+/// The duration is asked again until it is valid. `None` is returned if the user cancels
+/// with "x" or an empty line.
+fn log_work(today: NaiveDate, io: &dyn Io) -> Option<WorkEntry> {
+    io.println("Time spent, e.g. 1h30m, 45m or 2h: ((x) cancel)");
+    let minutes = loop {
+        let line = io.prompt_line().filter(|line| line.to_lowercase() != "x")?;
+        match parse_duration(&line) {
+            Some(minutes) => break minutes,
+            None => io.println("Durations look like 1h30m, 45m or 2h. Please try again:"),
+        }
+    };
+    io.println("Note: (optional)");
+    Some(WorkEntry {
+        minutes,
+        note: io.prompt_line().unwrap_or_default(),
+        date: today.format(DATE_FORMAT).to_string(),
+    })
+}
+
+/// `add_comment` prompts for a comment by `author`, made `at` the given time, reading its
+/// body from `io`. `None` is returned if the user cancels with "x" or an empty line.
+fn add_comment(author: &str, at: DateTime<Utc>, io: &dyn Io) -> Option<Comment> {
+    io.println(&format!("Comment as {}: ((x) cancel)", author));
+    let body = io.prompt_line().filter(|line| line.to_lowercase() != "x")?;
+    Some(Comment {
+        author: author.to_owned(),
+        body,
+        at,
+    })
+}
+
+/// `comment_author` returns the name comments are signed with: `CLIRA_USER`, then the
+/// user's login name, or "anonymous" if neither is known.
+fn comment_author() -> String {
+    clira_user()
+        .or_else(|| {
+            ["USER", "USERNAME"]
+                .into_iter()
+                .filter_map(|var| env::var(var).ok())
+                .find(|name| !name.trim().is_empty())
+        })
+        .unwrap_or_else(|| "anonymous".to_owned())
+}
+
+/// `confirm_recreate_board` asks whether to create an empty board at `path`, where the board
+/// file went missing.
+fn confirm_recreate_board(path: &str, io: &dyn Io) -> bool {
+    io.println(&format!("The board at {} was deleted.", path));
+    confirm(
+        "Create an empty board in its place? Otherwise, clira quits.",
+        false,
+        io,
+    )
+}
+
+fn export_path(io: &dyn Io) -> Option<String> {
+    io.println("Export to which file? (leave empty to cancel)");
+    io.prompt_line()
+}
+
+/// `import_path` asks for the file to import. Appending `--skip-existing` to the path skips
+/// the stories that already exist.
+fn import_path(io: &dyn Io) -> Option<(String, bool)> {
+    io.println("Import from which file? (append --skip-existing to skip existing stories)");
+    io.println("(leave empty to cancel)");
+    let input = io.prompt_line()?;
+    let skip_existing = input.ends_with("--skip-existing");
+    let path = input.trim_end_matches("--skip-existing").trim().to_owned();
+    if path.is_empty() {
+        return None;
+    }
+    Some((path, skip_existing))
+}
+
+/// `replace_in_descriptions` prompts for the text to find in descriptions and the text to
+/// replace it with, reading each line of input from `io`. An empty replacement removes
+/// the text. `None` is returned if the user leaves the text to find empty.
+fn replace_in_descriptions(io: &dyn Io) -> Option<(String, String)> {
+    io.println("Find what in descriptions? (case-sensitive, leave empty to cancel)");
+    let find = io.prompt_line()?;
+    io.println(&format!(
+        "Replace {:?} with? (leave empty to remove it)",
+        find
+    ));
+    let replace = io.prompt_line().unwrap_or_default();
+    Some((find, replace))
+}
+
+fn notify(message: &str, io: &dyn Io) {
+    io.println(message);
+    io.println("Press (enter) to continue..");
+    let _ = io.prompt_line();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{db::validate::MAX_NAME_LENGTH, ui::io::ScriptedIo};
+
+    use super::*;
+
+    /// `no_editor` stands in for the editor in prompts that should not open it.
+    fn no_editor(_: &str) -> anyhow::Result<String> {
+        panic!("the editor was not expected to open")
+    }
+
+    #[test]
+    fn create_epic_should_read_description_from_editor() {
+        let mut edit = |initial: &str| {
+            assert_eq!(initial, "");
+            Ok("First paragraph\n\nSecond paragraph".to_owned())
+        };
+        let epic = create_epic(&ScriptedIo::new(&["Epic 1", "e"]), &mut edit).unwrap();
+        assert_eq!(epic.description, "First paragraph\n\nSecond paragraph");
+
+        let mut failing = |_: &str| Err(anyhow::anyhow!("vi exited with exit status: 1"));
+        let story =
+            create_story(&ScriptedIo::new(&["Story 1", "e", "Typed"]), &mut failing).unwrap();
+        assert_eq!(story.description, "Typed");
+    }
+
+    #[test]
+    fn update_description_should_edit_current_description() {
+        let mut edit = |current: &str| Ok(format!("{}\nMore", current));
+        assert_eq!(
+            update_description("Original", &ScriptedIo::new(&["e"]), &mut edit),
+            Some("Original\nMore".to_owned())
+        );
+    }
+
+    #[test]
+    fn update_description_should_keep_original_when_editor_fails() {
+        let mut failing = |_: &str| Err(anyhow::anyhow!("vi exited with exit status: 1"));
+        assert_eq!(
+            update_description("Original", &ScriptedIo::new(&["E"]), &mut failing),
+            Some("Original".to_owned())
+        );
+    }
+
+    #[test]
+    fn create_epic_should_succeed() {
+        let epic = create_epic(
+            &ScriptedIo::new(&["Epic 1", "Short description"]),
+            &mut no_editor,
+        )
+        .unwrap();
+        assert_eq!(epic, Epic::new("Epic 1", "Short description"));
+    }
+
+    #[test]
+    fn create_stories_should_read_names_until_empty_line() {
+        let long = "a".repeat(MAX_NAME_LENGTH + 1);
+        let io = ScriptedIo::new(&["Story 1", &long, " Story 2 ", "", "Story 3"]);
+        assert_eq!(
+            create_stories(&io),
+            Some(vec!["Story 1".to_owned(), "Story 2".to_owned()])
+        );
+        assert!(io.output().contains(&format!("Skipped \"{}\"", long)));
+
+        assert_eq!(
+            create_stories(&ScriptedIo::new(&["Story 1"])),
+            Some(vec!["Story 1".to_owned()])
+        );
+        assert!(create_stories(&ScriptedIo::new(&["Story 1", "x"])).is_none());
+        assert!(create_stories(&ScriptedIo::new(&[""])).is_none());
+    }
+
+    #[test]
+    fn update_points_should_ask_again_until_valid() {
+        let io = ScriptedIo::new(&["three", "-1", "3000000000", " 3 "]);
+        assert_eq!(update_points(&io), Some(Some(3)));
+        assert_eq!(io.output().matches("Please try again").count(), 3);
+
+        assert_eq!(update_points(&ScriptedIo::new(&[""])), Some(None));
+        assert_eq!(update_points(&ScriptedIo::new(&["X"])), None);
+        assert_eq!(update_points(&ScriptedIo::new(&[])), None);
+    }
+
+    #[test]
+    fn select_template_should_ask_again_until_listed() {
+        let template = |name: &str| Template {
+            name: name.to_owned(),
+            description: "".to_owned(),
+            stories: vec![],
+        };
+        let templates = [template("Release {date}"), template("Onboarding")];
+        let io = ScriptedIo::new(&["0", "three", "2"]);
+        assert_eq!(select_template(&templates, &io), Some(1));
+        assert!(io.output().contains("(1) Release {date} - 0 stories"));
+        assert_eq!(
+            io.output()
+                .matches("Enter a number from 1 to 2. Please try again:")
+                .count(),
+            2
+        );
+
+        assert_eq!(select_template(&templates, &ScriptedIo::new(&["x"])), None);
+        assert_eq!(select_template(&templates, &ScriptedIo::new(&[""])), None);
+    }
+
+    #[test]
+    fn select_story_ids_should_split_entries_on_commas() {
+        assert_eq!(
+            select_story_ids(&ScriptedIo::new(&["3, 5,,x2 "])),
+            Some(vec!["3".to_owned(), "5".to_owned(), "x2".to_owned()])
+        );
+        assert_eq!(select_story_ids(&ScriptedIo::new(&[" , "])), None);
+        assert_eq!(select_story_ids(&ScriptedIo::new(&["x"])), None);
+        assert_eq!(select_story_ids(&ScriptedIo::new(&[""])), None);
+    }
+
+    #[test]
+    fn goto_query_should_read_id_or_name() {
+        assert_eq!(
+            goto_query(&ScriptedIo::new(&[" 12 "])),
+            Some("12".to_owned())
+        );
+        assert_eq!(
+            goto_query(&ScriptedIo::new(&[" rel chk "])),
+            Some("rel chk".to_owned())
+        );
+        assert_eq!(goto_query(&ScriptedIo::new(&[""])), None);
+        assert_eq!(goto_query(&ScriptedIo::new(&["x"])), None);
+    }
+
+    #[test]
+    fn parse_selection_should_accept_listed_numbers() {
+        assert_eq!(parse_selection("1", 9), Some(0));
+        assert_eq!(parse_selection(" 9 ", 9), Some(8));
+        assert_eq!(parse_selection("3", 2), None);
+        assert_eq!(parse_selection("0", 9), None);
+        assert_eq!(parse_selection("-1", 9), None);
+        assert_eq!(parse_selection("one", 9), None);
+        assert_eq!(parse_selection("1", 0), None);
+    }
+
+    #[test]
+    fn select_entry_should_list_entries_and_ask_again_until_listed() {
+        let entries = ["Epic #1 Release".to_owned(), "Story #2 Tag".to_owned()];
+        let io = ScriptedIo::new(&["5", "2"]);
+        assert_eq!(select_entry(&entries, "Number to open:", &io), Some(1));
+        assert!(io
+            .output()
+            .starts_with("(1) Epic #1 Release\n(2) Story #2 Tag\nNumber to open: ((x) cancel)"));
+        assert_eq!(io.output().matches("Please try again").count(), 1);
+    }
+
+    #[test]
+    fn update_rows_per_page_should_ask_again_until_valid() {
+        let io = ScriptedIo::new(&["0", "many", " 20 "]);
+        assert_eq!(update_rows_per_page(&io), Some(Some(20)));
+        assert_eq!(io.output().matches("Please try again").count(), 2);
+
+        assert_eq!(update_rows_per_page(&ScriptedIo::new(&[""])), Some(None));
+        assert_eq!(update_rows_per_page(&ScriptedIo::new(&["x"])), None);
+        assert_eq!(update_rows_per_page(&ScriptedIo::new(&[])), None);
+    }
+
+    #[test]
+    fn update_theme_color_should_ask_again_until_known() {
+        let io = ScriptedIo::new(&["done", "In Progress", "teal", "bright_cyan"]);
+        assert_eq!(
+            update_theme_color(&io),
+            Some((Status::InProgress, ThemeColor::BrightCyan))
+        );
+        assert!(io
+            .output()
+            .contains("unknown status \"done\". Please try again"));
+        assert!(io
+            .output()
+            .contains("unknown color \"teal\". Please try again"));
+
+        assert_eq!(update_theme_color(&ScriptedIo::new(&["x"])), None);
+        assert_eq!(update_theme_color(&ScriptedIo::new(&["open", ""])), None);
+    }
+
+    #[test]
+    fn create_story_should_cancel_on_x() {
+        assert!(create_story(&ScriptedIo::new(&["x"]), &mut no_editor).is_none());
+        assert!(create_story(&ScriptedIo::new(&["Story 1", "X"]), &mut no_editor).is_none());
+    }
+
+    #[test]
+    fn create_story_should_keep_long_description_on_continue() {
+        let long = "word ".repeat(ColumnWidths::from_env().description);
+        let story =
+            create_story(&ScriptedIo::new(&["Story 1", &long, "c"]), &mut no_editor).unwrap();
+        assert_eq!(story.description, long);
+    }
+
+    #[test]
+    fn create_story_should_count_description_in_characters() {
+        let accented = "é".repeat(ColumnWidths::from_env().description);
+        let io = ScriptedIo::new(&["Story 1", &accented]);
+        let story = create_story(&io, &mut no_editor).unwrap();
+        assert_eq!(story.description, accented);
+        assert!(!io.output().contains("fit on a line"));
+    }
+
+    #[test]
+    fn create_epic_should_reenter_long_description() {
+        let long = "word ".repeat(ColumnWidths::from_env().description);
+        let epic = create_epic(
+            &ScriptedIo::new(&["Epic 1", &long, "r", "Shorter"]),
+            &mut no_editor,
+        )
+        .unwrap();
+        assert_eq!(epic.description, "Shorter");
+    }
+
+    #[test]
+    fn create_task_should_cancel_long_description() {
+        let long = "word ".repeat(ColumnWidths::from_env().description);
+        assert!(create_task(&ScriptedIo::new(&["Task 1", &long, "x"]), &mut no_editor).is_none());
+    }
+
+    #[test]
+    fn create_task_should_reject_long_names() {
+        let long = "a".repeat(MAX_NAME_LENGTH + 1);
+        let task = create_task(
+            &ScriptedIo::new(&[&long, "Task 1", "", "Description"]),
+            &mut no_editor,
+        )
+        .unwrap();
+        assert_eq!(task, Task::new("Task 1", "Description"));
+    }
+
+    #[test]
+    fn update_name_should_retry_long_names() {
+        let long = "a".repeat(MAX_NAME_LENGTH + 1);
+        assert_eq!(
+            update_name("Old", &ScriptedIo::new(&[&long, "Name"])),
+            Some("Name".to_owned())
+        );
+    }
+
+    #[test]
+    fn update_name_should_keep_current_name_on_empty_input() {
+        assert_eq!(
+            update_name("Old", &ScriptedIo::new(&[""])),
+            Some("Old".to_owned())
+        );
+        let long = "a".repeat(MAX_NAME_LENGTH + 1);
+        assert_eq!(
+            update_name("Old", &ScriptedIo::new(&[&long, ""])),
+            Some("Old".to_owned())
+        );
+    }
+
+    #[test]
+    fn update_prompts_should_cancel_on_x() {
+        assert_eq!(update_name("Old", &ScriptedIo::new(&["x"])), None);
+        let long = "a".repeat(MAX_NAME_LENGTH + 1);
+        assert_eq!(update_name("Old", &ScriptedIo::new(&[&long, "X"])), None);
+        assert_eq!(
+            update_description("Old", &ScriptedIo::new(&["x"]), &mut no_editor),
+            None
+        );
+    }
+
+    #[test]
+    fn update_name_should_strip_control_characters() {
+        assert_eq!(
+            update_name("Old", &ScriptedIo::new(&["Na\u{1b}me"])),
+            Some("Name".to_owned())
+        );
+    }
+
+    #[test]
+    fn update_description_should_retry_long_descriptions() {
+        let long = "a".repeat(validate::DEFAULT_MAX_DESCRIPTION_LENGTH + 1);
+        assert_eq!(
+            update_description(
+                "",
+                &ScriptedIo::new(&[&long, "Description"]),
+                &mut no_editor
+            ),
+            Some("Description".to_owned())
+        );
+        assert_eq!(
+            update_description("Old", &ScriptedIo::new(&[""]), &mut no_editor),
+            Some("Old".to_owned())
+        );
+    }
+
+    #[test]
+    fn update_status_should_parse_choice() {
+        assert_eq!(update_status(&ScriptedIo::new(&["1"])), Some(Status::Open));
+        assert_eq!(
+            update_status(&ScriptedIo::new(&["2"])),
+            Some(Status::InProgress)
+        );
+        assert_eq!(
+            update_status(&ScriptedIo::new(&["3"])),
+            Some(Status::Resolved)
+        );
+        assert_eq!(
+            update_status(&ScriptedIo::new(&["4"])),
+            Some(Status::Closed)
+        );
+        assert_eq!(
+            update_status(&ScriptedIo::new(&["5"])),
+            Some(Status::Blocked)
+        );
+        assert_eq!(update_status(&ScriptedIo::new(&["x"])), None);
+        assert_eq!(update_status(&ScriptedIo::new(&[""])), None);
+    }
+
+    #[test]
+    fn update_epic_status_should_parse_auto() {
+        assert!(matches!(
+            update_epic_status(&ScriptedIo::new(&["auto"])),
+            Some(EpicStatusChoice::Auto)
+        ));
+        assert!(matches!(
+            update_epic_status(&ScriptedIo::new(&["4"])),
+            Some(EpicStatusChoice::Manual(Status::Closed))
+        ));
+        assert!(update_epic_status(&ScriptedIo::new(&["x"])).is_none());
+    }
+
+    #[test]
+    fn update_tags_should_read_change() {
+        assert!(matches!(
+            update_tags(&ScriptedIo::new(&["a", "bug"])),
+            Some(TagChange::Add(tag)) if tag == "bug"
+        ));
+        assert!(matches!(
+            update_tags(&ScriptedIo::new(&["r", "bug"])),
+            Some(TagChange::Remove(tag)) if tag == "bug"
+        ));
+        assert!(update_tags(&ScriptedIo::new(&["x"])).is_none());
+    }
+
+    #[test]
+    fn import_path_should_read_skip_existing_flag() {
+        assert_eq!(
+            import_path(&ScriptedIo::new(&["board.csv --skip-existing"])),
+            Some(("board.csv".to_owned(), true))
+        );
+        assert_eq!(
+            import_path(&ScriptedIo::new(&["board.csv"])),
+            Some(("board.csv".to_owned(), false))
+        );
+        assert_eq!(import_path(&ScriptedIo::new(&[""])), None);
+    }
+
+    #[test]
+    fn replace_in_descriptions_should_read_find_and_replace() {
+        assert_eq!(
+            replace_in_descriptions(&ScriptedIo::new(&["widget", "gadget"])),
+            Some(("widget".to_owned(), "gadget".to_owned()))
+        );
+        assert_eq!(
+            replace_in_descriptions(&ScriptedIo::new(&["widget", ""])),
+            Some(("widget".to_owned(), String::new()))
+        );
+        assert_eq!(replace_in_descriptions(&ScriptedIo::new(&[""])), None);
+    }
+
+    #[test]
+    fn update_blockers_should_read_story_id() {
+        assert!(matches!(
+            update_blockers(&ScriptedIo::new(&["a", " 3 "])),
+            Some(BlockerChange::Add(3))
+        ));
+        assert!(matches!(
+            update_blockers(&ScriptedIo::new(&["r", "3"])),
+            Some(BlockerChange::Remove(3))
+        ));
+        assert!(update_blockers(&ScriptedIo::new(&["a", "three"])).is_none());
+        assert!(update_blockers(&ScriptedIo::new(&["x"])).is_none());
+    }
+
+    #[test]
+    fn create_sprint_should_retry_invalid_dates() {
+        let sprint = create_sprint(
+            &ScriptedIo::new(&[
+                "Sprint 1",
+                "June 3rd",
+                "2024-06-03",
+                "2024-06-01",
+                "2024-06-14",
+            ]),
+            DATE_FORMAT,
+        )
+        .unwrap();
+        assert_eq!(sprint.name, "Sprint 1");
+        assert_eq!(sprint.start, parse_date("2024-06-03", DATE_FORMAT).unwrap());
+        assert_eq!(sprint.end, parse_date("2024-06-14", DATE_FORMAT).unwrap());
+        assert!(create_sprint(&ScriptedIo::new(&["Sprint 1", "x"]), DATE_FORMAT).is_none());
+    }
+
+    #[test]
+    fn create_sprint_should_read_dates_in_configured_format() {
+        let io = ScriptedIo::new(&["Sprint 1", "2024-06-03", "03/06/2024", "14/06/2024"]);
+        let sprint = create_sprint(&io, "%d/%m/%Y").unwrap();
+        assert_eq!(sprint.start, parse_date("2024-06-03", DATE_FORMAT).unwrap());
+        assert_eq!(sprint.end, parse_date("2024-06-14", DATE_FORMAT).unwrap());
+        assert!(io.output().contains("Dates look like 31/05/2024"));
+    }
+
+    #[test]
+    fn update_sprint_stories_should_read_story_id() {
+        assert!(matches!(
+            update_sprint_stories(&ScriptedIo::new(&["a", "3"])),
+            Some(SprintChange::Add(3))
+        ));
+        assert!(matches!(
+            update_sprint_stories(&ScriptedIo::new(&["r", "3"])),
+            Some(SprintChange::Remove(3))
+        ));
+        assert!(update_sprint_stories(&ScriptedIo::new(&["x"])).is_none());
+    }
+
+    #[test]
+    fn add_comment_should_sign_comment() {
+        let at = Utc::now();
+        assert_eq!(
+            add_comment("alice", at, &ScriptedIo::new(&["Looks good"])),
+            Some(Comment {
+                author: "alice".to_owned(),
+                body: "Looks good".to_owned(),
+                at,
+            })
+        );
+        assert_eq!(add_comment("alice", at, &ScriptedIo::new(&["x"])), None);
+        assert_eq!(add_comment("alice", at, &ScriptedIo::new(&[""])), None);
+    }
+
+    #[test]
+    fn log_work_should_retry_invalid_durations() {
+        let today = parse_date("2024-06-03", DATE_FORMAT).unwrap();
+        let entry = log_work(today, &ScriptedIo::new(&["an hour", "1h30m", "Pairing"])).unwrap();
+        assert_eq!(
+            entry,
+            WorkEntry {
+                minutes: 90,
+                note: "Pairing".to_owned(),
+                date: "2024-06-03".to_owned(),
+            }
+        );
+        assert_eq!(
+            log_work(today, &ScriptedIo::new(&["45m", ""]))
+                .unwrap()
+                .note,
+            ""
+        );
+        assert!(log_work(today, &ScriptedIo::new(&["x"])).is_none());
     }
 }
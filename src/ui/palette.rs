@@ -0,0 +1,96 @@
+use std::cmp::Reverse;
+
+use crate::{models::Action, utils::fuzzy_score};
+
+/// A `PaletteTarget` is what a command palette entry does when it is chosen.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PaletteTarget {
+    /// `Action` dispatches the action.
+    Action(Action),
+    /// `HomeCommand` goes back to the home page and enters the command there, for what the
+    /// home page does without an action, e.g. searching.
+    HomeCommand(&'static str),
+}
+
+/// `entries` returns the command palette: every action that can be taken from any page,
+/// with the label it is listed and searched by. New global actions belong here, so that
+/// the palette stays in sync with them.
+pub fn entries() -> Vec<(&'static str, PaletteTarget)> {
+    use PaletteTarget::{Action as Run, HomeCommand as Home};
+
+    vec![
+        ("New epic", Run(Action::CreateEpic)),
+        (
+            "New epic from template",
+            Run(Action::CreateEpicFromTemplate),
+        ),
+        ("New sprint", Run(Action::CreateSprint)),
+        ("Go to epic or story", Run(Action::JumpToItem)),
+        ("Search epics", Home("/")),
+        ("Search epics by regex", Home("~")),
+        ("Filter epics by assignee", Home("a")),
+        ("Show or hide archived epics", Home("A")),
+        ("Home", Run(Action::NavigateHome)),
+        ("Back", Run(Action::NavigateToPreviousPage)),
+        ("History", Run(Action::NavigateToHistory)),
+        ("Recently viewed", Run(Action::NavigateToRecent)),
+        ("Recently modified", Run(Action::NavigateToModified)),
+        ("Sprints", Run(Action::NavigateToSprints)),
+        ("Workspaces", Run(Action::NavigateToWorkspaces)),
+        ("Settings", Run(Action::NavigateToSettings)),
+        ("Trash", Run(Action::NavigateToTrash)),
+        ("Empty trash", Run(Action::PurgeTrash)),
+        ("Export CSV", Run(Action::ExportCsv)),
+        (
+            "Export stories as JSON lines",
+            Run(Action::ExportStoriesJsonl),
+        ),
+        ("Export HTML report", Run(Action::ExportHtml)),
+        ("Import CSV", Run(Action::ImportCsv)),
+        (
+            "Find and replace in descriptions",
+            Run(Action::ReplaceInDescriptions),
+        ),
+        ("Undo", Run(Action::Undo)),
+        ("Quit", Run(Action::Exit)),
+    ]
+}
+
+/// `matching` returns the palette entries whose labels match `query`, see `fuzzy_score`,
+/// best first. Every entry is returned, in palette order, for an empty query.
+pub fn matching(query: &str) -> Vec<(&'static str, PaletteTarget)> {
+    let mut matches: Vec<_> = entries()
+        .into_iter()
+        .filter_map(|entry| Some((fuzzy_score(query, entry.0)?, entry)))
+        .collect();
+    matches.sort_by_key(|(score, _)| Reverse(*score));
+    matches.into_iter().map(|(_, entry)| entry).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn entries_should_have_unique_labels() {
+        let entries = entries();
+        assert!(!entries.is_empty());
+        let labels: HashSet<&str> = entries.iter().map(|(label, _)| *label).collect();
+        assert_eq!(labels.len(), entries.len());
+    }
+
+    #[test]
+    fn matching_should_rank_best_match_first() {
+        assert_eq!(matching("").len(), entries().len());
+        let matches = matching("export");
+        assert!(matches.iter().all(|(label, _)| label.starts_with("Export")));
+        assert_eq!(matches.len(), 3);
+        assert_eq!(
+            matching("trash").first(),
+            Some(&("Trash", PaletteTarget::Action(Action::NavigateToTrash)))
+        );
+        assert!(matching("zzz").is_empty());
+    }
+}
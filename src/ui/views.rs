@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{DatabaseState, Epic, Story};
+
+/// `EpicView` is an epic along with its id, serialized for scripts. `stories` holds the
+/// resolved stories of the epic when they were requested.
+#[derive(Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct EpicView {
+    pub id: u32,
+    #[serde(flatten)]
+    pub epic: Epic,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stories: Option<Vec<StoryView>>,
+}
+
+/// `StoryView` is a story along with its id, serialized for scripts.
+#[derive(Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct StoryView {
+    pub id: u32,
+    #[serde(flatten)]
+    pub story: Story,
+}
+
+impl EpicView {
+    /// `list` returns a view of every epic in `state` without its stories, ordered by id.
+    pub fn list(state: &DatabaseState) -> Vec<Self> {
+        state
+            .epics
+            .iter()
+            .map(|(id, epic)| Self {
+                id: *id,
+                epic: epic.clone(),
+                stories: None,
+            })
+            .collect()
+    }
+
+    /// `with_stories` returns a view of the epic `id` in `state` with its stories resolved,
+    /// or `None` if the epic does not exist.
+    pub fn with_stories(state: &DatabaseState, id: u32) -> Option<Self> {
+        let epic = state.epics.get(&id)?;
+        let stories = epic
+            .story_ids
+            .iter()
+            .filter_map(|story_id| {
+                state.stories.get(story_id).map(|story| StoryView {
+                    id: *story_id,
+                    story: story.clone(),
+                })
+            })
+            .collect();
+        Some(Self {
+            id,
+            epic: epic.clone(),
+            stories: Some(stories),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_stories_should_resolve_stories() {
+        let mut state = DatabaseState::default();
+        let mut epic = Epic::new("Epic 1", "Epic 1 description");
+        epic.story_ids = vec![2, 1];
+        state.epics.insert(0, epic.clone());
+        state.stories.insert(1, Story::new("Story 1", ""));
+        state.stories.insert(2, Story::new("Story 2", ""));
+
+        let view = EpicView::with_stories(&state, 0).unwrap();
+        let stories = view.stories.unwrap();
+        assert_eq!(view.epic, epic);
+        assert_eq!(
+            stories.iter().map(|story| story.id).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert_eq!(stories[0].story.name, "Story 2");
+        assert!(EpicView::with_stories(&state, 9).is_none());
+    }
+}
@@ -1,23 +1,157 @@
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
 use owo_colors::OwoColorize;
-use std::io::stdin;
+use std::{
+    env, fs,
+    io::{self, stdin, BufRead},
+    process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
 use tabled::settings::Color;
 
-use crate::models::Status;
+use crate::{theme::Theme, ui::io::Io};
 
-/// `read_line` reads an entire line from `stdin` and returns `Some` string with
-/// the leading and trailing whitespace removed. `None` is returned if an empty
-/// string is read.
-pub fn read_line() -> Option<String> {
+/// A `Line` is a line of input read by `read_line`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Line {
+    /// `Text` is a line with the leading and trailing whitespace removed.
+    Text(String),
+    /// `Empty` is a line with nothing but whitespace.
+    Empty,
+    /// `Eof` means that the input was closed, e.g. with ctrl-d or at the end of piped input.
+    Eof,
+}
+
+impl Line {
+    /// `text` returns the text of the line, or `None` if there is none.
+    pub fn text(self) -> Option<String> {
+        match self {
+            Self::Text(text) => Some(text),
+            Self::Empty | Self::Eof => None,
+        }
+    }
+}
+
+/// `STDIN_CLOSED` is set once `read_line` reaches the end of `stdin`.
+static STDIN_CLOSED: AtomicBool = AtomicBool::new(false);
+
+/// `read_line` reads an entire line from `stdin`, see `read_line_from`.
+pub fn read_line() -> io::Result<Line> {
+    let line = read_line_from(&mut stdin().lock())?;
+    if line == Line::Eof {
+        STDIN_CLOSED.store(true, Ordering::Relaxed);
+    }
+    Ok(line)
+}
+
+/// `stdin_closed` returns whether `read_line` reached the end of `stdin`, after which
+/// nothing more can be read. Prompts that ask again on an empty line give up instead.
+pub fn stdin_closed() -> bool {
+    STDIN_CLOSED.load(Ordering::Relaxed)
+}
+
+/// `read_line_from` reads an entire line from `reader`. Returns `Err` if it could not be
+/// read.
+pub fn read_line_from(reader: &mut dyn BufRead) -> io::Result<Line> {
     let mut input = String::new();
-    stdin().read_line(&mut input).unwrap();
-    let input = input.trim().to_string();
+    if reader.read_line(&mut input)? == 0 {
+        return Ok(Line::Eof);
+    }
+    let input = input.trim();
     if input.is_empty() {
-        None
+        Ok(Line::Empty)
     } else {
-        Some(input)
+        Ok(Line::Text(input.to_owned()))
     }
 }
 
+/// `CONFIRM_ATTEMPTS` is how many answers `confirm` reads before falling back to the
+/// default.
+const CONFIRM_ATTEMPTS: usize = 3;
+
+/// `confirm` asks the yes/no `question` on `io`. Only `y`, `yes`, `n` and `no` are
+/// accepted, ignoring case; anything else is asked again, up to `CONFIRM_ATTEMPTS` times. An
+/// empty answer, or running out of attempts, returns `default`.
+pub fn confirm(question: &str, default: bool, io: &dyn Io) -> bool {
+    io.println(question);
+    io.println("\t(y) yes | (n) no");
+    for _ in 0..CONFIRM_ATTEMPTS {
+        let Some(answer) = io.prompt_line() else {
+            return default;
+        };
+        match parse_yes_no(&answer) {
+            Some(answer) => return answer,
+            None => io.println("Please answer y or n:"),
+        }
+    }
+    default
+}
+
+/// `parse_yes_no` reads a yes/no `answer`, or `None` if it is neither.
+fn parse_yes_no(answer: &str) -> Option<bool> {
+    match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// `DEFAULT_ROWS_PER_PAGE` is the number of table rows shown per page when the terminal
+/// height is unknown.
+pub const DEFAULT_ROWS_PER_PAGE: usize = 15;
+
+/// `rows_per_page` returns how many table rows fit on the screen. `CLIRA_ROWS_PER_PAGE`
+/// takes precedence, then the `configured` number of rows, then the terminal height from
+/// `LINES` less room for the headers and menu. Falls back to `DEFAULT_ROWS_PER_PAGE`.
+pub fn rows_per_page(configured: Option<usize>) -> usize {
+    if let Some(rows) = env::var("CLIRA_ROWS_PER_PAGE")
+        .ok()
+        .and_then(|rows| rows.parse::<usize>().ok())
+        .filter(|rows| *rows > 0)
+        .or(configured)
+    {
+        return rows;
+    }
+    env::var("LINES")
+        .ok()
+        .and_then(|lines| lines.parse::<usize>().ok())
+        .map(|lines| (lines.saturating_sub(20) / 2).max(5))
+        .unwrap_or(DEFAULT_ROWS_PER_PAGE)
+}
+
+/// `clira_user` returns the user named by `CLIRA_USER`, who is the default author and
+/// assignee. `None` is returned if it is unset or blank.
+pub fn clira_user() -> Option<String> {
+    user_from_var(env::var("CLIRA_USER").ok().as_deref())
+}
+
+/// `user_from_var` reads the user name from the value of `CLIRA_USER`.
+fn user_from_var(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_owned)
+}
+
+/// `page_count` returns the number of pages needed to show `len` rows, `per_page` at a
+/// time. There is always at least one page.
+pub fn page_count(len: usize, per_page: usize) -> usize {
+    len.div_ceil(per_page.max(1)).max(1)
+}
+
+/// `paginate` returns the rows of `items` on the zero-based `page`, `per_page` at a time.
+/// A `page` past the end is clamped to the last page.
+pub fn paginate<T>(items: &[T], page: usize, per_page: usize) -> &[T] {
+    let per_page = per_page.max(1);
+    let page = page.min(page_count(items.len(), per_page) - 1);
+    let start = page * per_page;
+    let end = (start + per_page).min(items.len());
+    &items[start..end]
+}
+
 /// `constrain_text` breaks a long, single-line string into a multi-line string
 /// with smart new-line breaks before a word begins. `line_limit` specifies how
 /// long a line needs to be before a new line is inserted.
@@ -37,30 +171,271 @@ pub fn constrain_text(text: &str, line_limit: usize) -> String {
     fmt_text.trim().to_owned()
 }
 
+/// `preview_text` breaks `text` into lines like `constrain_text`, but keeps only the first
+/// `max_lines` of them. A cut text ends with "…".
+pub fn preview_text(text: &str, line_limit: usize, max_lines: usize) -> String {
+    let text = constrain_text(text, line_limit);
+    let lines: Vec<_> = text.lines().collect();
+    if lines.len() <= max_lines {
+        return text;
+    }
+    format!("{} …", lines[..max_lines].join("\n"))
+}
+
+/// `FUZZY_CONSECUTIVE_BONUS` is added by `fuzzy_score` for a character matched right after
+/// the previous one.
+const FUZZY_CONSECUTIVE_BONUS: u32 = 4;
+
+/// `FUZZY_WORD_START_BONUS` is added by `fuzzy_score` for a character matched at the start of
+/// a word.
+const FUZZY_WORD_START_BONUS: u32 = 3;
+
+/// `fuzzy_score` scores how well `needle` matches `haystack`, ignoring case and the spaces in
+/// `needle`. Every character of `needle` must appear in `haystack` in order, but not
+/// necessarily next to each other. Characters matched in a row or at the start of a word
+/// score higher, so the higher the score, the better the match. `None` is returned if
+/// `needle` does not match.
+pub fn fuzzy_score(needle: &str, haystack: &str) -> Option<u32> {
+    let needle: Vec<char> = needle
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let haystack: Vec<char> = haystack.to_lowercase().chars().collect();
+    let Some(first) = needle.first() else {
+        return Some(0);
+    };
+    // The match is tried from every occurrence of the first character, since the leftmost
+    // one is not always the best, e.g. "ab" in "a cab".
+    (0..haystack.len())
+        .filter(|start| haystack[*start] == *first)
+        .filter_map(|start| fuzzy_score_from(&needle, &haystack, start))
+        .max()
+}
+
+/// `fuzzy_score_from` matches each character of `needle` to its next occurrence in
+/// `haystack`, from `start` on, and scores the match. `None` is returned if a character is
+/// not found.
+fn fuzzy_score_from(needle: &[char], haystack: &[char], start: usize) -> Option<u32> {
+    let mut score = 0;
+    let mut next = start;
+    for c in needle {
+        let index = next + haystack.get(next..)?.iter().position(|h| h == c)?;
+        score += 1;
+        if index > start && index == next {
+            score += FUZZY_CONSECUTIVE_BONUS;
+        }
+        if index == 0 || !haystack[index - 1].is_alphanumeric() {
+            score += FUZZY_WORD_START_BONUS;
+        }
+        next = index + 1;
+    }
+    Some(score)
+}
+
+/// `DATE_FORMAT` is the format in which dates are stored, and the default format of the
+/// dates entered by the user, e.g. 2024-05-31.
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// `parse_date` parses a date in `format`, ignoring surrounding whitespace. Returns `None`
+/// if `input` is not a valid date.
+pub fn parse_date(input: &str, format: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(input.trim(), format).ok()
+}
+
+/// `parse_duration` parses a duration such as `1h30m`, `45m` or `2h` into minutes. Spaces
+/// between the hours and the minutes are allowed. Returns `None` if `input` is not a
+/// duration or if it is zero.
+pub fn parse_duration(input: &str) -> Option<u32> {
+    let number = |text: &str| {
+        let text = text.trim();
+        if text.is_empty() || !text.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        text.parse::<u32>().ok()
+    };
+    let input = input.trim().to_lowercase();
+    let (hours, rest) = match input.split_once('h') {
+        Some((hours, rest)) => (number(hours)?, rest.trim()),
+        None => (0, input.as_str()),
+    };
+    let minutes = if rest.is_empty() && input.contains('h') {
+        0
+    } else {
+        number(rest.strip_suffix('m')?)?
+    };
+    let total = hours.checked_mul(60)?.checked_add(minutes)?;
+    (total > 0).then_some(total)
+}
+
+/// `format_duration` formats `minutes` the way `parse_duration` reads them, e.g. `1h 30m`.
+pub fn format_duration(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
+
+/// `DEFAULT_EDITOR` is the editor used when neither `VISUAL` nor `EDITOR` is set.
+const DEFAULT_EDITOR: &str = "vi";
+
+/// `editor_command` returns the editor to launch: `visual` if set, then `editor`, then
+/// `DEFAULT_EDITOR`. Empty values count as unset.
+fn editor_command(visual: Option<&str>, editor: Option<&str>) -> String {
+    [visual, editor]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|command| !command.is_empty())
+        .unwrap_or(DEFAULT_EDITOR)
+        .to_owned()
+}
+
+/// `edit_in_editor` opens `initial` in the user's editor, picked from `$VISUAL`, `$EDITOR` or
+/// `vi`, and returns the saved text without its trailing line breaks. Returns `Err` if the
+/// editor could not be launched or exited with an error.
+pub fn edit_in_editor(initial: &str) -> Result<String> {
+    let editor = editor_command(
+        env::var("VISUAL").ok().as_deref(),
+        env::var("EDITOR").ok().as_deref(),
+    );
+    edit_with(&editor, initial)
+}
+
+/// `edit_with` opens `initial` in a temporary file with the `editor` command, which may
+/// include arguments such as `code --wait`, and returns the saved text.
+fn edit_with(editor: &str, initial: &str) -> Result<String> {
+    let file = tempfile::Builder::new()
+        .prefix("clira-")
+        .suffix(".md")
+        .tempfile()?;
+    fs::write(file.path(), initial)?;
+    let mut args = editor.split_whitespace();
+    let program = args.next().unwrap_or(DEFAULT_EDITOR);
+    let status = process::Command::new(program)
+        .args(args)
+        .arg(file.path())
+        .status()
+        .with_context(|| format!("could not launch {}", editor))?;
+    if !status.success() {
+        bail!("{} exited with {}", editor, status);
+    }
+    let text = fs::read_to_string(file.path())?;
+    Ok(text.trim_end_matches(['\r', '\n']).to_owned())
+}
+
+/// `ColorMode` is whether output is colored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorMode {
+    Enabled,
+    Disabled,
+}
+
+/// `COLOR_MODE` is the color mode of the program, set once by `ColorMode::init`.
+static COLOR_MODE: OnceLock<ColorMode> = OnceLock::new();
+
+impl ColorMode {
+    /// `init` sets the color mode for the rest of the program. Colors are disabled with
+    /// `no_color` or when the `NO_COLOR` environment variable is set. Later calls have no
+    /// effect.
+    pub fn init(no_color: bool) {
+        let mode = if no_color {
+            Self::Disabled
+        } else {
+            Self::from_var(env::var("NO_COLOR").ok().as_deref())
+        };
+        let _ = COLOR_MODE.set(mode);
+    }
+
+    /// `current` returns the color mode set by `init`, or the one picked by `NO_COLOR` if
+    /// `init` was not called.
+    pub fn current() -> Self {
+        *COLOR_MODE.get_or_init(|| Self::from_var(env::var("NO_COLOR").ok().as_deref()))
+    }
+
+    /// `from_var` disables colors when `NO_COLOR` is set to a non-empty `value`, as
+    /// described on no-color.org.
+    fn from_var(value: Option<&str>) -> Self {
+        match value {
+            Some(value) if !value.is_empty() => Self::Disabled,
+            _ => Self::Enabled,
+        }
+    }
+}
+
 /// `color_table_column` parses `status` as the `Status` type, colors it
-/// according to its state, and returns it again as a string. Non-status returns
-/// the input string.
-pub fn color_table_column(status: &str) -> String {
-    match Status::from(status.to_owned()) {
-        Status::Open => status.to_string(),
-        Status::InProgress => status.yellow().to_string(),
-        Status::Resolved => status.blue().to_string(),
-        Status::Closed => status.green().to_string(),
+/// according to `theme`, and returns it again as a string. Non-status, or any
+/// status with colors disabled, returns the input string.
+pub fn color_table_column(status: &str, colors: ColorMode, theme: &Theme) -> String {
+    if colors == ColorMode::Disabled {
+        return status.to_string();
+    }
+    match status
+        .parse()
+        .ok()
+        .and_then(|status| theme.color(status).ansi())
+    {
+        Some(color) => status.color(color).to_string(),
+        None => status.to_string(),
     }
 }
 
-/// `color_for_table_header` returns the `Color` for the `status`, parsed as `Status`.
-pub fn color_for_table_header(status: &str) -> Color {
-    match Status::from(status.to_owned()) {
-        Status::Open => Color::empty(),
-        Status::InProgress => Color::FG_YELLOW,
-        Status::Resolved => Color::FG_BLUE,
-        Status::Closed => Color::FG_GREEN,
+/// `dim` returns `text` dimmed, or as is with colors disabled.
+pub fn dim(text: &str, colors: ColorMode) -> String {
+    match colors {
+        ColorMode::Disabled => text.to_string(),
+        ColorMode::Enabled => text.dimmed().to_string(),
+    }
+}
+
+/// `progress_bar` renders `done` out of `total` items as a bar of `width` segments followed
+/// by the count, e.g. `▓▓▓░░░░░ 1/3`. Segments are rounded to the nearest. A `total` of 0
+/// renders as `—`.
+pub fn progress_bar(done: usize, total: usize, width: usize) -> String {
+    progress_bar_with_partial(done, 0, total, width)
+}
+
+/// `progress_bar_with_partial` renders a `progress_bar` where each of the `partial` items
+/// counts as half done. Their segments are drawn in a lighter, dimmed shade, so it is only
+/// meant for colored output.
+pub fn progress_bar_with_partial(
+    done: usize,
+    partial: usize,
+    total: usize,
+    width: usize,
+) -> String {
+    if total == 0 {
+        return "—".to_owned();
+    }
+    // `segments` rounds the share of `halves` half items to the nearest segment.
+    let segments = |halves: usize| (halves * width + total) / (2 * total);
+    let filled = segments(2 * done);
+    let shaded = segments(2 * done + partial) - filled;
+    let mut bar = "▓".repeat(filled);
+    if shaded > 0 {
+        bar.push_str(&"▒".repeat(shaded).dimmed().to_string());
+    }
+    bar.push_str(&"░".repeat(width.saturating_sub(filled + shaded)));
+    format!("{} {}/{}", bar, done, total)
+}
+
+/// `color_for_table_header` returns the `Color` for the `status`, parsed as `Status`,
+/// according to `theme`. Non-status, or any status with colors disabled, returns no color.
+pub fn color_for_table_header(status: &str, colors: ColorMode, theme: &Theme) -> Color {
+    if colors == ColorMode::Disabled {
+        return Color::empty();
+    }
+    match status.parse() {
+        Ok(status) => theme.color(status).table_color(),
+        Err(_) => Color::empty(),
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::{theme::ThemeColor, ui::io::ScriptedIo};
+
     use super::*;
 
     #[test]
@@ -79,26 +454,330 @@ mod tests {
         )
     }
 
+    #[test]
+    fn preview_text_should_keep_first_lines() {
+        let text = "This will be very interesting";
+        assert_eq!(preview_text(text, 10, 2), "This will\nbe very …");
+        assert_eq!(preview_text(text, 10, 3), constrain_text(text, 10));
+        assert_eq!(preview_text("", 10, 1), "");
+    }
+
+    #[test]
+    fn fuzzy_score_should_ignore_case() {
+        assert_eq!(fuzzy_score("REL", "release"), fuzzy_score("rel", "Release"));
+        assert!(fuzzy_score("rElEaSe", "RELEASE").is_some());
+        assert_eq!(fuzzy_score("", "Release"), Some(0));
+    }
+
+    #[test]
+    fn fuzzy_score_should_match_characters_in_order() {
+        assert!(fuzzy_score("rls", "Release").is_some());
+        assert!(fuzzy_score("rel chk", "Release checklist").is_some());
+        assert!(fuzzy_score("lr", "Release").is_none());
+        assert!(fuzzy_score("rex", "Release").is_none());
+        assert!(fuzzy_score("releases", "Release").is_none());
+        assert!(fuzzy_score("a", "").is_none());
+    }
+
+    #[test]
+    fn fuzzy_score_should_rank_closer_matches_higher() {
+        let score = |needle, haystack| fuzzy_score(needle, haystack).unwrap();
+        // Characters in a row beat scattered ones.
+        assert!(score("rel", "Release") > score("rel", "Refactor logging"));
+        // The start of a word beats the middle of one.
+        assert!(score("log", "Refactor logging") > score("log", "Catalogue"));
+        assert!(score("tag", "Tag v2") > score("tag", "Vintage"));
+        // The best occurrence is found, not the leftmost.
+        assert!(score("ab", "a cab") > score("ab", "a cxb"));
+        assert_eq!(score("ab", "a cab"), score("ab", "cab"));
+        // An exact name scores highest.
+        assert!(score("release", "Release") > score("release", "Re lease"));
+    }
+
+    #[test]
+    fn progress_bar_should_round_to_nearest_segment() {
+        assert_eq!(progress_bar(0, 3, 8), "░░░░░░░░ 0/3");
+        assert_eq!(progress_bar(1, 3, 8), "▓▓▓░░░░░ 1/3");
+        assert_eq!(progress_bar(2, 3, 8), "▓▓▓▓▓░░░ 2/3");
+        assert_eq!(progress_bar(3, 3, 8), "▓▓▓▓▓▓▓▓ 3/3");
+        assert_eq!(progress_bar(4, 9, 8), "▓▓▓▓░░░░ 4/9");
+        assert_eq!(progress_bar(0, 0, 8), "—");
+    }
+
+    #[test]
+    fn progress_bar_with_partial_should_shade_half_done_items() {
+        assert_eq!(
+            progress_bar_with_partial(1, 2, 4, 8),
+            format!("▓▓{}░░░░ 1/4", "▒▒".dimmed())
+        );
+        assert_eq!(
+            progress_bar_with_partial(1, 1, 3, 8),
+            format!("▓▓▓{}░░░░ 1/3", "▒".dimmed())
+        );
+        assert_eq!(progress_bar_with_partial(0, 0, 4, 8), "░░░░░░░░ 0/4");
+    }
+
+    #[test]
+    fn paginate_should_succeed() {
+        let items: Vec<_> = (1..=7).collect();
+        assert_eq!(page_count(items.len(), 3), 3);
+        assert_eq!(paginate(&items, 0, 3), &[1, 2, 3]);
+        assert_eq!(paginate(&items, 2, 3), &[7]);
+        assert_eq!(paginate(&items, 9, 3), &[7]);
+        assert_eq!(page_count(0, 3), 1);
+        assert!(paginate::<u32>(&[], 0, 3).is_empty());
+    }
+
     #[test]
     fn color_table_column_should_succeed() {
-        assert_eq!(color_table_column("foo"), "foo");
-        assert_eq!(color_table_column("Open"), "Open");
         assert_eq!(
-            color_table_column("In Progress"),
+            color_table_column("foo", ColorMode::Enabled, &Theme::default()),
+            "foo"
+        );
+        assert_eq!(
+            color_table_column("Open", ColorMode::Enabled, &Theme::default()),
+            "Open"
+        );
+        assert_eq!(
+            color_table_column("Blocked", ColorMode::Enabled, &Theme::default()),
+            "Blocked".red().to_string()
+        );
+        assert_eq!(
+            color_table_column("In Progress", ColorMode::Enabled, &Theme::default()),
             "In Progress".yellow().to_string()
         );
         assert_eq!(
-            color_table_column("Resolved"),
+            color_table_column("Resolved", ColorMode::Enabled, &Theme::default()),
             "Resolved".blue().to_string()
         );
-        assert_eq!(color_table_column("Closed"), "Closed".green().to_string());
+        assert_eq!(
+            color_table_column("Closed", ColorMode::Enabled, &Theme::default()),
+            "Closed".green().to_string()
+        );
     }
 
     #[test]
     fn color_for_table_header_should_succeed() {
-        assert_eq!(color_for_table_header("Open"), Color::empty());
-        assert_eq!(color_for_table_header("In Progress"), Color::FG_YELLOW);
-        assert_eq!(color_for_table_header("Resolved"), Color::FG_BLUE);
-        assert_eq!(color_for_table_header("Closed"), Color::FG_GREEN);
+        assert_eq!(
+            color_for_table_header("Open", ColorMode::Enabled, &Theme::default()),
+            Color::empty()
+        );
+        assert_eq!(
+            color_for_table_header("Blocked", ColorMode::Enabled, &Theme::default()),
+            Color::FG_RED
+        );
+        assert_eq!(
+            color_for_table_header("In Progress", ColorMode::Enabled, &Theme::default()),
+            Color::FG_YELLOW
+        );
+        assert_eq!(
+            color_for_table_header("Resolved", ColorMode::Enabled, &Theme::default()),
+            Color::FG_BLUE
+        );
+        assert_eq!(
+            color_for_table_header("Closed", ColorMode::Enabled, &Theme::default()),
+            Color::FG_GREEN
+        );
+        assert_eq!(
+            color_for_table_header("Status", ColorMode::Enabled, &Theme::default()),
+            Color::empty()
+        );
+    }
+
+    #[test]
+    fn read_line_from_should_tell_empty_lines_from_eof() {
+        let mut reader = " 1 \n\n  \nlast".as_bytes();
+        assert_eq!(
+            read_line_from(&mut reader).unwrap(),
+            Line::Text("1".to_owned())
+        );
+        assert_eq!(read_line_from(&mut reader).unwrap(), Line::Empty);
+        assert_eq!(read_line_from(&mut reader).unwrap(), Line::Empty);
+        assert_eq!(
+            read_line_from(&mut reader).unwrap(),
+            Line::Text("last".to_owned())
+        );
+        assert_eq!(read_line_from(&mut reader).unwrap(), Line::Eof);
+        assert_eq!(read_line_from(&mut reader).unwrap(), Line::Eof);
+    }
+
+    #[test]
+    fn read_line_from_should_return_read_errors() {
+        let mut reader = io::BufReader::new(&[0xff, b'\n'][..]);
+        assert!(read_line_from(&mut reader).is_err());
+    }
+
+    #[test]
+    fn parse_yes_no_should_only_accept_whole_answers() {
+        for answer in ["y", "Y", "yes", " YES "] {
+            assert_eq!(parse_yes_no(answer), Some(true));
+        }
+        for answer in ["n", "No", "NO"] {
+            assert_eq!(parse_yes_no(answer), Some(false));
+        }
+        for answer in ["nyet", "maybe", "definitely not... ok yes", "yess", ""] {
+            assert_eq!(parse_yes_no(answer), None);
+        }
+    }
+
+    #[test]
+    fn confirm_should_ask_again_on_unknown_answers() {
+        let io = ScriptedIo::new(&["maybe", "yes"]);
+        assert!(confirm("Delete?", false, &io));
+        assert!(io
+            .output()
+            .starts_with("Delete?\n\t(y) yes | (n) no\nPlease answer y or n:"));
+
+        assert!(!confirm("Delete?", true, &ScriptedIo::new(&["nyet", "n"])));
+        assert!(!confirm(
+            "Delete?",
+            false,
+            &ScriptedIo::new(&["a", "b", "c", "y"])
+        ));
+        assert!(confirm("Quit?", true, &ScriptedIo::new(&[""])));
+        assert!(!confirm("Delete?", false, &ScriptedIo::new(&[])));
+    }
+
+    #[test]
+    fn user_from_var_should_ignore_blank_names() {
+        assert_eq!(user_from_var(Some(" alice ")), Some("alice".to_owned()));
+        assert_eq!(user_from_var(Some(" ")), None);
+        assert_eq!(user_from_var(None), None);
+    }
+
+    #[test]
+    fn editor_command_should_fall_back_to_vi() {
+        assert_eq!(editor_command(Some("nano"), Some("emacs")), "nano");
+        assert_eq!(editor_command(None, Some("emacs")), "emacs");
+        assert_eq!(editor_command(Some(" "), Some("emacs -nw")), "emacs -nw");
+        assert_eq!(editor_command(None, Some("")), "vi");
+        assert_eq!(editor_command(None, None), "vi");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn edit_with_should_return_saved_text() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let editor = dir.path().join("editor.sh");
+        fs::write(
+            &editor,
+            "#!/bin/sh\nprintf 'first\\n\\nsecond\\n' >> \"$1\"\n",
+        )
+        .unwrap();
+        fs::set_permissions(&editor, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let text = edit_with(editor.to_str().unwrap(), "original\n").unwrap();
+        assert_eq!(text, "original\nfirst\n\nsecond");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn edit_with_should_fail_when_editor_fails() {
+        assert!(edit_with("false", "original").is_err());
+        assert!(edit_with("clira-missing-editor", "original").is_err());
+    }
+
+    #[test]
+    fn colors_should_follow_theme() {
+        let theme = Theme {
+            resolved: ThemeColor::Cyan,
+            closed: ThemeColor::Default,
+            ..Theme::default()
+        };
+        assert_eq!(
+            color_table_column("Resolved", ColorMode::Enabled, &theme),
+            "Resolved".cyan().to_string()
+        );
+        assert_eq!(
+            color_table_column("Closed", ColorMode::Enabled, &theme),
+            "Closed"
+        );
+        assert_eq!(
+            color_for_table_header("Resolved", ColorMode::Enabled, &theme),
+            Color::FG_CYAN
+        );
+        assert_eq!(
+            color_for_table_header("Closed", ColorMode::Enabled, &theme),
+            Color::empty()
+        );
+    }
+
+    #[test]
+    fn colors_should_be_plain_when_disabled() {
+        for status in [
+            "Open",
+            "Blocked",
+            "In Progress",
+            "Resolved",
+            "Closed",
+            "foo",
+        ] {
+            assert_eq!(
+                color_table_column(status, ColorMode::Disabled, &Theme::default()),
+                status
+            );
+            assert_eq!(
+                color_for_table_header(status, ColorMode::Disabled, &Theme::default()),
+                Color::empty()
+            );
+        }
+    }
+
+    #[test]
+    fn color_mode_from_var_should_follow_no_color() {
+        assert_eq!(ColorMode::from_var(None), ColorMode::Enabled);
+        assert_eq!(ColorMode::from_var(Some("")), ColorMode::Enabled);
+        assert_eq!(ColorMode::from_var(Some("1")), ColorMode::Disabled);
+    }
+
+    #[test]
+    fn parse_date_should_succeed() {
+        assert_eq!(
+            parse_date(" 2024-05-31 ", DATE_FORMAT),
+            NaiveDate::from_ymd_opt(2024, 5, 31)
+        );
+        assert_eq!(parse_date("2024-02-30", DATE_FORMAT), None);
+        assert_eq!(parse_date("31/05/2024", DATE_FORMAT), None);
+        assert_eq!(
+            parse_date("31/05/2024", "%d/%m/%Y"),
+            NaiveDate::from_ymd_opt(2024, 5, 31)
+        );
+        assert_eq!(parse_date("", DATE_FORMAT), None);
+    }
+
+    #[test]
+    fn parse_duration_should_accept_hours_and_minutes() {
+        assert_eq!(parse_duration("1h30m"), Some(90));
+        assert_eq!(parse_duration("1h 30m"), Some(90));
+        assert_eq!(parse_duration(" 45m "), Some(45));
+        assert_eq!(parse_duration("2h"), Some(120));
+        assert_eq!(parse_duration("2H15M"), Some(135));
+        assert_eq!(parse_duration("90m"), Some(90));
+    }
+
+    #[test]
+    fn parse_duration_should_reject_invalid_input() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("h"), None);
+        assert_eq!(parse_duration("m"), None);
+        assert_eq!(parse_duration("45"), None);
+        assert_eq!(parse_duration("1h30"), None);
+        assert_eq!(parse_duration("0m"), None);
+        assert_eq!(parse_duration("-5m"), None);
+        assert_eq!(parse_duration("+5m"), None);
+        assert_eq!(parse_duration("1.5h"), None);
+        assert_eq!(parse_duration("30m1h"), None);
+        assert_eq!(parse_duration("1h1h"), None);
+        assert_eq!(parse_duration("99999999999h"), None);
+    }
+
+    #[test]
+    fn format_duration_should_succeed() {
+        assert_eq!(format_duration(45), "45m");
+        assert_eq!(format_duration(120), "2h");
+        assert_eq!(format_duration(90), "1h 30m");
+        assert_eq!(format_duration(0), "0m");
     }
 }
@@ -0,0 +1,72 @@
+use std::{collections::BTreeMap, fs, io::ErrorKind, path::Path};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// `Workspaces` maps workspace names to the board files they use, ordered by name. It is
+/// read from a JSON object such as `{ "work": "/home/me/boards/work.json" }`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct Workspaces(pub BTreeMap<String, String>);
+
+impl Workspaces {
+    /// `parse` reads the workspaces from the JSON `config`. Returns `Err` if `config` is not
+    /// an object of names to paths.
+    pub fn parse(config: &str) -> Result<Self> {
+        Ok(serde_json::from_str(config)?)
+    }
+
+    /// `load` reads the workspaces from the file at `path`. A missing file means there are no
+    /// workspaces. Returns `Err` if the file could not be read or parsed.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(config) => Self::parse(&config),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// `config_path` returns where the workspaces are configured, under the user's `home`.
+    pub fn config_path(home: &str) -> String {
+        Path::new(home)
+            .join(".config/clira/workspaces.json")
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// `get` returns the board file of the workspace `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_should_read_names_and_paths() {
+        let workspaces =
+            Workspaces::parse(r#"{ "work": "/boards/work.json", "home": "home.json" }"#).unwrap();
+        assert_eq!(workspaces.get("work"), Some("/boards/work.json"));
+        assert_eq!(workspaces.get("home"), Some("home.json"));
+        assert_eq!(workspaces.get("other"), None);
+        assert_eq!(
+            workspaces.0.keys().collect::<Vec<_>>(),
+            vec!["home", "work"]
+        );
+        assert!(Workspaces::parse(r#"["work"]"#).is_err());
+    }
+
+    #[test]
+    fn load_should_default_to_no_workspaces() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("workspaces.json");
+        assert_eq!(Workspaces::load(&path).unwrap(), Workspaces::default());
+
+        fs::write(&path, r#"{ "work": "work.json" }"#).unwrap();
+        assert_eq!(
+            Workspaces::load(&path).unwrap().get("work"),
+            Some("work.json")
+        );
+    }
+}